@@ -0,0 +1,75 @@
+#ifndef FUZZYWUZZY_H
+#define FUZZYWUZZY_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+
+
+
+
+/**
+ * See [fuzz::ratio]. Returns -1 if either argument is null or not valid UTF-8.
+ *
+ * # Safety
+ * `a` and `b` must be null or point to NUL-terminated strings.
+ */
+int fuzzywuzzy_ratio(const char *a, const char *b);
+
+/**
+ * See [fuzz::partial_ratio]. Returns -1 if either argument is null or not valid UTF-8.
+ *
+ * # Safety
+ * `a` and `b` must be null or point to NUL-terminated strings.
+ */
+int fuzzywuzzy_partial_ratio(const char *a, const char *b);
+
+/**
+ * See [fuzz::token_sort_ratio]. Returns -1 if either argument is null or not valid UTF-8.
+ *
+ * # Safety
+ * `a` and `b` must be null or point to NUL-terminated strings.
+ */
+int fuzzywuzzy_token_sort_ratio(const char *a, const char *b, bool force_ascii, bool full_process);
+
+/**
+ * See [fuzz::token_set_ratio]. Returns -1 if either argument is null or not valid UTF-8.
+ *
+ * # Safety
+ * `a` and `b` must be null or point to NUL-terminated strings.
+ */
+int fuzzywuzzy_token_set_ratio(const char *a, const char *b, bool force_ascii, bool full_process);
+
+/**
+ * See [fuzz::wratio]. Returns -1 if either argument is null or not valid UTF-8.
+ *
+ * # Safety
+ * `a` and `b` must be null or point to NUL-terminated strings.
+ */
+int fuzzywuzzy_wratio(const char *a, const char *b, bool force_ascii, bool full_process);
+
+/**
+ * Finds the choice best matching `query` with [process::extract_one_with_payload],
+ * [utils::full_process] and [fuzz::WRatio], as [process::extract_one] would.
+ *
+ * Returns the index of the best choice, writing its score to `out_score` if it
+ * is not null. Returns -1 if no choice scores at least `score_cutoff`, and -2
+ * if `query`, `choices` or any choice is null or not valid UTF-8.
+ *
+ * # Safety
+ * `query` must be null or point to a NUL-terminated string. `choices` must be
+ * null or point to `len` pointers which are each null or point to a
+ * NUL-terminated string. `out_score` must be null or valid for writes.
+ */
+ptrdiff_t fuzzywuzzy_extract_one(const char *query,
+                                 const char *const *choices,
+                                 size_t len,
+                                 uint8_t score_cutoff,
+                                 uint8_t *out_score);
+
+#endif  /* FUZZYWUZZY_H */
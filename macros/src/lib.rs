@@ -0,0 +1,69 @@
+#![deny(warnings)]
+//! Procedural macros for [fuzzywuzzy](https://docs.rs/fuzzywuzzy), re-exported
+//! by it with feature "macros". Use them through `fuzzywuzzy::pattern`.
+
+use fuzzywuzzy_processing::process_chars;
+use proc_macro::TokenStream;
+use std::fmt::Write;
+use syn::LitStr;
+
+/// The longest pattern fuzzywuzzy's bitap search handles.
+const BITAP_MAX_LEN: usize = 64;
+
+/// Compiles a string literal into a `&'static fuzzywuzzy::pattern::CompiledPattern`.
+///
+/// See `fuzzywuzzy::pattern` for details.
+#[proc_macro]
+pub fn fuzzy_pattern(input: TokenStream) -> TokenStream {
+    match syn::parse::<LitStr>(input) {
+        Ok(literal) => expand(&literal.value()),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(literal: &str) -> TokenStream {
+    // `fuzzywuzzy::utils::full_process(literal, false)`.
+    let processed = process_chars(literal, false, char::is_alphanumeric, true);
+    let chars: Vec<char> = processed.chars().collect();
+    let mut char_list = String::new();
+    for c in chars.iter() {
+        write!(char_list, "{:?},", c).expect("writing to a string");
+    }
+    let masks = if chars.len() <= BITAP_MAX_LEN {
+        let mut ascii = [0u64; 128];
+        let mut other: Vec<(char, u64)> = vec![];
+        for (i, &c) in chars.iter().enumerate() {
+            match ascii.get_mut(c as usize) {
+                Some(mask) => *mask |= 1 << i,
+                None => match other.iter_mut().find(|(o, _)| *o == c) {
+                    Some((_, mask)) => *mask |= 1 << i,
+                    None => other.push((c, 1 << i)),
+                },
+            }
+        }
+        let mut other_list = String::new();
+        for (c, mask) in other {
+            write!(other_list, "({:?}, {}),", c, mask).expect("writing to a string");
+        }
+        format!(
+            "::core::option::Option::Some((&{:?}, &[{}]))",
+            ascii, other_list
+        )
+    } else {
+        "::core::option::Option::None".to_string()
+    };
+    format!(
+        "{{
+            static PATTERN: ::fuzzywuzzy::pattern::CompiledPattern =
+                ::fuzzywuzzy::pattern::CompiledPattern::__from_parts({:?}, {:?}, &[{}], {}, {});
+            &PATTERN
+        }}",
+        literal,
+        processed,
+        char_list,
+        processed.split_whitespace().count(),
+        masks,
+    )
+    .parse()
+    .expect("a valid expansion")
+}
@@ -0,0 +1,51 @@
+#![deny(warnings)]
+//! String processing shared by [fuzzywuzzy](https://docs.rs/fuzzywuzzy) and
+//! fuzzywuzzy-macros, so that patterns compiled by the macros process exactly
+//! as they would at run time. Use `fuzzywuzzy::utils` instead.
+
+/// Replaces the characters for which `preserve` returns `false` with a
+/// space, lower-cases the rest if `lowercase` is set, and trims whitespace.
+/// Non-ASCII characters are removed first if `force_ascii` is set.
+///
+/// `fuzzywuzzy::utils::full_process(s, force_ascii)` is
+/// `process_chars(s, force_ascii, char::is_alphanumeric, true)`.
+pub fn process_chars<F: Fn(char) -> bool>(
+    s: &str,
+    force_ascii: bool,
+    preserve: F,
+    lowercase: bool,
+) -> String {
+    // Done in a single pass over `s` rather than allocating an intermediate
+    // string per step. Leading whitespace is never written and trailing
+    // whitespace is truncated at the end, which is equivalent to `trim`.
+    let mut result = String::with_capacity(s.len());
+    let mut sigma = false;
+    for c in s.chars() {
+        if force_ascii && !c.is_ascii() {
+            continue;
+        }
+        let preserved = preserve(c);
+        if result.is_empty() && (!preserved || c.is_whitespace()) {
+            continue;
+        }
+        if preserved && lowercase && c == 'Σ' {
+            // Lower-cased with its neighbours below, since a word-final Σ
+            // becomes ς rather than σ.
+            sigma = true;
+            result.push(c);
+        } else if preserved && lowercase {
+            result.extend(c.to_lowercase());
+        } else if preserved {
+            result.push(c);
+        } else {
+            result.push(' ');
+        }
+    }
+    let trimmed_len = result.trim_end().len();
+    result.truncate(trimmed_len);
+    if sigma {
+        // Everything else is lower case already, and stays so.
+        result = result.to_lowercase();
+    }
+    result
+}
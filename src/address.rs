@@ -0,0 +1,295 @@
+//! Matching street addresses.
+//!
+//! General purpose scorers are thrown off by the many ways of writing the
+//! same address: "123 N Main St" and "123 Main Street North" share few
+//! characters in order, so [wratio](crate::fuzz::wratio) ranks them below
+//! "123 N Maine St". [parse_address] instead splits an address into its
+//! parts, abbreviating directionals and street types as in USPS Publication
+//! 28 and writing ordinals as numbers, and [address_ratio] compares the parts.
+//!
+//! ```
+//! # use fuzzywuzzy::address::{address_ratio, normalize_address};
+//! assert_eq!(normalize_address("123 Main Street North, Apt. 4B"), "123 n main st");
+//! assert_eq!(address_ratio("123 N Main St", "123 Main Street North"), 100);
+//! assert_eq!(address_ratio("123 N Main St", "123 N Maine St"), 93);
+//! ```
+
+use crate::fuzz::{self, Scorer};
+use crate::score::Score;
+use crate::utils::{self, Rounding};
+use std::fmt;
+
+/// Words introducing a unit within a building, which is stripped along with the word after it.
+const UNIT_DESIGNATORS: &[&str] = &[
+    "apt",
+    "apartment",
+    "bldg",
+    "building",
+    "dept",
+    "department",
+    "fl",
+    "floor",
+    "lot",
+    "rm",
+    "room",
+    "ste",
+    "suite",
+    "unit",
+    "#",
+];
+
+const DIRECTIONALS: &[(&str, &str)] = &[
+    ("north", "n"),
+    ("south", "s"),
+    ("east", "e"),
+    ("west", "w"),
+    ("northeast", "ne"),
+    ("northwest", "nw"),
+    ("southeast", "se"),
+    ("southwest", "sw"),
+];
+
+const STREET_TYPES: &[(&str, &str)] = &[
+    ("alley", "aly"),
+    ("avenue", "ave"),
+    ("av", "ave"),
+    ("boulevard", "blvd"),
+    ("circle", "cir"),
+    ("court", "ct"),
+    ("drive", "dr"),
+    ("expressway", "expy"),
+    ("freeway", "fwy"),
+    ("highway", "hwy"),
+    ("lane", "ln"),
+    ("parkway", "pkwy"),
+    ("place", "pl"),
+    ("plaza", "plz"),
+    ("road", "rd"),
+    ("square", "sq"),
+    ("street", "st"),
+    ("str", "st"),
+    ("terrace", "ter"),
+    ("trail", "trl"),
+    ("way", "way"),
+];
+
+const ORDINALS: &[(&str, &str)] = &[
+    ("first", "1st"),
+    ("second", "2nd"),
+    ("third", "3rd"),
+    ("fourth", "4th"),
+    ("fifth", "5th"),
+    ("sixth", "6th"),
+    ("seventh", "7th"),
+    ("eighth", "8th"),
+    ("ninth", "9th"),
+    ("tenth", "10th"),
+    ("eleventh", "11th"),
+    ("twelfth", "12th"),
+    ("thirteenth", "13th"),
+    ("fourteenth", "14th"),
+    ("fifteenth", "15th"),
+    ("sixteenth", "16th"),
+    ("seventeenth", "17th"),
+    ("eighteenth", "18th"),
+    ("nineteenth", "19th"),
+    ("twentieth", "20th"),
+];
+
+/// The abbreviation of `word` in `table`, if it's a full or abbreviated entry.
+fn abbreviate(table: &[(&str, &'static str)], word: &str) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|&&(full, short)| word == full || word == short)
+        .map(|&(_, short)| short)
+}
+
+/// The parts of a street address.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Address {
+    /// The house number, e.g. "123" or "12b".
+    pub number: Option<String>,
+    /// Abbreviated directionals, whether they came before or after the street name.
+    pub directionals: Vec<String>,
+    /// The words of the street name, with ordinals as numbers.
+    pub street: Vec<String>,
+    /// The abbreviated street type, e.g. "st" or "ave".
+    pub street_type: Option<String>,
+    /// The unit within the building, e.g. "4b" from "Apt. 4B".
+    pub unit: Option<String>,
+}
+
+/// Splits `address` into its parts, normalizing each.
+///
+/// The address is lower-cased and punctuation other than `#` is dropped. A
+/// unit designator like "Apt" or "#" and the word after it become the unit,
+/// and so does a word starting with `#`. A leading word starting with a
+/// digit is the house number. Directionals are recognized before and after
+/// the street name, and a street type at its end, unless that would leave
+/// the street name empty, as in "North Street".
+///
+/// ```
+/// # use fuzzywuzzy::address::{parse_address, Address};
+/// assert_eq!(parse_address("123 Fifth Avenue SW, Suite 200"), Address {
+///     number: Some("123".to_string()),
+///     directionals: vec!["sw".to_string()],
+///     street: vec!["5th".to_string()],
+///     street_type: Some("ave".to_string()),
+///     unit: Some("200".to_string()),
+/// });
+/// assert_eq!(parse_address("North St").street, vec!["n"]);
+/// ```
+pub fn parse_address(address: &str) -> Address {
+    let processed =
+        utils::full_process_preserving(address, false, |c| c.is_alphanumeric() || c == '#');
+    let mut parsed = Address::default();
+    let mut words = vec![];
+    let mut tokens = processed.split_whitespace();
+    while let Some(token) = tokens.next() {
+        if UNIT_DESIGNATORS.contains(&token) {
+            parsed.unit = tokens
+                .next()
+                .map(|unit| unit.trim_start_matches('#').to_string());
+        } else if let Some(unit) = token.strip_prefix('#') {
+            parsed.unit = Some(unit.to_string());
+        } else if words.is_empty()
+            && parsed.number.is_none()
+            && token.starts_with(|c: char| c.is_ascii_digit())
+        {
+            parsed.number = Some(token.to_string());
+        } else {
+            let ordinal = abbreviate(ORDINALS, token);
+            words.push(ordinal.map_or_else(|| token.to_string(), str::to_string));
+        }
+    }
+    // Trailing directionals, the street type and leading directionals are
+    // split off in that order, while at least one word remains for the street name.
+    let (mut start, mut end) = (0, words.len());
+    let mut trailing = vec![];
+    while end - start > 1 {
+        match abbreviate(DIRECTIONALS, &words[end - 1]) {
+            Some(directional) => trailing.push(directional.to_string()),
+            None => break,
+        }
+        end -= 1;
+    }
+    if end - start > 1 {
+        if let Some(street_type) = abbreviate(STREET_TYPES, &words[end - 1]) {
+            parsed.street_type = Some(street_type.to_string());
+            end -= 1;
+        }
+    }
+    while end - start > 1 {
+        match abbreviate(DIRECTIONALS, &words[start]) {
+            Some(directional) => parsed.directionals.push(directional.to_string()),
+            None => break,
+        }
+        start += 1;
+    }
+    parsed.directionals.extend(trailing.into_iter().rev());
+    parsed.street = words[start..end]
+        .iter()
+        .map(|word| match abbreviate(DIRECTIONALS, word) {
+            Some(directional) => directional.to_string(),
+            None => word.clone(),
+        })
+        .collect();
+    parsed
+}
+
+impl fmt::Display for Address {
+    /// Writes the number, directionals, street name and type separated by
+    /// spaces, leaving out the unit.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<&str> = self
+            .number
+            .iter()
+            .chain(self.directionals.iter())
+            .chain(self.street.iter())
+            .chain(self.street_type.iter())
+            .map(String::as_str)
+            .collect();
+        f.write_str(&parts.join(" "))
+    }
+}
+
+/// `address` in a canonical form, without its unit. See [parse_address].
+pub fn normalize_address(address: &str) -> String {
+    parse_address(address).to_string()
+}
+
+/// How much two optional parts agree: fully if they're equal, half if only
+/// one is known and not at all if they differ.
+fn agreement<T: PartialEq>(a: Option<T>, b: Option<T>) -> f64 {
+    match (a, b) {
+        (Some(a), Some(b)) if a != b => 0.0,
+        (Some(_), None) | (None, Some(_)) => 0.5,
+        _ => 1.0,
+    }
+}
+
+/// Compares two addresses part by part, ignoring units.
+///
+/// The street names' [ratio](fuzz::ratio) makes up 60% of the score. The
+/// house number makes up 20%, and the street type and the directionals 10%
+/// each; a part scores fully when both addresses agree on it, half when only
+/// one has it and nothing when they disagree.
+///
+/// ```
+/// # use fuzzywuzzy::address::address_ratio;
+/// assert_eq!(address_ratio("42 W 3rd Street", "42 West Third St."), 100);
+/// assert_eq!(address_ratio("42 W 3rd Street", "44 W 3rd Street"), 80);
+/// assert_eq!(address_ratio("42 W 3rd Street", "42 W 3rd Avenue"), 90);
+/// assert_eq!(address_ratio("42 W 3rd Street", "42 3rd Street"), 95);
+/// ```
+pub fn address_ratio(a: &str, b: &str) -> u8 {
+    let (a, b) = (parse_address(a), parse_address(b));
+    let street = fuzz::ratio(a.street.join(" "), b.street.join(" ")) as f64 / 100.0;
+    let directionals = match (a.directionals.is_empty(), b.directionals.is_empty()) {
+        (false, false) => {
+            let (mut a, mut b) = (a.directionals.clone(), b.directionals.clone());
+            a.sort();
+            b.sort();
+            agreement(Some(a), Some(b))
+        }
+        (true, true) => 1.0,
+        _ => 0.5,
+    };
+    let score = 60.0 * street
+        + 20.0 * agreement(a.number, b.number)
+        + 10.0 * agreement(a.street_type, b.street_type)
+        + 10.0 * directionals;
+    Rounding::Python.round(score)
+}
+
+/// [Scorer] for [address_ratio].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AddressRatio;
+
+impl Scorer for AddressRatio {
+    fn score(&self, a: &str, b: &str) -> Score {
+        Score::new(address_ratio(a, b)).expect("address_ratio returns at most 100")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn equivalent_spellings_normalize_alike() {
+        let spellings = [
+            "123 N Main St",
+            "123 North Main Street",
+            "123 Main St. N.",
+            "123 main street north #7",
+            "123 N. MAIN ST, Apartment 7",
+        ];
+        for spelling in spellings.iter() {
+            assert_eq!(normalize_address(spelling), "123 n main st", "{}", spelling);
+        }
+        assert_eq!(normalize_address("1 Avenue Rd"), "1 avenue rd");
+        assert_eq!(normalize_address("Main"), "main");
+        assert_eq!(normalize_address(""), "");
+    }
+}
@@ -0,0 +1,135 @@
+//! Matching a string column of an [Arrow](https://docs.rs/arrow-array) array
+//! against a list of choices. Requires feature "arrow".
+//!
+//! Each value of the column is scored against every choice in parallel, and
+//! the best matches come back as columns of the same length, ready to be
+//! added to a record batch or a Polars data frame, which share Arrow's
+//! memory layout. Values are scored as given, so the scorer should process
+//! them if needed. Ties go to the first choice.
+//!
+//! ```
+//! # use fuzzywuzzy::arrow::match_column;
+//! # use fuzzywuzzy::fuzz::WRatio;
+//! use arrow_array::{Array, StringArray, UInt8Array};
+//! let names = StringArray::from(vec![Some("ACME Corp."), None, Some("Globex Inc"), Some("Hooli")]);
+//! let matches = match_column(&names, &["Acme Corporation", "Globex", "Initech"], WRatio, 80);
+//! assert_eq!(
+//!     matches.best_match,
+//!     StringArray::from(vec![Some("Acme Corporation"), None, Some("Globex"), None])
+//! );
+//! assert_eq!(matches.score, UInt8Array::from(vec![Some(90), None, Some(90), None]));
+//! assert_eq!(matches.index.null_count(), 2);
+//! ```
+
+use crate::fuzz::Scorer;
+use arrow_array::{
+    Array, GenericStringArray, OffsetSizeTrait, StringArray, UInt32Array, UInt8Array,
+};
+use rayon::prelude::*;
+use std::convert::TryFrom;
+
+/// The best match of each value of a column, see [match_column].
+///
+/// Each column has a value per value of the matched column, which is null
+/// where that value is null or no choice reaches the cutoff.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnMatches {
+    /// The best matching choice.
+    pub best_match: StringArray,
+    /// The index of the best matching choice.
+    pub index: UInt32Array,
+    /// The score of the best matching choice.
+    pub score: UInt8Array,
+}
+
+/// Finds the choice scoring highest against each value of `column`, if it
+/// scores at least `score_cutoff`.
+///
+/// # Panics
+///
+/// Panics if there are more than `u32::MAX` choices.
+pub fn match_column<O, T, S>(
+    column: &GenericStringArray<O>,
+    choices: &[T],
+    scorer: S,
+    score_cutoff: u8,
+) -> ColumnMatches
+where
+    O: OffsetSizeTrait,
+    T: AsRef<str> + Sync,
+    S: Scorer + Sync,
+{
+    assert!(
+        u32::try_from(choices.len()).is_ok(),
+        "there must be at most u32::MAX choices"
+    );
+    let best: Vec<Option<(usize, u8)>> = (0..column.len())
+        .into_par_iter()
+        .map(|i| {
+            if column.is_null(i) {
+                return None;
+            }
+            let value = column.value(i);
+            let mut best: Option<(usize, u8)> = None;
+            for (j, choice) in choices.iter().enumerate() {
+                let score = scorer.score(value, choice.as_ref()).percent();
+                if score >= score_cutoff && best.is_none_or(|(_, best)| score > best) {
+                    best = Some((j, score));
+                }
+            }
+            best
+        })
+        .collect();
+    ColumnMatches {
+        best_match: best
+            .iter()
+            .map(|best| best.map(|(j, _)| choices[j].as_ref()))
+            .collect(),
+        index: best
+            .iter()
+            .map(|best| best.map(|(j, _)| j as u32))
+            .collect(),
+        score: best
+            .iter()
+            .map(|best| best.map(|(_, score)| score))
+            .collect(),
+    }
+}
+
+/// [match_column] with the non-null values of another column as the choices.
+///
+/// The indices in the result are those of the other column.
+///
+/// ```
+/// # use fuzzywuzzy::arrow::match_columns;
+/// # use fuzzywuzzy::fuzz::Ratio;
+/// use arrow_array::{LargeStringArray, StringArray, UInt32Array};
+/// let left = StringArray::from(vec!["apple", "banana"]);
+/// let right = LargeStringArray::from(vec![None, Some("bananas"), Some("apples")]);
+/// let matches = match_columns(&left, &right, Ratio, 0);
+/// assert_eq!(matches.index, UInt32Array::from(vec![2, 1]));
+/// ```
+pub fn match_columns<O, P, S>(
+    column: &GenericStringArray<O>,
+    other: &GenericStringArray<P>,
+    scorer: S,
+    score_cutoff: u8,
+) -> ColumnMatches
+where
+    O: OffsetSizeTrait,
+    P: OffsetSizeTrait,
+    S: Scorer + Sync,
+{
+    let (indices, choices): (Vec<u32>, Vec<&str>) = other
+        .iter()
+        .enumerate()
+        .filter_map(|(j, choice)| Some((j as u32, choice?)))
+        .unzip();
+    let mut matches = match_column(column, &choices, scorer, score_cutoff);
+    matches.index = matches
+        .index
+        .iter()
+        .map(|j| j.map(|j| indices[j as usize]))
+        .collect();
+    matches
+}
@@ -0,0 +1,242 @@
+//! Fuzzy string matching from the command line. Requires feature "cli".
+//!
+//! Choices are read one per line from a file or standard input.
+
+use fuzzywuzzy::fuzz::{
+    DiceRatio, PartialRatio, PartialTokenRatio, PartialTokenSetRatio, PartialTokenSortRatio,
+    PartialTranspositionRatio, QRatio, Ratio, Scorer, TokenSetRatio, TokenSetTranspositionRatio,
+    TokenSortRatio, TokenSortTranspositionRatio, TranspositionRatio, WRatio, WordBigramRatio,
+    WordBigramSetRatio,
+};
+use fuzzywuzzy::process::{dedupe, extract_without_order};
+use fuzzywuzzy::search::find_near_matches;
+use fuzzywuzzy::subsequence::{FzfScorer, SublimeScorer};
+use fuzzywuzzy::utils::full_process;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::exit;
+
+const USAGE: &str = "\
+Usage: fuzzywuzzy <COMMAND> [OPTIONS]
+
+Commands:
+  extract <QUERY>   Print choices scoring at least the cutoff against QUERY, best first
+  dedupe            Print choices with near-duplicates removed
+  grep <PATTERN>    Print lines containing an approximate occurrence of PATTERN, scored by
+                    the percentage of its characters which needn't be edited
+
+Options:
+  -f, --file <PATH>     Read choices from PATH instead of standard input
+  -s, --scorer <NAME>   ratio, partial_ratio, token_sort_ratio, partial_token_sort_ratio,
+                        token_set_ratio, partial_token_set_ratio, partial_token_ratio, qratio,
+                        transposition_ratio, partial_transposition_ratio,
+                        token_sort_transposition_ratio, token_set_transposition_ratio,
+                        dice_ratio, word_bigram_ratio, word_bigram_set_ratio, wratio, fzf
+                        or sublime
+                        [defaults: extract wratio, dedupe token_set_ratio; not for grep]
+  -c, --cutoff <N>      Minimum score from 0 to 100
+                        [defaults: extract 0, dedupe 70, grep 80]
+  -n, --limit <N>       Print at most N results (extract only)
+      --scores          Prefix each result with its score and a tab (extract and grep)
+  -h, --help            Print this help
+";
+
+enum Command {
+    Extract(String),
+    Dedupe,
+    Grep(String),
+}
+
+struct Options {
+    command: Command,
+    file: Option<String>,
+    scorer: Option<String>,
+    cutoff: Option<u8>,
+    limit: Option<usize>,
+    scores: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut positional = vec![];
+    let mut options = Options {
+        command: Command::Dedupe,
+        file: None,
+        scorer: None,
+        cutoff: None,
+        limit: None,
+        scores: false,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{} requires a value", name));
+        match arg.as_str() {
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                exit(0);
+            }
+            "-f" | "--file" => options.file = Some(value(&arg)?),
+            "-s" | "--scorer" => options.scorer = Some(value(&arg)?),
+            "-c" | "--cutoff" => {
+                let cutoff = value(&arg)?;
+                options.cutoff = match cutoff.parse() {
+                    Ok(cutoff) if cutoff <= 100 => Some(cutoff),
+                    _ => return Err(format!("invalid cutoff '{}'", cutoff)),
+                };
+            }
+            "-n" | "--limit" => {
+                let limit = value(&arg)?;
+                options.limit = Some(
+                    limit
+                        .parse()
+                        .map_err(|_| format!("invalid limit '{}'", limit))?,
+                );
+            }
+            "--scores" => options.scores = true,
+            _ if arg.starts_with('-') && arg != "-" => {
+                return Err(format!("unknown option '{}'", arg))
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let mut positional = positional.into_iter();
+    options.command = match (positional.next().as_deref(), positional.next()) {
+        (Some("extract"), Some(query)) => Command::Extract(query),
+        (Some("dedupe"), None) => Command::Dedupe,
+        (Some("grep"), Some(pattern)) => Command::Grep(pattern),
+        (Some("extract"), None) => return Err("extract requires a QUERY".to_string()),
+        (Some("grep"), None) => return Err("grep requires a PATTERN".to_string()),
+        (Some("dedupe"), Some(_)) => return Err("dedupe takes no arguments".to_string()),
+        (Some(command), _) => return Err(format!("unknown command '{}'", command)),
+        (None, _) => return Err("missing command".to_string()),
+    };
+    if positional.next().is_some() {
+        return Err("too many arguments".to_string());
+    }
+    Ok(options)
+}
+
+/// The scorer called `name`, and whether choices should be processed before scoring.
+fn scorer(name: &str) -> Result<(Box<dyn Scorer + Send + Sync>, bool), String> {
+    Ok(match name {
+        "ratio" => (Box::new(Ratio), true),
+        "partial_ratio" => (Box::new(PartialRatio), true),
+        "token_sort_ratio" => (Box::new(TokenSortRatio), true),
+        "partial_token_sort_ratio" => (Box::new(PartialTokenSortRatio), true),
+        "token_set_ratio" => (Box::new(TokenSetRatio), true),
+        "partial_token_set_ratio" => (Box::new(PartialTokenSetRatio), true),
+        "partial_token_ratio" => (Box::new(PartialTokenRatio), true),
+        "qratio" => (Box::new(QRatio), true),
+        "transposition_ratio" => (Box::new(TranspositionRatio), true),
+        "partial_transposition_ratio" => (Box::new(PartialTranspositionRatio), true),
+        "token_sort_transposition_ratio" => (Box::new(TokenSortTranspositionRatio), true),
+        "token_set_transposition_ratio" => (Box::new(TokenSetTranspositionRatio), true),
+        "dice_ratio" => (Box::new(DiceRatio), true),
+        "word_bigram_ratio" => (Box::new(WordBigramRatio), true),
+        "word_bigram_set_ratio" => (Box::new(WordBigramSetRatio), true),
+        "wratio" => (Box::new(WRatio), true),
+        // Subsequence scorers rely on separators and case, which processing removes.
+        "fzf" => (Box::new(FzfScorer), false),
+        "sublime" => (Box::new(SublimeScorer), false),
+        _ => return Err(format!("unknown scorer '{}'", name)),
+    })
+}
+
+fn read_choices(file: Option<&str>) -> Result<Vec<String>, String> {
+    let reader: Box<dyn BufRead> = match file {
+        None | Some("-") => Box::new(BufReader::new(io::stdin())),
+        Some(path) => Box::new(BufReader::new(
+            std::fs::File::open(path).map_err(|e| format!("{}: {}", path, e))?,
+        )),
+    };
+    reader
+        .lines()
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())
+}
+
+/// Runs the command, returning whether anything was printed.
+fn run(options: Options) -> Result<bool, String> {
+    let scorer_or = |default| scorer(options.scorer.as_deref().unwrap_or(default));
+    let choices = || read_choices(options.file.as_deref());
+    let results: Vec<(String, Option<u8>)> = match &options.command {
+        Command::Extract(query) => {
+            let (scorer, process) = scorer_or("wratio")?;
+            let processor = |s: &str, force_ascii: bool| {
+                if process {
+                    full_process(s, force_ascii)
+                } else {
+                    s.to_string()
+                }
+            };
+            let cutoff = options.cutoff.unwrap_or(0);
+            let mut results = extract_without_order(query, choices()?, processor, scorer, cutoff);
+            results.sort_by(|(_, a), (_, b)| b.cmp(a));
+            results.truncate(options.limit.unwrap_or(usize::MAX));
+            results.into_iter().map(|(c, s)| (c, Some(s))).collect()
+        }
+        // Scores don't apply to deduplicated choices.
+        Command::Dedupe => {
+            let (scorer, _) = scorer_or("token_set_ratio")?;
+            dedupe(choices()?, options.cutoff.unwrap_or(70), scorer)
+                .into_iter()
+                .map(|choice| (choice, None))
+                .collect()
+        }
+        Command::Grep(pattern) => {
+            if options.scorer.is_some() {
+                return Err("grep doesn't take a scorer".to_string());
+            }
+            grep(pattern, choices()?, options.cutoff.unwrap_or(80))
+        }
+    };
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    for (choice, score) in results.iter() {
+        let written = match score {
+            Some(score) if options.scores => writeln!(out, "{}\t{}", score, choice),
+            _ => writeln!(out, "{}", choice),
+        };
+        match written {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(true),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+    Ok(!results.is_empty())
+}
+
+/// The lines containing an occurrence of `pattern` scoring at least
+/// `cutoff`, with the score of their closest occurrence: the percentage of
+/// the pattern's characters it doesn't need edited.
+fn grep(pattern: &str, lines: Vec<String>, cutoff: u8) -> Vec<(String, Option<u8>)> {
+    let pattern = full_process(pattern, false);
+    let len = pattern.chars().count();
+    // The most edits an occurrence scoring at least the cutoff may have.
+    let max_distance = len * (100 - cutoff as usize) / 100;
+    lines
+        .into_iter()
+        .filter_map(|line| {
+            let distance = find_near_matches(&pattern, &full_process(&line, false), max_distance)
+                .iter()
+                .map(|m| m.distance)
+                .min()?;
+            Some((line, Some((100 * (len - distance) / len) as u8)))
+        })
+        .collect()
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("fuzzywuzzy: {}\n\n{}", message, USAGE);
+            exit(2);
+        }
+    };
+    match run(options) {
+        // Like grep, exit with 1 if nothing matched.
+        Ok(found) => exit(if found { 0 } else { 1 }),
+        Err(message) => {
+            eprintln!("fuzzywuzzy: {}", message);
+            exit(2);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! [Canonicalizer], which maps messy input to a fixed set of canonical labels.
+//! Requires feature "token".
+//!
+//! Entity resolution often comes down to a table of canonical names and the
+//! spellings each is known by, with anything else matched to the closest
+//! known spelling, or flagged as unknown when nothing is close enough. A
+//! [Canonicalizer] wraps that around [extract_one_with_payload].
+//!
+//! ```
+//! # use fuzzywuzzy::canonical::Canonicalizer;
+//! let countries = Canonicalizer::new()
+//!     .label("United States", ["USA", "U.S.", "United States of America"])
+//!     .label("United Kingdom", ["UK", "Great Britain", "Britain"])
+//!     .label("Germany", ["Deutschland", "DE"])
+//!     .unknown("Other");
+//! assert_eq!(countries.canonicalize("usa"), Some("United States"));
+//! assert_eq!(countries.canonicalize("united states of amerca"), Some("United States"));
+//! assert_eq!(countries.canonicalize("Great Britian"), Some("United Kingdom"));
+//! assert_eq!(countries.canonicalize("Atlantis"), Some("Other"));
+//! assert_eq!(countries.best_match("Atlantis"), None);
+//! ```
+
+use crate::fuzz::{Scorer, WRatio};
+use crate::process::extract_one_with_payload;
+use crate::processing::Processor;
+use crate::score::Score;
+use crate::utils::full_process;
+use std::iter::FromIterator;
+
+/// Maps strings to the canonical label of their closest known variant, see
+/// the [module](self) docs.
+///
+/// By default strings are processed with [full_process] and scored with
+/// [WRatio], and variants must score at least 80 to match. Each label is
+/// also a variant of itself. If several variants score best, the first one
+/// added wins.
+///
+/// The labels and variants can also be collected from a mapping of labels to
+/// their variants:
+///
+/// ```
+/// # use fuzzywuzzy::canonical::Canonicalizer;
+/// # use std::collections::BTreeMap;
+/// let mut variants = BTreeMap::new();
+/// variants.insert("Acme Corporation", vec!["ACME Corp", "Acme Inc"]);
+/// variants.insert("Globex", vec!["Globex Corporation"]);
+/// let companies: Canonicalizer = variants.into_iter().collect();
+/// assert_eq!(companies.best_match("acme corp."), Some(("Acme Corporation", 100)));
+/// assert_eq!(companies.canonicalize("Initech"), None);
+/// ```
+pub struct Canonicalizer {
+    labels: Vec<String>,
+    /// Each variant with the index of its label.
+    variants: Vec<(String, usize)>,
+    processor: Option<Box<dyn Processor + Send + Sync>>,
+    scorer: Box<dyn Scorer + Send + Sync>,
+    score_cutoff: u8,
+    unknown: Option<String>,
+}
+
+impl Default for Canonicalizer {
+    fn default() -> Canonicalizer {
+        Canonicalizer::new()
+    }
+}
+
+impl Canonicalizer {
+    /// A canonicalizer without labels, with the default configuration.
+    pub fn new() -> Canonicalizer {
+        Canonicalizer {
+            labels: vec![],
+            variants: vec![],
+            processor: None,
+            scorer: Box::new(WRatio),
+            score_cutoff: 80,
+            unknown: None,
+        }
+    }
+
+    /// Adds the canonical `label`, known by itself and `variants`. Adding a
+    /// label again adds to its variants.
+    pub fn label<I>(mut self, label: impl AsRef<str>, variants: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.add(label.as_ref(), variants);
+        self
+    }
+
+    /// Processes strings with `processor` instead.
+    pub fn processor<P: Processor + Send + Sync + 'static>(mut self, processor: P) -> Self {
+        self.processor = Some(Box::new(processor));
+        self
+    }
+
+    /// Scores strings with `scorer` instead.
+    pub fn scorer<S: Scorer + Send + Sync + 'static>(mut self, scorer: S) -> Self {
+        self.scorer = Box::new(scorer);
+        self
+    }
+
+    /// Only matches variants scoring at least `score_cutoff`.
+    pub fn score_cutoff(mut self, score_cutoff: u8) -> Self {
+        self.score_cutoff = score_cutoff;
+        self
+    }
+
+    /// Canonicalizes strings matching no variant to `label`, rather than to
+    /// nothing.
+    pub fn unknown(mut self, label: impl Into<String>) -> Self {
+        self.unknown = Some(label.into());
+        self
+    }
+
+    fn add<I>(&mut self, label: &str, variants: I)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let index = match self.labels.iter().position(|known| known == label) {
+            Some(index) => index,
+            None => {
+                self.labels.push(label.to_string());
+                self.variants
+                    .push((label.to_string(), self.labels.len() - 1));
+                self.labels.len() - 1
+            }
+        };
+        self.variants.extend(
+            variants
+                .into_iter()
+                .map(|variant| (variant.as_ref().to_string(), index)),
+        );
+    }
+
+    /// The canonical labels, in the order they were added.
+    pub fn labels(&self) -> impl Iterator<Item = &str> + '_ {
+        self.labels.iter().map(String::as_str)
+    }
+
+    /// The label of the variant best matching `input`, and its score, or
+    /// `None` if no variant scores at least the cutoff.
+    pub fn best_match(&self, input: impl AsRef<str>) -> Option<(&str, u8)> {
+        let processor = |s: &str, force_ascii: bool| match &self.processor {
+            Some(processor) => processor.process(s, force_ascii),
+            None => full_process(s, force_ascii),
+        };
+        let scorer = |a: &str, b: &str| -> Score { self.scorer.score(a, b) };
+        let variants = self
+            .variants
+            .iter()
+            .map(|(variant, index)| (variant.as_str(), *index));
+        let (_, index, score) =
+            extract_one_with_payload(input, variants, processor, scorer, self.score_cutoff)?;
+        Some((&self.labels[index], score))
+    }
+
+    /// The label of the variant best matching `input`, or the
+    /// [unknown](Canonicalizer::unknown) label if no variant scores at least
+    /// the cutoff, if there is one.
+    pub fn canonicalize(&self, input: impl AsRef<str>) -> Option<&str> {
+        self.best_match(input)
+            .map(|(label, _)| label)
+            .or(self.unknown.as_deref())
+    }
+}
+
+impl<L, I> FromIterator<(L, I)> for Canonicalizer
+where
+    L: AsRef<str>,
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    fn from_iter<T: IntoIterator<Item = (L, I)>>(labels: T) -> Canonicalizer {
+        let mut canonicalizer = Canonicalizer::new();
+        for (label, variants) in labels {
+            canonicalizer.add(label.as_ref(), variants);
+        }
+        canonicalizer
+    }
+}
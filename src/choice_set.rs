@@ -0,0 +1,217 @@
+//! [ChoiceSet], choices tokenized once for extracting with the token scorers.
+//! Requires feature "token".
+//!
+//! [token_sort_ratio](fuzz::token_sort_ratio) and
+//! [token_set_ratio](fuzz::token_set_ratio) process and split both of their
+//! strings on every call, so extracting from the same choices again and again
+//! tokenizes every choice for every query. A [ChoiceSet] does that once, as
+//! choices are added, keeping each choice's sorted-token string and the set
+//! of its tokens, and only tokenizes the query when extracting.
+//!
+//! ```
+//! # use fuzzywuzzy::choice_set::ChoiceSet;
+//! let choices: ChoiceSet = ["new york mets", "New York Yankees", "atlanta braves"].iter().collect();
+//! assert_eq!(choices.sorted_tokens(1), Some("new yankees york"));
+//! assert_eq!(choices.extract_token_sort("mets new york", 50), vec![(0, 100), (1, 55)]);
+//! assert_eq!(choices.extract_token_set("the new york mets", 60), vec![(0, 100), (1, 67)]);
+//! ```
+
+use crate::fuzz::{self, CharRatio};
+use crate::utils::Mode;
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+/// A choice of a [ChoiceSet] with its tokens.
+#[derive(Clone, Debug)]
+struct Entry {
+    original: String,
+    /// The processed choice's tokens, sorted and joined by spaces.
+    sorted: String,
+    /// The distinct tokens, sorted.
+    distinct: Vec<String>,
+    set: HashSet<String>,
+}
+
+impl Entry {
+    fn new(original: &str) -> Entry {
+        let processed = Mode::PythonCompat.process(original, true);
+        let mut tokens: Vec<&str> = processed.split_whitespace().collect();
+        tokens.sort_unstable();
+        let sorted = tokens.join(" ");
+        tokens.dedup();
+        Entry {
+            original: original.to_string(),
+            sorted,
+            distinct: tokens.iter().map(|&token| token.to_string()).collect(),
+            set: tokens.iter().map(|&token| token.to_string()).collect(),
+        }
+    }
+}
+
+/// Choices with their tokens precomputed, see the [module](self) docs.
+#[derive(Clone, Debug, Default)]
+pub struct ChoiceSet {
+    choices: Vec<Entry>,
+}
+
+impl ChoiceSet {
+    /// An empty set.
+    pub fn new() -> ChoiceSet {
+        ChoiceSet::default()
+    }
+
+    /// Adds a choice, returning its index.
+    pub fn push(&mut self, choice: &str) -> usize {
+        self.choices.push(Entry::new(choice));
+        self.choices.len() - 1
+    }
+
+    /// The number of choices.
+    pub fn len(&self) -> usize {
+        self.choices.len()
+    }
+
+    /// Whether there are no choices.
+    pub fn is_empty(&self) -> bool {
+        self.choices.is_empty()
+    }
+
+    /// The choice at `index`, as it was added.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.choices.get(index).map(|entry| entry.original.as_str())
+    }
+
+    /// The processed choice at `index` with its tokens sorted, as compared by
+    /// [ChoiceSet::extract_token_sort].
+    pub fn sorted_tokens(&self, index: usize) -> Option<&str> {
+        self.choices.get(index).map(|entry| entry.sorted.as_str())
+    }
+
+    /// The choices in order, as they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.choices.iter().map(|entry| entry.original.as_str())
+    }
+
+    /// The indices of the choices whose
+    /// [token_sort_ratio](fuzz::token_sort_ratio) with `query` is at least
+    /// `score_cutoff`, with their scores, in order.
+    ///
+    /// Scores are those of `token_sort_ratio(query, choice, true, true)`.
+    pub fn extract_token_sort(&self, query: impl AsRef<str>, score_cutoff: u8) -> Vec<(usize, u8)> {
+        let query = Entry::new(query.as_ref());
+        self.extract(&query, score_cutoff, |entry| {
+            fuzz::ratio_with_mode(&query.sorted, &entry.sorted, Mode::PythonCompat)
+        })
+    }
+
+    /// The indices of the choices whose
+    /// [token_set_ratio](fuzz::token_set_ratio) with `query` is at least
+    /// `score_cutoff`, with their scores, in order.
+    ///
+    /// Scores are those of `token_set_ratio(query, choice, true, true)`.
+    pub fn extract_token_set(&self, query: impl AsRef<str>, score_cutoff: u8) -> Vec<(usize, u8)> {
+        let query = Entry::new(query.as_ref());
+        self.extract(&query, score_cutoff, |entry| {
+            let (intersection, diff2to1): (Vec<&str>, Vec<&str>) = entry
+                .distinct
+                .iter()
+                .map(String::as_str)
+                .partition(|&token| query.set.contains(token));
+            let diff1to2: Vec<&str> = query
+                .distinct
+                .iter()
+                .map(String::as_str)
+                .filter(|&token| !entry.set.contains(token))
+                .collect();
+            fuzz::sorted_set_ratio(
+                &intersection,
+                &diff1to2,
+                &diff2to1,
+                CharRatio::Ratio,
+                Mode::PythonCompat,
+            )
+        })
+    }
+
+    fn extract<F: Fn(&Entry) -> u8>(
+        &self,
+        query: &Entry,
+        score_cutoff: u8,
+        score: F,
+    ) -> Vec<(usize, u8)> {
+        count!(candidates_scored, self.choices.len());
+        self.choices
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                // The trivial cases of the token scorers, on the unprocessed strings.
+                let score = if query.original == entry.original {
+                    100
+                } else if query.original.is_empty() ^ entry.original.is_empty() {
+                    0
+                } else {
+                    score(entry)
+                };
+                Some((index, score)).filter(|&(_, score)| score >= score_cutoff)
+            })
+            .collect()
+    }
+}
+
+impl<T: AsRef<str>> Extend<T> for ChoiceSet {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, choices: I) {
+        for choice in choices {
+            self.push(choice.as_ref());
+        }
+    }
+}
+
+impl<T: AsRef<str>> FromIterator<T> for ChoiceSet {
+    fn from_iter<I: IntoIterator<Item = T>>(choices: I) -> ChoiceSet {
+        let mut set = ChoiceSet::new();
+        set.extend(choices);
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fuzz::{Scorer, TokenSetRatio, TokenSortRatio};
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn extraction_matches_the_token_scorers() {
+        let mut rng = thread_rng();
+        let mut random = || -> String {
+            (0..rng.gen_range(0..12))
+                .map(|_| ['a', 'b', 'B', ' ', ' ', '-', 'é'][rng.gen_range(0..7)])
+                .collect()
+        };
+        for _ in 0..100 {
+            let choices: Vec<String> = (0..20).map(|_| random()).collect();
+            let set: ChoiceSet = choices.iter().collect();
+            let query = random();
+            let expected = |scorer: &dyn Scorer| -> Vec<(usize, u8)> {
+                choices
+                    .iter()
+                    .map(|choice| scorer.score(&query, choice).percent())
+                    .enumerate()
+                    .filter(|&(_, score)| score >= 40)
+                    .collect()
+            };
+            assert_eq!(
+                set.extract_token_sort(&query, 40),
+                expected(&TokenSortRatio),
+                "{:?}",
+                query
+            );
+            assert_eq!(
+                set.extract_token_set(&query, 40),
+                expected(&TokenSetRatio),
+                "{:?}",
+                query
+            );
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! Grouping pairwise matches into clusters of duplicates.
+//!
+//! Scoring choices against each other, e.g. with
+//! [match_pairs](crate::process::match_pairs) or a `cdist` matrix, gives
+//! pairs of near-duplicates, but reviewing duplicates goes by group: if `a`
+//! matches `b` and `b` matches `c`, all three are one entity, or need a human
+//! to say otherwise. [Clusterer] joins matching pairs into their connected
+//! components with a union-find, best matches first, optionally keeping
+//! clusters below a size, since one long chain of weak matches can otherwise
+//! swallow a whole dataset.
+//!
+//! ```
+//! # use fuzzywuzzy::cluster::{Cluster, Clusterer};
+//! let matches = [(0, 1, 95), (1, 2, 88), (3, 4, 90), (2, 5, 60)];
+//! let clusters = Clusterer::new().threshold(80).cluster(6, matches.iter().copied());
+//! assert_eq!(clusters, vec![
+//!     Cluster { members: vec![0, 1, 2], score: 88 },
+//!     Cluster { members: vec![3, 4], score: 90 },
+//! ]);
+//! ```
+
+use std::cmp::Reverse;
+
+/// How [Clusterer] treats matches with equal scores competing for room in a
+/// cluster of limited size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ties {
+    /// Join equally scored matches in the order they were given, as long as
+    /// each fits.
+    InOrder,
+    /// Join equally scored matches only if all of those connected to each
+    /// other fit in one cluster together, so the clusters don't depend on the
+    /// order of the matches. Matches left out leave their choices for a
+    /// human to review.
+    Skip,
+}
+
+/// A group of choices connected by matches.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cluster {
+    /// The indices of the choices, in ascending order.
+    pub members: Vec<usize>,
+    /// The lowest score of the matches joining the cluster, i.e. how weakly
+    /// its least similar member is attached.
+    pub score: u8,
+}
+
+/// Clusters pairwise matches, see the [module](self) docs.
+///
+/// By default all given matches are joined, clusters can grow to any size,
+/// and ties are joined [in order](Ties::InOrder).
+///
+/// ```
+/// # use fuzzywuzzy::cluster::{Clusterer, Ties};
+/// // Choice 0 matches 1 and 2 equally well, but only one of them fits.
+/// let matches = [(0, 1, 90), (0, 2, 90), (3, 4, 85)];
+/// let members = |clusterer: Clusterer| -> Vec<Vec<usize>> {
+///     clusterer.cluster(5, matches.iter().copied())
+///         .into_iter()
+///         .map(|cluster| cluster.members)
+///         .collect()
+/// };
+/// assert_eq!(members(Clusterer::new()), vec![vec![0, 1, 2], vec![3, 4]]);
+/// assert_eq!(members(Clusterer::new().max_size(2)), vec![vec![0, 1], vec![3, 4]]);
+/// assert_eq!(members(Clusterer::new().max_size(2).ties(Ties::Skip)), vec![vec![3, 4]]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Clusterer {
+    threshold: u8,
+    max_size: Option<usize>,
+    ties: Ties,
+}
+
+impl Default for Clusterer {
+    fn default() -> Clusterer {
+        Clusterer::new()
+    }
+}
+
+impl Clusterer {
+    /// A clusterer with the default configuration.
+    pub fn new() -> Clusterer {
+        Clusterer {
+            threshold: 0,
+            max_size: None,
+            ties: Ties::InOrder,
+        }
+    }
+
+    /// Ignores matches scoring below `threshold`.
+    pub fn threshold(mut self, threshold: u8) -> Clusterer {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Doesn't join matches which would make a cluster larger than
+    /// `max_size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_size` is less than 2.
+    pub fn max_size(mut self, max_size: usize) -> Clusterer {
+        assert!(max_size >= 2, "clusters must be allowed two members");
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Treats equally scored matches as `ties` says.
+    pub fn ties(mut self, ties: Ties) -> Clusterer {
+        self.ties = ties;
+        self
+    }
+
+    /// Clusters `len` choices by `matches` of the choices at two indices with
+    /// a score.
+    ///
+    /// Returns the clusters of at least two choices, ordered by their first
+    /// member. Choices in no cluster matched nothing that was joined.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a match has an index of `len` or more.
+    pub fn cluster<I>(&self, len: usize, matches: I) -> Vec<Cluster>
+    where
+        I: IntoIterator<Item = (usize, usize, u8)>,
+    {
+        let mut matches: Vec<(usize, usize, u8)> = matches
+            .into_iter()
+            .inspect(|&(a, b, _)| assert!(a < len && b < len, "match indices must be below len"))
+            .filter(|&(a, b, score)| a != b && score >= self.threshold)
+            .collect();
+        matches.sort_by_key(|&(_, _, score)| Reverse(score));
+
+        let max_size = self.max_size.unwrap_or(usize::MAX);
+        let mut sets = DisjointSets::new(len);
+        for ties in matches.chunk_by(|x, y| x.2 == y.2) {
+            // What joining all the ties would make, to tell which fit.
+            let mut tentative = match self.ties {
+                Ties::Skip if self.max_size.is_some() => {
+                    let mut tentative = sets.clone();
+                    for &(a, b, score) in ties {
+                        tentative.union(a, b, score);
+                    }
+                    Some(tentative)
+                }
+                _ => None,
+            };
+            for &(a, b, score) in ties {
+                let fits = match &mut tentative {
+                    Some(tentative) => tentative.size(a) <= max_size,
+                    None => sets.size(a) + sets.size(b) <= max_size,
+                };
+                if fits {
+                    sets.union(a, b, score);
+                }
+            }
+        }
+        sets.clusters()
+    }
+}
+
+/// A union-find over choices, with the lowest score joining each set.
+#[derive(Clone, Debug)]
+struct DisjointSets {
+    parents: Vec<usize>,
+    /// The size of the set each root represents.
+    sizes: Vec<usize>,
+    /// The lowest score joining the set each root represents.
+    scores: Vec<u8>,
+}
+
+impl DisjointSets {
+    fn new(len: usize) -> DisjointSets {
+        DisjointSets {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            scores: vec![u8::MAX; len],
+        }
+    }
+
+    fn find(&mut self, mut x: usize) -> usize {
+        while self.parents[x] != x {
+            // Path halving.
+            self.parents[x] = self.parents[self.parents[x]];
+            x = self.parents[x];
+        }
+        x
+    }
+
+    fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.sizes[root]
+    }
+
+    /// Joins the sets of `a` and `b`, if they differ.
+    fn union(&mut self, a: usize, b: usize, score: u8) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        let (root, child) = if self.sizes[a] >= self.sizes[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parents[child] = root;
+        self.sizes[root] += self.sizes[child];
+        self.scores[root] = self.scores[root].min(self.scores[child]).min(score);
+    }
+
+    fn clusters(mut self) -> Vec<Cluster> {
+        let mut clusters: Vec<Cluster> = vec![];
+        // The index in `clusters` of each root's cluster.
+        let mut index = vec![usize::MAX; self.parents.len()];
+        for x in 0..self.parents.len() {
+            let root = self.find(x);
+            if self.sizes[root] < 2 {
+                continue;
+            }
+            if index[root] == usize::MAX {
+                index[root] = clusters.len();
+                clusters.push(Cluster {
+                    members: vec![],
+                    score: self.scores[root],
+                });
+            }
+            clusters[index[root]].members.push(x);
+        }
+        clusters
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn skipped_ties_are_independent_of_order() {
+        let matches = [
+            (0, 1, 90),
+            (1, 2, 90),
+            (2, 3, 90),
+            (4, 5, 90),
+            (5, 6, 80),
+            (7, 8, 70),
+        ];
+        let clusterer = Clusterer::new().max_size(3).ties(Ties::Skip);
+        let expected = vec![
+            Cluster {
+                members: vec![4, 5, 6],
+                score: 80,
+            },
+            Cluster {
+                members: vec![7, 8],
+                score: 70,
+            },
+        ];
+        assert_eq!(clusterer.cluster(9, matches.iter().copied()), expected);
+        assert_eq!(
+            clusterer.cluster(9, matches.iter().rev().copied()),
+            expected
+        );
+        // Joined in order, the chain of ties is cut where it outgrows the limit.
+        assert_eq!(
+            Clusterer::new()
+                .max_size(3)
+                .cluster(9, matches.iter().copied())[0],
+            Cluster {
+                members: vec![0, 1, 2],
+                score: 90,
+            }
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Locale aware token ordering. Requires feature "collation".
+//!
+//! The token sorting scorers order tokens by code point by default, which
+//! sorts e.g. every word starting with `ä` after every word starting with `z`.
+//! A [LocaleCollator] orders tokens following the
+//! [Unicode Collation Algorithm](https://www.unicode.org/reports/tr10/) as
+//! tailored for a locale, via [icu_collator].
+//!
+//! ```
+//! # use fuzzywuzzy::collation::LocaleCollator;
+//! # use fuzzywuzzy::fuzz::{token_sort_ratio, token_sort_ratio_by};
+//! # use fuzzywuzzy::segmentation::WhitespaceSegmenter;
+//! let german = LocaleCollator::new("de").unwrap();
+//! assert_eq!(token_sort_ratio("zebra äpfel", "apfel zebra", false, true), 45);
+//! assert_eq!(
+//!     token_sort_ratio_by("zebra äpfel", "apfel zebra", false, true, &WhitespaceSegmenter, german.comparator()),
+//!     91);
+//! ```
+
+use icu_collator::options::CollatorOptions;
+use icu_collator::{Collator, CollatorBorrowed};
+use icu_locale_core::Locale;
+use std::cmp::Ordering;
+
+/// Compares strings according to the collation rules of a locale.
+pub struct LocaleCollator {
+    collator: CollatorBorrowed<'static>,
+}
+
+impl LocaleCollator {
+    /// Creates a collator for a [BCP 47](https://www.rfc-editor.org/info/bcp47) language tag like `"de"` or `"sv-SE"`.
+    ///
+    /// Returns `None` if the tag can't be parsed or no collation data is available for it.
+    pub fn new(locale: &str) -> Option<LocaleCollator> {
+        let locale: Locale = locale.parse().ok()?;
+        let collator = Collator::try_new((&locale).into(), CollatorOptions::default()).ok()?;
+        Some(LocaleCollator { collator })
+    }
+
+    /// Compares `a` and `b` under this collator's rules.
+    ///
+    /// ```
+    /// # use fuzzywuzzy::collation::LocaleCollator;
+    /// # use std::cmp::Ordering;
+    /// let german = LocaleCollator::new("de").unwrap();
+    /// assert_eq!("äpfel".cmp("zebra"), Ordering::Greater);
+    /// assert_eq!(german.compare("äpfel", "zebra"), Ordering::Less);
+    /// // Swedish sorts ä after z.
+    /// let swedish = LocaleCollator::new("sv").unwrap();
+    /// assert_eq!(swedish.compare("äpple", "zebra"), Ordering::Greater);
+    /// ```
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        self.collator.compare(a, b)
+    }
+
+    /// Returns [LocaleCollator::compare] as a closure, for use with e.g.
+    /// [token_sort_ratio_by](crate::fuzz::token_sort_ratio_by).
+    pub fn comparator(&self) -> impl Fn(&str, &str) -> Ordering + '_ {
+        move |a, b| self.compare(a, b)
+    }
+}
@@ -0,0 +1,196 @@
+//! [FuzzyComparer], which bundles configuration for repeated comparisons.
+//! Requires feature "token".
+
+use crate::fuzz::{self, Scorer};
+use crate::processing::Processor;
+use crate::score::Score;
+use crate::utils::Mode;
+
+/// Captures how strings are processed and scored once, so the options don't
+/// have to be passed to every call.
+///
+/// By default strings are processed with [Mode::process] in [Mode::PythonCompat]
+/// mode, without forcing ASCII, and scored with [wratio](crate::fuzz::wratio).
+/// There is no score cutoff.
+///
+/// ```
+/// # use fuzzywuzzy::comparer::FuzzyComparer;
+/// # use fuzzywuzzy::utils::Mode;
+/// let comparer = FuzzyComparer::new().score_cutoff(80).mode(Mode::Improved);
+/// assert_eq!(comparer.compare("new york mets", "new YORK mets"), 100);
+/// let choices = ["new york mets", "atlanta braves", "new york yankees"];
+/// let results: Vec<_> = comparer.extract("new york", choices.iter())
+///     .into_iter()
+///     .map(|(choice, score)| (choice, score.percent()))
+///     .collect();
+/// assert_eq!(results, vec![("new york mets".to_string(), 90), ("new york yankees".to_string(), 90)]);
+/// let (best, score) = comparer.extract_one("braves", choices.iter()).unwrap();
+/// assert_eq!((best.as_str(), score.percent()), ("atlanta braves", 90));
+/// let case_sensitive = FuzzyComparer::new().case_sensitive(true);
+/// assert_eq!(case_sensitive.compare("new york mets", "new YORK mets"), 72);
+/// ```
+///
+/// A custom [Scorer] is given already processed strings. Note that built-in
+/// scorers like [WRatio](crate::fuzz::WRatio) process their inputs again, which
+/// e.g. undoes [FuzzyComparer::case_sensitive].
+/// ```
+/// # use fuzzywuzzy::comparer::FuzzyComparer;
+/// # use fuzzywuzzy::fuzz::TokenSetRatio;
+/// let comparer = FuzzyComparer::new()
+///     .processor(|s: &str, _: bool| s.replace("St.", "Street"))
+///     .scorer(TokenSetRatio);
+/// assert_eq!(comparer.compare("Main St.", "main street"), 100);
+/// ```
+pub struct FuzzyComparer {
+    processor: Option<Box<dyn Processor + Send + Sync>>,
+    scorer: Option<Box<dyn Scorer + Send + Sync>>,
+    score_cutoff: u8,
+    force_ascii: bool,
+    case_sensitive: bool,
+    mode: Mode,
+}
+
+impl Default for FuzzyComparer {
+    fn default() -> FuzzyComparer {
+        FuzzyComparer::new()
+    }
+}
+
+impl FuzzyComparer {
+    /// A comparer with the default configuration.
+    pub fn new() -> FuzzyComparer {
+        FuzzyComparer {
+            processor: None,
+            scorer: None,
+            score_cutoff: 0,
+            force_ascii: false,
+            case_sensitive: false,
+            mode: Mode::PythonCompat,
+        }
+    }
+
+    /// Processes strings with `processor`, replacing [Mode::process].
+    ///
+    /// [FuzzyComparer::case_sensitive] has no effect with a custom processor.
+    pub fn processor<P: Processor + Send + Sync + 'static>(
+        mut self,
+        processor: P,
+    ) -> FuzzyComparer {
+        self.processor = Some(Box::new(processor));
+        self
+    }
+
+    /// Scores processed strings with `scorer`, replacing [wratio](crate::fuzz::wratio).
+    pub fn scorer<S: Scorer + Send + Sync + 'static>(mut self, scorer: S) -> FuzzyComparer {
+        self.scorer = Some(Box::new(scorer));
+        self
+    }
+
+    /// Drops choices scoring less than `score_cutoff` from extraction results.
+    pub fn score_cutoff(mut self, score_cutoff: u8) -> FuzzyComparer {
+        self.score_cutoff = score_cutoff;
+        self
+    }
+
+    /// Whether to remove non-ASCII characters while processing.
+    pub fn force_ascii(mut self, force_ascii: bool) -> FuzzyComparer {
+        self.force_ascii = force_ascii;
+        self
+    }
+
+    /// Whether to keep letters' case while processing, rather than lower-casing them.
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> FuzzyComparer {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Which [Mode] to process and score in.
+    pub fn mode(mut self, mode: Mode) -> FuzzyComparer {
+        self.mode = mode;
+        self
+    }
+
+    /// Processes `s` as configured.
+    pub fn process(&self, s: &str) -> String {
+        match &self.processor {
+            Some(processor) => processor.process(s, self.force_ascii),
+            None if self.case_sensitive => self.mode.process_preserving_case(s, self.force_ascii),
+            None => self.mode.process(s, self.force_ascii),
+        }
+    }
+
+    /// Processes and scores `a` against `b`.
+    pub fn compare(&self, a: impl AsRef<str>, b: impl AsRef<str>) -> Score {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        self.score_processed(&self.process(a), &self.process(b))
+    }
+
+    fn score_processed(&self, a: &str, b: &str) -> Score {
+        match &self.scorer {
+            Some(scorer) => scorer.score(a, b),
+            None => Score::new(fuzz::wratio_with_mode(
+                a,
+                b,
+                self.force_ascii,
+                false,
+                self.mode,
+            ))
+            .expect("wratio returns at most 100"),
+        }
+    }
+
+    fn upper_bound(&self, a: &str, b: &str) -> Score {
+        match &self.scorer {
+            Some(scorer) => scorer.upper_bound(a, b),
+            None => Score::MAX,
+        }
+    }
+
+    /// Scores each of `choices` against `query`, returning those meeting the
+    /// score cutoff in their original order.
+    ///
+    /// Like [extract_without_order](crate::process::extract_without_order), but `query`
+    /// is only processed once.
+    pub fn extract<I, T>(&self, query: impl AsRef<str>, choices: I) -> Vec<(String, Score)>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let query = query.as_ref();
+        let processed_query = self.process(query);
+        let mut results = vec![];
+        for choice in choices {
+            let processed = self.process(choice.as_ref());
+            if self.score_cutoff > 0
+                && self.upper_bound(&processed_query, &processed) < self.score_cutoff
+            {
+                count!(candidates_pruned, 1);
+                continue;
+            }
+            let score = self.score_processed(&processed_query, &processed);
+            count!(candidates_scored, 1);
+            if score >= self.score_cutoff {
+                results.push((choice.as_ref().to_string(), score));
+            } else {
+                count!(candidates_pruned, 1);
+            }
+        }
+        results
+    }
+
+    /// The best scoring of `choices` meeting the score cutoff, the first one if there's a tie.
+    ///
+    /// See [extract_one](crate::process::extract_one).
+    pub fn extract_one<I, T>(&self, query: impl AsRef<str>, choices: I) -> Option<(String, Score)>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let query = query.as_ref();
+        // Reversed so the first of several equal maxima wins, like in Python.
+        self.extract(query, choices)
+            .into_iter()
+            .rev()
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+    }
+}
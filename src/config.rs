@@ -0,0 +1,262 @@
+//! Scoring configurations which can be loaded with [serde](https://serde.rs),
+//! e.g. from TOML or JSON. Requires feature "serde".
+//!
+//! A [ScoringConfig] describes a [FuzzyComparer]: the processing steps of a
+//! [Pipeline], the scorer or weighted scorers, the cutoff and the other
+//! options, so matching can be tuned without recompiling. Every field is
+//! optional and defaults to [FuzzyComparer::new]'s behavior.
+//!
+//! ```
+//! # use fuzzywuzzy::config::ScoringConfig;
+//! let config: ScoringConfig = toml::from_str(r#"
+//!     processor = ["replace_non_alphanumeric", "lowercase", "english_stopwords", "collapse_whitespace"]
+//!     score_cutoff = 80
+//!     scorer = [
+//!         { kind = "token_set_ratio", weight = 3 },
+//!         { kind = "ratio", weight = 1 },
+//!     ]
+//! "#).unwrap();
+//! let comparer = config.comparer().unwrap();
+//! assert_eq!(comparer.process("The Lord of the Rings"), "lord rings");
+//! assert_eq!(comparer.compare("The Lord of the Rings", "lord of the rings"), 100);
+//! assert_eq!(comparer.compare("The Two Towers", "two towers extended"), 92);
+//! ```
+//!
+//! ```
+//! # use fuzzywuzzy::config::ScoringConfig;
+//! let config: ScoringConfig = serde_json::from_str(r#"{"scorer": "ratio", "mode": "improved"}"#).unwrap();
+//! assert_eq!(config.comparer().unwrap().compare("New York", "new york!"), 100);
+//! ```
+
+use crate::comparer::FuzzyComparer;
+use crate::error::Error;
+#[cfg(feature = "ngram")]
+use crate::fuzz::{DiceRatio, WordBigramRatio, WordBigramSetRatio};
+use crate::fuzz::{
+    PartialRatio, PartialTokenRatio, PartialTokenSetRatio, PartialTokenSortRatio,
+    PartialTranspositionRatio, QRatio, Ratio, Scorer, TokenSetRatio, TokenSetTranspositionRatio,
+    TokenSortRatio, TokenSortTranspositionRatio, TranspositionRatio, WRatio,
+};
+use crate::normalization::StopwordNormalizer;
+use crate::processing::Pipeline;
+use crate::score::Score;
+use crate::utils::Mode;
+use serde::{Deserialize, Serialize};
+
+/// A step of a [Pipeline], named in snake case, e.g. `"lowercase"`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessorStep {
+    /// [Pipeline::lowercase].
+    Lowercase,
+    /// [Pipeline::ascii_only].
+    AsciiOnly,
+    /// `Pipeline::strip_accents`. Requires default feature "normalization".
+    #[cfg(feature = "normalization")]
+    StripAccents,
+    /// [Pipeline::strip_invisible].
+    StripInvisible,
+    /// [Pipeline::replace_non_alphanumeric].
+    ReplaceNonAlphanumeric,
+    /// [Pipeline::trim].
+    Trim,
+    /// [Pipeline::collapse_whitespace].
+    CollapseWhitespace,
+    /// Removes [StopwordNormalizer::english] stopwords.
+    EnglishStopwords,
+    /// Removes the given stopwords, written `{ stopwords = ["a", "the"] }`.
+    Stopwords(Vec<String>),
+}
+
+/// A built-in scorer, named in snake case, e.g. `"wratio"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScorerKind {
+    Ratio,
+    PartialRatio,
+    TokenSortRatio,
+    PartialTokenSortRatio,
+    TokenSetRatio,
+    PartialTokenSetRatio,
+    PartialTokenRatio,
+    QRatio,
+    WRatio,
+    TranspositionRatio,
+    PartialTranspositionRatio,
+    TokenSortTranspositionRatio,
+    TokenSetTranspositionRatio,
+    #[cfg(feature = "ngram")]
+    DiceRatio,
+    #[cfg(feature = "ngram")]
+    WordBigramRatio,
+    #[cfg(feature = "ngram")]
+    WordBigramSetRatio,
+}
+
+impl ScorerKind {
+    fn scorer(self) -> Box<dyn Scorer + Send + Sync> {
+        match self {
+            ScorerKind::Ratio => Box::new(Ratio),
+            ScorerKind::PartialRatio => Box::new(PartialRatio),
+            ScorerKind::TokenSortRatio => Box::new(TokenSortRatio),
+            ScorerKind::PartialTokenSortRatio => Box::new(PartialTokenSortRatio),
+            ScorerKind::TokenSetRatio => Box::new(TokenSetRatio),
+            ScorerKind::PartialTokenSetRatio => Box::new(PartialTokenSetRatio),
+            ScorerKind::PartialTokenRatio => Box::new(PartialTokenRatio),
+            ScorerKind::QRatio => Box::new(QRatio),
+            ScorerKind::WRatio => Box::new(WRatio),
+            ScorerKind::TranspositionRatio => Box::new(TranspositionRatio),
+            ScorerKind::PartialTranspositionRatio => Box::new(PartialTranspositionRatio),
+            ScorerKind::TokenSortTranspositionRatio => Box::new(TokenSortTranspositionRatio),
+            ScorerKind::TokenSetTranspositionRatio => Box::new(TokenSetTranspositionRatio),
+            #[cfg(feature = "ngram")]
+            ScorerKind::DiceRatio => Box::new(DiceRatio),
+            #[cfg(feature = "ngram")]
+            ScorerKind::WordBigramRatio => Box::new(WordBigramRatio),
+            #[cfg(feature = "ngram")]
+            ScorerKind::WordBigramSetRatio => Box::new(WordBigramSetRatio),
+        }
+    }
+}
+
+/// A scorer and its weight in a weighted average of scores.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WeightedScorer {
+    pub kind: ScorerKind,
+    pub weight: f64,
+}
+
+/// How a [ScoringConfig] scores: one scorer, or the weighted average of several.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ScorerConfig {
+    Single(ScorerKind),
+    Weighted(Vec<WeightedScorer>),
+}
+
+/// The configuration of a [FuzzyComparer], see the [module](self) docs.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoringConfig {
+    /// The steps of the processing [Pipeline], if not the default processing.
+    pub processor: Option<Vec<ProcessorStep>>,
+    /// The scorer, if not [wratio](crate::fuzz::wratio).
+    pub scorer: Option<ScorerConfig>,
+    /// See [FuzzyComparer::score_cutoff].
+    pub score_cutoff: u8,
+    /// See [FuzzyComparer::force_ascii].
+    pub force_ascii: bool,
+    /// See [FuzzyComparer::case_sensitive].
+    pub case_sensitive: bool,
+    /// See [FuzzyComparer::mode]. Also rounds weighted averages.
+    pub mode: Mode,
+}
+
+impl ScoringConfig {
+    /// The processing pipeline, if the configuration has one.
+    pub fn pipeline(&self) -> Option<Pipeline> {
+        let steps = self.processor.as_ref()?;
+        Some(
+            steps
+                .iter()
+                .fold(Pipeline::new(), |pipeline, step| match step {
+                    ProcessorStep::Lowercase => pipeline.lowercase(),
+                    ProcessorStep::AsciiOnly => pipeline.ascii_only(),
+                    #[cfg(feature = "normalization")]
+                    ProcessorStep::StripAccents => pipeline.strip_accents(),
+                    ProcessorStep::StripInvisible => pipeline.strip_invisible(),
+                    ProcessorStep::ReplaceNonAlphanumeric => pipeline.replace_non_alphanumeric(),
+                    ProcessorStep::Trim => pipeline.trim(),
+                    ProcessorStep::CollapseWhitespace => pipeline.collapse_whitespace(),
+                    ProcessorStep::EnglishStopwords => {
+                        pipeline.normalizer(StopwordNormalizer::english())
+                    }
+                    ProcessorStep::Stopwords(words) => {
+                        pipeline.normalizer(StopwordNormalizer::with(words.iter()))
+                    }
+                }),
+        )
+    }
+
+    /// The comparer this configuration describes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [Error::InvalidConfig] if the weights of weighted scorers are
+    /// negative or not finite, or don't add up to more than 0.
+    pub fn comparer(&self) -> Result<FuzzyComparer, Error> {
+        let mut comparer = FuzzyComparer::new()
+            .score_cutoff(self.score_cutoff)
+            .force_ascii(self.force_ascii)
+            .case_sensitive(self.case_sensitive)
+            .mode(self.mode);
+        if let Some(pipeline) = self.pipeline() {
+            comparer = comparer.processor(pipeline);
+        }
+        match &self.scorer {
+            None => {}
+            Some(ScorerConfig::Single(kind)) => comparer = comparer.scorer(kind.scorer()),
+            Some(ScorerConfig::Weighted(scorers)) => {
+                if scorers
+                    .iter()
+                    .any(|s| !s.weight.is_finite() || s.weight < 0.0)
+                {
+                    return Err(Error::InvalidConfig(
+                        "weights must be finite and not negative",
+                    ));
+                }
+                let total: f64 = scorers.iter().map(|s| s.weight).sum();
+                if total <= 0.0 {
+                    return Err(Error::InvalidConfig("weights must add up to more than 0"));
+                }
+                let scorers: Vec<(Box<dyn Scorer + Send + Sync>, f64)> = scorers
+                    .iter()
+                    .map(|s| (s.kind.scorer(), s.weight / total))
+                    .collect();
+                let rounding = self.mode.rounding();
+                comparer = comparer.scorer(move |a: &str, b: &str| {
+                    let score: f64 = scorers
+                        .iter()
+                        .map(|(scorer, weight)| weight * scorer.score(a, b).percent() as f64)
+                        .sum();
+                    Score::new(rounding.round(score)).expect("averages of scores are at most 100")
+                });
+            }
+        }
+        Ok(comparer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn configs_round_trip_and_reject_bad_weights() {
+        let config = ScoringConfig {
+            processor: Some(vec![
+                ProcessorStep::Lowercase,
+                ProcessorStep::Stopwords(vec!["the".to_string()]),
+            ]),
+            scorer: Some(ScorerConfig::Weighted(vec![WeightedScorer {
+                kind: ScorerKind::PartialRatio,
+                weight: 0.0,
+            }])),
+            score_cutoff: 50,
+            mode: Mode::Improved,
+            ..ScoringConfig::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ScoringConfig>(&json).unwrap(),
+            config
+        );
+        assert_eq!(
+            config.comparer().err(),
+            Some(Error::InvalidConfig("weights must add up to more than 0"))
+        );
+        assert!(serde_json::from_str::<ScoringConfig>(r#"{"scorer": "nope"}"#).is_err());
+        assert!(serde_json::from_str::<ScoringConfig>(r#"{"cutoff": 80}"#).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+//! Levenshtein distance with substitution costs learned from examples.
+//!
+//! Some substitutions are much likelier than others: OCR mistakes `l` for
+//! `1` and `O` for `0`, and typists hit neighboring keys. [SubstitutionCosts]
+//! gives each substitution its own cost, which [SubstitutionCosts::learn]
+//! estimates from pairs of misspelled and correct strings by aligning them
+//! and counting the substitutions. [weighted_levenshtein] then charges those
+//! costs, and [SubstitutionCosts] scores as a [Scorer] by it.
+//!
+//! ```
+//! # use fuzzywuzzy::confusion::{weighted_levenshtein, SubstitutionCosts};
+//! # use fuzzywuzzy::fuzz::Scorer;
+//! let costs = SubstitutionCosts::learn(&[
+//!     ("he11o", "hello"),
+//!     ("wor1d", "world"),
+//!     ("1ine", "line"),
+//!     ("b00k", "book"),
+//! ]);
+//! assert_eq!(costs.cost('1', 'l'), 0.2);
+//! assert_eq!(costs.cost('0', 'o'), 1.0 / 3.0);
+//! assert_eq!(weighted_levenshtein("1ab", "lab", &costs), 0.2);
+//! assert_eq!(weighted_levenshtein("2ab", "lab", &costs), 1.0);
+//! assert_eq!(costs.score("c0o1", "cool").percent(), 87);
+//! ```
+
+use crate::fuzz::Scorer;
+use crate::primitives::{self, EditKind};
+use crate::score::Score;
+use crate::utils::Rounding;
+use std::collections::HashMap;
+
+/// The costs of substituting one character for another, see the
+/// [module](self) docs. Insertions and deletions cost 1.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SubstitutionCosts {
+    costs: HashMap<(char, char), f64>,
+}
+
+impl SubstitutionCosts {
+    /// Costs of 1 for every substitution, as with [primitives::levenshtein].
+    pub fn new() -> SubstitutionCosts {
+        SubstitutionCosts::default()
+    }
+
+    /// Estimates costs from pairs of misspelled and correct strings.
+    ///
+    /// Each pair is aligned by [primitives::levenshtein_editops], and a
+    /// substitution of the correct character by the misspelled one seen `n`
+    /// times in all costs `1 / (n + 1)`. Substitutions never seen cost 1.
+    pub fn learn<A, B>(pairs: &[(A, B)]) -> SubstitutionCosts
+    where
+        A: AsRef<str>,
+        B: AsRef<str>,
+    {
+        let mut counts: HashMap<(char, char), usize> = HashMap::new();
+        for (misspelled, correct) in pairs {
+            let misspelled: Vec<char> = misspelled.as_ref().chars().collect();
+            let correct: Vec<char> = correct.as_ref().chars().collect();
+            for op in primitives::levenshtein_editops(&misspelled, &correct) {
+                if op.kind == EditKind::Replace {
+                    *counts.entry((misspelled[op.a], correct[op.b])).or_insert(0) += 1;
+                }
+            }
+        }
+        SubstitutionCosts {
+            costs: counts
+                .into_iter()
+                .map(|(pair, n)| (pair, 1.0 / (n + 1) as f64))
+                .collect(),
+        }
+    }
+
+    /// Sets the cost of substituting `to` for `from`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cost` isn't between 0.0 and 1.0.
+    pub fn set(&mut self, from: char, to: char, cost: f64) {
+        assert!((0.0..=1.0).contains(&cost), "cost must be in [0.0, 1.0]");
+        self.costs.insert((from, to), cost);
+    }
+
+    /// The cost of substituting `to` for `from`: 0 if they are equal, 1 if
+    /// the cost wasn't learned or set.
+    pub fn cost(&self, from: char, to: char) -> f64 {
+        if from == to {
+            0.0
+        } else {
+            self.costs.get(&(from, to)).copied().unwrap_or(1.0)
+        }
+    }
+}
+
+/// Returns the least total cost of insertions, deletions and substitutions
+/// turning `a` into `b`, with substitutions costing as given by `costs`.
+///
+/// The costs are directional: substituting `b`'s character for `a`'s costs
+/// [SubstitutionCosts::cost] of `a`'s and `b`'s character, so learned costs
+/// apply when `a` is the misspelled string.
+///
+/// Runs in `O(a.len() * b.len())` time and `O(b.len())` space.
+pub fn weighted_levenshtein(a: &str, b: &str, costs: &SubstitutionCosts) -> f64 {
+    let b: Vec<char> = b.chars().collect();
+    // `row[j]` is the distance between the prefix of `a` processed so far
+    // and the first `j` characters of `b`.
+    let mut row: Vec<f64> = (0..=b.len()).map(|j| j as f64).collect();
+    for (i, x) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = (i + 1) as f64;
+        for (j, &y) in b.iter().enumerate() {
+            let substitution = diagonal + costs.cost(x, y);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1.0).min(diagonal + 1.0);
+        }
+    }
+    count!(dp_cells, a.chars().count() * b.len());
+    row[b.len()]
+}
+
+/// Scores by [weighted_levenshtein] relative to the length of the longer
+/// string, so strings differing only by cheap substitutions score high.
+impl Scorer for SubstitutionCosts {
+    fn score(&self, a: &str, b: &str) -> Score {
+        let longest = a.chars().count().max(b.chars().count());
+        if longest == 0 {
+            return Score::MAX;
+        }
+        let distance = weighted_levenshtein(a, b, self);
+        let score = Rounding::Python.round(100.0 * (1.0 - distance / longest as f64));
+        Score::new(score).expect("distances are at most the longer length")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn uniform_costs_match_levenshtein() {
+        let costs = SubstitutionCosts::new();
+        for &(a, b) in [
+            ("kitten", "sitting"),
+            ("", "abc"),
+            ("café", "cafe"),
+            ("ab", "ba"),
+        ]
+        .iter()
+        {
+            let (x, y): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+            assert_eq!(
+                weighted_levenshtein(a, b, &costs),
+                primitives::levenshtein(&x, &y) as f64,
+                "{} {}",
+                a,
+                b
+            );
+        }
+    }
+}
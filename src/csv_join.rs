@@ -0,0 +1,269 @@
+//! Joining two CSV files on a fuzzily matching key column. Requires feature "csv".
+//!
+//! Each row of the left file is matched against the key of every row of the
+//! right file, which is read into memory, so the right file should be the
+//! smaller one. The output has the left file's columns, then the right
+//! file's and finally a `score` column.
+//!
+//! ```
+//! # use fuzzywuzzy::csv_join::{CsvJoin, JoinSummary};
+//! let orders = "customer,total\nACME Corp.,120\nGlobex,80\nInitech,45\n";
+//! let customers = "name,country\nAcme Corporation,US\nGlobex Inc,US\nHooli,US\n";
+//! let mut output = vec![];
+//! let summary = CsvJoin::new("customer", "name")
+//!     .cutoff(80)
+//!     .join(orders.as_bytes(), customers.as_bytes(), &mut output)
+//!     .unwrap();
+//! assert_eq!(
+//!     String::from_utf8(output).unwrap(),
+//!     "customer,total,name,country,score\n\
+//!      ACME Corp.,120,Acme Corporation,US,90\n\
+//!      Globex,80,Globex Inc,US,90\n"
+//! );
+//! assert_eq!(summary, JoinSummary { matched: 2, ambiguous: 0, unmatched: 1 });
+//! ```
+
+use crate::fuzz::{Scorer, WRatio};
+use csv::{ReaderBuilder, StringRecord, Writer};
+use std::fmt;
+use std::io;
+
+/// What to do with a left row whose key matches several right rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ambiguity {
+    /// Join the best scoring right row, the earliest one on ties.
+    Best,
+    /// Join every right row scoring at least the cutoff, best first.
+    All,
+    /// Don't join the row if the second best right row scores within
+    /// `margin` points of the best one.
+    Reject { margin: u8 },
+}
+
+/// Why a [CsvJoin] failed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum JoinError {
+    /// Reading or writing CSV failed.
+    Csv(csv::Error),
+    /// The header of an input has no column with this name.
+    MissingColumn(String),
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JoinError::Csv(e) => e.fmt(f),
+            JoinError::MissingColumn(name) => write!(f, "no column named {:?}", name),
+        }
+    }
+}
+
+impl std::error::Error for JoinError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JoinError::Csv(e) => Some(e),
+            JoinError::MissingColumn(_) => None,
+        }
+    }
+}
+
+impl From<csv::Error> for JoinError {
+    fn from(e: csv::Error) -> JoinError {
+        JoinError::Csv(e)
+    }
+}
+
+/// How many left rows a [CsvJoin] matched.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct JoinSummary {
+    /// Rows joined to at least one right row.
+    pub matched: usize,
+    /// Rows not joined because of [Ambiguity::Reject].
+    pub ambiguous: usize,
+    /// Rows without any right row scoring at least the cutoff.
+    pub unmatched: usize,
+}
+
+/// Joins CSV inputs with headers on a key column of each.
+///
+/// By default keys are compared with [WRatio], rows join at a score of 90,
+/// only the [best](Ambiguity::Best) match is joined and left rows without a
+/// match are dropped.
+pub struct CsvJoin {
+    left_key: String,
+    right_key: String,
+    scorer: Box<dyn Scorer>,
+    cutoff: u8,
+    ambiguity: Ambiguity,
+    keep_unmatched: bool,
+}
+
+impl CsvJoin {
+    /// Joins on the column named `left_key` in the left input and the one
+    /// named `right_key` in the right input.
+    pub fn new(left_key: &str, right_key: &str) -> CsvJoin {
+        CsvJoin {
+            left_key: left_key.to_string(),
+            right_key: right_key.to_string(),
+            scorer: Box::new(WRatio),
+            cutoff: 90,
+            ambiguity: Ambiguity::Best,
+            keep_unmatched: false,
+        }
+    }
+
+    /// Compares keys with `scorer`.
+    pub fn scorer<S: Scorer + 'static>(mut self, scorer: S) -> CsvJoin {
+        self.scorer = Box::new(scorer);
+        self
+    }
+
+    /// The least score at which rows join.
+    pub fn cutoff(mut self, cutoff: u8) -> CsvJoin {
+        self.cutoff = cutoff;
+        self
+    }
+
+    /// How to handle keys matching several right rows.
+    pub fn ambiguity(mut self, ambiguity: Ambiguity) -> CsvJoin {
+        self.ambiguity = ambiguity;
+        self
+    }
+
+    /// Whether to also write left rows which weren't joined, with empty
+    /// right columns and score, as in a left outer join.
+    pub fn keep_unmatched(mut self, keep_unmatched: bool) -> CsvJoin {
+        self.keep_unmatched = keep_unmatched;
+        self
+    }
+
+    /// The right rows to join the left row with `key` to, with their scores,
+    /// or `None` if the match is rejected as ambiguous.
+    fn matches(
+        &self,
+        key: &str,
+        right: &[StringRecord],
+        right_key: usize,
+    ) -> Option<Vec<(usize, u8)>> {
+        let mut matches: Vec<(usize, u8)> = right
+            .iter()
+            .enumerate()
+            .map(|(i, record)| (i, self.scorer.score(key, &record[right_key]).into()))
+            .filter(|&(_, score)| score >= self.cutoff)
+            .collect();
+        matches.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        match self.ambiguity {
+            Ambiguity::Best => matches.truncate(1),
+            Ambiguity::All => {}
+            Ambiguity::Reject { margin } => {
+                if matches.len() > 1 && matches[0].1 - matches[1].1 <= margin {
+                    return None;
+                }
+                matches.truncate(1);
+            }
+        }
+        Some(matches)
+    }
+
+    /// Joins the rows of `left` to those of `right`, writing the joined
+    /// rows to `output` in the order of `left`.
+    pub fn join<L, R, W>(&self, left: L, right: R, output: W) -> Result<JoinSummary, JoinError>
+    where
+        L: io::Read,
+        R: io::Read,
+        W: io::Write,
+    {
+        let column = |headers: &StringRecord, name: &str| {
+            headers
+                .iter()
+                .position(|header| header == name)
+                .ok_or_else(|| JoinError::MissingColumn(name.to_string()))
+        };
+        let mut right = ReaderBuilder::new().from_reader(right);
+        let right_headers = right.headers()?.clone();
+        let right_key = column(&right_headers, &self.right_key)?;
+        let right = right.records().collect::<Result<Vec<_>, _>>()?;
+        let mut left = ReaderBuilder::new().from_reader(left);
+        let left_headers = left.headers()?.clone();
+        let left_key = column(&left_headers, &self.left_key)?;
+
+        let mut output = Writer::from_writer(output);
+        output.write_record(
+            left_headers
+                .iter()
+                .chain(right_headers.iter())
+                .chain(Some("score")),
+        )?;
+        let no_match = vec![""; right_headers.len() + 1];
+        let mut summary = JoinSummary::default();
+        for record in left.records() {
+            let record = record?;
+            let matches = self.matches(&record[left_key], &right, right_key);
+            for &(i, score) in matches.iter().flatten() {
+                let score = score.to_string();
+                output.write_record(
+                    record
+                        .iter()
+                        .chain(right[i].iter())
+                        .chain(Some(score.as_str())),
+                )?;
+            }
+            match matches {
+                Some(matches) if !matches.is_empty() => {
+                    summary.matched += 1;
+                    continue;
+                }
+                Some(_) => summary.unmatched += 1,
+                None => summary.ambiguous += 1,
+            }
+            if self.keep_unmatched {
+                output.write_record(record.iter().chain(no_match.iter().copied()))?;
+            }
+        }
+        output.flush().map_err(csv::Error::from)?;
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fuzz::Ratio;
+
+    fn run(join: CsvJoin, left: &str, right: &str) -> (String, JoinSummary) {
+        let mut output = vec![];
+        let summary = join
+            .join(left.as_bytes(), right.as_bytes(), &mut output)
+            .unwrap();
+        (String::from_utf8(output).unwrap(), summary)
+    }
+
+    #[test]
+    fn ambiguity_handling() {
+        let left = "k\nabcd\nwxyz\n";
+        let right = "k,n\nabce,1\nabcf,2\nwxy,3\n";
+        let join = || CsvJoin::new("k", "k").scorer(Ratio).cutoff(70);
+        let (all, summary) = run(join().ambiguity(Ambiguity::All), left, right);
+        assert_eq!(
+            all,
+            "k,k,n,score\nabcd,abce,1,75\nabcd,abcf,2,75\nwxyz,wxy,3,86\n"
+        );
+        assert_eq!(summary.matched, 2);
+        let rejecting = join()
+            .ambiguity(Ambiguity::Reject { margin: 0 })
+            .keep_unmatched(true);
+        let (rejected, summary) = run(rejecting, left, right);
+        assert_eq!(rejected, "k,k,n,score\nabcd,,,\nwxyz,wxy,3,86\n");
+        assert_eq!(
+            summary,
+            JoinSummary {
+                matched: 1,
+                ambiguous: 1,
+                unmatched: 0
+            }
+        );
+        let missing = join().join(left.as_bytes(), "x\n".as_bytes(), vec![]);
+        assert!(matches!(missing, Err(JoinError::MissingColumn(name)) if name == "k"));
+    }
+}
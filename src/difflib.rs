@@ -0,0 +1,687 @@
+//! A port of Python's `difflib.SequenceMatcher`.
+//!
+//! The free functions in [primitives](crate::primitives) recompute everything
+//! on each call. [SequenceMatcher] instead caches what it knows about each
+//! sequence, so comparing one sequence against many others only indexes the
+//! fixed one once. Like in difflib, the second sequence is the one which is
+//! indexed, so set the fixed sequence with [SequenceMatcher::set_seq2] and
+//! vary the first.
+//!
+//! ```
+//! # use fuzzywuzzy::difflib::SequenceMatcher;
+//! let query: Vec<char> = "apple".chars().collect();
+//! let choices: Vec<Vec<char>> = ["ape", "apply", "peach"].iter().map(|s| s.chars().collect()).collect();
+//! let mut matcher = SequenceMatcher::new(&[], &query);
+//! let ratios: Vec<f64> = choices.iter().map(|choice| {
+//!     matcher.set_seq1(choice);
+//!     matcher.ratio()
+//! }).collect();
+//! assert_eq!(ratios, vec![0.75, 0.8, 0.4]);
+//! ```
+//!
+//! [quick_ratio] and [real_quick_ratio] are cheap upper bounds on the ratio,
+//! useful to rule out candidates before computing it. `unified_diff` and
+//! `ndiff` render the differences between two texts line by line, and
+//! require feature "alignment".
+
+use crate::error::Error;
+use crate::primitives::{JunkIndex, MatchingStreak};
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// What an [Opcode] does to turn part of the first sequence into the second.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Tag {
+    /// `a[i1..i2]` should be replaced by `b[j1..j2]`.
+    Replace,
+    /// `a[i1..i2]` should be deleted. `j1 == j2` in this case.
+    Delete,
+    /// `b[j1..j2]` should be inserted at `a[i1..i1]`. `i1 == i2` in this case.
+    Insert,
+    /// `a[i1..i2] == b[j1..j2]`.
+    Equal,
+}
+
+/// One step of turning the first sequence into the second, see [SequenceMatcher::get_opcodes].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Opcode {
+    pub tag: Tag,
+    pub i1: usize,
+    pub i2: usize,
+    pub j1: usize,
+    pub j2: usize,
+}
+
+/// Compares pairs of sequences of hashable elements, caching information
+/// about each of them.
+///
+/// Elements of the second sequence can be ignored as junk, and popular
+/// elements are ignored with autojunk, see
+/// [get_matching_blocks_with_junk](crate::primitives::get_matching_blocks_with_junk).
+/// [SequenceMatcher::new] enables autojunk without any junk, like difflib's default.
+///
+/// ```
+/// # use fuzzywuzzy::difflib::{Opcode, SequenceMatcher, Tag};
+/// let a: Vec<char> = "qabxcd".chars().collect();
+/// let b: Vec<char> = "abycdf".chars().collect();
+/// let matcher = SequenceMatcher::new(&a, &b);
+/// assert_eq!(matcher.get_matching_blocks(), &[(1, 0, 2), (4, 3, 2), (6, 6, 0)]);
+/// assert_eq!(matcher.get_opcodes()[..2], [
+///     Opcode { tag: Tag::Delete, i1: 0, i2: 1, j1: 0, j2: 0 },
+///     Opcode { tag: Tag::Equal, i1: 1, i2: 3, j1: 0, j2: 2 },
+/// ]);
+/// assert_eq!(matcher.ratio(), 2.0 * 4.0 / 12.0);
+/// ```
+pub struct SequenceMatcher<'a, T: Eq + Hash> {
+    a: &'a [T],
+    isjunk: Box<dyn Fn(&T) -> bool + 'a>,
+    autojunk: bool,
+    index: JunkIndex<'a, T>,
+    matching_blocks: OnceCell<Vec<(usize, usize, usize)>>,
+    opcodes: OnceCell<Vec<Opcode>>,
+    b_counts: OnceCell<HashMap<&'a T, usize>>,
+}
+
+impl<'a, T: Eq + Hash> SequenceMatcher<'a, T> {
+    /// A matcher comparing `a` to `b`, without junk but with autojunk.
+    pub fn new(a: &'a [T], b: &'a [T]) -> SequenceMatcher<'a, T> {
+        SequenceMatcher::with_junk(a, b, |_| false, true)
+    }
+
+    /// A matcher comparing `a` to `b`, treating elements of `b` for which
+    /// `isjunk` returns `true` as junk.
+    pub fn with_junk<J: Fn(&T) -> bool + 'a>(
+        a: &'a [T],
+        b: &'a [T],
+        isjunk: J,
+        autojunk: bool,
+    ) -> SequenceMatcher<'a, T> {
+        let index = JunkIndex::new(b, &isjunk, autojunk);
+        SequenceMatcher {
+            a,
+            isjunk: Box::new(isjunk),
+            autojunk,
+            index,
+            matching_blocks: OnceCell::new(),
+            opcodes: OnceCell::new(),
+            b_counts: OnceCell::new(),
+        }
+    }
+
+    /// Sets both sequences to compare.
+    pub fn set_seqs(&mut self, a: &'a [T], b: &'a [T]) {
+        self.set_seq1(a);
+        self.set_seq2(b);
+    }
+
+    /// Sets the first sequence to compare, keeping what's cached about the second.
+    pub fn set_seq1(&mut self, a: &'a [T]) {
+        self.a = a;
+        self.reset();
+    }
+
+    /// Sets and indexes the second sequence to compare.
+    pub fn set_seq2(&mut self, b: &'a [T]) {
+        self.index = JunkIndex::new(b, &self.isjunk, self.autojunk);
+        self.b_counts = OnceCell::new();
+        self.reset();
+    }
+
+    fn reset(&mut self) {
+        self.matching_blocks = OnceCell::new();
+        self.opcodes = OnceCell::new();
+    }
+
+    /// The longest matching block in `a[alo..ahi]` and `b[blo..bhi]`, see
+    /// [find_longest_match_with_junk](crate::primitives::find_longest_match_with_junk).
+    ///
+    /// # Panics
+    /// If the ranges are out of bounds, see [SequenceMatcher::try_find_longest_match].
+    pub fn find_longest_match(
+        &self,
+        alo: usize,
+        ahi: usize,
+        blo: usize,
+        bhi: usize,
+    ) -> MatchingStreak {
+        self.try_find_longest_match(alo, ahi, blo, bhi)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like [SequenceMatcher::find_longest_match], but returns
+    /// [Error::InvalidRange] instead of panicking if `alo..ahi` or `blo..bhi`
+    /// is out of bounds.
+    ///
+    /// ```
+    /// # use fuzzywuzzy::difflib::SequenceMatcher;
+    /// # use fuzzywuzzy::error::Error;
+    /// # use fuzzywuzzy::primitives::MatchingStreak;
+    /// let (a, b) = (b"foo bar", b"bar baz");
+    /// let matcher = SequenceMatcher::new(a, b);
+    /// assert_eq!(matcher.try_find_longest_match(0, 7, 0, 7),
+    ///            Ok(MatchingStreak { idx1: 3, idx2: 3, size: 3 }));
+    /// assert_eq!(matcher.try_find_longest_match(0, 7, 4, 8),
+    ///            Err(Error::InvalidRange { low: 4, high: 8, len: 7 }));
+    /// ```
+    pub fn try_find_longest_match(
+        &self,
+        alo: usize,
+        ahi: usize,
+        blo: usize,
+        bhi: usize,
+    ) -> Result<MatchingStreak, Error> {
+        for &(low, high, len) in &[(alo, ahi, self.a.len()), (blo, bhi, self.index.b.len())] {
+            if low > high || high > len {
+                return Err(Error::InvalidRange { low, high, len });
+            }
+        }
+        Ok(self.index.find_longest_match(self.a, alo, ahi, blo, bhi))
+    }
+
+    /// Triples `(i, j, n)` meaning `a[i..i + n] == b[j..j + n]`, in increasing
+    /// order and ending with `(a.len(), b.len(), 0)`.
+    pub fn get_matching_blocks(&self) -> &[(usize, usize, usize)] {
+        self.matching_blocks
+            .get_or_init(|| self.index.matching_blocks(self.a))
+    }
+
+    /// The steps turning `a` into `b`, covering both sequences in order.
+    pub fn get_opcodes(&self) -> &[Opcode] {
+        self.opcodes.get_or_init(|| {
+            // https://github.com/python/cpython/blob/3.12/Lib/difflib.py#L509
+            let (mut i, mut j) = (0, 0);
+            let mut opcodes = Vec::new();
+            for &(ai, bj, size) in self.get_matching_blocks() {
+                let tag = match (i < ai, j < bj) {
+                    (true, true) => Some(Tag::Replace),
+                    (true, false) => Some(Tag::Delete),
+                    (false, true) => Some(Tag::Insert),
+                    (false, false) => None,
+                };
+                if let Some(tag) = tag {
+                    opcodes.push(Opcode {
+                        tag,
+                        i1: i,
+                        i2: ai,
+                        j1: j,
+                        j2: bj,
+                    });
+                }
+                i = ai + size;
+                j = bj + size;
+                if size != 0 {
+                    opcodes.push(Opcode {
+                        tag: Tag::Equal,
+                        i1: ai,
+                        i2: i,
+                        j1: bj,
+                        j2: j,
+                    });
+                }
+            }
+            opcodes
+        })
+    }
+
+    /// The opcodes split into hunks of changes, each with up to `n` elements
+    /// of unchanged context on either side, as used by `unified_diff`.
+    ///
+    /// Returns no hunks if the sequences are equal.
+    pub fn get_grouped_opcodes(&self, n: usize) -> Vec<Vec<Opcode>> {
+        // https://github.com/python/cpython/blob/3.12/Lib/difflib.py#L565
+        let mut codes = self.get_opcodes().to_vec();
+        if codes.is_empty() {
+            codes.push(Opcode {
+                tag: Tag::Equal,
+                i1: 0,
+                i2: 1,
+                j1: 0,
+                j2: 1,
+            });
+        }
+        if let Some(first) = codes.first_mut().filter(|code| code.tag == Tag::Equal) {
+            first.i1 = first.i1.max(first.i2.saturating_sub(n));
+            first.j1 = first.j1.max(first.j2.saturating_sub(n));
+        }
+        if let Some(last) = codes.last_mut().filter(|code| code.tag == Tag::Equal) {
+            last.i2 = last.i2.min(last.i1 + n);
+            last.j2 = last.j2.min(last.j1 + n);
+        }
+        let mut groups = vec![];
+        let mut group = vec![];
+        for mut code in codes {
+            if code.tag == Tag::Equal && code.i2 - code.i1 > 2 * n {
+                group.push(Opcode {
+                    i2: code.i2.min(code.i1 + n),
+                    j2: code.j2.min(code.j1 + n),
+                    ..code
+                });
+                groups.push(std::mem::take(&mut group));
+                code.i1 = code.i1.max(code.i2 - n);
+                code.j1 = code.j1.max(code.j2 - n);
+            }
+            group.push(code);
+        }
+        if !(group.is_empty() || group.len() == 1 && group[0].tag == Tag::Equal) {
+            groups.push(group);
+        }
+        groups
+    }
+
+    /// The similarity of the sequences between 0.0 and 1.0: twice the number
+    /// of matching elements over the total number of elements.
+    ///
+    /// Identical sequences, including two empty ones, have a ratio of 1.0.
+    pub fn ratio(&self) -> f64 {
+        let matches: usize = self.get_matching_blocks().iter().map(|&(_, _, n)| n).sum();
+        fraction(matches, self.a.len() + self.index.b.len())
+    }
+
+    /// An upper bound on [SequenceMatcher::ratio], which is faster to compute.
+    ///
+    /// The counts of elements in `b` are cached, so this is linear in `a.len()`.
+    pub fn quick_ratio(&self) -> f64 {
+        let b_counts = self.b_counts.get_or_init(|| counts(self.index.b));
+        let matches = count_common(self.a, b_counts);
+        fraction(matches, self.a.len() + self.index.b.len())
+    }
+
+    /// An upper bound on [SequenceMatcher::quick_ratio], computed from the
+    /// sequences' lengths alone.
+    pub fn real_quick_ratio(&self) -> f64 {
+        real_quick_ratio(self.a, self.index.b)
+    }
+}
+
+/// An upper bound on the [ratio](SequenceMatcher::ratio) of `a` and `b`,
+/// counting the elements they have in common regardless of order.
+///
+/// ```
+/// # use fuzzywuzzy::difflib::{quick_ratio, SequenceMatcher};
+/// let (a, b): (Vec<char>, Vec<char>) = ("abcd".chars().collect(), "dcba".chars().collect());
+/// assert_eq!(quick_ratio(&a, &b), 1.0);
+/// assert_eq!(SequenceMatcher::new(&a, &b).ratio(), 0.25);
+/// ```
+pub fn quick_ratio<T: Eq + Hash>(a: &[T], b: &[T]) -> f64 {
+    fraction(quick_matches(a, b), a.len() + b.len())
+}
+
+/// An upper bound on [quick_ratio], assuming every element of the shorter
+/// sequence has a match in the longer one.
+///
+/// ```
+/// # use fuzzywuzzy::difflib::real_quick_ratio;
+/// assert_eq!(real_quick_ratio(b"abcd", b"xy"), 2.0 * 2.0 / 6.0);
+/// ```
+pub fn real_quick_ratio<T>(a: &[T], b: &[T]) -> f64 {
+    fraction(a.len().min(b.len()), a.len() + b.len())
+}
+
+/// The number of elements `a` and `b` have in common as multisets, which is
+/// at least the number of matching elements found by any matching blocks.
+pub(crate) fn quick_matches<T, A, B>(a: A, b: B) -> usize
+where
+    T: Eq + Hash,
+    A: IntoIterator<Item = T>,
+    B: IntoIterator<Item = T>,
+{
+    count_common(a, &counts(b))
+}
+
+fn counts<T: Eq + Hash, B: IntoIterator<Item = T>>(b: B) -> HashMap<T, usize> {
+    let mut counts = HashMap::new();
+    for elt in b {
+        *counts.entry(elt).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn count_common<T, Q, A>(a: A, b_counts: &HashMap<Q, usize>) -> usize
+where
+    T: std::borrow::Borrow<Q>,
+    Q: Eq + Hash,
+    A: IntoIterator<Item = T>,
+{
+    // https://github.com/python/cpython/blob/3.12/Lib/difflib.py#L622
+    let mut available: HashMap<&Q, usize> = HashMap::new();
+    let mut matches = 0;
+    for elt in a {
+        if let Some((key, &count)) = b_counts.get_key_value(elt.borrow()) {
+            let left = available.entry(key).or_insert(count);
+            if *left > 0 {
+                *left -= 1;
+                matches += 1;
+            }
+        }
+    }
+    matches
+}
+
+#[cfg(feature = "alignment")]
+/// Compares `a` and `b` line by line, returning their differences in unified
+/// diff format with `n` lines of context, like Python's `difflib.unified_diff`.
+///
+/// `fromfile` and `tofile` name the sides in the header. Returns an empty
+/// string if the texts are equal. As in difflib, a last line without a
+/// newline is emitted as is.
+///
+/// ```
+/// # use fuzzywuzzy::difflib::unified_diff;
+/// let a = "one\ntwo\nthree\nfour\n";
+/// let b = "zero\none\ntree\nfour\n";
+/// assert_eq!(unified_diff(a, b, "before", "after", 3),
+///            "--- before\n+++ after\n@@ -1,4 +1,4 @@\n+zero\n one\n-two\n-three\n+tree\n four\n");
+/// assert_eq!(unified_diff(a, a, "before", "after", 3), "");
+/// ```
+pub fn unified_diff(a: &str, b: &str, fromfile: &str, tofile: &str, n: usize) -> String {
+    // https://github.com/python/cpython/blob/3.12/Lib/difflib.py#L1095
+    let (a, b): (Vec<&str>, Vec<&str>) = (
+        a.split_inclusive('\n').collect(),
+        b.split_inclusive('\n').collect(),
+    );
+    let matcher = SequenceMatcher::new(&a, &b);
+    let mut diff = String::new();
+    for group in matcher.get_grouped_opcodes(n) {
+        if diff.is_empty() {
+            diff.push_str(&format!("--- {}\n+++ {}\n", fromfile, tofile));
+        }
+        let (first, last) = (group[0], group[group.len() - 1]);
+        diff.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            format_range_unified(first.i1, last.i2),
+            format_range_unified(first.j1, last.j2)
+        ));
+        for code in group {
+            if code.tag == Tag::Equal {
+                push_lines(&mut diff, " ", &a[code.i1..code.i2]);
+                continue;
+            }
+            if code.tag != Tag::Insert {
+                push_lines(&mut diff, "-", &a[code.i1..code.i2]);
+            }
+            if code.tag != Tag::Delete {
+                push_lines(&mut diff, "+", &b[code.j1..code.j2]);
+            }
+        }
+    }
+    diff
+}
+
+#[cfg(feature = "alignment")]
+/// A hunk's range of lines, 1-based and inclusive of its start.
+fn format_range_unified(start: usize, stop: usize) -> String {
+    match stop - start {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        length => format!("{},{}", start + 1, length),
+    }
+}
+
+#[cfg(feature = "alignment")]
+fn push_lines(diff: &mut String, prefix: &str, lines: &[&str]) {
+    for line in lines {
+        diff.push_str(prefix);
+        diff.push_str(line);
+    }
+}
+
+#[cfg(feature = "alignment")]
+/// Compares `a` and `b` line by line, returning every line prefixed by how it
+/// changed, like Python's `difflib.ndiff`.
+///
+/// Lines are prefixed with `"- "` if they're only in `a`, `"+ "` if they're
+/// only in `b` and `"  "` if they're in both. A changed line which is similar
+/// to its replacement is followed by a `"? "` line marking the changed
+/// characters: `^` for replaced, `-` for deleted and `+` for inserted ones.
+/// Spaces and tabs are ignored as junk when aligning characters, like
+/// difflib's `IS_CHARACTER_JUNK`.
+///
+/// ```
+/// # use fuzzywuzzy::difflib::ndiff;
+/// let a = "one\ntwo\nthree\n";
+/// let b = "ore\ntree\nemu\n";
+/// assert_eq!(ndiff(a, b), "- one\n?  ^\n+ ore\n?  ^\n- two\n- three\n?  -\n+ tree\n+ emu\n");
+/// ```
+pub fn ndiff(a: &str, b: &str) -> String {
+    let (a, b): (Vec<&str>, Vec<&str>) = (
+        a.split_inclusive('\n').collect(),
+        b.split_inclusive('\n').collect(),
+    );
+    let mut differ = Differ {
+        a_chars: a.iter().map(|line| line.chars().collect()).collect(),
+        b_chars: b.iter().map(|line| line.chars().collect()).collect(),
+        a: &a,
+        b: &b,
+        diff: String::new(),
+    };
+    // https://github.com/python/cpython/blob/3.12/Lib/difflib.py#L870
+    for code in SequenceMatcher::new(&a, &b).get_opcodes() {
+        match code.tag {
+            Tag::Replace => differ.fancy_replace(code.i1, code.i2, code.j1, code.j2),
+            Tag::Delete => differ.dump_a("- ", code.i1, code.i2),
+            Tag::Insert => differ.dump_b("+ ", code.j1, code.j2),
+            Tag::Equal => differ.dump_a("  ", code.i1, code.i2),
+        }
+    }
+    differ.diff
+}
+
+#[cfg(feature = "alignment")]
+/// The state of an [ndiff], difflib's `Differ`.
+struct Differ<'a> {
+    a: &'a [&'a str],
+    b: &'a [&'a str],
+    a_chars: Vec<Vec<char>>,
+    b_chars: Vec<Vec<char>>,
+    diff: String,
+}
+
+#[cfg(feature = "alignment")]
+impl Differ<'_> {
+    fn dump_a(&mut self, prefix: &str, lo: usize, hi: usize) {
+        push_lines(&mut self.diff, prefix, &self.a[lo..hi]);
+    }
+
+    fn dump_b(&mut self, prefix: &str, lo: usize, hi: usize) {
+        push_lines(&mut self.diff, prefix, &self.b[lo..hi]);
+    }
+
+    /// Replaces a block of lines with another, pairing up the most similar
+    /// lines to mark their changes within the line.
+    fn fancy_replace(&mut self, alo: usize, ahi: usize, blo: usize, bhi: usize) {
+        // https://github.com/python/cpython/blob/3.12/Lib/difflib.py#L928
+        let (mut best_ratio, cutoff) = (0.74, 0.75);
+        let (mut best_i, mut best_j) = (0, 0);
+        let mut identical = None;
+        let is_character_junk = |c: &char| *c == ' ' || *c == '\t';
+        {
+            let mut cruncher = SequenceMatcher::with_junk(&[], &[], is_character_junk, true);
+            for j in blo..bhi {
+                cruncher.set_seq2(&self.b_chars[j]);
+                for i in alo..ahi {
+                    if self.a[i] == self.b[j] {
+                        identical.get_or_insert((i, j));
+                        continue;
+                    }
+                    cruncher.set_seq1(&self.a_chars[i]);
+                    if cruncher.real_quick_ratio() > best_ratio
+                        && cruncher.quick_ratio() > best_ratio
+                        && cruncher.ratio() > best_ratio
+                    {
+                        best_ratio = cruncher.ratio();
+                        best_i = i;
+                        best_j = j;
+                    }
+                }
+            }
+        }
+        if best_ratio < cutoff {
+            match identical {
+                // No similar lines, so replace the whole block.
+                None => {
+                    if bhi - blo < ahi - alo {
+                        self.dump_b("+ ", blo, bhi);
+                        self.dump_a("- ", alo, ahi);
+                    } else {
+                        self.dump_a("- ", alo, ahi);
+                        self.dump_b("+ ", blo, bhi);
+                    }
+                    return;
+                }
+                // Sync up on an identical line instead.
+                Some((i, j)) => {
+                    best_i = i;
+                    best_j = j;
+                }
+            }
+        } else {
+            identical = None;
+        }
+        self.fancy_helper(alo, best_i, blo, best_j);
+        if identical.is_some() {
+            self.dump_a("  ", best_i, best_i + 1);
+        } else {
+            self.mark_changes(best_i, best_j);
+        }
+        self.fancy_helper(best_i + 1, ahi, best_j + 1, bhi);
+    }
+
+    fn fancy_helper(&mut self, alo: usize, ahi: usize, blo: usize, bhi: usize) {
+        if alo < ahi {
+            if blo < bhi {
+                self.fancy_replace(alo, ahi, blo, bhi);
+            } else {
+                self.dump_a("- ", alo, ahi);
+            }
+        } else if blo < bhi {
+            self.dump_b("+ ", blo, bhi);
+        }
+    }
+
+    /// Emits the similar lines `a[i]` and `b[j]`, each followed by a line
+    /// marking its changed characters.
+    fn mark_changes(&mut self, i: usize, j: usize) {
+        let is_character_junk = |c: &char| *c == ' ' || *c == '\t';
+        let (aline, bline) = (&self.a_chars[i], &self.b_chars[j]);
+        let cruncher = SequenceMatcher::with_junk(aline, bline, is_character_junk, true);
+        let (mut atags, mut btags) = (String::new(), String::new());
+        for code in cruncher.get_opcodes() {
+            let (la, lb) = (code.i2 - code.i1, code.j2 - code.j1);
+            let (atag, btag) = match code.tag {
+                Tag::Replace => ('^', '^'),
+                Tag::Delete => ('-', ' '),
+                Tag::Insert => (' ', '+'),
+                Tag::Equal => (' ', ' '),
+            };
+            atags.extend(std::iter::repeat_n(atag, la));
+            btags.extend(std::iter::repeat_n(btag, lb));
+        }
+        let (atags, btags) = (
+            keep_original_ws(aline, &atags),
+            keep_original_ws(bline, &btags),
+        );
+        push_lines(&mut self.diff, "- ", &[self.a[i]]);
+        if !atags.is_empty() {
+            self.diff.push_str(&format!("? {}\n", atags));
+        }
+        push_lines(&mut self.diff, "+ ", &[self.b[j]]);
+        if !btags.is_empty() {
+            self.diff.push_str(&format!("? {}\n", btags));
+        }
+    }
+}
+
+#[cfg(feature = "alignment")]
+/// Replaces unmarked positions of `tags` with the whitespace at the same
+/// position in `line`, so tabs keep markers aligned, and trims the end.
+fn keep_original_ws(line: &[char], tags: &str) -> String {
+    let tags: String = line
+        .iter()
+        .zip(tags.chars())
+        .map(|(&c, tag)| {
+            if tag == ' ' && c.is_whitespace() {
+                c
+            } else {
+                tag
+            }
+        })
+        .collect();
+    tags.trim_end().to_string()
+}
+
+fn fraction(matches: usize, length: usize) -> f64 {
+    if length == 0 {
+        1.0
+    } else {
+        2.0 * matches as f64 / length as f64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fuzz::{Ratio, Scorer};
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn quick_ratios_bound_ratio() {
+        let alphabet: Vec<char> = "abcé ".chars().collect();
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let mut random = || -> String {
+                (0..rng.gen_range(0..12))
+                    .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                    .collect()
+            };
+            let (a, b) = (random(), random());
+            let (a_chars, b_chars): (Vec<char>, Vec<char>) =
+                (a.chars().collect(), b.chars().collect());
+            let matcher = SequenceMatcher::new(&a_chars, &b_chars);
+            assert!(matcher.ratio() <= matcher.quick_ratio(), "{:?} {:?}", a, b);
+            assert_eq!(matcher.quick_ratio(), quick_ratio(&a_chars, &b_chars));
+            assert!(
+                matcher.quick_ratio() <= matcher.real_quick_ratio(),
+                "{:?} {:?}",
+                a,
+                b
+            );
+            assert!(
+                Ratio.score(&a, &b) <= Ratio.upper_bound(&a, &b),
+                "{:?} {:?}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn cached_results_follow_sequence_changes() {
+        let (a, b, c): (Vec<char>, Vec<char>, Vec<char>) = (
+            "abcd".chars().collect(),
+            "bcde".chars().collect(),
+            "xyz".chars().collect(),
+        );
+        let mut matcher = SequenceMatcher::new(&a, &b);
+        assert_eq!(matcher.ratio(), 0.75);
+        matcher.set_seq1(&c);
+        assert_eq!(matcher.ratio(), 0.0);
+        assert_eq!(
+            matcher.get_opcodes(),
+            &[Opcode {
+                tag: Tag::Replace,
+                i1: 0,
+                i2: 3,
+                j1: 0,
+                j2: 4
+            }]
+        );
+        matcher.set_seq2(&a);
+        assert_eq!(matcher.quick_ratio(), 0.0);
+        matcher.set_seqs(&b, &b);
+        assert_eq!(matcher.ratio(), 1.0);
+        matcher.set_seqs(&[], &[]);
+        assert_eq!(matcher.ratio(), 1.0);
+        assert_eq!(matcher.get_opcodes(), &[]);
+    }
+}
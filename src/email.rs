@@ -0,0 +1,120 @@
+//! Matching email addresses.
+//!
+//! Many addresses deliver to the same mailbox: "Jane.Doe+news@GMail.com" and
+//! "janedoe@gmail.com" are one person's. [normalize_email] folds case,
+//! removes "+tag" suffixes from the local part and, for Gmail, which ignores
+//! them, the dots too. [email_ratio] then scores the local part and the
+//! domain separately, since a shared domain like "gmail.com" says little.
+//!
+//! ```
+//! # use fuzzywuzzy::email::{email_ratio, normalize_email};
+//! assert_eq!(normalize_email("Jane.Doe+news@GoogleMail.com"), "janedoe@gmail.com");
+//! assert_eq!(email_ratio("Jane.Doe+news@gmail.com", "janedoe@gmail.com"), 100);
+//! assert_eq!(email_ratio("jane.doe@example.com", "jane.doe@example.org"), 96);
+//! assert_eq!(email_ratio("jane.doe@gmail.com", "john.smith@gmail.com"), 40);
+//! ```
+
+use crate::fuzz::{self, Scorer};
+use crate::score::Score;
+use crate::utils::Rounding;
+use std::fmt;
+
+/// Domains whose mailboxes ignore dots in the local part, and the domain they're an alias of.
+const DOTLESS_DOMAINS: &[(&str, &str)] =
+    &[("gmail.com", "gmail.com"), ("googlemail.com", "gmail.com")];
+
+/// The parts of an email address.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Email {
+    /// The part before the "@".
+    pub local: String,
+    /// The part after the "@", which is empty if there is none.
+    pub domain: String,
+}
+
+impl fmt::Display for Email {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.domain.is_empty() {
+            f.write_str(&self.local)
+        } else {
+            write!(f, "{}@{}", self.local, self.domain)
+        }
+    }
+}
+
+/// Splits `email` at its last "@" and normalizes both parts. See the
+/// [module](self) docs.
+///
+/// ```
+/// # use fuzzywuzzy::email::{parse_email, Email};
+/// assert_eq!(parse_email(" J.Doe+work@Example.COM "), Email {
+///     local: "j.doe".to_string(),
+///     domain: "example.com".to_string(),
+/// });
+/// ```
+pub fn parse_email(email: &str) -> Email {
+    let email = email.trim().to_lowercase();
+    let (local, domain) = email.rsplit_once('@').unwrap_or((&email, ""));
+    let mut local = local.split('+').next().unwrap_or("").to_string();
+    let mut domain = domain.to_string();
+    if let Some(&(_, alias_of)) = DOTLESS_DOMAINS.iter().find(|&&(d, _)| d == domain) {
+        local.retain(|c| c != '.');
+        domain = alias_of.to_string();
+    }
+    Email { local, domain }
+}
+
+/// `email` in a canonical form. See [parse_email].
+pub fn normalize_email(email: &str) -> String {
+    parse_email(email).to_string()
+}
+
+/// Compares two email addresses after [parse_email].
+///
+/// The [ratio](fuzz::ratio) of the local parts makes up 80% of the score
+/// and that of the domains 20%, which count as equal if both are missing.
+///
+/// ```
+/// # use fuzzywuzzy::email::email_ratio;
+/// assert_eq!(email_ratio("j.doe@example.com", "jdoe@example.com"), 91);
+/// assert_eq!(email_ratio("jdoe@example.com", "jdoe"), 80);
+/// ```
+pub fn email_ratio(a: &str, b: &str) -> u8 {
+    let (a, b) = (parse_email(a), parse_email(b));
+    let domain = if a.domain.is_empty() && b.domain.is_empty() {
+        100
+    } else {
+        fuzz::ratio(&a.domain, &b.domain)
+    };
+    let score = 0.8 * fuzz::ratio(&a.local, &b.local) as f64 + 0.2 * domain as f64;
+    Rounding::Python.round(score)
+}
+
+/// [Scorer] for [email_ratio].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EmailRatio;
+
+impl Scorer for EmailRatio {
+    fn score(&self, a: &str, b: &str) -> Score {
+        Score::new(email_ratio(a, b)).expect("email_ratio returns at most 100")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn equivalent_addresses_normalize_alike() {
+        let cases = [
+            ("a.b+c+d@GoogleMail.com", "ab@gmail.com"),
+            ("a.b+c@example.com", "a.b@example.com"),
+            ("\"a@b\"@example.com", "\"a@b\"@example.com"),
+            ("+tag@example.com", "@example.com"),
+            ("no-at-sign", "no-at-sign"),
+        ];
+        for &(email, normalized) in cases.iter() {
+            assert_eq!(normalize_email(email), normalized, "{}", email);
+        }
+    }
+}
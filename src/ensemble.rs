@@ -0,0 +1,230 @@
+//! Combining several scorers into one, with weights learned from examples.
+//!
+//! An [EnsembleScorer] scores by the weighted average of its scorers. The
+//! weights can be set by hand, or fitted to pairs labeled as duplicates or
+//! not with [EnsembleScorer::fit], which also suggests a score cutoff
+//! separating the two.
+//!
+//! ```
+//! # use fuzzywuzzy::ensemble::EnsembleScorer;
+//! # use fuzzywuzzy::fuzz::{Ratio, Scorer, TokenSetRatio};
+//! let mut scorer = EnsembleScorer::new().scorer(Ratio, 1.0).scorer(TokenSetRatio, 1.0);
+//! let pairs = [
+//!     ("Acme Corporation", "Corporation Acme"),
+//!     ("Globex Inc", "Globex"),
+//!     ("Initech", "Initrode"),
+//!     ("Hooli", "Hooli XYZ"),
+//!     ("Umbrella Corp", "Umbrella Academy"),
+//!     ("Stark Industries", "Wayne Industries"),
+//! ];
+//! let labels = [true, true, false, true, false, false];
+//! let cutoff = scorer.fit(&pairs, &labels).unwrap();
+//! // Only the token set ratio tells these apart.
+//! assert_eq!(scorer.weights(), vec![0.0, 1.0]);
+//! assert_eq!(cutoff, 87);
+//! assert!(pairs.iter().zip(labels).all(|(&(a, b), duplicate)| {
+//!     (scorer.score(a, b).percent() >= cutoff) == duplicate
+//! }));
+//! ```
+
+use crate::error::Error;
+use crate::fuzz::Scorer;
+use crate::score::Score;
+
+/// The gradient descent steps taken by [EnsembleScorer::fit].
+const ITERATIONS: usize = 2000;
+/// The step size of [EnsembleScorer::fit], for scores as fractions.
+const LEARNING_RATE: f64 = 1.0;
+
+/// A weighted average of scorers, see the [module](self) docs.
+pub struct EnsembleScorer {
+    scorers: Vec<(Box<dyn Scorer + Send + Sync>, f64)>,
+}
+
+impl Default for EnsembleScorer {
+    fn default() -> EnsembleScorer {
+        EnsembleScorer::new()
+    }
+}
+
+impl EnsembleScorer {
+    /// An ensemble without scorers, which scores everything 0.
+    pub fn new() -> EnsembleScorer {
+        EnsembleScorer {
+            scorers: Vec::new(),
+        }
+    }
+
+    /// Adds `scorer` with `weight`. Weights are relative to each other and
+    /// needn't add up to 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weight` is negative or not finite.
+    pub fn scorer<S: Scorer + Send + Sync + 'static>(mut self, scorer: S, weight: f64) -> Self {
+        assert!(
+            weight.is_finite() && weight >= 0.0,
+            "weight must be finite and not negative"
+        );
+        self.scorers.push((Box::new(scorer), weight));
+        self
+    }
+
+    /// The weights of the scorers, in the order they were added, as fractions
+    /// adding up to 1, or all 0 if the weights are.
+    pub fn weights(&self) -> Vec<f64> {
+        let total: f64 = self.scorers.iter().map(|&(_, weight)| weight).sum();
+        self.scorers
+            .iter()
+            .map(|&(_, weight)| if total > 0.0 { weight / total } else { 0.0 })
+            .collect()
+    }
+
+    /// Learns the weights from `pairs` labeled as duplicates (`true`) or not
+    /// by `labels`, and returns the cutoff at which the learned weights best
+    /// tell them apart.
+    ///
+    /// Fits a logistic regression on the scores of the pairs by each scorer,
+    /// keeping the coefficients from becoming negative. The coefficients
+    /// become the weights, and the cutoff is where the regression predicts
+    /// duplicates and non-duplicates as equally likely.
+    ///
+    /// # Errors
+    ///
+    /// Returns [Error::LengthMismatch] if `pairs` and `labels` differ in
+    /// length, [Error::EmptyInput] if there are no pairs, and
+    /// [Error::InvalidConfig] if the ensemble has no scorers, if `labels`
+    /// don't include both duplicates and non-duplicates, or if no scorer
+    /// scores duplicates higher. The weights are unchanged then.
+    pub fn fit<A, B>(&mut self, pairs: &[(A, B)], labels: &[bool]) -> Result<u8, Error>
+    where
+        A: AsRef<str>,
+        B: AsRef<str>,
+    {
+        if labels.len() != pairs.len() {
+            return Err(Error::LengthMismatch {
+                expected: pairs.len(),
+                actual: labels.len(),
+            });
+        }
+        if pairs.is_empty() {
+            return Err(Error::EmptyInput);
+        }
+        if self.scorers.is_empty() {
+            return Err(Error::InvalidConfig("an ensemble needs a scorer to fit"));
+        }
+        if labels.iter().all(|&label| label) || labels.iter().all(|&label| !label) {
+            return Err(Error::InvalidConfig(
+                "labels must include both duplicates and non-duplicates",
+            ));
+        }
+        let features: Vec<Vec<f64>> = pairs
+            .iter()
+            .map(|(a, b)| {
+                self.scorers
+                    .iter()
+                    .map(|(scorer, _)| scorer.score(a.as_ref(), b.as_ref()).fraction())
+                    .collect()
+            })
+            .collect();
+        count!(candidates_scored, pairs.len() * self.scorers.len());
+
+        let n = pairs.len() as f64;
+        let mut coefficients = vec![0.0; self.scorers.len()];
+        let mut intercept = 0.0;
+        for _ in 0..ITERATIONS {
+            let mut gradient = vec![0.0; coefficients.len()];
+            let mut intercept_gradient = 0.0;
+            for (x, &label) in features.iter().zip(labels) {
+                let z: f64 =
+                    intercept + x.iter().zip(&coefficients).map(|(x, w)| x * w).sum::<f64>();
+                let error = 1.0 / (1.0 + (-z).exp()) - if label { 1.0 } else { 0.0 };
+                for (g, x) in gradient.iter_mut().zip(x) {
+                    *g += error * x;
+                }
+                intercept_gradient += error;
+            }
+            for (w, g) in coefficients.iter_mut().zip(&gradient) {
+                *w = (*w - LEARNING_RATE * g / n).max(0.0);
+            }
+            intercept -= LEARNING_RATE * intercept_gradient / n;
+        }
+
+        let total: f64 = coefficients.iter().sum();
+        if total <= 0.0 {
+            return Err(Error::InvalidConfig(
+                "no scorer scores duplicates higher than non-duplicates",
+            ));
+        }
+        for ((_, weight), coefficient) in self.scorers.iter_mut().zip(&coefficients) {
+            *weight = coefficient / total;
+        }
+        // The regression predicts even odds where the weighted average of
+        // the fractions, times `total`, cancels out the intercept.
+        Ok((-intercept / total * 100.0).round().clamp(0.0, 100.0) as u8)
+    }
+
+    /// The weighted average of `score` for each scorer.
+    fn average<F: Fn(&dyn Scorer) -> Score>(&self, score: F) -> Score {
+        let total: f64 = self.scorers.iter().map(|&(_, weight)| weight).sum();
+        if total <= 0.0 {
+            return Score::MIN;
+        }
+        let sum: f64 = self
+            .scorers
+            .iter()
+            .filter(|&&(_, weight)| weight > 0.0)
+            .map(|(scorer, weight)| weight * score(scorer).percent() as f64)
+            .sum();
+        Score::new((sum / total).round().min(100.0) as u8)
+            .expect("averages of scores are at most 100")
+    }
+}
+
+impl Scorer for EnsembleScorer {
+    fn score(&self, a: &str, b: &str) -> Score {
+        self.average(|scorer| scorer.score(a, b))
+    }
+
+    fn upper_bound(&self, a: &str, b: &str) -> Score {
+        self.average(|scorer| scorer.upper_bound(a, b))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fuzz::{PartialRatio, Ratio};
+
+    #[test]
+    fn fitting_favors_the_scorer_that_separates_the_labels() {
+        // Duplicates here are prefixes, which only PartialRatio sees.
+        let pairs = [
+            ("new york", "new york city"),
+            ("san fran", "san francisco"),
+            ("la", "los angeles"),
+            ("boston", "austin"),
+            ("denver", "dover"),
+            ("miami", "maui"),
+        ];
+        let labels = [true, true, false, false, false, false];
+        let mut scorer = EnsembleScorer::new()
+            .scorer(Ratio, 1.0)
+            .scorer(PartialRatio, 1.0);
+        let cutoff = scorer.fit(&pairs, &labels).unwrap();
+        let weights = scorer.weights();
+        assert!(weights[1] > weights[0], "{:?}", weights);
+        for (&(a, b), &label) in pairs.iter().zip(&labels) {
+            assert_eq!(scorer.score(a, b).percent() >= cutoff, label, "{} {}", a, b);
+        }
+
+        assert_eq!(
+            scorer.fit(&pairs[..2], &labels[..2]),
+            Err(Error::InvalidConfig(
+                "labels must include both duplicates and non-duplicates"
+            ))
+        );
+        assert_eq!(scorer.weights(), weights);
+        assert_eq!(scorer.fit::<&str, &str>(&[], &[]), Err(Error::EmptyInput));
+    }
+}
@@ -0,0 +1,52 @@
+//! The [Error] type returned by fallible variants of the API.
+//!
+//! Functions which would otherwise panic, or silently return meaningless
+//! results, on invalid input have `try_` variants returning [Error] instead,
+//! so services can reject bad input gracefully.
+
+use std::fmt;
+
+/// Everything that can go wrong in the fallible functions of this crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// `low..high` is not a valid range into a sequence of `len` elements.
+    InvalidRange { low: usize, high: usize, len: usize },
+    /// A score was greater than 100.
+    ScoreOutOfRange(u8),
+    /// An input which must not be empty was empty, possibly after processing.
+    EmptyInput,
+    /// A configuration was invalid, for the given reason.
+    InvalidConfig(&'static str),
+    /// A computation needed `required` bytes, more than its
+    /// [MemoryLimit](crate::primitives::MemoryLimit) of `limit`.
+    MemoryLimitExceeded { required: usize, limit: usize },
+    /// A sequence had `actual` elements where `expected` were needed, e.g.
+    /// one confidence for each character.
+    LengthMismatch { expected: usize, actual: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidRange { low, high, len } => write!(
+                f,
+                "range {}..{} is invalid for a sequence of length {}",
+                low, high, len
+            ),
+            Error::ScoreOutOfRange(score) => write!(f, "score {} is out of range 0..=100", score),
+            Error::EmptyInput => write!(f, "input is empty"),
+            Error::InvalidConfig(reason) => write!(f, "invalid configuration: {}", reason),
+            Error::MemoryLimitExceeded { required, limit } => write!(
+                f,
+                "{} bytes are required, more than the limit of {}",
+                required, limit
+            ),
+            Error::LengthMismatch { expected, actual } => {
+                write!(f, "expected {} elements, got {}", expected, actual)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
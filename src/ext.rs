@@ -0,0 +1,81 @@
+//! Fuzzy lookups as methods of slices and `Vec`s of strings.
+//!
+//! These cover the common one-liners without the processors and owned
+//! results of [process](crate::process). Elements are scored against the
+//! query by [WRatio] unless a [Scorer] is given, and ties go to the first
+//! element, as with [extract_one](crate::process::extract_one).
+//!
+//! ```
+//! # use fuzzywuzzy::ext::SliceFuzzyExt;
+//! let fruits = vec!["apple", "banana", "cherry"];
+//! assert_eq!(fruits.fuzzy_position("bananna", 80), Some(1));
+//! assert!(fruits.fuzzy_contains("Cherry!", 90));
+//! assert!(!fruits.fuzzy_contains("grape", 80));
+//! ```
+
+use crate::fuzz::Scorer;
+#[cfg(feature = "token")]
+use crate::fuzz::WRatio;
+use crate::score::Score;
+
+/// Fuzzy lookups on a slice of strings, see the [module](self) docs.
+pub trait SliceFuzzyExt<T> {
+    /// The element scoring highest against `query` by `scorer`, with its
+    /// score, or `None` if the slice is empty.
+    ///
+    /// ```
+    /// # use fuzzywuzzy::ext::SliceFuzzyExt;
+    /// # use fuzzywuzzy::fuzz::{Ratio, TokenSortRatio};
+    /// let names = ["smith john", "john smyth"];
+    /// let best = names.fuzzy_max_by_score("john smith", Ratio);
+    /// assert_eq!(best.map(|(name, score)| (*name, score.percent())), Some(("john smyth", 90)));
+    /// let best = names.fuzzy_max_by_score("john smith", TokenSortRatio);
+    /// assert_eq!(best.map(|(name, score)| (*name, score.percent())), Some(("smith john", 100)));
+    /// assert_eq!(Vec::<String>::new().fuzzy_max_by_score("john smith", Ratio), None);
+    /// ```
+    fn fuzzy_max_by_score<S: Scorer>(
+        &self,
+        query: impl AsRef<str>,
+        scorer: S,
+    ) -> Option<(&T, Score)>;
+
+    /// The index of the element best matching `query`, if it scores at least
+    /// `cutoff`.
+    #[cfg(feature = "token")]
+    fn fuzzy_position(&self, query: impl AsRef<str>, cutoff: u8) -> Option<usize>;
+
+    /// Whether any element scores at least `cutoff` against `query`.
+    #[cfg(feature = "token")]
+    fn fuzzy_contains(&self, query: impl AsRef<str>, cutoff: u8) -> bool {
+        self.fuzzy_position(query, cutoff).is_some()
+    }
+}
+
+/// The index of the first element of `slice` scoring highest, and its score.
+fn best<T: AsRef<str>, S: Scorer>(slice: &[T], query: &str, scorer: S) -> Option<(usize, Score)> {
+    let mut best: Option<(usize, Score)> = None;
+    for (i, choice) in slice.iter().enumerate() {
+        let score = scorer.score(query, choice.as_ref());
+        if best.is_none_or(|(_, best)| score > best) {
+            best = Some((i, score));
+        }
+    }
+    best
+}
+
+impl<T: AsRef<str>> SliceFuzzyExt<T> for [T] {
+    fn fuzzy_max_by_score<S: Scorer>(
+        &self,
+        query: impl AsRef<str>,
+        scorer: S,
+    ) -> Option<(&T, Score)> {
+        best(self, query.as_ref(), scorer).map(|(i, score)| (&self[i], score))
+    }
+
+    #[cfg(feature = "token")]
+    fn fuzzy_position(&self, query: impl AsRef<str>, cutoff: u8) -> Option<usize> {
+        best(self, query.as_ref(), WRatio)
+            .filter(|&(_, score)| score.percent() >= cutoff)
+            .map(|(i, _)| i)
+    }
+}
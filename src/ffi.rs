@@ -0,0 +1,206 @@
+//! C-compatible API. Requires feature "ffi".
+//!
+//! `cargo rustc --lib --release --features ffi --crate-type cdylib` builds a
+//! shared library exporting the functions below. The matching C header lives
+//! at `include/fuzzywuzzy.h` and is regenerated with
+//! `cbindgen --config cbindgen.toml --output include/fuzzywuzzy.h`.
+//!
+//! All strings are borrowed, NUL-terminated UTF-8. Nothing is allocated on the
+//! caller's behalf, so there is nothing to free.
+
+use crate::{fuzz, process, utils};
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+
+/// Borrows a C string as `&str`, returning `None` if it is null or not UTF-8.
+///
+/// # Safety
+/// `s` must be null or point to a NUL-terminated string which outlives `'a`.
+unsafe fn borrow_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
+
+/// Runs `scorer` over two C strings, returning -1 if either is invalid.
+unsafe fn score_with<F: Fn(&str, &str) -> u8>(
+    a: *const c_char,
+    b: *const c_char,
+    scorer: F,
+) -> c_int {
+    match (borrow_str(a), borrow_str(b)) {
+        (Some(a), Some(b)) => scorer(a, b) as c_int,
+        _ => -1,
+    }
+}
+
+/// See [fuzz::ratio]. Returns -1 if either argument is null or not valid UTF-8.
+///
+/// # Safety
+/// `a` and `b` must be null or point to NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzywuzzy_ratio(a: *const c_char, b: *const c_char) -> c_int {
+    score_with(a, b, |a, b| fuzz::ratio(a, b))
+}
+
+/// See [fuzz::partial_ratio]. Returns -1 if either argument is null or not valid UTF-8.
+///
+/// # Safety
+/// `a` and `b` must be null or point to NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzywuzzy_partial_ratio(a: *const c_char, b: *const c_char) -> c_int {
+    score_with(a, b, |a, b| fuzz::partial_ratio(a, b))
+}
+
+/// See [fuzz::token_sort_ratio]. Returns -1 if either argument is null or not valid UTF-8.
+///
+/// # Safety
+/// `a` and `b` must be null or point to NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzywuzzy_token_sort_ratio(
+    a: *const c_char,
+    b: *const c_char,
+    force_ascii: bool,
+    full_process: bool,
+) -> c_int {
+    score_with(a, b, |a, b| {
+        fuzz::token_sort_ratio(a, b, force_ascii, full_process)
+    })
+}
+
+/// See [fuzz::token_set_ratio]. Returns -1 if either argument is null or not valid UTF-8.
+///
+/// # Safety
+/// `a` and `b` must be null or point to NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzywuzzy_token_set_ratio(
+    a: *const c_char,
+    b: *const c_char,
+    force_ascii: bool,
+    full_process: bool,
+) -> c_int {
+    score_with(a, b, |a, b| {
+        fuzz::token_set_ratio(a, b, force_ascii, full_process)
+    })
+}
+
+/// See [fuzz::wratio]. Returns -1 if either argument is null or not valid UTF-8.
+///
+/// # Safety
+/// `a` and `b` must be null or point to NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzywuzzy_wratio(
+    a: *const c_char,
+    b: *const c_char,
+    force_ascii: bool,
+    full_process: bool,
+) -> c_int {
+    score_with(a, b, |a, b| fuzz::wratio(a, b, force_ascii, full_process))
+}
+
+/// Finds the choice best matching `query` with [process::extract_one_with_payload],
+/// [utils::full_process] and [fuzz::WRatio], as [process::extract_one] would.
+///
+/// Returns the index of the best choice, writing its score to `out_score` if it
+/// is not null. Returns -1 if no choice scores at least `score_cutoff`, and -2
+/// if `query`, `choices` or any choice is null or not valid UTF-8.
+///
+/// # Safety
+/// `query` must be null or point to a NUL-terminated string. `choices` must be
+/// null or point to `len` pointers which are each null or point to a
+/// NUL-terminated string. `out_score` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzywuzzy_extract_one(
+    query: *const c_char,
+    choices: *const *const c_char,
+    len: usize,
+    score_cutoff: u8,
+    out_score: *mut u8,
+) -> isize {
+    let query = match borrow_str(query) {
+        Some(query) => query,
+        None => return -2,
+    };
+    if choices.is_null() {
+        return -2;
+    }
+    let mut borrowed = Vec::with_capacity(len);
+    for &choice in std::slice::from_raw_parts(choices, len) {
+        match borrow_str(choice) {
+            Some(choice) => borrowed.push(choice),
+            None => return -2,
+        }
+    }
+    // The index of each choice is its payload, to be returned.
+    let best = process::extract_one_with_payload(
+        query,
+        borrowed.into_iter().zip(0..),
+        utils::full_process,
+        fuzz::WRatio,
+        score_cutoff,
+    );
+    match best {
+        Some((_, idx, score)) => {
+            if !out_score.is_null() {
+                *out_score = score;
+            }
+            idx
+        }
+        None => -1,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::process::extract_one;
+    use std::ffi::CString;
+    use std::ptr;
+
+    #[test]
+    fn scorers_reject_invalid_input() {
+        let a = CString::new("new york mets").unwrap();
+        let b = CString::new("new YORK mets").unwrap();
+        let invalid = CString::new(vec![0xffu8, 0xfe]).unwrap();
+        unsafe {
+            assert_eq!(fuzzywuzzy_ratio(a.as_ptr(), b.as_ptr()), 69);
+            assert_eq!(fuzzywuzzy_ratio(a.as_ptr(), ptr::null()), -1);
+            assert_eq!(fuzzywuzzy_ratio(a.as_ptr(), invalid.as_ptr()), -1);
+        }
+    }
+
+    #[test]
+    fn extract_one_agrees_with_process() {
+        let choices = [
+            "new york mets vs chicago cubs",
+            "chicago cubs vs chicago white sox",
+            "philladelphia phillies vs atlanta braves",
+            "braves vs mets",
+        ];
+        let c_choices: Vec<CString> = choices.iter().map(|c| CString::new(*c).unwrap()).collect();
+        let ptrs: Vec<*const c_char> = c_choices.iter().map(|c| c.as_ptr()).collect();
+        for query in ["brave new cubs", "new york mets at atlanta braves"].iter() {
+            let expected =
+                extract_one(query, choices.iter(), utils::full_process, fuzz::WRatio, 0).unwrap();
+            let c_query = CString::new(*query).unwrap();
+            let mut score = 0u8;
+            let idx = unsafe {
+                fuzzywuzzy_extract_one(c_query.as_ptr(), ptrs.as_ptr(), ptrs.len(), 0, &mut score)
+            };
+            assert_eq!(choices[idx as usize], expected.0);
+            assert_eq!(score, expected.1);
+        }
+        let c_query = CString::new("zzz").unwrap();
+        let idx = unsafe {
+            fuzzywuzzy_extract_one(
+                c_query.as_ptr(),
+                ptrs.as_ptr(),
+                ptrs.len(),
+                101,
+                ptr::null_mut(),
+            )
+        };
+        assert_eq!(idx, -1);
+    }
+}
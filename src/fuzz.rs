@@ -1,7 +1,24 @@
 //! Fuzzy string matching scoring primitives.
 
-use crate::utils;
-use std::collections::HashSet;
+use crate::difflib;
+#[cfg(feature = "token")]
+use crate::error::Error;
+#[cfg(feature = "token")]
+use crate::normalization::{Normalizer, SynonymNormalizer};
+use crate::primitives;
+use crate::score::Score;
+use crate::segmentation::Segmenter;
+#[cfg(feature = "token")]
+use crate::segmentation::{Tokenizer, WhitespaceSegmenter};
+#[cfg(feature = "token")]
+use crate::utils::Rounding;
+use crate::utils::{self, Mode};
+#[cfg(feature = "token")]
+use std::cmp::Ordering;
+use std::cmp::Reverse;
+#[cfg(feature = "token")]
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 
 /// Returns the ratio of the length of matching character sequences to the sum of the length of the input strings.
 ///
@@ -23,18 +40,171 @@ use std::collections::HashSet;
 /// assert_eq!(ratio("new york mets", "new YORK mets"), 69);
 /// assert_eq!(ratio("hello test", "hello world"), 57);
 /// ```
-pub fn ratio(a: &str, b: &str) -> u8 {
+///
+/// Like every scorer in this module, it accepts anything implementing `AsRef<str>`.
+/// ```
+/// # use fuzzywuzzy::fuzz::ratio;
+/// use std::borrow::Cow;
+/// use std::rc::Rc;
+/// let owned = String::from("new york mets");
+/// let shared: Rc<str> = Rc::from("new YORK mets");
+/// assert_eq!(ratio(&owned, Cow::Borrowed("new york mets")), 100);
+/// assert_eq!(ratio(owned, shared), 69);
+/// ```
+pub fn ratio(a: impl AsRef<str>, b: impl AsRef<str>) -> u8 {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    ratio_with_mode(a, b, Mode::PythonCompat)
+}
+
+/// [ratio], converting the similarity to an integer score as `mode` prescribes.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::ratio_with_mode;
+/// # use fuzzywuzzy::utils::Mode;
+/// assert_eq!(ratio_with_mode("cd", "abcd", Mode::PythonCompat), 67);
+/// assert_eq!(ratio_with_mode("cd", "abcd", Mode::Improved), 67);
+/// ```
+pub fn ratio_with_mode(a: impl AsRef<str>, b: impl AsRef<str>, mode: Mode) -> u8 {
+    let (a, b) = (a.as_ref(), b.as_ref());
     check_trivial!(a, b);
     let matches: usize = utils::get_matching_blocks(a, b)
         .iter()
         .map(|&(_, _, s)| s)
         .sum();
-    let sumlength: f32 = (a.chars().count() + b.chars().count()) as f32;
-    if sumlength > 0.0 {
-        (100.0 * (2.0 * (matches as f32) / sumlength)).round() as u8
-    } else {
+    mode.score(matches, a.chars().count() + b.chars().count())
+}
+
+/// [ratio], or `None` if it is below `score_cutoff`.
+///
+/// Matching stops as soon as the score can no longer reach the cutoff, see
+/// [get_matching_blocks_reaching](primitives::get_matching_blocks_reaching),
+/// so dissimilar strings are ruled out after their longest matches. Used by
+/// [Ratio] during extraction with a cutoff.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::ratio_with_cutoff;
+/// assert_eq!(ratio_with_cutoff("hello test", "hello world", 50), Some(57));
+/// assert_eq!(ratio_with_cutoff("hello test", "hello world", 60), None);
+/// assert_eq!(ratio_with_cutoff("", "", 100), Some(100));
+/// ```
+pub fn ratio_with_cutoff(a: impl AsRef<str>, b: impl AsRef<str>, score_cutoff: u8) -> Option<u8> {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    let score = if a == b {
         100
+    } else if a.is_empty() || b.is_empty() {
+        0
+    } else {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        let total = a.len() + b.len();
+        // The fewest matches scoring at least the cutoff.
+        let needed = (0..=a.len().min(b.len()))
+            .find(|&matches| Mode::PythonCompat.score(matches, total) >= score_cutoff)?;
+        let matches: usize = primitives::get_matching_blocks_reaching(&a, &b, needed)?
+            .iter()
+            .map(|&(_, _, k)| k)
+            .sum();
+        Mode::PythonCompat.score(matches, total)
+    };
+    Some(score).filter(|&score| score >= score_cutoff)
+}
+
+/// [ratio], but counting a swap of adjacent characters as a single edit.
+///
+/// [ratio] matches one of the swapped characters and counts the other as
+/// deleted from one string and inserted into the other, two edits. Here,
+/// when a single matched character has an unmatched character next to it in
+/// one string and the same unmatched character on its other side in the
+/// other string, the pair counts as half matched, as a Damerau-Levenshtein
+/// distance would. [partial_transposition_ratio],
+/// [token_sort_transposition_ratio] and [token_set_transposition_ratio] do
+/// the same for the partial and token scorers.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{ratio, transposition_ratio};
+/// assert_eq!(ratio("recieve", "receive"), 86);
+/// assert_eq!(transposition_ratio("recieve", "receive"), 93);
+/// assert_eq!(transposition_ratio("ab", "ba"), 75);
+/// assert_eq!(ratio("new york", "new yrok"), 88);
+/// assert_eq!(transposition_ratio("new york", "new yrok"), 94);
+/// assert_eq!(transposition_ratio("abc", "xyz"), 0);
+/// ```
+pub fn transposition_ratio(a: impl AsRef<str>, b: impl AsRef<str>) -> u8 {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    transposition_ratio_with_mode(a, b, Mode::PythonCompat)
+}
+
+/// [transposition_ratio], converting the similarity to an integer score as `mode` prescribes.
+pub fn transposition_ratio_with_mode(a: impl AsRef<str>, b: impl AsRef<str>, mode: Mode) -> u8 {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    check_trivial!(a, b);
+    let blocks = utils::get_matching_blocks(a, b);
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let (mut a_free, mut b_free) = (vec![true; a.len()], vec![true; b.len()]);
+    for &(i, j, k) in blocks.iter() {
+        a_free[i..i + k].iter_mut().for_each(|free| *free = false);
+        b_free[j..j + k].iter_mut().for_each(|free| *free = false);
+    }
+    let matches: usize = blocks.iter().map(|&(_, _, k)| k).sum();
+    let mut transpositions = 0;
+    for &(i, j, _) in blocks.iter().filter(|&&(_, _, k)| k == 1) {
+        // The unmatched character before the match in one string and after it in the other.
+        let sides = [
+            (i.checked_sub(1), Some(j + 1)),
+            (Some(i + 1), j.checked_sub(1)),
+        ];
+        for (x, y) in sides.iter().filter_map(|&(x, y)| Some((x?, y?))) {
+            if x < a.len() && y < b.len() && a_free[x] && b_free[y] && a[x] == b[y] {
+                a_free[x] = false;
+                b_free[y] = false;
+                transpositions += 1;
+                break;
+            }
+        }
     }
+    // Each transposition adds half a match on both sides.
+    mode.score(2 * matches + transpositions, 2 * (a.len() + b.len()))
+}
+
+#[cfg(feature = "ngram")]
+/// Returns the Sørensen–Dice coefficient of the strings' character bigrams
+/// as a number between 0 and 100: twice the number of bigrams they have in
+/// common over the total number of bigrams.
+///
+/// Much cheaper than [ratio] and insensitive to where the bigrams are, which
+/// suits shortlisting candidates for a more precise scorer. A string of one
+/// character counts as a single bigram.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::dice_ratio;
+/// assert_eq!(dice_ratio("night", "nacht"), 25);
+/// assert_eq!(dice_ratio("new york", "york new"), 71);
+/// assert_eq!(dice_ratio("a", "a"), 100);
+/// assert_eq!(dice_ratio("abc", "xyz"), 0);
+/// ```
+pub fn dice_ratio(a: impl AsRef<str>, b: impl AsRef<str>) -> u8 {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    check_trivial!(a, b);
+    let bigrams = |s: &str| -> Vec<(char, Option<char>)> {
+        let chars: Vec<char> = s.chars().collect();
+        match chars.len() {
+            1 => vec![(chars[0], None)],
+            _ => chars.windows(2).map(|w| (w[0], Some(w[1]))).collect(),
+        }
+    };
+    let (a, b) = (bigrams(a), bigrams(b));
+    let mut counts: HashMap<(char, Option<char>), usize> = HashMap::new();
+    for &bigram in &a {
+        *counts.entry(bigram).or_insert(0) += 1;
+    }
+    let mut common = 0;
+    for bigram in &b {
+        if let Some(count) = counts.get_mut(bigram).filter(|count| **count > 0) {
+            *count -= 1;
+            common += 1;
+        }
+    }
+    Mode::PythonCompat.score(common, a.len() + b.len())
 }
 
 /// Return the ratio of the most similar substring as a number between 0 and 100.
@@ -65,7 +235,44 @@ pub fn ratio(a: &str, b: &str) -> u8 {
 ///    "supercalifragilisticexpialidocious about what",
 ///    "what about supercalifragilisticexpialidocious"), 86);
 /// ```
-pub fn partial_ratio(s1: &str, s2: &str) -> u8 {
+pub fn partial_ratio(s1: impl AsRef<str>, s2: impl AsRef<str>) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    partial_ratio_with_mode(s1, s2, Mode::PythonCompat)
+}
+
+/// [partial_ratio], converting the similarity to an integer score as `mode` prescribes.
+pub fn partial_ratio_with_mode(s1: impl AsRef<str>, s2: impl AsRef<str>, mode: Mode) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    partial_ratio_by(s1, s2, mode, |a, b, mode| ratio_with_mode(a, b, mode))
+}
+
+/// [partial_ratio], but counting a swap of adjacent characters as a single
+/// edit, see [transposition_ratio].
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{partial_ratio, partial_transposition_ratio};
+/// assert_eq!(partial_ratio("recieve", "please receive it"), 86);
+/// assert_eq!(partial_transposition_ratio("recieve", "please receive it"), 93);
+/// ```
+pub fn partial_transposition_ratio(s1: impl AsRef<str>, s2: impl AsRef<str>) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    partial_transposition_ratio_with_mode(s1, s2, Mode::PythonCompat)
+}
+
+/// [partial_transposition_ratio], converting the similarity to an integer score as `mode` prescribes.
+pub fn partial_transposition_ratio_with_mode(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    mode: Mode,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    partial_ratio_by(s1, s2, mode, |a, b, mode| {
+        transposition_ratio_with_mode(a, b, mode)
+    })
+}
+
+/// Scores the substrings of the longer string [partial_ratio] looks at with `ratio`.
+fn partial_ratio_by<F: Fn(&str, &str, Mode) -> u8>(s1: &str, s2: &str, mode: Mode, ratio: F) -> u8 {
     check_trivial!(s1, s2);
     let (shorter, longer) = if s1.chars().count() <= s2.chars().count() {
         (s1, s2)
@@ -75,10 +282,11 @@ pub fn partial_ratio(s1: &str, s2: &str) -> u8 {
     let blocks = utils::get_matching_blocks(shorter, longer);
     let mut max: u8 = 0;
     for (i, j, _) in blocks {
-        let long_start = if j > i { j - i } else { 0 };
+        let long_start = j.saturating_sub(i);
         let long_end = std::cmp::min(long_start + shorter.chars().count(), longer.chars().count());
-        let long_substr = &longer[long_start..long_end];
-        let r = ratio(shorter, long_substr);
+        let long_substr =
+            utils::slice_chars(longer, long_start..long_end).expect("bounds are within `longer`");
+        let r = ratio(shorter, long_substr, mode);
         if r > 99 {
             return 100;
         } else if r > max {
@@ -88,33 +296,535 @@ pub fn partial_ratio(s1: &str, s2: &str) -> u8 {
     max
 }
 
+/// A window of the text searched by [partial_ratio_windows], and its score.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Window {
+    /// The byte range of the window in the text.
+    pub span: Range<usize>,
+    /// The [ratio] of the needle and the text in `span`.
+    pub score: u8,
+}
+
+/// Finds every approximate occurrence of `needle` in `haystack`: the windows
+/// of `haystack` as long as `needle`, trimmed to the characters aligned with
+/// it, whose [ratio] with it is at least `score_cutoff`, in order.
+///
+/// Windows overlapping a better scoring one, or an equally scored one
+/// further left, are left out, so each occurrence is found once, and windows
+/// with no character in common with `needle` are never found. Where
+/// [partial_ratio] only looks at windows aligned with difflib's matching
+/// blocks and returns the best score, this tries every window, so it takes
+/// `O(haystack.len() * needle.len()^2)` time in the worst case. A `haystack`
+/// shorter than `needle` is a single window. The strings are compared as
+/// they are, so process them first to ignore case and punctuation.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{partial_ratio_windows, Window};
+/// let paragraph = "Jon Smith called. Later, John Smyth wrote back, cc'ing Jane Smith.";
+/// let windows = partial_ratio_windows("John Smith", paragraph, 80);
+/// let mentions: Vec<_> = windows.iter().map(|window| &paragraph[window.span.clone()]).collect();
+/// assert_eq!(mentions, vec!["Jon Smith", "John Smyth", "Jane Smith"]);
+/// assert_eq!(windows[0].score, 95);
+/// assert_eq!(windows[1], Window { span: 25..35, score: 90 });
+/// assert_eq!(partial_ratio_windows("John Smith", paragraph, 85).len(), 2);
+/// assert!(partial_ratio_windows("John Smith", "", 0).is_empty());
+/// ```
+pub fn partial_ratio_windows(
+    needle: impl AsRef<str>,
+    haystack: impl AsRef<str>,
+    score_cutoff: u8,
+) -> Vec<Window> {
+    let (needle, haystack) = (needle.as_ref(), haystack.as_ref());
+    let needle_chars: Vec<char> = needle.chars().collect();
+    let haystack_chars: Vec<char> = haystack.chars().collect();
+    // The byte offset of each character of `haystack`, and of its end.
+    let offsets: Vec<usize> = haystack
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(haystack.len()))
+        .collect();
+    let chars = haystack_chars.len();
+    if needle_chars.is_empty() {
+        return vec![];
+    }
+    let width = needle_chars.len().min(chars);
+    let mut candidates = vec![];
+    for start in 0..=chars - width {
+        let window = &haystack_chars[start..start + width];
+        // Trimming leaves the matches and shortens the window to at least
+        // as many characters, so this bounds the trimmed window's score.
+        let matches = difflib::quick_matches(needle_chars.iter(), window.iter());
+        if Mode::PythonCompat.score(matches, needle_chars.len() + matches) < score_cutoff {
+            continue;
+        }
+        let blocks = primitives::get_matching_blocks(&needle_chars, window);
+        let (first, last) = match (blocks.first(), blocks.iter().rev().nth(1)) {
+            (Some(&(_, j, n)), Some(&(_, k, m))) if n > 0 => (start + j, start + k + m),
+            _ => continue,
+        };
+        let span = offsets[first]..offsets[last];
+        let score = ratio(needle, &haystack[span.clone()]);
+        if score >= score_cutoff {
+            candidates.push((first..last, Window { span, score }));
+        }
+    }
+    // Best first, and leftmost first among equals.
+    candidates.sort_by_key(|(range, window)| (Reverse(window.score), range.start));
+    let mut taken: Vec<(Range<usize>, Window)> = vec![];
+    for (range, window) in candidates {
+        if taken
+            .iter()
+            .all(|(other, _)| range.end <= other.start || other.end <= range.start)
+        {
+            taken.push((range, window));
+        }
+    }
+    taken.sort_by_key(|(range, _)| range.start);
+    taken.into_iter().map(|(_, window)| window).collect()
+}
+
+/// Like [ratio], but compares raw bytes, with no assumption that either input is valid UTF-8.
+///
+/// Useful for binary identifiers, or scraped text which may be invalid UTF-8
+/// and shouldn't be lossily converted. For ASCII inputs this agrees with [ratio].
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{ratio, ratio_bytes};
+/// assert_eq!(ratio_bytes(b"new york mets", b"new YORK mets"), ratio("new york mets", "new YORK mets"));
+/// assert_eq!(ratio_bytes(b"caf\xe9", b"caf\xc3\xa9"), 67);
+/// assert_eq!(ratio_bytes(&[0xde, 0xad, 0xbe, 0xef], &[0xde, 0xad, 0xbe, 0xef]), 100);
+/// ```
+pub fn ratio_bytes(a: &[u8], b: &[u8]) -> u8 {
+    slice_ratio(a, b)
+}
+
+/// Returns the Levenshtein distance between two byte strings, with no assumption that either is valid UTF-8.
+///
+/// See [primitives::levenshtein].
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::levenshtein_bytes;
+/// assert_eq!(levenshtein_bytes(b"kitten", b"sitting"), 3);
+/// // A non-ASCII character differs in as many bytes as its encoding takes.
+/// assert_eq!(levenshtein_bytes("café".as_bytes(), b"cafe"), 2);
+/// ```
+pub fn levenshtein_bytes(a: &[u8], b: &[u8]) -> usize {
+    primitives::levenshtein(a, b)
+}
+
+/// Like [ratio], but compares the units produced by `segmenter` rather than Unicode scalar values.
+///
+/// With [GraphemeSegmenter](crate::segmentation::GraphemeSegmenter) (default feature "segmentation"),
+/// user-perceived characters such as `"y̆"` (`y` + U+0306) or an emoji with a skin tone
+/// modifier count as a single unit, so scores line up with what a reader sees.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{ratio, segmented_ratio};
+/// # use fuzzywuzzy::segmentation::{CodePointSegmenter, GraphemeSegmenter};
+/// assert_eq!(segmented_ratio("cd", "abcd", &CodePointSegmenter), ratio("cd", "abcd"));
+/// // "y̆" is two code points but one grapheme cluster.
+/// assert_eq!(segmented_ratio("y\u{0306}es", "yes", &CodePointSegmenter), 86);
+/// assert_eq!(segmented_ratio("y\u{0306}es", "yes", &GraphemeSegmenter), 67);
+/// ```
+pub fn segmented_ratio<'a, S: Segmenter<'a>>(a: &'a str, b: &'a str, segmenter: &S) -> u8 {
+    check_trivial!(a, b);
+    slice_ratio(&segmenter.segment(a), &segmenter.segment(b))
+}
+
+/// Like [partial_ratio], but aligns and compares the units produced by `segmenter`
+/// rather than Unicode scalar values.
+///
+/// Substrings are taken on unit boundaries, so a grapheme cluster is never split.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{partial_ratio, segmented_partial_ratio};
+/// # use fuzzywuzzy::segmentation::{CodePointSegmenter, GraphemeSegmenter};
+/// assert_eq!(segmented_partial_ratio("bc", "abcd", &CodePointSegmenter), partial_ratio("bc", "abcd"));
+/// assert_eq!(segmented_partial_ratio("y", "y\u{0306}es", &CodePointSegmenter), 100);
+/// assert_eq!(segmented_partial_ratio("y", "y\u{0306}es", &GraphemeSegmenter), 0);
+/// ```
+pub fn segmented_partial_ratio<'a, S: Segmenter<'a>>(
+    s1: &'a str,
+    s2: &'a str,
+    segmenter: &S,
+) -> u8 {
+    check_trivial!(s1, s2);
+    let (a, b) = (segmenter.segment(s1), segmenter.segment(s2));
+    let (shorter, longer) = if a.len() <= b.len() {
+        (&a[..], &b[..])
+    } else {
+        (&b[..], &a[..])
+    };
+    let mut max: u8 = 0;
+    for (i, j, _) in primitives::get_matching_blocks(shorter, longer) {
+        let long_start = j.saturating_sub(i);
+        let long_end = std::cmp::min(long_start + shorter.len(), longer.len());
+        let r = slice_ratio(shorter, &longer[long_start..long_end]);
+        if r > 99 {
+            return 100;
+        } else if r > max {
+            max = r;
+        }
+    }
+    max
+}
+
+#[cfg(feature = "alignment")]
+/// A score together with the matching blocks it was computed from.
+///
+/// Each block `(i, j, n)` means `n` units starting at index `i` of the first
+/// input match those starting at index `j` of the second. Blocks are
+/// non-empty and in increasing order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alignment {
+    pub score: u8,
+    pub blocks: Vec<(usize, usize, usize)>,
+}
+
+#[cfg(feature = "alignment")]
+impl Alignment {
+    /// The indices of units of the first input which are part of a match.
+    pub fn positions_in_first(&self) -> Vec<usize> {
+        self.blocks.iter().flat_map(|&(i, _, n)| i..i + n).collect()
+    }
+
+    /// The indices of units of the second input which are part of a match.
+    pub fn positions_in_second(&self) -> Vec<usize> {
+        self.blocks.iter().flat_map(|&(_, j, n)| j..j + n).collect()
+    }
+}
+
+#[cfg(feature = "alignment")]
+/// [ratio], along with which characters of `a` and `b` matched.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{ratio_alignment, Alignment};
+/// assert_eq!(ratio_alignment("cd", "abcd"), Alignment { score: 67, blocks: vec![(0, 2, 2)] });
+/// assert_eq!(ratio_alignment("ab", "a-b").positions_in_second(), vec![0, 2]);
+/// ```
+pub fn ratio_alignment(a: impl AsRef<str>, b: impl AsRef<str>) -> Alignment {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    slice_alignment(&a, &b)
+}
+
+#[cfg(feature = "alignment")]
+/// [partial_ratio], along with which characters of `s1` and `s2` matched in
+/// the best scoring substring.
+///
+/// Indices in the longer input are relative to the whole input, not the substring.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::partial_ratio_alignment;
+/// let alignment = partial_ratio_alignment("yonkers", "new york yankees");
+/// assert_eq!(alignment.score, 71);
+/// assert_eq!(alignment.positions_in_second(), vec![9, 11, 12, 13, 15]);
+/// ```
+pub fn partial_ratio_alignment(s1: impl AsRef<str>, s2: impl AsRef<str>) -> Alignment {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    let (a, b): (Vec<char>, Vec<char>) = (s1.chars().collect(), s2.chars().collect());
+    slice_partial_alignment(&a, &b)
+}
+
+#[cfg(feature = "alignment")]
+/// Like [ratio_alignment], but compares the units produced by `segmenter`,
+/// so e.g. with [GraphemeSegmenter](crate::segmentation::GraphemeSegmenter)
+/// positions are grapheme cluster indices.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::segmented_ratio_alignment;
+/// # use fuzzywuzzy::segmentation::GraphemeSegmenter;
+/// let alignment = segmented_ratio_alignment("yes", "y\u{0306}yes", &GraphemeSegmenter);
+/// assert_eq!(alignment.positions_in_second(), vec![1, 2, 3]);
+/// ```
+pub fn segmented_ratio_alignment<'a, S: Segmenter<'a>>(
+    a: &'a str,
+    b: &'a str,
+    segmenter: &S,
+) -> Alignment {
+    slice_alignment(&segmenter.segment(a), &segmenter.segment(b))
+}
+
+#[cfg(feature = "alignment")]
+/// Like [partial_ratio_alignment], but aligns and compares the units produced by `segmenter`.
+pub fn segmented_partial_ratio_alignment<'a, S: Segmenter<'a>>(
+    s1: &'a str,
+    s2: &'a str,
+    segmenter: &S,
+) -> Alignment {
+    slice_partial_alignment(&segmenter.segment(s1), &segmenter.segment(s2))
+}
+
+#[cfg(feature = "alignment")]
+/// Up to `k` distinct alignments of `a` and `b` scoring as well as
+/// [ratio_alignment], for when there are several ways to match them equally
+/// well.
+///
+/// [ratio] repeatedly matches the longest common block, preferring the one
+/// starting earliest in the shorter input, then earliest in the longer one.
+/// The alternatives pick other blocks of the same length instead, and are
+/// kept if they match as many characters in total. The first alignment is
+/// always [ratio_alignment], the rest follow in the same order of preference.
+///
+/// Takes `O(a.len() * b.len())` time for each block of each alternative
+/// tried, and no more than `O(a.len() + b.len())` memory besides the
+/// alignments.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{ratio_alignment, ratio_alignments, Alignment};
+/// let alignments = ratio_alignments("abab", "ab", 5);
+/// let blocks: Vec<_> = alignments.iter().map(|alignment| alignment.blocks.clone()).collect();
+/// assert_eq!(blocks, vec![vec![(0, 0, 2)], vec![(2, 0, 2)]]);
+/// assert!(alignments.iter().all(|alignment| alignment.score == 67));
+/// assert_eq!(ratio_alignments("abab", "ab", 1), vec![ratio_alignment("abab", "ab")]);
+/// ```
+pub fn ratio_alignments(a: impl AsRef<str>, b: impl AsRef<str>, k: usize) -> Vec<Alignment> {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    slice_alignments(&a, &b, k)
+}
+
+#[cfg(feature = "alignment")]
+/// Like [ratio_alignments], but aligns the units produced by `segmenter`.
+pub fn segmented_ratio_alignments<'a, S: Segmenter<'a>>(
+    a: &'a str,
+    b: &'a str,
+    segmenter: &S,
+    k: usize,
+) -> Vec<Alignment> {
+    slice_alignments(&segmenter.segment(a), &segmenter.segment(b), k)
+}
+
+#[cfg(feature = "alignment")]
+/// Every longest common block of `shorter[low1..high1]` and
+/// `longer[low2..high2]`, in the order [find_longest_match](primitives::find_longest_match)
+/// prefers them, or none if they have no element in common.
+fn longest_matches<T: Eq>(
+    shorter: &[T],
+    longer: &[T],
+    (low1, high1): (usize, usize),
+    (low2, high2): (usize, usize),
+) -> Vec<(usize, usize, usize)> {
+    let len2 = high2 - low2;
+    let (mut prev, mut cur) = (vec![0; len2 + 1], vec![0; len2 + 1]);
+    let mut best = (1, vec![]);
+    for (i, x) in shorter[low1..high1].iter().enumerate() {
+        for (j, y) in longer[low2..high2].iter().enumerate() {
+            let size = if x == y { prev[j] + 1 } else { 0 };
+            cur[j + 1] = size;
+            if size > best.0 {
+                best = (size, vec![]);
+            }
+            if size == best.0 {
+                best.1
+                    .push((low1 + i + 1 - size, low2 + j + 1 - size, size));
+            }
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    count!(dp_cells, (high1 - low1) * len2);
+    // Streaks of one size end in the order they start.
+    best.1
+}
+
+#[cfg(feature = "alignment")]
+fn slice_alignments<T: Eq>(a: &[T], b: &[T], k: usize) -> Vec<Alignment> {
+    let mut alignments = vec![slice_alignment(a, b)];
+    let target: usize = alignments[0].blocks.iter().map(|&(_, _, n)| n).sum();
+    let flipped = a.len() > b.len();
+    let (shorter, longer) = if flipped { (b, a) } else { (a, b) };
+
+    /// Ranges left to match, blocks matched, and the most elements they can
+    /// add up to, like [get_matching_blocks_reaching](primitives::get_matching_blocks_reaching).
+    #[derive(Clone)]
+    struct Partial {
+        queue: Vec<((usize, usize), (usize, usize))>,
+        blocks: Vec<(usize, usize, usize)>,
+        reachable: usize,
+    }
+    let span = |(low, high): (usize, usize)| high - low;
+    let mut stack = vec![Partial {
+        queue: vec![((0, shorter.len()), (0, longer.len()))],
+        blocks: vec![],
+        reachable: shorter.len(),
+    }];
+    while alignments.len() < k {
+        let Some(mut partial) = stack.pop() else {
+            break;
+        };
+        while partial.reachable >= target {
+            let Some((range1, range2)) = partial.queue.pop() else {
+                let mut blocks = vec![];
+                partial.blocks.sort_unstable();
+                for &(i, j, n) in &partial.blocks {
+                    let (i, j) = if flipped { (j, i) } else { (i, j) };
+                    match blocks.last_mut() {
+                        Some((bi, bj, bn)) if *bi + *bn == i && *bj + *bn == j => *bn += n,
+                        _ => blocks.push((i, j, n)),
+                    }
+                }
+                if partial.reachable == target
+                    && alignments
+                        .iter()
+                        .all(|alignment| alignment.blocks != blocks)
+                {
+                    let score = alignments[0].score;
+                    alignments.push(Alignment { score, blocks });
+                }
+                break;
+            };
+            partial.reachable -= span(range1).min(span(range2));
+            let matches = longest_matches(shorter, longer, range1, range2);
+            // Each block leaves the ranges before and after it to match.
+            let apply = |partial: &mut Partial, (i, j, n): (usize, usize, usize)| {
+                partial.blocks.push((i, j, n));
+                partial.reachable += n;
+                for (before, after) in [
+                    ((range1.0, i), (range2.0, j)),
+                    ((i + n, range1.1), (j + n, range2.1)),
+                ] {
+                    if span(before) > 0 && span(after) > 0 {
+                        partial.queue.push((before, after));
+                        partial.reachable += span(before).min(span(after));
+                    }
+                }
+            };
+            for &block in matches.iter().skip(1).rev() {
+                let mut alternative = partial.clone();
+                apply(&mut alternative, block);
+                stack.push(alternative);
+            }
+            if let Some(&block) = matches.first() {
+                apply(&mut partial, block);
+            }
+        }
+    }
+    alignments.truncate(k);
+    alignments
+}
+
+#[cfg(feature = "alignment")]
+fn slice_alignment<T: Eq>(a: &[T], b: &[T]) -> Alignment {
+    let blocks: Vec<_> = primitives::get_matching_blocks(a, b)
+        .into_iter()
+        .filter(|&(_, _, n)| n > 0)
+        .collect();
+    Alignment {
+        score: slice_ratio(a, b),
+        blocks,
+    }
+}
+
+#[cfg(feature = "alignment")]
+/// The alignment [segmented_partial_ratio] scores, searching windows the same way.
+fn slice_partial_alignment<T: Eq>(a: &[T], b: &[T]) -> Alignment {
+    let flipped = a.len() > b.len();
+    let (shorter, longer) = if flipped { (b, a) } else { (a, b) };
+    let mut best = Alignment {
+        score: 0,
+        blocks: vec![],
+    };
+    if a.is_empty() ^ b.is_empty() {
+        return best;
+    }
+    let mut best_start = 0;
+    for (i, j, _) in primitives::get_matching_blocks(shorter, longer) {
+        let long_start = j.saturating_sub(i);
+        let long_end = std::cmp::min(long_start + shorter.len(), longer.len());
+        let alignment = slice_alignment(shorter, &longer[long_start..long_end]);
+        if alignment.score > best.score {
+            best = alignment;
+            best_start = long_start;
+            if best.score > 99 {
+                break;
+            }
+        }
+    }
+    for block in best.blocks.iter_mut() {
+        block.1 += best_start;
+        if flipped {
+            *block = (block.1, block.0, block.2);
+        }
+    }
+    best
+}
+
+/// [ratio] over already segmented inputs.
+fn slice_ratio<T: Eq>(a: &[T], b: &[T]) -> u8 {
+    check_trivial!(a, b);
+    let matches: usize = primitives::get_matching_blocks(a, b)
+        .iter()
+        .map(|&(_, _, s)| s)
+        .sum();
+    Mode::PythonCompat.score(matches, a.len() + b.len())
+}
+
+#[cfg(feature = "token")]
+/// The character level ratio token scorers compare the strings they build with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CharRatio {
+    /// [ratio]
+    Ratio,
+    /// [partial_ratio]
+    Partial,
+    /// [transposition_ratio]
+    Transposition,
+}
+
+#[cfg(feature = "token")]
+impl CharRatio {
+    fn score(self, a: &str, b: &str, mode: Mode) -> u8 {
+        match self {
+            CharRatio::Ratio => ratio_with_mode(a, b, mode),
+            CharRatio::Partial => partial_ratio_with_mode(a, b, mode),
+            CharRatio::Transposition => transposition_ratio_with_mode(a, b, mode),
+        }
+    }
+}
+
+#[cfg(feature = "token")]
 /// Return a cleaned string with token sorted.
-fn process_and_sort(s: &str, force_ascii: bool, full_process: bool) -> String {
+fn process_and_sort<T: Tokenizer + ?Sized, C: Fn(&str, &str) -> Ordering>(
+    s: &str,
+    force_ascii: bool,
+    full_process: bool,
+    tokenizer: &T,
+    compare: &C,
+    mode: Mode,
+) -> String {
     let ts = if full_process {
-        utils::full_process(s, force_ascii)
+        mode.process(s, force_ascii)
     } else {
         s.to_string()
     };
-    let mut ts_split: Vec<_> = ts.split_whitespace().collect();
-    ts_split.sort_unstable();
+    let mut ts_split: Vec<_> = tokenizer.segment(&ts);
+    ts_split.sort_unstable_by(|a, b| compare(a, b));
     ts_split.join(" ")
 }
 
+#[cfg(feature = "token")]
 /// Sorted Token
 /// # find all alphanumeric tokens in the string
 /// # sort those tokens and take ratio of resulting joined strings
 /// # controls for unordered string elements
-fn token_sort(s1: &str, s2: &str, partial: bool, force_ascii: bool, full_process: bool) -> u8 {
+#[allow(clippy::too_many_arguments)]
+fn token_sort<T: Tokenizer + ?Sized, C: Fn(&str, &str) -> Ordering>(
+    s1: &str,
+    s2: &str,
+    ratio: CharRatio,
+    force_ascii: bool,
+    full_process: bool,
+    tokenizer: &T,
+    compare: &C,
+    mode: Mode,
+) -> u8 {
     check_trivial!(s1, s2);
-    let sorted1 = process_and_sort(s1, force_ascii, full_process);
-    let sorted2 = process_and_sort(s2, force_ascii, full_process);
-    if partial {
-        partial_ratio(sorted1.as_ref(), sorted2.as_ref())
-    } else {
-        ratio(sorted1.as_ref(), sorted2.as_ref())
-    }
+    let sorted1 = process_and_sort(s1, force_ascii, full_process, tokenizer, compare, mode);
+    let sorted2 = process_and_sort(s2, force_ascii, full_process, tokenizer, compare, mode);
+    ratio.score(&sorted1, &sorted2, mode)
 }
 
+#[cfg(feature = "token")]
 /// Return a measure of the sequences' similarity between 0 and 100, but sort the token before
 /// comparing.
 ///
@@ -131,11 +841,88 @@ fn token_sort(s1: &str, s2: &str, partial: bool, force_ascii: bool, full_process
 ///    "what about supercalifragilisticexpialidocious",
 ///    "supercalifragilisticexpialidocious about what", true, true), 100);
 /// ```
-pub fn token_sort_ratio(s1: &str, s2: &str, force_ascii: bool, full_process: bool) -> u8 {
+pub fn token_sort_ratio(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    // trivial check omitted because this is a shallow delegator to token_sort which checks.
+    token_sort(
+        s1,
+        s2,
+        CharRatio::Ratio,
+        force_ascii,
+        full_process,
+        &WhitespaceSegmenter,
+        &str::cmp,
+        Mode::PythonCompat,
+    )
+}
+
+#[cfg(feature = "token")]
+/// [token_sort_ratio], but splitting strings into tokens with `tokenizer` instead of on whitespace.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{token_sort_ratio, token_sort_ratio_with};
+/// # use fuzzywuzzy::segmentation::WordSegmenter;
+/// assert_eq!(token_sort_ratio("我喜欢猫", "猫喜欢我", true, false), 50);
+/// assert_eq!(token_sort_ratio_with("我喜欢猫", "猫喜欢我", true, false, &WordSegmenter), 100);
+/// ```
+pub fn token_sort_ratio_with<T: Tokenizer + ?Sized>(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+    tokenizer: &T,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    // trivial check omitted because this is a shallow delegator to token_sort which checks.
+    token_sort(
+        s1,
+        s2,
+        CharRatio::Ratio,
+        force_ascii,
+        full_process,
+        tokenizer,
+        &str::cmp,
+        Mode::PythonCompat,
+    )
+}
+
+#[cfg(feature = "token")]
+/// [token_sort_ratio], but counting a swap of adjacent characters as a
+/// single edit, see [transposition_ratio].
+///
+/// By default, force_ascii and full_process should be true.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{token_sort_ratio, token_sort_transposition_ratio};
+/// assert_eq!(token_sort_ratio("new yrok mets", "mets new york", true, true), 92);
+/// assert_eq!(token_sort_transposition_ratio("new yrok mets", "mets new york", true, true), 96);
+/// ```
+pub fn token_sort_transposition_ratio(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
     // trivial check omitted because this is a shallow delegator to token_sort which checks.
-    token_sort(s1, s2, false, force_ascii, full_process)
+    token_sort(
+        s1,
+        s2,
+        CharRatio::Transposition,
+        force_ascii,
+        full_process,
+        &WhitespaceSegmenter,
+        &str::cmp,
+        Mode::PythonCompat,
+    )
 }
 
+#[cfg(feature = "token")]
 /// Return the ratio of the most similar substring as a number between 0 and 100, but sort the tokens
 /// before comparing.
 ///
@@ -153,34 +940,161 @@ pub fn token_sort_ratio(s1: &str, s2: &str, force_ascii: bool, full_process: boo
 ///    "what about supercalifragilisticexpialidocious",
 ///    "supercalifragilisticexpialidocious about what", true, true), 100);
 /// ```
-pub fn partial_token_sort_ratio(s1: &str, s2: &str, force_ascii: bool, full_process: bool) -> u8 {
+pub fn partial_token_sort_ratio(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
     // trivial check omitted because this is a shallow delegator to token_sort which checks.
-    token_sort(s1, s2, true, force_ascii, full_process)
+    token_sort(
+        s1,
+        s2,
+        CharRatio::Partial,
+        force_ascii,
+        full_process,
+        &WhitespaceSegmenter,
+        &str::cmp,
+        Mode::PythonCompat,
+    )
 }
 
+#[cfg(feature = "token")]
+/// [partial_token_sort_ratio], but splitting strings into tokens with `tokenizer` instead of on whitespace.
+pub fn partial_token_sort_ratio_with<T: Tokenizer + ?Sized>(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+    tokenizer: &T,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    // trivial check omitted because this is a shallow delegator to token_sort which checks.
+    token_sort(
+        s1,
+        s2,
+        CharRatio::Partial,
+        force_ascii,
+        full_process,
+        tokenizer,
+        &str::cmp,
+        Mode::PythonCompat,
+    )
+}
+
+#[cfg(feature = "token")]
+/// [token_sort_ratio_with], but ordering tokens with `compare` instead of by code point.
+///
+/// Sorting by code point puts e.g. `"äpfel"` after `"zebra"`. A locale aware
+/// comparison, like the one provided by `collation::LocaleCollator` (feature
+/// "collation"), sorts tokens the way users of that locale expect.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{token_sort_ratio, token_sort_ratio_by};
+/// # use fuzzywuzzy::segmentation::WhitespaceSegmenter;
+/// // Compare tokens ignoring a leading umlaut, a crude stand-in for real collation.
+/// let base = |s: &str| s.replacen('ä', "a", 1);
+/// let by_base = |a: &str, b: &str| base(a).cmp(&base(b));
+/// assert_eq!(token_sort_ratio("zebra äpfel", "apfel zebra", false, true), 45);
+/// assert_eq!(token_sort_ratio_by("zebra äpfel", "apfel zebra", false, true, &WhitespaceSegmenter, by_base), 91);
+/// ```
+pub fn token_sort_ratio_by<T, C>(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+    tokenizer: &T,
+    compare: C,
+) -> u8
+where
+    T: Tokenizer + ?Sized,
+    C: Fn(&str, &str) -> Ordering,
+{
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    // trivial check omitted because this is a shallow delegator to token_sort which checks.
+    token_sort(
+        s1,
+        s2,
+        CharRatio::Ratio,
+        force_ascii,
+        full_process,
+        tokenizer,
+        &compare,
+        Mode::PythonCompat,
+    )
+}
+
+#[cfg(feature = "token")]
+/// [partial_token_sort_ratio_with], but ordering tokens with `compare` instead of by code point.
+pub fn partial_token_sort_ratio_by<T, C>(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+    tokenizer: &T,
+    compare: C,
+) -> u8
+where
+    T: Tokenizer + ?Sized,
+    C: Fn(&str, &str) -> Ordering,
+{
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    // trivial check omitted because this is a shallow delegator to token_sort which checks.
+    token_sort(
+        s1,
+        s2,
+        CharRatio::Partial,
+        force_ascii,
+        full_process,
+        tokenizer,
+        &compare,
+        Mode::PythonCompat,
+    )
+}
+
+#[cfg(feature = "token")]
 /// Find all alphanumeric tokens in each string...
 ///  # treat them as a set
 ///  # construct two strings of the form: <sorted_intersection><sorted_remainder>
 ///  # take ratios of those two strings
 ///  # controls for unordered partial matches
-fn token_set(s1: &str, s2: &str, partial: bool, force_ascii: bool, full_process: bool) -> u8 {
+fn token_set<T: Tokenizer + ?Sized>(
+    s1: &str,
+    s2: &str,
+    ratio: CharRatio,
+    force_ascii: bool,
+    full_process: bool,
+    tokenizer: &T,
+    mode: Mode,
+) -> u8 {
     check_trivial!(s1, s2);
     let (p1, p2) = if full_process {
-        (
-            utils::full_process(s1, force_ascii),
-            utils::full_process(s2, force_ascii),
-        )
+        (mode.process(s1, force_ascii), mode.process(s2, force_ascii))
     } else {
         (s1.to_string(), s2.to_string())
     };
-    let t1: HashSet<_> = p1.split_whitespace().collect();
-    let t2: HashSet<_> = p2.split_whitespace().collect();
+    let t1: HashSet<_> = tokenizer.segment(&p1).into_iter().collect();
+    let t2: HashSet<_> = tokenizer.segment(&p2).into_iter().collect();
     let mut intersection: Vec<_> = t1.intersection(&t2).cloned().collect();
     let mut diff1to2: Vec<_> = t1.difference(&t2).cloned().collect();
     let mut diff2to1: Vec<_> = t2.difference(&t1).cloned().collect();
     intersection.sort_unstable();
     diff1to2.sort_unstable();
     diff2to1.sort_unstable();
+    sorted_set_ratio(&intersection, &diff1to2, &diff2to1, ratio, mode)
+}
+
+#[cfg(feature = "token")]
+/// The ratio of [token_set] given the sorted tokens in both strings, only in
+/// the first and only in the second.
+pub(crate) fn sorted_set_ratio(
+    intersection: &[&str],
+    diff1to2: &[&str],
+    diff2to1: &[&str],
+    ratio: CharRatio,
+    mode: Mode,
+) -> u8 {
     let intersect_str = intersection.join(" ");
     let diff1to2_str = diff1to2.join(" ");
     let diff2to1_str = diff2to1.join(" ");
@@ -194,27 +1108,17 @@ fn token_set(s1: &str, s2: &str, partial: bool, force_ascii: bool, full_process:
     } else {
         intersect_str.to_string()
     };
-    if partial {
-        *vec![
-            partial_ratio(&intersect_str, &combined_1to2),
-            partial_ratio(&intersect_str, &combined_2to1),
-            partial_ratio(&combined_1to2, &combined_2to1),
-        ]
-        .iter()
-        .max()
-        .unwrap()
-    } else {
-        *vec![
-            ratio(&intersect_str, &combined_1to2),
-            ratio(&intersect_str, &combined_2to1),
-            ratio(&combined_1to2, &combined_2to1),
-        ]
-        .iter()
-        .max()
-        .unwrap()
-    }
+    *[
+        ratio.score(&intersect_str, &combined_1to2, mode),
+        ratio.score(&intersect_str, &combined_2to1, mode),
+        ratio.score(&combined_1to2, &combined_2to1, mode),
+    ]
+    .iter()
+    .max()
+    .unwrap()
 }
 
+#[cfg(feature = "token")]
 /// Return the ratio of the most similar substring constructed from the strings treated as sets, as a number between 0 and 100.
 ///
 /// Creates three sets from the two strings:
@@ -236,11 +1140,455 @@ fn token_set(s1: &str, s2: &str, partial: bool, force_ascii: bool, full_process:
 ///    "what about supercalifragilisticexpialidocious",
 ///    "supercalifragilisticexpialidocious about what", true, true), 100);
 /// ```
-pub fn token_set_ratio(s1: &str, s2: &str, force_ascii: bool, full_process: bool) -> u8 {
+pub fn token_set_ratio(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
     // trivial check omitted because this is a shallow delegator to token_set which checks.
-    token_set(s1, s2, false, force_ascii, full_process)
+    token_set(
+        s1,
+        s2,
+        CharRatio::Ratio,
+        force_ascii,
+        full_process,
+        &WhitespaceSegmenter,
+        Mode::PythonCompat,
+    )
 }
 
+#[cfg(feature = "token")]
+/// [token_set_ratio], but splitting strings into tokens with `tokenizer` instead of on whitespace.
+pub fn token_set_ratio_with<T: Tokenizer + ?Sized>(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+    tokenizer: &T,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    // trivial check omitted because this is a shallow delegator to token_set which checks.
+    token_set(
+        s1,
+        s2,
+        CharRatio::Ratio,
+        force_ascii,
+        full_process,
+        tokenizer,
+        Mode::PythonCompat,
+    )
+}
+
+#[cfg(feature = "token")]
+/// [token_set_ratio], but counting a swap of adjacent characters as a
+/// single edit, see [transposition_ratio].
+///
+/// By default, force_ascii and full_process should be true.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{token_set_ratio, token_set_transposition_ratio};
+/// assert_eq!(token_set_ratio("the new yrok mets", "new york mets", true, true), 80);
+/// assert_eq!(token_set_transposition_ratio("the new yrok mets", "new york mets", true, true), 83);
+/// ```
+pub fn token_set_transposition_ratio(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    // trivial check omitted because this is a shallow delegator to token_set which checks.
+    token_set(
+        s1,
+        s2,
+        CharRatio::Transposition,
+        force_ascii,
+        full_process,
+        &WhitespaceSegmenter,
+        Mode::PythonCompat,
+    )
+}
+
+#[cfg(feature = "token")]
+/// Processes `s` like the token scorers do, then replaces synonyms.
+fn replace_synonyms(
+    s: &str,
+    force_ascii: bool,
+    full_process: bool,
+    synonyms: &SynonymNormalizer,
+) -> String {
+    if full_process {
+        synonyms.normalize(&Mode::PythonCompat.process(s, force_ascii))
+    } else {
+        synonyms.normalize(s)
+    }
+}
+
+#[cfg(feature = "token")]
+/// [token_sort_ratio], but treating the synonyms grouped by `synonyms` as
+/// the same token, so they sort alike and match each other.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{token_sort_ratio, token_sort_ratio_with_synonyms};
+/// # use fuzzywuzzy::normalization::SynonymNormalizer;
+/// let synonyms = SynonymNormalizer::with(vec![vec!["road", "rd", "rte"], vec!["north", "n"]]);
+/// assert_eq!(token_sort_ratio("N Lake Rd", "lake road north", true, true), 75);
+/// assert_eq!(token_sort_ratio_with_synonyms("N Lake Rd", "lake road north", true, true, &synonyms), 100);
+/// ```
+pub fn token_sort_ratio_with_synonyms(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+    synonyms: &SynonymNormalizer,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    check_trivial!(s1, s2);
+    token_sort(
+        &replace_synonyms(s1, force_ascii, full_process, synonyms),
+        &replace_synonyms(s2, force_ascii, full_process, synonyms),
+        CharRatio::Ratio,
+        force_ascii,
+        false,
+        &WhitespaceSegmenter,
+        &str::cmp,
+        Mode::PythonCompat,
+    )
+}
+
+#[cfg(feature = "token")]
+/// [token_set_ratio], but treating the synonyms grouped by `synonyms` as the
+/// same token, so they count as shared by both strings.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{token_set_ratio, token_set_ratio_with_synonyms};
+/// # use fuzzywuzzy::normalization::SynonymNormalizer;
+/// let synonyms = SynonymNormalizer::with(vec![vec!["road", "rd", "rte"]]);
+/// assert_eq!(token_set_ratio("12 Mill Rd", "12 Mill Road Suite 4", true, true), 82);
+/// assert_eq!(token_set_ratio_with_synonyms("12 Mill Rd", "12 Mill Road Suite 4", true, true, &synonyms), 100);
+/// ```
+pub fn token_set_ratio_with_synonyms(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+    synonyms: &SynonymNormalizer,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    check_trivial!(s1, s2);
+    token_set(
+        &replace_synonyms(s1, force_ascii, full_process, synonyms),
+        &replace_synonyms(s2, force_ascii, full_process, synonyms),
+        CharRatio::Ratio,
+        force_ascii,
+        false,
+        &WhitespaceSegmenter,
+        Mode::PythonCompat,
+    )
+}
+
+#[cfg(feature = "token")]
+/// How much each token counts towards [token_sort_ratio_weighted] and
+/// [token_set_ratio_weighted], e.g. its inverse document frequency.
+///
+/// Tokens without a weight of their own have the default weight, 1 unless
+/// set otherwise. Weighting every token 1 scores like the unweighted scorers.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::TokenWeights;
+/// let weights = TokenWeights::with(vec![("the", 0.1), ("inc", 0.2)]).default_weight(2.0);
+/// assert_eq!(weights.get("the"), 0.1);
+/// assert_eq!(weights.get("acme"), 2.0);
+/// let idf = TokenWeights::idf(["Acme Inc", "Globex Inc", "Initech Inc"].iter());
+/// assert!(idf.get("acme") > idf.get("inc"));
+/// assert_eq!(idf.get("hooli"), idf.default());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenWeights {
+    weights: HashMap<String, f64>,
+    default: f64,
+}
+
+#[cfg(feature = "token")]
+impl Default for TokenWeights {
+    fn default() -> TokenWeights {
+        TokenWeights::new()
+    }
+}
+
+#[cfg(feature = "token")]
+impl TokenWeights {
+    /// Weights of 1 for every token.
+    pub fn new() -> TokenWeights {
+        TokenWeights {
+            weights: HashMap::new(),
+            default: 1.0,
+        }
+    }
+
+    /// The given weights for their tokens, and 1 for the others.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a weight is negative or not finite, see [TokenWeights::try_with].
+    pub fn with<I, S>(weights: I) -> TokenWeights
+    where
+        I: IntoIterator<Item = (S, f64)>,
+        S: Into<String>,
+    {
+        TokenWeights::try_with(weights).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like [TokenWeights::with], but returns [Error::InvalidConfig] instead
+    /// of panicking if a weight is negative or not finite.
+    ///
+    /// ```
+    /// # use fuzzywuzzy::fuzz::TokenWeights;
+    /// # use fuzzywuzzy::error::Error;
+    /// assert_eq!(TokenWeights::try_with(vec![("the", 0.1)]).unwrap().get("the"), 0.1);
+    /// assert!(matches!(TokenWeights::try_with(vec![("the", -1.0)]), Err(Error::InvalidConfig(_))));
+    /// ```
+    pub fn try_with<I, S>(weights: I) -> Result<TokenWeights, Error>
+    where
+        I: IntoIterator<Item = (S, f64)>,
+        S: Into<String>,
+    {
+        let mut token_weights = TokenWeights::new();
+        for (token, weight) in weights {
+            token_weights.try_set(token, weight)?;
+        }
+        Ok(token_weights)
+    }
+
+    /// Inverse document frequencies of the tokens of `documents`, processed
+    /// like the token scorers do.
+    ///
+    /// A token appearing in `n` of the `len` documents weighs
+    /// `ln((1 + len) / (1 + n)) + 1`, so tokens common to every document
+    /// weigh 1 and rarer ones more. Tokens in no document weigh the most.
+    pub fn idf<I, T>(documents: I) -> TokenWeights
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let mut frequencies: HashMap<String, usize> = HashMap::new();
+        let mut len = 0;
+        for document in documents {
+            len += 1;
+            let processed = Mode::PythonCompat.process(document.as_ref(), true);
+            let tokens: HashSet<&str> = processed.split_whitespace().collect();
+            for token in tokens {
+                *frequencies.entry(token.to_string()).or_insert(0) += 1;
+            }
+        }
+        let idf = |n: usize| ((1 + len) as f64 / (1 + n) as f64).ln() + 1.0;
+        TokenWeights {
+            weights: frequencies
+                .into_iter()
+                .map(|(token, n)| (token, idf(n)))
+                .collect(),
+            default: idf(0),
+        }
+    }
+
+    /// Weighs tokens without a weight of their own `weight`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weight` is negative or not finite, see [TokenWeights::try_default_weight].
+    pub fn default_weight(self, weight: f64) -> TokenWeights {
+        self.try_default_weight(weight)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like [TokenWeights::default_weight], but returns
+    /// [Error::InvalidConfig] instead of panicking if `weight` is negative or
+    /// not finite.
+    pub fn try_default_weight(mut self, weight: f64) -> Result<TokenWeights, Error> {
+        self.default = checked_weight(weight)?;
+        Ok(self)
+    }
+
+    /// Weighs `token` `weight`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weight` is negative or not finite, see [TokenWeights::try_set].
+    pub fn set(&mut self, token: impl Into<String>, weight: f64) {
+        self.try_set(token, weight)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like [TokenWeights::set], but returns [Error::InvalidConfig] instead
+    /// of panicking if `weight` is negative or not finite. The weights are
+    /// unchanged then.
+    pub fn try_set(&mut self, token: impl Into<String>, weight: f64) -> Result<(), Error> {
+        self.weights.insert(token.into(), checked_weight(weight)?);
+        Ok(())
+    }
+
+    /// The weight of `token`.
+    pub fn get(&self, token: &str) -> f64 {
+        self.weights.get(token).copied().unwrap_or(self.default)
+    }
+
+    /// The weight of tokens without a weight of their own.
+    pub fn default(&self) -> f64 {
+        self.default
+    }
+}
+
+#[cfg(feature = "token")]
+/// `weight`, if it is a valid [TokenWeights] weight.
+fn checked_weight(weight: f64) -> Result<f64, Error> {
+    if weight.is_finite() && weight >= 0.0 {
+        Ok(weight)
+    } else {
+        Err(Error::InvalidConfig(
+            "weight must be finite and not negative",
+        ))
+    }
+}
+
+#[cfg(feature = "token")]
+/// [ratio] of the tokens joined by spaces, with each matching character
+/// counting the weight of its token. A space counts the weight of the token
+/// it precedes.
+fn weighted_ratio(a: &[&str], b: &[&str], weights: &TokenWeights) -> u8 {
+    let (joined_a, joined_b) = (a.join(" "), b.join(" "));
+    check_trivial!(joined_a, joined_b);
+    let char_weights = |tokens: &[&str]| -> Vec<f64> {
+        let mut char_weights = vec![];
+        for (i, token) in tokens.iter().enumerate() {
+            let weight = weights.get(token);
+            let len = token.chars().count() + if i > 0 { 1 } else { 0 };
+            char_weights.extend(std::iter::repeat_n(weight, len));
+        }
+        char_weights
+    };
+    let (weights_a, weights_b) = (char_weights(a), char_weights(b));
+    let total: f64 = weights_a.iter().sum::<f64>() + weights_b.iter().sum::<f64>();
+    if total == 0.0 {
+        return 100;
+    }
+    let matched: f64 = utils::get_matching_blocks(&joined_a, &joined_b)
+        .iter()
+        .map(|&(i, j, size)| {
+            weights_a[i..i + size].iter().sum::<f64>() + weights_b[j..j + size].iter().sum::<f64>()
+        })
+        .sum();
+    Rounding::Python.round(100.0 * (matched / total))
+}
+
+#[cfg(feature = "token")]
+/// [token_sort_ratio], but with tokens counting by their `weights`, so a
+/// mismatched rare token costs more than a mismatched common one.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{token_sort_ratio, token_sort_ratio_weighted, TokenWeights};
+/// let weights = TokenWeights::with(vec![("corporation", 0.1), ("company", 0.1)]);
+/// let (a, b) = ("Acme Corporation", "Acme Company");
+/// assert_eq!(token_sort_ratio(a, b, true, true), 71);
+/// assert_eq!(token_sort_ratio_weighted(a, b, true, true, &weights), 92);
+/// assert_eq!(token_sort_ratio_weighted(a, b, true, true, &TokenWeights::new()), 71);
+/// ```
+pub fn token_sort_ratio_weighted(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+    weights: &TokenWeights,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    check_trivial!(s1, s2);
+    let sorted = |s: &str| -> String {
+        process_and_sort(
+            s,
+            force_ascii,
+            full_process,
+            &WhitespaceSegmenter,
+            &str::cmp,
+            Mode::PythonCompat,
+        )
+    };
+    let (sorted1, sorted2) = (sorted(s1), sorted(s2));
+    let tokens1: Vec<&str> = sorted1.split_whitespace().collect();
+    let tokens2: Vec<&str> = sorted2.split_whitespace().collect();
+    weighted_ratio(&tokens1, &tokens2, weights)
+}
+
+#[cfg(feature = "token")]
+/// [token_set_ratio], but with tokens counting by their `weights`, so shared
+/// rare tokens count for more than shared stopwords, and a remainder of
+/// common tokens costs little.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{token_set_ratio, token_set_ratio_weighted, TokenWeights};
+/// let weights = TokenWeights::idf(vec![
+///     "the acme group", "the apex group", "the globex group", "the initech group",
+/// ]);
+/// let (a, b) = ("The Acme Group", "The Apex Group");
+/// assert_eq!(token_set_ratio(a, b, true, true), 86);
+/// // The tokens they don't share are the rare ones, which count for more.
+/// assert_eq!(token_set_ratio_weighted(a, b, true, true, &weights), 79);
+/// assert_eq!(token_set_ratio_weighted(a, b, true, true, &TokenWeights::new()), 86);
+/// ```
+pub fn token_set_ratio_weighted(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+    weights: &TokenWeights,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    check_trivial!(s1, s2);
+    let (p1, p2) = if full_process {
+        (
+            Mode::PythonCompat.process(s1, force_ascii),
+            Mode::PythonCompat.process(s2, force_ascii),
+        )
+    } else {
+        (s1.to_string(), s2.to_string())
+    };
+    let t1: HashSet<&str> = p1.split_whitespace().collect();
+    let t2: HashSet<&str> = p2.split_whitespace().collect();
+    let mut intersection: Vec<&str> = t1.intersection(&t2).cloned().collect();
+    let mut diff1to2: Vec<&str> = t1.difference(&t2).cloned().collect();
+    let mut diff2to1: Vec<&str> = t2.difference(&t1).cloned().collect();
+    intersection.sort_unstable();
+    diff1to2.sort_unstable();
+    diff2to1.sort_unstable();
+    // Joined like in [sorted_set_ratio], which leaves a leading space when
+    // there is no intersection.
+    fn combined<'a>(intersection: &[&'a str], diff: &[&'a str]) -> Vec<&'a str> {
+        let lead: &[&str] = if intersection.is_empty() && !diff.is_empty() {
+            &[""]
+        } else {
+            &[]
+        };
+        lead.iter()
+            .chain(intersection)
+            .chain(diff)
+            .cloned()
+            .collect()
+    }
+    let (combined_1to2, combined_2to1) = (
+        combined(&intersection, &diff1to2),
+        combined(&intersection, &diff2to1),
+    );
+    *[
+        weighted_ratio(&intersection, &combined_1to2, weights),
+        weighted_ratio(&intersection, &combined_2to1, weights),
+        weighted_ratio(&combined_1to2, &combined_2to1, weights),
+    ]
+    .iter()
+    .max()
+    .unwrap()
+}
+
+#[cfg(feature = "token")]
 /// Return the partial ratio of the most similar substring constructed from the strings treated as sets, as a number between 0 and 100.
 ///
 /// Creates three sets from the two strings:
@@ -262,16 +1610,254 @@ pub fn token_set_ratio(s1: &str, s2: &str, force_ascii: bool, full_process: bool
 ///    "what about supercalifragilisticexpialidocious",
 ///    "supercalifragilisticexpialidocious about what", true, true), 100);
 /// ```
-pub fn partial_token_set_ratio(s1: &str, s2: &str, force_ascii: bool, full_process: bool) -> u8 {
+pub fn partial_token_set_ratio(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    // trivial check omitted because this is a shallow delegator to token_set which checks.
+    token_set(
+        s1,
+        s2,
+        CharRatio::Partial,
+        force_ascii,
+        full_process,
+        &WhitespaceSegmenter,
+        Mode::PythonCompat,
+    )
+}
+
+#[cfg(feature = "token")]
+/// [partial_token_set_ratio], but splitting strings into tokens with `tokenizer` instead of on whitespace.
+pub fn partial_token_set_ratio_with<T: Tokenizer + ?Sized>(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+    tokenizer: &T,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
     // trivial check omitted because this is a shallow delegator to token_set which checks.
-    token_set(s1, s2, true, force_ascii, full_process)
+    token_set(
+        s1,
+        s2,
+        CharRatio::Partial,
+        force_ascii,
+        full_process,
+        tokenizer,
+        Mode::PythonCompat,
+    )
+}
+
+#[cfg(feature = "ngram")]
+/// Returns the word `n`-grams of a string, or shingles: each run of `n`
+/// consecutive tokens, joined by single spaces. A string of fewer than `n`
+/// but at least one token is a single shingle.
+fn shingles(s: &str, n: usize, force_ascii: bool, full_process: bool) -> Vec<String> {
+    assert!(n > 0, "shingles must have at least one word");
+    let processed = if full_process {
+        Mode::PythonCompat.process(s, force_ascii)
+    } else {
+        s.to_string()
+    };
+    let tokens: Vec<&str> = processed.split_whitespace().collect();
+    match tokens.len() {
+        0 => vec![],
+        len if len < n => vec![tokens.join(" ")],
+        _ => tokens.windows(n).map(|words| words.join(" ")).collect(),
+    }
+}
+
+#[cfg(feature = "ngram")]
+/// Returns the Sørensen–Dice coefficient of the strings' word `n`-grams as a
+/// number between 0 and 100: twice the number of `n`-grams they have in
+/// common over the total number of `n`-grams.
+///
+/// Unlike [token_set_ratio], which only sees which words the strings share,
+/// this only credits runs of `n` words appearing in the same order in both,
+/// which suits short phrases where order matters. Only whole words are
+/// compared, so a misspelled word spoils every `n`-gram it is part of. A
+/// string of fewer than `n` words counts as a single `n`-gram.
+///
+/// By default, force_ascii and full_process should be true.
+///
+/// # Panics
+///
+/// Panics if `n` is 0, see [try_shingle_ratio].
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{shingle_ratio, token_set_ratio};
+/// assert_eq!(token_set_ratio("new york to boston", "boston to new york", true, true), 100);
+/// assert_eq!(shingle_ratio("new york to boston", "boston to new york", 2, true, true), 33);
+/// assert_eq!(shingle_ratio("new york to boston", "New York to Boston!", 3, true, true), 100);
+/// assert_eq!(shingle_ratio("new york", "new york mets", 2, true, true), 67);
+/// ```
+pub fn shingle_ratio(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    n: usize,
+    force_ascii: bool,
+    full_process: bool,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    check_trivial!(s1, s2);
+    let (a, b) = (
+        shingles(s1, n, force_ascii, full_process),
+        shingles(s2, n, force_ascii, full_process),
+    );
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for shingle in &a {
+        *counts.entry(shingle).or_insert(0) += 1;
+    }
+    let mut common = 0;
+    for shingle in &b {
+        if let Some(count) = counts.get_mut(shingle.as_str()).filter(|count| **count > 0) {
+            *count -= 1;
+            common += 1;
+        }
+    }
+    Mode::PythonCompat.score(common, a.len() + b.len())
+}
+
+#[cfg(feature = "ngram")]
+/// Like [shingle_ratio], but returns [Error::InvalidConfig] instead of
+/// panicking if `n` is 0.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::try_shingle_ratio;
+/// # use fuzzywuzzy::error::Error;
+/// assert_eq!(try_shingle_ratio("new york", "new york mets", 2, true, true), Ok(67));
+/// assert!(matches!(try_shingle_ratio("new york", "new york", 0, true, true), Err(Error::InvalidConfig(_))));
+/// ```
+pub fn try_shingle_ratio(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    n: usize,
+    force_ascii: bool,
+    full_process: bool,
+) -> Result<u8, Error> {
+    if n == 0 {
+        return Err(Error::InvalidConfig("n must be greater than 0"));
+    }
+    Ok(shingle_ratio(s1, s2, n, force_ascii, full_process))
+}
+
+#[cfg(feature = "ngram")]
+/// [token_set_ratio], but over the strings' word `n`-grams instead of their
+/// words.
+///
+/// The `n`-grams both strings have, and those only one of them has, are
+/// sorted and joined by spaces, and the best [ratio] between them is
+/// returned, as [token_set_ratio] does with words. Words shared out of order
+/// don't count as shared, but the text of the `n`-grams is still compared
+/// character by character, so near misses still score.
+///
+/// By default, force_ascii and full_process should be true.
+///
+/// # Panics
+///
+/// Panics if `n` is 0, see [try_shingle_set_ratio].
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{shingle_set_ratio, token_set_ratio};
+/// assert_eq!(token_set_ratio("new york to boston", "boston to new york", true, true), 100);
+/// assert_eq!(shingle_set_ratio("new york to boston", "boston to new york", 2, true, true), 78);
+/// assert_eq!(shingle_set_ratio("new york mets", "the new york mets", 2, true, true), 100);
+/// ```
+pub fn shingle_set_ratio(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    n: usize,
+    force_ascii: bool,
+    full_process: bool,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    check_trivial!(s1, s2);
+    let sorted_set = |s: &str| -> Vec<String> {
+        let mut shingles = shingles(s, n, force_ascii, full_process);
+        shingles.sort_unstable();
+        shingles.dedup();
+        shingles
+    };
+    let (a, b) = (sorted_set(s1), sorted_set(s2));
+    let (intersection, diff1to2): (Vec<&str>, Vec<&str>) =
+        a.iter().map(String::as_str).partition(|shingle| {
+            b.binary_search_by(|other| other.as_str().cmp(shingle))
+                .is_ok()
+        });
+    let diff2to1: Vec<&str> = b
+        .iter()
+        .map(String::as_str)
+        .filter(|shingle| {
+            a.binary_search_by(|other| other.as_str().cmp(shingle))
+                .is_err()
+        })
+        .collect();
+    sorted_set_ratio(
+        &intersection,
+        &diff1to2,
+        &diff2to1,
+        CharRatio::Ratio,
+        Mode::PythonCompat,
+    )
+}
+
+#[cfg(feature = "ngram")]
+/// Like [shingle_set_ratio], but returns [Error::InvalidConfig] instead of
+/// panicking if `n` is 0.
+pub fn try_shingle_set_ratio(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    n: usize,
+    force_ascii: bool,
+    full_process: bool,
+) -> Result<u8, Error> {
+    if n == 0 {
+        return Err(Error::InvalidConfig("n must be greater than 0"));
+    }
+    Ok(shingle_set_ratio(s1, s2, n, force_ascii, full_process))
+}
+
+#[cfg(feature = "token")]
+/// The greater of [partial_token_sort_ratio] and [partial_token_set_ratio],
+/// like RapidFuzz's `partial_token_ratio`.
+///
+/// By default, force_ascii and full_process should be true.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{partial_token_ratio, partial_token_set_ratio, partial_token_sort_ratio};
+/// let (a, b) = ("new york mets", "the new york mets");
+/// assert_eq!(partial_token_sort_ratio(a, b, true, true), 69);
+/// assert_eq!(partial_token_set_ratio(a, b, true, true), 100);
+/// assert_eq!(partial_token_ratio(a, b, true, true), 100);
+/// let (a, b) = ("fuzzy wuzzy", "wuzy fuzy");
+/// assert_eq!(partial_token_sort_ratio(a, b, true, true), 89);
+/// assert_eq!(partial_token_set_ratio(a, b, true, true), 90);
+/// assert_eq!(partial_token_ratio(a, b, true, true), 90);
+/// ```
+pub fn partial_token_ratio(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    partial_token_sort_ratio(s1, s2, force_ascii, full_process).max(partial_token_set_ratio(
+        s1,
+        s2,
+        force_ascii,
+        full_process,
+    ))
 }
 
 /// Quick `ratio` comparison between two strings.
 ///
 //  Runs utils::full_process on both strings.
 //  Short circuits if either of the strings is empty after processing.
-pub fn qratio(s1: &str, s2: &str, force_ascii: bool) -> u8 {
+pub fn qratio(s1: impl AsRef<str>, s2: impl AsRef<str>, force_ascii: bool) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
     check_trivial!(s1, s2);
     let (p1, p2) = (
         utils::full_process(s1, force_ascii),
@@ -284,11 +1870,13 @@ pub fn qratio(s1: &str, s2: &str, force_ascii: bool) -> u8 {
 }
 
 /// micro-quick-ratio: `qratio` comparison between two strings without forcing to ascii.
-pub fn uqratio(s1: &str, s2: &str) -> u8 {
+pub fn uqratio(s1: impl AsRef<str>, s2: impl AsRef<str>) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
     // trivial check omitted because this is a shallow delegator to qratio which checks.
     qratio(s1, s2, false)
 }
 
+#[cfg(feature = "token")]
 /// Return a measure of the sequences' similarity between 0 and 100, using a composite algorithm.
 ///
 /// *Steps in the order they occur*
@@ -322,13 +1910,37 @@ pub fn uqratio(s1: &str, s2: &str) -> u8 {
 /// assert_eq!(wratio("new york mets", "the wonderful new york mets", true, true), 90);
 /// assert_eq!(wratio("new york mets vs atlanta braves", "atlanta braves vs new york mets", true, true), 95);
 /// ```
-pub fn wratio(s1: &str, s2: &str, force_ascii: bool, full_process: bool) -> u8 {
+pub fn wratio(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
+    wratio_with_mode(s1, s2, force_ascii, full_process, Mode::PythonCompat)
+}
+
+#[cfg(feature = "token")]
+/// [wratio], processing strings and converting similarities to integer scores as `mode` prescribes.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{wratio, wratio_with_mode};
+/// # use fuzzywuzzy::utils::Mode;
+/// // Python drops the non-ASCII characters, gluing "4" and "2" into one token.
+/// assert_eq!(wratio("a¬4ሴ2", "a 4 2", true, true), 75);
+/// assert_eq!(wratio_with_mode("a¬4ሴ2", "a 4 2", true, true, Mode::Improved), 100);
+/// ```
+pub fn wratio_with_mode(
+    s1: impl AsRef<str>,
+    s2: impl AsRef<str>,
+    force_ascii: bool,
+    full_process: bool,
+    mode: Mode,
+) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
     check_trivial!(s1, s2);
     let (p1, p2) = if full_process {
-        (
-            utils::full_process(s1, force_ascii),
-            utils::full_process(s2, force_ascii),
-        )
+        (mode.process(s1, force_ascii), mode.process(s2, force_ascii))
     } else {
         (s1.to_string(), s2.to_string())
     };
@@ -340,7 +1952,7 @@ pub fn wratio(s1: &str, s2: &str, force_ascii: bool, full_process: bool) -> u8 {
     const UNBASE_SCALE: f64 = 0.95;
     let mut partial_scale = 0.90;
 
-    let base = ratio(p1r, p2r);
+    let base = ratio_with_mode(p1r, p2r, mode);
     let (p1_len, p2_len) = (p1.chars().count(), p2.chars().count());
     let len_ratio = std::cmp::max(p1_len, p2_len) as f64 / std::cmp::min(p1_len, p2_len) as f64;
 
@@ -355,36 +1967,274 @@ pub fn wratio(s1: &str, s2: &str, force_ascii: bool, full_process: bool) -> u8 {
     }
 
     if try_partial {
-        let partial = partial_ratio(p1r, p2r) as f64 * partial_scale;
-        let ptsor =
-            partial_token_sort_ratio(p1r, p2r, true, false) as f64 * UNBASE_SCALE * partial_scale;
-        let ptser =
-            partial_token_set_ratio(p1r, p2r, true, false) as f64 * UNBASE_SCALE * partial_scale;
+        let partial = partial_ratio_with_mode(p1r, p2r, mode) as f64 * partial_scale;
+        let ptsor = token_sort(
+            p1r,
+            p2r,
+            CharRatio::Partial,
+            true,
+            false,
+            &WhitespaceSegmenter,
+            &str::cmp,
+            mode,
+        ) as f64
+            * UNBASE_SCALE
+            * partial_scale;
+        let ptser = token_set(
+            p1r,
+            p2r,
+            CharRatio::Partial,
+            true,
+            false,
+            &WhitespaceSegmenter,
+            mode,
+        ) as f64
+            * UNBASE_SCALE
+            * partial_scale;
         // This conversion to u8 from the maximum f64 seems spooky, but let's hope nothing bad happens!
-        return vec![base as f64, partial, ptsor, ptser]
+        return mode.rounding().round(
+            [base as f64, partial, ptsor, ptser]
+                .iter()
+                .cloned()
+                .fold(f64::NAN, f64::max),
+        );
+    }
+    let tsor = token_sort(
+        p1r,
+        p2r,
+        CharRatio::Ratio,
+        true,
+        false,
+        &WhitespaceSegmenter,
+        &str::cmp,
+        mode,
+    ) as f64
+        * UNBASE_SCALE;
+    let tser = token_set(
+        p1r,
+        p2r,
+        CharRatio::Ratio,
+        true,
+        false,
+        &WhitespaceSegmenter,
+        mode,
+    ) as f64
+        * UNBASE_SCALE;
+    mode.rounding().round(
+        [base as f64, tsor, tser]
             .iter()
             .cloned()
-            .fold(f64::NAN, f64::max)
-            .round() as u8;
-    }
-    let tsor = token_sort_ratio(p1r, p2r, true, false) as f64 * UNBASE_SCALE;
-    let tser = token_set_ratio(p1r, p2r, true, false) as f64 * UNBASE_SCALE;
-    vec![base as f64, tsor, tser]
-        .iter()
-        .cloned()
-        .fold(f64::NAN, f64::max)
-        .round() as u8
+            .fold(f64::NAN, f64::max),
+    )
 }
 
+#[cfg(feature = "token")]
 /// Runs `wratio` without forcing to ascii.
-pub fn uwratio(s1: &str, s2: &str, full_process: bool) -> u8 {
+pub fn uwratio(s1: impl AsRef<str>, s2: impl AsRef<str>, full_process: bool) -> u8 {
+    let (s1, s2) = (s1.as_ref(), s2.as_ref());
     // trivial check omitted because this is a shallow delegator to wratio which checks.
     wratio(s1, s2, false, full_process)
 }
 
+/// Represents a strategy for scoring the similarity of two strings.
+///
+/// This is what [process](crate::process) functions score choices with. All
+/// built-in scorers are available as implementers, with `force_ascii` and
+/// `full_process` both `true` where they apply. Closures returning a [Score] also work.
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{partial_ratio, wratio, Ratio, Scorer, TokenSortRatio, WRatio};
+/// # use fuzzywuzzy::score::Score;
+/// assert_eq!(WRatio.score("new york mets", "new YORK mets"), wratio("new york mets", "new YORK mets", true, true));
+/// assert_eq!(TokenSortRatio.score("hello world", "world hello"), Score::MAX);
+/// assert_eq!(Ratio.score("cd", "abcd"), 67);
+/// let halved_partial = |a: &str, b: &str| Score::new(partial_ratio(a, b) / 2).unwrap();
+/// assert_eq!(halved_partial.score("York", "new york"), 37);
+/// ```
+pub trait Scorer {
+    fn score(&self, a: &str, b: &str) -> Score;
+
+    /// A score at least as high as `self.score(a, b)`, which should be cheaper to compute.
+    ///
+    /// Extraction with a score cutoff skips choices whose bound falls below
+    /// it. Defaults to [Score::MAX], which never skips anything.
+    fn upper_bound(&self, a: &str, b: &str) -> Score {
+        let _ = (a, b);
+        Score::MAX
+    }
+
+    /// `self.score(a, b)`, or `None` if it is below `score_cutoff`.
+    ///
+    /// Extraction with a score cutoff scores choices with this, so scorers
+    /// able to give up early on choices which can't reach the cutoff, like
+    /// [Ratio], should override it. Defaults to scoring in full.
+    fn score_with_cutoff(&self, a: &str, b: &str, score_cutoff: u8) -> Option<Score> {
+        Some(self.score(a, b)).filter(|score| score.percent() >= score_cutoff)
+    }
+}
+
+impl<F: Fn(&str, &str) -> Score> Scorer for F {
+    fn score(&self, a: &str, b: &str) -> Score {
+        self(a, b)
+    }
+}
+
+impl Scorer for Box<dyn Scorer + Send + Sync> {
+    fn score(&self, a: &str, b: &str) -> Score {
+        (**self).score(a, b)
+    }
+
+    fn upper_bound(&self, a: &str, b: &str) -> Score {
+        (**self).upper_bound(a, b)
+    }
+
+    fn score_with_cutoff(&self, a: &str, b: &str, score_cutoff: u8) -> Option<Score> {
+        (**self).score_with_cutoff(a, b, score_cutoff)
+    }
+}
+
+/// Declares a unit struct implementing [Scorer] by calling a built-in scorer.
+macro_rules! scorer {
+    (
+        $(#[$doc:meta])* $name:ident, |$a:ident, $b:ident| $body:expr
+        $(, upper_bound: $bound:expr)?
+        $(, with_cutoff: |$cutoff:ident| $with_cutoff:expr)?
+    ) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+        pub struct $name;
+
+        impl Scorer for $name {
+            fn score(&self, $a: &str, $b: &str) -> Score {
+                Score::new($body).expect("built-in scorers return at most 100")
+            }
+
+            $(
+            fn upper_bound(&self, $a: &str, $b: &str) -> Score {
+                Score::new($bound).expect("built-in scorers return at most 100")
+            }
+            )?
+
+            $(
+            fn score_with_cutoff(&self, $a: &str, $b: &str, $cutoff: u8) -> Option<Score> {
+                $with_cutoff.map(|score| Score::new(score).expect("built-in scorers return at most 100"))
+            }
+            )?
+        }
+    };
+}
+
+scorer!(
+    /// [Scorer] for [ratio].
+    ///
+    /// Its upper bound is the score of the characters `a` and `b` have in
+    /// common regardless of order, see [quick_ratio](crate::difflib::quick_ratio),
+    /// and it scores with a cutoff by [ratio_with_cutoff].
+    Ratio,
+    |a, b| ratio(a, b),
+    upper_bound: Mode::PythonCompat.score(
+        difflib::quick_matches(a.chars(), b.chars()),
+        a.chars().count() + b.chars().count()
+    ),
+    with_cutoff: |score_cutoff| ratio_with_cutoff(a, b, score_cutoff)
+);
+scorer!(
+    /// [Scorer] for [transposition_ratio].
+    TranspositionRatio,
+    |a, b| transposition_ratio(a, b)
+);
+scorer!(
+    /// [Scorer] for [partial_transposition_ratio].
+    PartialTranspositionRatio,
+    |a, b| partial_transposition_ratio(a, b)
+);
+#[cfg(feature = "token")]
+scorer!(
+    /// [Scorer] for [token_sort_transposition_ratio].
+    TokenSortTranspositionRatio,
+    |a, b| token_sort_transposition_ratio(a, b, true, true)
+);
+#[cfg(feature = "token")]
+scorer!(
+    /// [Scorer] for [token_set_transposition_ratio].
+    TokenSetTranspositionRatio,
+    |a, b| token_set_transposition_ratio(a, b, true, true)
+);
+#[cfg(feature = "ngram")]
+scorer!(
+    /// [Scorer] for [dice_ratio].
+    DiceRatio,
+    |a, b| dice_ratio(a, b)
+);
+scorer!(
+    /// [Scorer] for [partial_ratio].
+    PartialRatio,
+    |a, b| partial_ratio(a, b)
+);
+#[cfg(feature = "token")]
+scorer!(
+    /// [Scorer] for [token_sort_ratio].
+    TokenSortRatio,
+    |a, b| token_sort_ratio(a, b, true, true)
+);
+#[cfg(feature = "token")]
+scorer!(
+    /// [Scorer] for [partial_token_sort_ratio].
+    PartialTokenSortRatio,
+    |a, b| partial_token_sort_ratio(a, b, true, true)
+);
+#[cfg(feature = "token")]
+scorer!(
+    /// [Scorer] for [token_set_ratio].
+    TokenSetRatio,
+    |a, b| token_set_ratio(a, b, true, true)
+);
+#[cfg(feature = "token")]
+scorer!(
+    /// [Scorer] for [partial_token_set_ratio].
+    PartialTokenSetRatio,
+    |a, b| partial_token_set_ratio(a, b, true, true)
+);
+#[cfg(feature = "token")]
+scorer!(
+    /// [Scorer] for [partial_token_ratio].
+    PartialTokenRatio,
+    |a, b| partial_token_ratio(a, b, true, true)
+);
+#[cfg(feature = "ngram")]
+scorer!(
+    /// [Scorer] for [shingle_ratio] over word bigrams.
+    WordBigramRatio,
+    |a, b| shingle_ratio(a, b, 2, true, true)
+);
+#[cfg(feature = "ngram")]
+scorer!(
+    /// [Scorer] for [shingle_set_ratio] over word bigrams.
+    WordBigramSetRatio,
+    |a, b| shingle_set_ratio(a, b, 2, true, true)
+);
+scorer!(
+    /// [Scorer] for [qratio].
+    QRatio,
+    |a, b| qratio(a, b, true)
+);
+#[cfg(feature = "token")]
+scorer!(
+    /// [Scorer] for [wratio].
+    WRatio,
+    |a, b| wratio(a, b, true, true)
+);
+
 #[cfg(test)]
 mod test {
-    use super::ratio;
+    use super::{
+        partial_ratio, partial_ratio_windows, partial_transposition_ratio, ratio,
+        ratio_with_cutoff, segmented_ratio, transposition_ratio,
+    };
+    #[cfg(feature = "alignment")]
+    use super::{partial_ratio_alignment, ratio_alignment, ratio_alignments};
+    use crate::segmentation::CodePointSegmenter;
+    use rand::{thread_rng, Rng};
     #[test]
     fn ratio_unicode() {
         let list = [
@@ -403,6 +2253,231 @@ mod test {
         ];
         for (a, b, r) in list.iter() {
             assert_eq!(ratio(a, b), *r);
+            assert_eq!(segmented_ratio(a, b, &CodePointSegmenter), *r);
+        }
+    }
+
+    #[test]
+    fn partial_ratio_slices_by_char() {
+        assert_eq!(partial_ratio("ab", "ñab"), 100);
+        assert_eq!(partial_ratio("chance", "スマホでchance"), 100);
+        assert_eq!(partial_ratio("cães", "os cães danados"), 100);
+    }
+
+    #[test]
+    fn ratio_with_cutoff_agrees_with_ratio() {
+        let mut rng = thread_rng();
+        for _ in 0..1000 {
+            let a: String = (0..rng.gen_range(0..16))
+                .map(|_| rng.gen_range('a'..='d'))
+                .collect();
+            let b: String = (0..rng.gen_range(0..16))
+                .map(|_| rng.gen_range('a'..='d'))
+                .collect();
+            let score_cutoff = rng.gen_range(0..=100);
+            let score = ratio(&a, &b);
+            assert_eq!(
+                ratio_with_cutoff(&a, &b, score_cutoff),
+                Some(score).filter(|&score| score >= score_cutoff),
+                "{:?} {:?} {}",
+                a,
+                b,
+                score_cutoff
+            );
+        }
+    }
+
+    #[test]
+    fn transpositions_count_as_single_edits() {
+        let word: Vec<char> = "transposición".chars().collect();
+        for i in 0..word.len() - 1 {
+            let mut swapped = word.clone();
+            swapped.swap(i, i + 1);
+            let (a, b): (String, String) = (word.iter().collect(), swapped.iter().collect());
+            // Like a single substitution, so one of 26 characters differs.
+            assert_eq!(transposition_ratio(&a, &b), 96, "{:?}", b);
+            assert!(ratio(&a, &b) < 96);
+        }
+        assert_eq!(transposition_ratio("abcd", "abcd"), 100);
+        assert_eq!(
+            (ratio("abcd", "abdc"), transposition_ratio("abcd", "abdc")),
+            (75, 88)
+        );
+    }
+
+    #[test]
+    fn transposition_variants_score_at_least_their_base_scorers() {
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let mut random = || -> String {
+                (0..rng.gen_range(0..12))
+                    .map(|_| ['a', 'b', 'c', ' '][rng.gen_range(0..4)])
+                    .collect()
+            };
+            let (a, b) = (random(), random());
+            assert!(transposition_ratio(&a, &b) >= ratio(&a, &b));
+            assert!(partial_transposition_ratio(&a, &b) >= partial_ratio(&a, &b));
+            #[cfg(feature = "token")]
+            {
+                use super::{
+                    token_set_ratio, token_set_transposition_ratio, token_sort_ratio,
+                    token_sort_transposition_ratio,
+                };
+                assert!(
+                    token_sort_transposition_ratio(&a, &b, true, true)
+                        >= token_sort_ratio(&a, &b, true, true)
+                );
+                assert!(
+                    token_set_transposition_ratio(&a, &b, true, true)
+                        >= token_set_ratio(&a, &b, true, true)
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "alignment")]
+    #[test]
+    fn alignments_agree_with_scores() {
+        let alphabet: Vec<char> = "abcñ ".chars().collect();
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let mut random = || -> String {
+                (0..rng.gen_range(0..12))
+                    .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                    .collect()
+            };
+            let (a, b) = (random(), random());
+            assert_eq!(
+                ratio_alignment(&a, &b).score,
+                ratio(&a, &b),
+                "{:?} {:?}",
+                a,
+                b
+            );
+            let alignment = partial_ratio_alignment(&a, &b);
+            assert_eq!(alignment.score, partial_ratio(&a, &b), "{:?} {:?}", a, b);
+            let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+            for (i, j, n) in alignment.blocks {
+                assert_eq!(a[i..i + n], b[j..j + n]);
+            }
+        }
+    }
+
+    #[test]
+    fn partial_ratio_windows_find_the_best_window() {
+        let mut rng = thread_rng();
+        for _ in 0..300 {
+            let mut random = |max: usize| -> String {
+                (0..rng.gen_range(1..max))
+                    .map(|_| ['a', 'b', 'c', 'é'][rng.gen_range(0..4)])
+                    .collect()
+            };
+            let (needle, haystack) = (random(5), random(30));
+            let windows = partial_ratio_windows(&needle, &haystack, 50);
+            let (needle_chars, chars): (Vec<char>, Vec<char>) =
+                (needle.chars().collect(), haystack.chars().collect());
+            let width = needle_chars.len().min(chars.len());
+            // Each window trimmed to its first and last matching block.
+            let best = (0..=chars.len() - width)
+                .filter_map(|start| {
+                    let window = &chars[start..start + width];
+                    let blocks = crate::primitives::get_matching_blocks(&needle_chars, window);
+                    let (&(_, first, _), &(_, last, n)) =
+                        (blocks.first()?, blocks.iter().rev().nth(1)?);
+                    Some(ratio(
+                        &needle,
+                        window[first..last + n].iter().collect::<String>(),
+                    ))
+                })
+                .max()
+                .unwrap_or(0);
+            let found = windows.iter().map(|window| window.score).max();
+            assert_eq!(
+                found,
+                Some(best).filter(|&best| best >= 50),
+                "{:?} {:?}",
+                needle,
+                haystack
+            );
+            for pair in windows.windows(2) {
+                assert!(pair[0].span.end <= pair[1].span.start);
+            }
+            for window in &windows {
+                let text = &haystack[window.span.clone()];
+                assert_eq!(window.score, ratio(&needle, text));
+                let ends = [text.chars().next(), text.chars().last()];
+                assert!(ends.iter().all(|c| needle.contains(c.unwrap())));
+            }
+        }
+    }
+
+    #[cfg(feature = "alignment")]
+    #[test]
+    fn n_best_alignments_are_distinct_and_score_like_ratio() {
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let mut random = || -> String {
+                (0..rng.gen_range(0..10))
+                    .map(|_| ['a', 'b', 'c'][rng.gen_range(0..3)])
+                    .collect()
+            };
+            let (a, b) = (random(), random());
+            let alignments = ratio_alignments(&a, &b, 20);
+            assert!(!alignments.is_empty() && alignments.len() <= 20);
+            assert_eq!(alignments[0], ratio_alignment(&a, &b));
+            let (x, y): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+            let mut seen = vec![];
+            for alignment in &alignments {
+                assert_eq!(alignment.score, ratio(&a, &b));
+                let (first, second) = (
+                    alignment.positions_in_first(),
+                    alignment.positions_in_second(),
+                );
+                assert_eq!(first.len(), alignments[0].positions_in_first().len());
+                assert!(first.windows(2).all(|pair| pair[0] < pair[1]));
+                assert!(second.windows(2).all(|pair| pair[0] < pair[1]));
+                assert!(first.iter().zip(&second).all(|(&i, &j)| x[i] == y[j]));
+                assert!(
+                    !seen.contains(&(first.clone(), second.clone())),
+                    "{:?} {:?}",
+                    a,
+                    b
+                );
+                seen.push((first, second));
+            }
+        }
+    }
+
+    #[cfg(feature = "token")]
+    #[test]
+    fn unit_token_weights_score_like_the_unweighted_scorers() {
+        use super::{
+            token_set_ratio, token_set_ratio_weighted, token_sort_ratio, token_sort_ratio_weighted,
+            TokenWeights,
+        };
+        let mut rng = thread_rng();
+        let mut random = || -> String {
+            (0..rng.gen_range(0..16))
+                .map(|_| ['a', 'b', 'c', ' ', ' ', 'A', '!'][rng.gen_range(0..7)])
+                .collect()
+        };
+        let weights = TokenWeights::new();
+        for _ in 0..500 {
+            let (a, b) = (random(), random());
+            assert_eq!(
+                token_sort_ratio_weighted(&a, &b, true, true, &weights),
+                token_sort_ratio(&a, &b, true, true),
+                "{:?} {:?}",
+                a,
+                b
+            );
+            assert_eq!(
+                token_set_ratio_weighted(&a, &b, true, true, &weights),
+                token_set_ratio(&a, &b, true, true),
+                "{:?} {:?}",
+                a,
+                b
+            );
         }
     }
 }
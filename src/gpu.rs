@@ -0,0 +1,542 @@
+//! Scoring every query against every choice on a GPU, through
+//! [wgpu](https://wgpu.rs). Requires feature "gpu". Experimental.
+//!
+//! Each pair is scored by a compute shader running Hyyrö's bit-parallel
+//! longest common subsequence algorithm, so the scores are the normalized
+//! InDel similarity: twice the length of the longest common subsequence
+//! over the total length, like RapidFuzz's `ratio`. This can be a few
+//! points higher than [ratio](crate::fuzz::ratio), whose matching blocks
+//! don't always find the longest common subsequence. Strings are compared
+//! by characters as given, so they should be processed first if needed.
+//!
+//! Queries of more than [MAX_GPU_QUERY_CHARS] characters are scored on the
+//! CPU. Rows are handed out as they are computed, so a matrix too large for
+//! memory can be thresholded or reduced as it goes.
+//!
+//! ```
+//! # use fuzzywuzzy::gpu::GpuScorer;
+//! // Without a GPU, there is no scorer.
+//! if let Some(gpu) = GpuScorer::new() {
+//!     let scores = gpu.cdist(&["apple", "banana"], &["apple", "apples", "bandana"]);
+//!     assert_eq!(scores, vec![100, 91, 17, 18, 17, 92]);
+//! }
+//! ```
+
+use crate::utils::Mode;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use wgpu::util::DeviceExt;
+
+/// The most characters of a query scored on the GPU, as in the shader.
+pub const MAX_GPU_QUERY_CHARS: usize = 256;
+
+/// The most pairs scored by one dispatch, bounding the memory it needs.
+const MAX_BATCH_PAIRS: usize = 1 << 24;
+/// The invocations per workgroup, as in the shader.
+const WORKGROUP_SIZE: usize = 64;
+/// The most workgroups a dispatch can have in a dimension.
+const MAX_WORKGROUPS: usize = 65535;
+
+const SHADER: &str = r#"
+struct Params {
+    query_count: u32,
+    choice_count: u32,
+    choice_base: u32,
+    _padding: u32,
+}
+
+@group(0) @binding(0) var<storage, read> query_alphabets: array<u32>;
+@group(0) @binding(1) var<storage, read> query_masks: array<u32>;
+@group(0) @binding(2) var<storage, read> query_spans: array<u32>;
+@group(0) @binding(3) var<storage, read> choice_chars: array<u32>;
+@group(0) @binding(4) var<storage, read> choice_spans: array<u32>;
+@group(0) @binding(5) var<storage, read_write> lcs: array<u32>;
+@group(0) @binding(6) var<uniform> params: Params;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let j = id.x;
+    let i = id.y;
+    if (j >= params.choice_count || i >= params.query_count) {
+        return;
+    }
+    let alphabet_start = query_spans[4u * i];
+    let alphabet_len = query_spans[4u * i + 1u];
+    let mask_start = query_spans[4u * i + 2u];
+    let query_len = query_spans[4u * i + 3u];
+    let choice_start = choice_spans[2u * (params.choice_base + j)];
+    let choice_len = choice_spans[2u * (params.choice_base + j) + 1u];
+    if (query_len > 256u) {
+        lcs[i * params.choice_count + j] = 0u;
+        return;
+    }
+    let words = (query_len + 31u) / 32u;
+    // A zero bit of `v` marks a query character in the common subsequence.
+    var v: array<u32, 8>;
+    for (var w = 0u; w < words; w++) {
+        v[w] = 0xffffffffu;
+    }
+    for (var k = 0u; k < choice_len; k++) {
+        // The character's entry in the sorted alphabet of the query, if any.
+        let c = choice_chars[choice_start + k];
+        var low = 0u;
+        var high = alphabet_len;
+        while (low < high) {
+            let middle = (low + high) / 2u;
+            if (query_alphabets[alphabet_start + middle] < c) {
+                low = middle + 1u;
+            } else {
+                high = middle;
+            }
+        }
+        let found = low < alphabet_len && query_alphabets[alphabet_start + low] == c;
+        var carry = 0u;
+        for (var w = 0u; w < words; w++) {
+            var matches = 0u;
+            if (found) {
+                matches = query_masks[mask_start + low * words + w];
+            }
+            let u = v[w] & matches;
+            let partial = v[w] + u;
+            let sum = partial + carry;
+            carry = select(0u, 1u, partial < v[w] || sum < partial);
+            v[w] = sum | (v[w] & ~matches);
+        }
+    }
+    var length = 0u;
+    for (var w = 0u; w < words; w++) {
+        let bits = min(32u, query_len - 32u * w);
+        let mask = select((1u << bits) - 1u, 0xffffffffu, bits == 32u);
+        length += bits - countOneBits(v[w] & mask);
+    }
+    lcs[i * params.choice_count + j] = length;
+}
+"#;
+
+/// A GPU device ready to score, see the [module](self) docs.
+pub struct GpuScorer {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+}
+
+/// Characters as `u32`s, and the start and length of each string in them.
+struct Packed {
+    chars: Vec<u32>,
+    spans: Vec<u32>,
+}
+
+impl Packed {
+    fn new<T: AsRef<str>>(strings: &[T]) -> Packed {
+        let mut packed = Packed {
+            chars: vec![],
+            spans: vec![],
+        };
+        for s in strings {
+            let start = packed.chars.len();
+            packed.chars.extend(s.as_ref().chars().map(u32::from));
+            packed.spans.push(to_u32(start));
+            packed.spans.push(to_u32(packed.chars.len() - start));
+        }
+        packed
+    }
+}
+
+/// The sorted distinct characters of each query, and for each of them the
+/// bits of the positions it occurs at, in words of 32.
+struct QueryMasks {
+    alphabets: Vec<u32>,
+    masks: Vec<u32>,
+    /// The start and length of the alphabet, the start of the masks and the
+    /// number of characters of each query.
+    spans: Vec<u32>,
+}
+
+impl QueryMasks {
+    fn new<T: AsRef<str>>(queries: &[T]) -> QueryMasks {
+        let mut packed = QueryMasks {
+            alphabets: vec![],
+            masks: vec![],
+            spans: vec![],
+        };
+        for query in queries {
+            let query: Vec<char> = query.as_ref().chars().collect();
+            let mut masks: BTreeMap<char, Vec<u32>> = BTreeMap::new();
+            if query.len() <= MAX_GPU_QUERY_CHARS {
+                for (p, &c) in query.iter().enumerate() {
+                    let words = masks
+                        .entry(c)
+                        .or_insert_with(|| vec![0; query.len().div_ceil(32)]);
+                    words[p / 32] |= 1 << (p % 32);
+                }
+            }
+            packed.spans.extend([
+                to_u32(packed.alphabets.len()),
+                to_u32(masks.len()),
+                to_u32(packed.masks.len()),
+                to_u32(query.len()),
+            ]);
+            for (c, words) in masks {
+                packed.alphabets.push(u32::from(c));
+                packed.masks.extend(words);
+            }
+        }
+        packed
+    }
+
+    fn query_len(&self, i: usize) -> usize {
+        self.spans[4 * i + 3] as usize
+    }
+}
+
+fn to_u32(n: usize) -> u32 {
+    u32::try_from(n).expect("inputs must have at most u32::MAX characters")
+}
+
+/// The bytes of `words`, with a word of padding since bindings can't be empty.
+fn bytes(words: &[u32]) -> Vec<u8> {
+    words
+        .iter()
+        .chain(Some(&0))
+        .flat_map(|word| word.to_le_bytes())
+        .collect()
+}
+
+/// The length of the longest common subsequence of `a` and `b`, on the CPU.
+fn lcs_len(a: &[char], b: &[char]) -> usize {
+    let mut row = vec![0; b.len() + 1];
+    for x in a {
+        let mut diagonal = 0;
+        for (j, y) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if x == y {
+                diagonal + 1
+            } else {
+                above.max(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    count!(dp_cells, a.len() * b.len());
+    row[b.len()]
+}
+
+impl GpuScorer {
+    /// A scorer on the default GPU, or `None` if there is no usable one.
+    pub fn new() -> Option<GpuScorer> {
+        let instance = wgpu::Instance::default();
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            ..Default::default()
+        }))
+        .ok()?;
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).ok()?;
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("fuzzywuzzy lcs"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("fuzzywuzzy lcs"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        Some(GpuScorer {
+            device,
+            queue,
+            pipeline,
+        })
+    }
+
+    /// The scores of each of `queries` against each of `choices`, row by row:
+    /// the score of query `i` against choice `j` is at `i * choices.len() + j`.
+    pub fn cdist<Q, C>(&self, queries: &[Q], choices: &[C]) -> Vec<u8>
+    where
+        Q: AsRef<str>,
+        C: AsRef<str>,
+    {
+        let mut scores = Vec::with_capacity(queries.len() * choices.len());
+        self.cdist_rows(queries, choices, |_, row| scores.extend_from_slice(row));
+        scores
+    }
+
+    /// Computes the scores like [cdist](Self::cdist), but hands each row of
+    /// scores to `f` with the index of its query, in order, instead of
+    /// collecting them.
+    ///
+    /// ```
+    /// # use fuzzywuzzy::gpu::GpuScorer;
+    /// if let Some(gpu) = GpuScorer::new() {
+    ///     let names = ["jon", "john", "joan", "jane"];
+    ///     let mut pairs = vec![];
+    ///     gpu.cdist_rows(&names, &names, |i, row| {
+    ///         pairs.extend((i + 1..names.len()).filter(|&j| row[j] >= 80).map(|j| (i, j)));
+    ///     });
+    ///     assert_eq!(pairs, vec![(0, 1), (0, 2)]);
+    /// }
+    /// ```
+    pub fn cdist_rows<Q, C, F>(&self, queries: &[Q], choices: &[C], f: F)
+    where
+        Q: AsRef<str>,
+        C: AsRef<str>,
+        F: FnMut(usize, &[u8]),
+    {
+        self.score_rows(queries, choices, None, f);
+    }
+
+    /// Like [cdist](Self::cdist), but stops early if `cancel` is set.
+    ///
+    /// `cancel` is checked before each batch of queries is sent to the GPU.
+    /// Returns `None` if the computation was cancelled.
+    pub fn cdist_cancellable<Q, C>(
+        &self,
+        queries: &[Q],
+        choices: &[C],
+        cancel: &AtomicBool,
+    ) -> Option<Vec<u8>>
+    where
+        Q: AsRef<str>,
+        C: AsRef<str>,
+    {
+        let mut scores = Vec::with_capacity(queries.len() * choices.len());
+        if self.score_rows(queries, choices, Some(cancel), |_, row| {
+            scores.extend_from_slice(row)
+        }) {
+            Some(scores)
+        } else {
+            None
+        }
+    }
+
+    /// [cdist_rows](Self::cdist_rows), returning `false` if cancelled.
+    fn score_rows<Q, C, F>(
+        &self,
+        queries: &[Q],
+        choices: &[C],
+        cancel: Option<&AtomicBool>,
+        mut f: F,
+    ) -> bool
+    where
+        Q: AsRef<str>,
+        C: AsRef<str>,
+        F: FnMut(usize, &[u8]),
+    {
+        if choices.is_empty() {
+            (0..queries.len()).for_each(|i| f(i, &[]));
+            return true;
+        }
+        let choice_lens: Vec<usize> = choices
+            .iter()
+            .map(|choice| choice.as_ref().chars().count())
+            .collect();
+        let packed_choices = Packed::new(choices);
+        let storage = |label, words: &[u32]| {
+            self.device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some(label),
+                    contents: &bytes(words),
+                    usage: wgpu::BufferUsages::STORAGE,
+                })
+        };
+        let choice_chars = storage("choice chars", &packed_choices.chars);
+        let choice_spans = storage("choice spans", &packed_choices.spans);
+
+        let columns = choices.len().clamp(1, MAX_WORKGROUPS * WORKGROUP_SIZE);
+        let rows = (MAX_BATCH_PAIRS / columns).clamp(1, MAX_WORKGROUPS);
+        let mut block = vec![0u8; rows * choices.len()];
+        for (row_base, row_queries) in (0..queries.len()).step_by(rows).zip(queries.chunks(rows)) {
+            if cancel.is_some_and(|c| c.load(Ordering::Relaxed)) {
+                return false;
+            }
+            let packed_queries = QueryMasks::new(row_queries);
+            let query_alphabets = storage("query alphabets", &packed_queries.alphabets);
+            let query_masks = storage("query masks", &packed_queries.masks);
+            let query_spans = storage("query spans", &packed_queries.spans);
+            for choice_base in (0..choices.len()).step_by(columns) {
+                let width = columns.min(choices.len() - choice_base);
+                let lcs = self.dispatch(
+                    [
+                        &query_alphabets,
+                        &query_masks,
+                        &query_spans,
+                        &choice_chars,
+                        &choice_spans,
+                    ],
+                    row_queries.len(),
+                    choice_base,
+                    width,
+                );
+                for (i, query) in row_queries.iter().enumerate() {
+                    let query_len = packed_queries.query_len(i);
+                    for j in 0..width {
+                        let choice_len = choice_lens[choice_base + j];
+                        block[i * choices.len() + choice_base + j] = Mode::PythonCompat
+                            .score(lcs[i * width + j] as usize, query_len + choice_len);
+                    }
+                    if query_len > MAX_GPU_QUERY_CHARS {
+                        let query: Vec<char> = query.as_ref().chars().collect();
+                        for (j, choice) in
+                            choices[choice_base..choice_base + width].iter().enumerate()
+                        {
+                            let choice: Vec<char> = choice.as_ref().chars().collect();
+                            block[i * choices.len() + choice_base + j] = Mode::PythonCompat
+                                .score(lcs_len(&query, &choice), query.len() + choice.len());
+                        }
+                    }
+                }
+            }
+            count!(candidates_scored, row_queries.len() * choices.len());
+            for (i, row) in block
+                .chunks(choices.len())
+                .take(row_queries.len())
+                .enumerate()
+            {
+                f(row_base + i, row);
+            }
+        }
+        true
+    }
+
+    /// Runs the shader on `query_count` queries and `choice_count` choices
+    /// from `choice_base`, returning the common subsequence lengths.
+    fn dispatch(
+        &self,
+        inputs: [&wgpu::Buffer; 5],
+        query_count: usize,
+        choice_base: usize,
+        choice_count: usize,
+    ) -> Vec<u32> {
+        let size = (4 * query_count * choice_count) as wgpu::BufferAddress;
+        let output = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("lcs"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("lcs staging"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let params = [
+            to_u32(query_count),
+            to_u32(choice_count),
+            to_u32(choice_base),
+            0,
+        ];
+        let params = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("params"),
+                contents: &bytes(&params)[..16],
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let entries: Vec<wgpu::BindGroupEntry> = inputs
+            .iter()
+            .chain([&&output, &&params])
+            .enumerate()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                binding: binding as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &entries,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(
+                to_u32(choice_count.div_ceil(WORKGROUP_SIZE)),
+                to_u32(query_count),
+                1,
+            );
+        }
+        encoder.copy_buffer_to_buffer(&output, 0, &staging, 0, size);
+        self.queue.submit(Some(encoder.finish()));
+
+        let (sender, receiver) = mpsc::channel();
+        staging.map_async(wgpu::MapMode::Read, .., move |result| {
+            let _ = sender.send(result);
+        });
+        self.device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .expect("the GPU device was lost");
+        receiver
+            .recv()
+            .expect("mapping completes after waiting")
+            .expect("the GPU device was lost");
+        let lcs = staging
+            .get_mapped_range(..)
+            .chunks_exact(4)
+            .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+            .collect();
+        staging.unmap();
+        lcs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    /// `len` random characters among the `alphabet` code points from `α` on.
+    fn random(rng: &mut impl Rng, len: usize, alphabet: u32) -> String {
+        (0..len)
+            .map(|_| char::from_u32(0x3b1 + rng.gen_range(0..alphabet)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn gpu_scores_match_the_cpu() {
+        let Some(gpu) = GpuScorer::new() else {
+            return;
+        };
+        let mut rng = thread_rng();
+        let long = "abcdefghij".repeat(30);
+        let mut queries = vec![
+            String::new(),
+            "a".to_string(),
+            "new york mets".to_string(),
+            long.clone(),
+            long[..256].to_string(),
+            random(&mut rng, 256, 400),
+        ];
+        let mut choices = vec![
+            String::new(),
+            "new yrok mets".to_string(),
+            long[1..].to_string(),
+            random(&mut rng, 300, 400),
+        ];
+        for _ in 0..20 {
+            let len = rng.gen_range(0..=300);
+            queries.push(random(&mut rng, len, 4));
+            let len = rng.gen_range(0..=300);
+            choices.push(random(&mut rng, len, 4));
+        }
+        let scores = gpu.cdist(&queries, &choices);
+        for (i, query) in queries.iter().enumerate() {
+            let query: Vec<char> = query.chars().collect();
+            for (j, choice) in choices.iter().enumerate() {
+                let choice: Vec<char> = choice.chars().collect();
+                let expected =
+                    Mode::PythonCompat.score(lcs_len(&query, &choice), query.len() + choice.len());
+                assert_eq!(scores[i * choices.len() + j], expected, "{} {}", i, j);
+            }
+        }
+    }
+}
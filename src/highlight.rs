@@ -0,0 +1,230 @@
+//! Highlighting the regions of two strings which match each other. Requires
+//! feature "alignment".
+//!
+//! Regions are the matching blocks [ratio](crate::fuzz::ratio) is computed
+//! from, found ignoring case. Positions are tracked in characters, so
+//! multi-byte characters are never split.
+//!
+//! ```
+//! # use fuzzywuzzy::highlight::highlight_html;
+//! let (query, choice) = highlight_html("new york", "New York Mets");
+//! assert_eq!(query, "<mark>new york</mark>");
+//! assert_eq!(choice, "<mark>New York</mark> Mets");
+//! ```
+//!
+//! For debugging scores in a terminal, [highlight_ansi] and [alignment_ansi]
+//! color matching regions green and edits red with ANSI escape codes.
+
+use crate::difflib::{SequenceMatcher, Tag};
+use crate::fuzz;
+use crate::primitives;
+use crate::segmentation::Segmenter;
+use std::ops::Range;
+
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const STRIKETHROUGH: &str = "\x1b[9m";
+const UNDERLINE: &str = "\x1b[4m";
+const RESET: &str = "\x1b[0m";
+
+/// Marks the regions of `a` and `b` which match each other with `<mark>` tags,
+/// escaping everything else for use in HTML.
+///
+/// ```
+/// # use fuzzywuzzy::highlight::highlight_html;
+/// assert_eq!(highlight_html("café <b>", "Cafe <B>"),
+///            ("<mark>caf</mark>é<mark> &lt;b&gt;</mark>".to_string(),
+///             "<mark>Caf</mark>e<mark> &lt;B&gt;</mark>".to_string()));
+/// ```
+pub fn highlight_html(a: &str, b: &str) -> (String, String) {
+    let (a_spans, b_spans) = matching_spans(a, b);
+    (html(a, &a_spans), html(b, &b_spans))
+}
+
+/// Marks the regions of `choice` which match `query` with `<mark>` tags,
+/// e.g. to show why an extraction result matched.
+///
+/// ```
+/// # use fuzzywuzzy::highlight::highlight_choice_html;
+/// # use fuzzywuzzy::process::extract_one;
+/// # use fuzzywuzzy::fuzz::WRatio;
+/// # use fuzzywuzzy::utils::full_process;
+/// let choices = ["Atlanta Falcons", "New York Jets", "Dallas Cowboys"];
+/// let (best, _) = extract_one("cowboys", choices.iter(), &full_process, WRatio, 0).unwrap();
+/// assert_eq!(highlight_choice_html("cowboys", &best), "Dallas <mark>Cowboys</mark>");
+/// ```
+pub fn highlight_choice_html(query: &str, choice: &str) -> String {
+    html(choice, &matching_spans(query, choice).1)
+}
+
+/// The indices of the characters of `choice` which match `query`, e.g. for
+/// bolding matched letters in a list of results.
+///
+/// ```
+/// # use fuzzywuzzy::highlight::match_positions;
+/// assert_eq!(match_positions("nyy", "New York Yankees"), vec![0, 4, 9]);
+/// ```
+pub fn match_positions(query: &str, choice: &str) -> Vec<usize> {
+    matching_spans(query, choice)
+        .1
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Like [match_positions], but positions index the units produced by
+/// `segmenter`, e.g. grapheme clusters with
+/// [GraphemeSegmenter](crate::segmentation::GraphemeSegmenter).
+///
+/// Unlike [match_positions], units are compared as they are, so case matters.
+///
+/// ```
+/// # use fuzzywuzzy::highlight::segmented_match_positions;
+/// # use fuzzywuzzy::segmentation::GraphemeSegmenter;
+/// assert_eq!(segmented_match_positions("nos", "ñoños", &GraphemeSegmenter), vec![3, 4]);
+/// ```
+pub fn segmented_match_positions<'a, S: Segmenter<'a>>(
+    query: &'a str,
+    choice: &'a str,
+    segmenter: &S,
+) -> Vec<usize> {
+    fuzz::segmented_ratio_alignment(query, choice, segmenter).positions_in_second()
+}
+
+/// Colors the regions of `a` and `b` which match each other green and the
+/// rest red, for showing the strings one above the other.
+///
+/// ```
+/// # use fuzzywuzzy::highlight::highlight_ansi;
+/// let (a, b) = highlight_ansi("new york", "new yonkers");
+/// assert_eq!(a, "\x1b[32mnew yor\x1b[0m\x1b[31mk\x1b[0m");
+/// assert_eq!(b, "\x1b[32mnew yo\x1b[0m\x1b[31mnke\x1b[0m\x1b[32mr\x1b[0m\x1b[31ms\x1b[0m");
+/// ```
+pub fn highlight_ansi(a: &str, b: &str) -> (String, String) {
+    let (a_spans, b_spans) = matching_spans(a, b);
+    (ansi(a, &a_spans), ansi(b, &b_spans))
+}
+
+/// Renders the alignment of `a` with `b` on one line: matching text in
+/// green, text only in `a` in red and struck through, and text only in `b`
+/// in red and underlined.
+///
+/// Matching text is shown as it is in `a`.
+///
+/// ```
+/// # use fuzzywuzzy::highlight::alignment_ansi;
+/// assert_eq!(alignment_ansi("colour", "Color"),
+///            "\x1b[32mcolo\x1b[0m\x1b[31m\x1b[9mu\x1b[0m\x1b[32mr\x1b[0m");
+/// assert_eq!(alignment_ansi("gray", "grey"),
+///            "\x1b[32mgr\x1b[0m\x1b[31m\x1b[9ma\x1b[0m\x1b[31m\x1b[4me\x1b[0m\x1b[32my\x1b[0m");
+/// ```
+pub fn alignment_ansi(a: &str, b: &str) -> String {
+    let (a_chars, b_chars): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let (a_folded, b_folded) = (fold(a), fold(b));
+    let matcher = SequenceMatcher::with_junk(&a_folded, &b_folded, |_| false, false);
+    let mut rendered = String::new();
+    let mut push = |style: &str, chars: &[char]| {
+        rendered.push_str(style);
+        rendered.extend(chars);
+        rendered.push_str(RESET);
+    };
+    for code in matcher.get_opcodes() {
+        let (removed, added) = (&a_chars[code.i1..code.i2], &b_chars[code.j1..code.j2]);
+        match code.tag {
+            Tag::Equal => push(GREEN, removed),
+            Tag::Delete => push(&format!("{}{}", RED, STRIKETHROUGH), removed),
+            Tag::Insert => push(&format!("{}{}", RED, UNDERLINE), added),
+            Tag::Replace => {
+                push(&format!("{}{}", RED, STRIKETHROUGH), removed);
+                push(&format!("{}{}", RED, UNDERLINE), added);
+            }
+        }
+    }
+    rendered
+}
+
+/// Lower-cases `s` character by character, which keeps positions aligned
+/// with the original string.
+fn fold(s: &str) -> Vec<char> {
+    s.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+/// The character ranges of `a` and of `b` which match each other, ignoring case.
+pub(crate) fn matching_spans(a: &str, b: &str) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let blocks = primitives::get_matching_blocks(&fold(a), &fold(b));
+    let (a_spans, b_spans) = blocks
+        .iter()
+        .filter(|&&(_, _, n)| n > 0)
+        .map(|&(i, j, n)| (i..i + n, j..j + n))
+        .unzip();
+    (merge_adjacent(a_spans), merge_adjacent(b_spans))
+}
+
+/// Joins spans which are adjacent on one side, though not on the other.
+fn merge_adjacent(spans: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(spans.len());
+    for span in spans {
+        match merged.last_mut() {
+            Some(last) if last.end == span.start => last.end = span.end,
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+/// Splits `s` into pieces alternating between outside and inside of `spans`,
+/// which are sorted and disjoint character ranges, starting with an outside piece.
+pub(crate) fn split_spans<'a>(s: &'a str, spans: &[Range<usize>]) -> Vec<&'a str> {
+    let mut offsets: Vec<usize> = s.char_indices().map(|(offset, _)| offset).collect();
+    offsets.push(s.len());
+    let mut pieces = vec![];
+    let mut start = 0;
+    for span in spans {
+        pieces.push(&s[offsets[start]..offsets[span.start]]);
+        pieces.push(&s[offsets[span.start]..offsets[span.end]]);
+        start = span.end;
+    }
+    pieces.push(&s[offsets[start]..]);
+    pieces
+}
+
+fn html(s: &str, spans: &[Range<usize>]) -> String {
+    let mut html = String::new();
+    for (k, piece) in split_spans(s, spans).into_iter().enumerate() {
+        if piece.is_empty() {
+            continue;
+        }
+        let marked = k % 2 == 1;
+        if marked {
+            html.push_str("<mark>");
+        }
+        for c in piece.chars() {
+            match c {
+                '&' => html.push_str("&amp;"),
+                '<' => html.push_str("&lt;"),
+                '>' => html.push_str("&gt;"),
+                '"' => html.push_str("&quot;"),
+                '\'' => html.push_str("&#39;"),
+                _ => html.push(c),
+            }
+        }
+        if marked {
+            html.push_str("</mark>");
+        }
+    }
+    html
+}
+
+fn ansi(s: &str, spans: &[Range<usize>]) -> String {
+    let mut ansi = String::new();
+    for (k, piece) in split_spans(s, spans).into_iter().enumerate() {
+        if !piece.is_empty() {
+            ansi.push_str(if k % 2 == 1 { GREEN } else { RED });
+            ansi.push_str(piece);
+            ansi.push_str(RESET);
+        }
+    }
+    ansi
+}
@@ -0,0 +1,56 @@
+//! Counters describing how much work scoring did. Requires feature "instrumentation".
+//!
+//! Counters are kept per thread and accumulate until [reset] is called, so the
+//! effect of a prefilter or a cutoff can be measured by resetting, running an
+//! extraction and reading the counters back.
+//!
+//! ```
+//! # use fuzzywuzzy::instrumentation;
+//! # use fuzzywuzzy::process::extract_without_order;
+//! # use fuzzywuzzy::fuzz::WRatio;
+//! # use fuzzywuzzy::utils::full_process;
+//! instrumentation::reset();
+//! let choices = vec!["new york mets", "atlanta braves", "chicago cubs"];
+//! let results = extract_without_order("new york", choices, &full_process, WRatio, 80);
+//! let counters = instrumentation::snapshot();
+//! assert_eq!(counters.candidates_scored, 3);
+//! assert_eq!(counters.candidates_pruned, 3 - results.len() as u64);
+//! assert!(counters.comparisons > 0);
+//! ```
+
+use std::cell::Cell;
+
+/// A snapshot of the work counters for the current thread.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Counters {
+    /// Cells of the longest-common-substring table which were filled in.
+    pub dp_cells: u64,
+    /// Substring comparisons made while searching for matching blocks.
+    pub comparisons: u64,
+    /// Choices which were run through a scorer during extraction.
+    pub candidates_scored: u64,
+    /// Choices which were discarded during extraction for scoring below the cutoff.
+    pub candidates_pruned: u64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = Cell::new(Counters::default());
+}
+
+/// Returns the counters accumulated on this thread since the last [reset].
+pub fn snapshot() -> Counters {
+    COUNTERS.with(Cell::get)
+}
+
+/// Zeroes the counters for this thread, returning their previous values.
+pub fn reset() -> Counters {
+    COUNTERS.with(|c| c.replace(Counters::default()))
+}
+
+pub(crate) fn record<F: FnOnce(&mut Counters)>(f: F) {
+    COUNTERS.with(|c| {
+        let mut counters = c.get();
+        f(&mut counters);
+        c.set(counters);
+    });
+}
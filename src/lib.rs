@@ -4,10 +4,92 @@
 //! [Levenshtein Distance](https://en.wikipedia.org/wiki/Levenshtein_distance)
 //! to calculate the differences between sequences in a simple-to-use package.
 
+// Lets the expansions of `pattern::fuzzy_pattern!` name this crate within it.
+#[cfg(feature = "macros")]
+extern crate self as fuzzywuzzy;
+
 #[macro_use]
 pub mod utils;
+#[cfg(feature = "address")]
+pub mod address;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "token")]
+pub mod canonical;
+#[cfg(feature = "token")]
+pub mod choice_set;
+#[cfg(feature = "cluster")]
+pub mod cluster;
+#[cfg(feature = "collation")]
+pub mod collation;
+#[cfg(feature = "token")]
+pub mod comparer;
+#[cfg(feature = "serde")]
+pub mod config;
+#[cfg(feature = "confusion")]
+pub mod confusion;
+#[cfg(feature = "csv")]
+pub mod csv_join;
+pub mod difflib;
+#[cfg(feature = "email")]
+pub mod email;
+#[cfg(feature = "ensemble")]
+pub mod ensemble;
+pub mod error;
+pub mod ext;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod fuzz;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+#[cfg(feature = "alignment")]
+pub mod highlight;
+#[cfg(feature = "instrumentation")]
+pub mod instrumentation;
+#[cfg(feature = "linkage")]
+pub mod linkage;
+#[cfg(feature = "logs")]
+pub mod logs;
+#[cfg(feature = "ndarray")]
+pub mod matrix;
+#[cfg(feature = "name")]
+pub mod name;
 pub mod normalization;
+#[cfg(feature = "ocr")]
+pub mod ocr;
+#[cfg(feature = "passages")]
+pub mod passages;
+#[cfg(feature = "path")]
+pub mod path;
+#[cfg(feature = "macros")]
+pub mod pattern;
+#[cfg(feature = "phone")]
+pub mod phone;
 pub mod primitives;
 pub mod process;
+pub mod processing;
+#[cfg(feature = "python")]
+mod python;
+pub mod score;
+#[cfg(feature = "search")]
+pub mod search;
 pub mod segmentation;
+#[cfg(feature = "spell")]
+pub mod spell;
+#[cfg(feature = "stats")]
+pub mod stats;
+#[cfg(feature = "store")]
+pub mod store;
+#[cfg(feature = "subsequence")]
+pub mod subsequence;
+#[cfg(feature = "suggest")]
+pub mod suggest;
+#[cfg(feature = "title")]
+pub mod title;
+mod trace;
+#[cfg(feature = "transcript")]
+pub mod transcript;
+#[cfg(feature = "url")]
+pub mod url;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
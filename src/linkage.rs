@@ -0,0 +1,308 @@
+//! Record linkage: finding the pairs of records from two datasets which
+//! refer to the same entity, in the style of Fellegi and Sunter.
+//!
+//! A [Linker] compares records [Field] by field. A field agrees when its
+//! score reaches the field's agreement threshold, and adds `log2(m / u)` to
+//! the pair's weight if it does and `log2((1 - m) / (1 - u))` if it doesn't,
+//! where `m` is the probability that the field agrees for a true match and
+//! `u` the probability that it agrees by chance. Fields which are empty on
+//! either side add nothing. The total weight is then compared with two
+//! thresholds to reach a [Decision].
+//!
+//! Comparing every pair of records is quadratic, so [Linker::block_on]
+//! restricts comparisons to pairs sharing a blocking key.
+//!
+//! ```
+//! # use fuzzywuzzy::linkage::{Decision, Field, Linker};
+//! # use fuzzywuzzy::fuzz::{Ratio, TokenSortRatio};
+//! struct Person { name: &'static str, city: &'static str, zip: &'static str }
+//! let customers = [
+//!     Person { name: "Jon Smith", city: "Springfield", zip: "12345" },
+//!     Person { name: "Ann Lee", city: "Shelbyville", zip: "54321" },
+//! ];
+//! let patients = [
+//!     Person { name: "Smith, John", city: "Springfeld", zip: "12345" },
+//!     Person { name: "Anne Leigh", city: "Shelbyville", zip: "54321" },
+//!     Person { name: "Jon Smith", city: "Capital City", zip: "99999" },
+//! ];
+//! let linker = Linker::new()
+//!     .block_on(|p: &Person| p.zip.to_string())
+//!     .field(Field::new(|p: &Person| p.name.to_string(), TokenSortRatio).agreement(85))
+//!     .field(Field::new(|p: &Person| p.city.to_string(), Ratio).agreement(90))
+//!     .thresholds(-1.0, 4.0);
+//! let links: Vec<_> = linker.link(&customers, &patients)
+//!     .into_iter()
+//!     .map(|link| (link.left, link.right, link.comparison.decision))
+//!     .collect();
+//! assert_eq!(links, vec![(0, 0, Decision::Match), (1, 1, Decision::Possible)]);
+//! ```
+
+use crate::fuzz::Scorer;
+use crate::score::Score;
+use std::collections::HashMap;
+
+/// How a [Linker] classifies a pair of records.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Decision {
+    /// The weight reached the match threshold.
+    Match,
+    /// The weight is between the thresholds, so the pair needs a closer look.
+    Possible,
+    /// The weight is below the possible match threshold.
+    NonMatch,
+}
+
+/// Extracts a value from a record of type `R`.
+type Accessor<R> = Box<dyn Fn(&R) -> String + Send + Sync>;
+
+/// One attribute compared between records of type `R`.
+pub struct Field<R> {
+    value: Accessor<R>,
+    scorer: Box<dyn Scorer + Send + Sync>,
+    agreement: u8,
+    m: f64,
+    u: f64,
+}
+
+impl<R> Field<R> {
+    /// Compares the `value`s of records with `scorer`.
+    ///
+    /// By default a field agrees at a score of 85, with `m = 0.9` and `u = 0.1`.
+    pub fn new<V, S>(value: V, scorer: S) -> Field<R>
+    where
+        V: Fn(&R) -> String + Send + Sync + 'static,
+        S: Scorer + Send + Sync + 'static,
+    {
+        Field {
+            value: Box::new(value),
+            scorer: Box::new(scorer),
+            agreement: 85,
+            m: 0.9,
+            u: 0.1,
+        }
+    }
+
+    /// The score at which the field agrees.
+    pub fn agreement(mut self, agreement: u8) -> Field<R> {
+        self.agreement = agreement;
+        self
+    }
+
+    /// The probabilities that the field agrees for a true match (`m`) and for
+    /// a non-match (`u`).
+    ///
+    /// # Panics
+    /// If either isn't strictly between 0 and 1.
+    pub fn probabilities(mut self, m: f64, u: f64) -> Field<R> {
+        assert!(m > 0.0 && m < 1.0, "m must be strictly between 0 and 1");
+        assert!(u > 0.0 && u < 1.0, "u must be strictly between 0 and 1");
+        self.m = m;
+        self.u = u;
+        self
+    }
+
+    /// The weight this field adds for a score.
+    fn weight(&self, score: Score) -> f64 {
+        if score >= self.agreement {
+            (self.m / self.u).log2()
+        } else {
+            ((1.0 - self.m) / (1.0 - self.u)).log2()
+        }
+    }
+}
+
+/// The outcome of comparing two records.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    /// The score of each field in the order they were added, or `None` if
+    /// the field was empty on either side.
+    pub scores: Vec<Option<Score>>,
+    /// The sum of the fields' weights.
+    pub weight: f64,
+    pub decision: Decision,
+}
+
+/// A pair of records which were linked, as indices into the two datasets.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Link {
+    pub left: usize,
+    pub right: usize,
+    pub comparison: Comparison,
+}
+
+/// Links records of type `R` from two datasets.
+///
+/// By default there is no blocking, so every pair is compared, and both
+/// thresholds are 0, so any pair with more evidence for a match than against
+/// it is a match.
+pub struct Linker<R> {
+    blocking_keys: Vec<Accessor<R>>,
+    fields: Vec<Field<R>>,
+    possible_threshold: f64,
+    match_threshold: f64,
+}
+
+impl<R> Default for Linker<R> {
+    fn default() -> Linker<R> {
+        Linker::new()
+    }
+}
+
+impl<R> Linker<R> {
+    /// A linker with no fields.
+    pub fn new() -> Linker<R> {
+        Linker {
+            blocking_keys: vec![],
+            fields: vec![],
+            possible_threshold: 0.0,
+            match_threshold: 0.0,
+        }
+    }
+
+    /// Only compares pairs of records with the same `key`, or the same key
+    /// for any of several blocking keys. Records with an empty key aren't
+    /// blocked together by it.
+    pub fn block_on<K>(mut self, key: K) -> Linker<R>
+    where
+        K: Fn(&R) -> String + Send + Sync + 'static,
+    {
+        self.blocking_keys.push(Box::new(key));
+        self
+    }
+
+    /// Adds a field to compare records by.
+    pub fn field(mut self, field: Field<R>) -> Linker<R> {
+        self.fields.push(field);
+        self
+    }
+
+    /// Pairs weighing at least `possible_threshold` are possible matches, and
+    /// those weighing at least `match_threshold` are matches.
+    ///
+    /// # Panics
+    /// If `possible_threshold` is greater than `match_threshold`.
+    pub fn thresholds(mut self, possible_threshold: f64, match_threshold: f64) -> Linker<R> {
+        assert!(
+            possible_threshold <= match_threshold,
+            "the possible match threshold can't exceed the match threshold"
+        );
+        self.possible_threshold = possible_threshold;
+        self.match_threshold = match_threshold;
+        self
+    }
+
+    /// Compares two records field by field.
+    pub fn compare(&self, a: &R, b: &R) -> Comparison {
+        let mut weight = 0.0;
+        let scores = self
+            .fields
+            .iter()
+            .map(|field| {
+                let (a, b) = ((field.value)(a), (field.value)(b));
+                if a.is_empty() || b.is_empty() {
+                    return None;
+                }
+                let score = field.scorer.score(&a, &b);
+                weight += field.weight(score);
+                Some(score)
+            })
+            .collect();
+        let decision = if weight >= self.match_threshold {
+            Decision::Match
+        } else if weight >= self.possible_threshold {
+            Decision::Possible
+        } else {
+            Decision::NonMatch
+        };
+        Comparison {
+            scores,
+            weight,
+            decision,
+        }
+    }
+
+    /// The pairs of indices into `left` and `right` sharing a blocking key,
+    /// or all pairs if there are no blocking keys, in order.
+    pub fn candidates(&self, left: &[R], right: &[R]) -> Vec<(usize, usize)> {
+        if self.blocking_keys.is_empty() {
+            return (0..left.len())
+                .flat_map(|i| (0..right.len()).map(move |j| (i, j)))
+                .collect();
+        }
+        let mut pairs = vec![];
+        for key in self.blocking_keys.iter() {
+            let mut blocks: HashMap<String, Vec<usize>> = HashMap::new();
+            for (j, record) in right.iter().enumerate() {
+                let key = key(record);
+                if !key.is_empty() {
+                    blocks.entry(key).or_default().push(j);
+                }
+            }
+            for (i, record) in left.iter().enumerate() {
+                if let Some(block) = blocks.get(&key(record)) {
+                    pairs.extend(block.iter().map(|&j| (i, j)));
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+
+    /// Compares the [candidates](Linker::candidates), returning the matches
+    /// and possible matches in order.
+    pub fn link(&self, left: &[R], right: &[R]) -> Vec<Link> {
+        self.candidates(left, right)
+            .into_iter()
+            .filter_map(|(i, j)| {
+                let comparison = self.compare(&left[i], &right[j]);
+                match comparison.decision {
+                    Decision::NonMatch => None,
+                    _ => Some(Link {
+                        left: i,
+                        right: j,
+                        comparison,
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fuzz::Ratio;
+
+    #[test]
+    fn blocking_keys_are_unioned() {
+        let left = ["ab1", "cd2", "ef"];
+        let right = ["ab2", "xx1", "ef", "cd2"];
+        let linker = Linker::new()
+            .block_on(|s: &&str| s[2..].to_string())
+            .block_on(|s: &&str| s[..2].to_string());
+        assert_eq!(
+            linker.candidates(&left, &right),
+            vec![(0, 0), (0, 1), (1, 0), (1, 3), (2, 2)]
+        );
+        assert_eq!(Linker::<&str>::new().candidates(&left, &right).len(), 12);
+    }
+
+    #[test]
+    fn weights_follow_agreement() {
+        let linker = Linker::new()
+            .field(Field::new(|s: &(&str, &str)| s.0.to_string(), Ratio).probabilities(0.8, 0.2))
+            .field(Field::new(|s: &(&str, &str)| s.1.to_string(), Ratio).probabilities(0.5, 0.25))
+            .thresholds(0.0, 2.5);
+        let agree_both = linker.compare(&("smith", "boston"), &("smith", "boston"));
+        assert!((agree_both.weight - 3.0).abs() < 1e-9);
+        assert_eq!(agree_both.decision, Decision::Match);
+        let agree_first = linker.compare(&("smith", "boston"), &("smith", "denver"));
+        assert!((agree_first.weight - 2.0 - (0.5f64 / 0.75).log2()).abs() < 1e-9);
+        assert_eq!(agree_first.decision, Decision::Possible);
+        let missing = linker.compare(&("smith", ""), &("jones", "boston"));
+        assert_eq!(missing.scores, vec![Score::new(20), None]);
+        assert!((missing.weight + 2.0).abs() < 1e-9);
+        assert_eq!(missing.decision, Decision::NonMatch);
+    }
+}
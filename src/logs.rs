@@ -0,0 +1,218 @@
+//! Grouping similar log lines and extracting their common templates.
+//!
+//! Lines are split into whitespace separated tokens, and tokens containing
+//! digits, like ids, counts and timestamps, are treated as variables from the
+//! start. A line joins the most similar cluster of lines with as many tokens,
+//! where similarity is the percentage of positions at which the line's token
+//! equals the cluster template's. Positions where the lines of a cluster
+//! differ become [WILDCARD]s in its template, which match nothing.
+//! This is the approach of the Drain log parser, without its parse tree.
+//!
+//! ```
+//! # use fuzzywuzzy::logs::cluster_logs;
+//! let lines = [
+//!     "Connection from 10.0.0.1 closed",
+//!     "User alice logged in",
+//!     "Connection from 10.0.0.7 closed",
+//!     "User bob logged in",
+//!     "Disk full",
+//! ];
+//! let clusters = cluster_logs(lines.iter(), 50);
+//! let templates: Vec<String> = clusters.iter().map(|c| c.template()).collect();
+//! assert_eq!(templates, vec!["Connection from <*> closed", "User <*> logged in", "Disk full"]);
+//! assert_eq!(clusters[1].members(), &[1, 3]);
+//! ```
+
+use crate::utils::Rounding;
+use std::collections::HashMap;
+
+/// Stands for a variable token in a template.
+pub const WILDCARD: &str = "<*>";
+
+/// Similar log lines and their template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogCluster {
+    /// The template's tokens, `None` for wildcards.
+    tokens: Vec<Option<String>>,
+    members: Vec<usize>,
+}
+
+impl LogCluster {
+    /// The tokens the cluster's lines share, with [WILDCARD] where they differ.
+    pub fn template(&self) -> String {
+        let tokens: Vec<&str> = self
+            .tokens
+            .iter()
+            .map(|token| token.as_deref().unwrap_or(WILDCARD))
+            .collect();
+        tokens.join(" ")
+    }
+
+    /// The indices of the lines in the cluster, in the order they were added.
+    pub fn members(&self) -> &[usize] {
+        &self.members
+    }
+
+    /// The percentage of `tokens` equal to the template's, not counting wildcards.
+    fn similarity(&self, tokens: &[Option<&str>]) -> u8 {
+        if tokens.is_empty() {
+            return 100;
+        }
+        let matching = self
+            .tokens
+            .iter()
+            .zip(tokens.iter())
+            .filter(|(template, token)| template.is_some() && template.as_deref() == **token)
+            .count();
+        Rounding::Python.round(100.0 * matching as f64 / tokens.len() as f64)
+    }
+
+    /// Adds a line, turning the tokens it differs in into wildcards.
+    fn merge(&mut self, index: usize, tokens: &[Option<&str>]) {
+        for (template, token) in self.tokens.iter_mut().zip(tokens.iter()) {
+            if template.as_deref() != *token {
+                *template = None;
+            }
+        }
+        self.members.push(index);
+    }
+}
+
+/// Clusters log lines as they arrive. See the [module](self) docs.
+///
+/// By default a line joins a cluster when at least half of its tokens match,
+/// and tokens containing digits are wildcards.
+///
+/// ```
+/// # use fuzzywuzzy::logs::LogClusterer;
+/// let mut clusterer = LogClusterer::new().threshold(60).mask_numbers(false);
+/// assert_eq!(clusterer.add("worker 3 started in 12ms"), 0);
+/// assert_eq!(clusterer.add("worker 5 started in 40ms"), 0);
+/// assert_eq!(clusterer.add("worker 5 stopped after 2h"), 1);
+/// assert_eq!(clusterer.clusters()[0].template(), "worker <*> started in <*>");
+/// ```
+#[derive(Clone, Debug)]
+pub struct LogClusterer {
+    threshold: u8,
+    mask_numbers: bool,
+    clusters: Vec<LogCluster>,
+    /// The indices of the clusters with each number of tokens.
+    by_len: HashMap<usize, Vec<usize>>,
+    lines: usize,
+}
+
+impl Default for LogClusterer {
+    fn default() -> LogClusterer {
+        LogClusterer::new()
+    }
+}
+
+impl LogClusterer {
+    /// A clusterer without any lines.
+    pub fn new() -> LogClusterer {
+        LogClusterer {
+            threshold: 50,
+            mask_numbers: true,
+            clusters: vec![],
+            by_len: HashMap::new(),
+            lines: 0,
+        }
+    }
+
+    /// The least similarity for a line to join a cluster.
+    ///
+    /// # Panics
+    /// If `threshold` is greater than 100.
+    pub fn threshold(mut self, threshold: u8) -> LogClusterer {
+        assert!(threshold <= 100, "threshold must be at most 100");
+        self.threshold = threshold;
+        self
+    }
+
+    /// Whether tokens containing digits are wildcards.
+    pub fn mask_numbers(mut self, mask_numbers: bool) -> LogClusterer {
+        self.mask_numbers = mask_numbers;
+        self
+    }
+
+    /// Adds a line to the most similar cluster, or to a new one if none is
+    /// similar enough, and returns the cluster's index. Ties go to the
+    /// earliest cluster.
+    pub fn add(&mut self, line: &str) -> usize {
+        let mask_numbers = self.mask_numbers;
+        let tokens: Vec<Option<&str>> = line
+            .split_whitespace()
+            .map(|token| {
+                if mask_numbers && token.contains(|c: char| c.is_ascii_digit()) {
+                    None
+                } else {
+                    Some(token)
+                }
+            })
+            .collect();
+        let index = self.lines;
+        self.lines += 1;
+        let candidates = self.by_len.entry(tokens.len()).or_default();
+        let mut best: Option<(u8, usize)> = None;
+        for &i in candidates.iter() {
+            let similarity = self.clusters[i].similarity(&tokens);
+            if similarity >= self.threshold && best.is_none_or(|(s, _)| similarity > s) {
+                best = Some((similarity, i));
+            }
+        }
+        match best {
+            Some((_, i)) => {
+                self.clusters[i].merge(index, &tokens);
+                i
+            }
+            None => {
+                candidates.push(self.clusters.len());
+                self.clusters.push(LogCluster {
+                    tokens: tokens.iter().map(|t| t.map(str::to_string)).collect(),
+                    members: vec![index],
+                });
+                self.clusters.len() - 1
+            }
+        }
+    }
+
+    /// The clusters in the order they were created.
+    pub fn clusters(&self) -> &[LogCluster] {
+        &self.clusters
+    }
+
+    /// Returns the clusters in the order they were created.
+    pub fn into_clusters(self) -> Vec<LogCluster> {
+        self.clusters
+    }
+}
+
+/// Clusters `lines` with a [LogClusterer] with the given threshold.
+pub fn cluster_logs<I, T>(lines: I, threshold: u8) -> Vec<LogCluster>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+{
+    let mut clusterer = LogClusterer::new().threshold(threshold);
+    for line in lines {
+        clusterer.add(line.as_ref());
+    }
+    clusterer.into_clusters()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lines_only_cluster_with_as_many_tokens() {
+        let lines = ["a b", "", "a b c", "x y", "   ", "a c"];
+        let clusters = cluster_logs(lines.iter(), 0);
+        let members: Vec<&[usize]> = clusters.iter().map(LogCluster::members).collect();
+        assert_eq!(members, vec![&[0, 3, 5][..], &[1, 4], &[2]]);
+        assert_eq!(clusters[0].template(), "<*> <*>");
+        assert_eq!(clusters[1].template(), "");
+        let strict = cluster_logs(lines.iter(), 100);
+        assert_eq!(strict.len(), 5);
+    }
+}
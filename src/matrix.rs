@@ -0,0 +1,124 @@
+//! Scoring every query against every choice into an
+//! [ndarray](https://docs.rs/ndarray) matrix. Requires feature "ndarray".
+//!
+//! Like RapidFuzz's `cdist`, row `i` and column `j` of the result hold the
+//! score of query `i` against choice `j`, as an `f32` from 0 to 100, ready
+//! for clustering or other numeric work without copying. Scores are computed
+//! as given, so the scorer should process the strings if needed.
+//!
+//! ```
+//! # use fuzzywuzzy::matrix::cdist;
+//! # use fuzzywuzzy::fuzz::Ratio;
+//! use ndarray::{array, ArrayView1};
+//! let queries = ["apple", "banana"];
+//! let choices = array!["apple", "apples", "bandana"];
+//! let scores = cdist(ArrayView1::from(&queries), choices.view(), Ratio);
+//! assert_eq!(scores, array![[100.0, 91.0, 17.0], [18.0, 17.0, 92.0]]);
+//! ```
+
+use crate::fuzz::Scorer;
+use ndarray::{Array2, ArrayView1, ArrayViewMut1, ArrayViewMut2};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The scores of each of `queries` against each of `choices` by `scorer`,
+/// one row per query. See the [module](self) docs.
+pub fn cdist<Q, C, S>(queries: ArrayView1<Q>, choices: ArrayView1<C>, scorer: S) -> Array2<f32>
+where
+    Q: AsRef<str>,
+    C: AsRef<str>,
+    S: Scorer,
+{
+    let mut scores = Array2::zeros((queries.len(), choices.len()));
+    cdist_into(queries, choices, scorer, scores.view_mut());
+    scores
+}
+
+/// Like [cdist], but stops early if `cancel` is set.
+///
+/// `cancel` is checked before each query's row is scored. Returns `None` if
+/// the computation was cancelled.
+///
+/// ```
+/// # use fuzzywuzzy::matrix::cdist_cancellable;
+/// # use fuzzywuzzy::fuzz::Ratio;
+/// use ndarray::array;
+/// use std::sync::atomic::AtomicBool;
+/// let names = array!["jon", "john"];
+/// let cancel = AtomicBool::new(false);
+/// assert_eq!(
+///     cdist_cancellable(names.view(), names.view(), Ratio, &cancel),
+///     Some(array![[100.0, 86.0], [86.0, 100.0]])
+/// );
+/// let cancel = AtomicBool::new(true);
+/// assert_eq!(cdist_cancellable(names.view(), names.view(), Ratio, &cancel), None);
+/// ```
+pub fn cdist_cancellable<Q, C, S>(
+    queries: ArrayView1<Q>,
+    choices: ArrayView1<C>,
+    scorer: S,
+    cancel: &AtomicBool,
+) -> Option<Array2<f32>>
+where
+    Q: AsRef<str>,
+    C: AsRef<str>,
+    S: Scorer,
+{
+    let mut scores = Array2::zeros((queries.len(), choices.len()));
+    for (query, mut row) in queries.iter().zip(scores.rows_mut()) {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        score_row(query.as_ref(), choices, &scorer, row.view_mut());
+    }
+    Some(scores)
+}
+
+/// [cdist], writing the scores into `out`, which may be a view of a larger
+/// matrix.
+///
+/// # Panics
+///
+/// Panics if `out` doesn't have a row per query and a column per choice.
+///
+/// ```
+/// # use fuzzywuzzy::matrix::cdist_into;
+/// # use fuzzywuzzy::fuzz::Ratio;
+/// use ndarray::{array, s, Array2};
+/// let names = array!["jon", "john", "joan"];
+/// let mut scores = Array2::from_elem((3, 3), f32::NAN);
+/// // Scores the last two names against all of them.
+/// cdist_into(names.slice(s![1..]), names.view(), Ratio, scores.slice_mut(s![1.., ..]));
+/// assert_eq!(scores.row(1), array![86.0, 100.0, 75.0]);
+/// assert!(scores.row(0).iter().all(|score| score.is_nan()));
+/// ```
+pub fn cdist_into<Q, C, S>(
+    queries: ArrayView1<Q>,
+    choices: ArrayView1<C>,
+    scorer: S,
+    mut out: ArrayViewMut2<f32>,
+) where
+    Q: AsRef<str>,
+    C: AsRef<str>,
+    S: Scorer,
+{
+    assert_eq!(
+        out.dim(),
+        (queries.len(), choices.len()),
+        "out must have a row per query and a column per choice"
+    );
+    for (query, row) in queries.iter().zip(out.rows_mut()) {
+        score_row(query.as_ref(), choices, &scorer, row);
+    }
+}
+
+/// Writes the scores of `query` against each of `choices` into `row`.
+fn score_row<C, S>(query: &str, choices: ArrayView1<C>, scorer: &S, mut row: ArrayViewMut1<f32>)
+where
+    C: AsRef<str>,
+    S: Scorer,
+{
+    for (choice, score) in choices.iter().zip(row.iter_mut()) {
+        *score = f32::from(scorer.score(query, choice.as_ref()).percent());
+    }
+    count!(candidates_scored, choices.len());
+}
@@ -0,0 +1,249 @@
+//! Matching person names.
+//!
+//! [name_ratio] compares the words of two names regardless of their order, so
+//! "Smith, John" matches "John Smith", and treats hyphenated surnames as
+//! separate words. Words match when they're equal, when one is the initial
+//! of the other, when one is a common nickname for the other, e.g. "Bill"
+//! and "William", or, with the `phonetic` feature, when they sound alike and
+//! are spelled similarly. Otherwise they're compared by [ratio](crate::fuzz::ratio). Extra words in
+//! the longer name, such as a middle name, cost up to 10 points.
+//!
+//! ```
+//! # use fuzzywuzzy::name::name_ratio;
+//! assert_eq!(name_ratio("Smith, William J.", "Bill Smith"), 94);
+//! assert_eq!(name_ratio("Mary Smith-Jones", "Mary Jones"), 97);
+//! assert_eq!(name_ratio("Jon Smyth", "John Smith"), 86);
+//! ```
+
+use crate::fuzz::{self, Scorer};
+use crate::score::Score;
+use crate::utils::{self, Rounding};
+
+/// Words which aren't part of the name itself.
+const TITLES: &[&str] = &[
+    "mr", "mrs", "ms", "miss", "mx", "dr", "prof", "rev", "sir", "jr", "sr", "ii", "iii", "iv",
+];
+
+/// Given names and their common nicknames.
+const NICKNAMES: &[(&str, &[&str])] = &[
+    ("albert", &["al", "bert"]),
+    ("alexander", &["alex", "al", "sandy"]),
+    ("andrew", &["andy", "drew"]),
+    ("anthony", &["tony"]),
+    ("barbara", &["barb", "babs"]),
+    ("benjamin", &["ben", "benny"]),
+    ("catherine", &["cathy", "kate", "katie"]),
+    ("charles", &["charlie", "chuck", "chas"]),
+    ("christopher", &["chris", "kit"]),
+    ("daniel", &["dan", "danny"]),
+    ("david", &["dave", "davy"]),
+    ("deborah", &["deb", "debbie"]),
+    ("donald", &["don", "donnie"]),
+    ("dorothy", &["dot", "dottie"]),
+    ("edward", &["ed", "eddie", "ted", "ned"]),
+    (
+        "elizabeth",
+        &["liz", "beth", "betty", "eliza", "lisa", "libby"],
+    ),
+    ("eugene", &["gene"]),
+    ("francis", &["frank", "fran"]),
+    ("frederick", &["fred", "freddie"]),
+    ("gerald", &["jerry", "gerry"]),
+    ("gregory", &["greg"]),
+    ("henry", &["hank", "harry"]),
+    ("jacob", &["jake"]),
+    ("james", &["jim", "jimmy", "jamie"]),
+    ("jennifer", &["jen", "jenny"]),
+    ("john", &["jack", "johnny"]),
+    ("jonathan", &["jon", "jonny"]),
+    ("joseph", &["joe", "joey"]),
+    ("katherine", &["kathy", "kate", "katie", "kitty"]),
+    ("kenneth", &["ken", "kenny"]),
+    ("lawrence", &["larry"]),
+    ("margaret", &["maggie", "meg", "peggy", "marge"]),
+    ("matthew", &["matt"]),
+    ("michael", &["mike", "mikey", "mick"]),
+    ("nicholas", &["nick", "nicky"]),
+    ("patricia", &["pat", "patty", "trish"]),
+    ("patrick", &["pat", "paddy"]),
+    ("peter", &["pete"]),
+    ("raymond", &["ray"]),
+    ("rebecca", &["becky"]),
+    ("richard", &["dick", "rick", "rich", "ricky"]),
+    ("robert", &["bob", "bobby", "rob", "robbie"]),
+    ("ronald", &["ron", "ronnie"]),
+    ("samuel", &["sam", "sammy"]),
+    ("stephen", &["steve"]),
+    ("steven", &["steve"]),
+    ("susan", &["sue", "susie"]),
+    ("theodore", &["ted", "teddy", "theo"]),
+    ("thomas", &["tom", "tommy"]),
+    ("timothy", &["tim", "timmy"]),
+    ("victoria", &["vicky", "tori"]),
+    ("william", &["bill", "billy", "will", "willy", "liam"]),
+];
+
+/// The score of a word matching the other's initial.
+const INITIAL_SCORE: u8 = 90;
+/// The score of a nickname matching its given name, or two nicknames of the same name.
+const NICKNAME_SCORE: u8 = 95;
+#[cfg(feature = "phonetic")]
+/// The least score of two words which sound alike and are spelled similarly.
+const PHONETIC_SCORE: u8 = 85;
+#[cfg(feature = "phonetic")]
+/// The ratio from which words sounding alike count as spelled similarly.
+/// Soundex alone is too coarse, e.g. "John" and "Jane" share a code.
+const PHONETIC_MIN_RATIO: u8 = 60;
+
+/// The words of `name`, without titles and with hyphenated parts separated.
+fn words(name: &str) -> Vec<String> {
+    utils::full_process(name, false)
+        .split_whitespace()
+        .filter(|word| !TITLES.contains(word))
+        .map(str::to_string)
+        .collect()
+}
+
+/// The given names `word` is a nickname for, including itself.
+fn given_names(word: &str) -> impl Iterator<Item = &str> {
+    NICKNAMES
+        .iter()
+        .filter(move |(name, nicknames)| *name == word || nicknames.contains(&word))
+        .map(|&(name, _)| name)
+}
+
+#[cfg(feature = "phonetic")]
+/// The American Soundex code of `word`, or `None` unless it's ASCII letters.
+fn soundex(word: &str) -> Option<String> {
+    let code = |c: char| match c {
+        'b' | 'f' | 'p' | 'v' => Some('1'),
+        'c' | 'g' | 'j' | 'k' | 'q' | 's' | 'x' | 'z' => Some('2'),
+        'd' | 't' => Some('3'),
+        'l' => Some('4'),
+        'm' | 'n' => Some('5'),
+        'r' => Some('6'),
+        _ => None,
+    };
+    let mut chars = word.chars();
+    let first = chars.next()?;
+    if !word.chars().all(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+    let mut soundex = first.to_ascii_uppercase().to_string();
+    let mut last = code(first);
+    for c in chars {
+        let digit = code(c);
+        if digit.is_some() && digit != last {
+            soundex.extend(digit);
+        }
+        // 'h' and 'w' don't separate letters with the same code, vowels do.
+        if c != 'h' && c != 'w' {
+            last = digit;
+        }
+    }
+    soundex.truncate(4);
+    while soundex.len() < 4 {
+        soundex.push('0');
+    }
+    Some(soundex)
+}
+
+/// How well two words of a name match.
+fn word_score(a: &str, b: &str) -> u8 {
+    if a == b {
+        return 100;
+    }
+    let is_initial =
+        |initial: &str, word: &str| initial.chars().count() == 1 && word.starts_with(initial);
+    if is_initial(a, b) || is_initial(b, a) {
+        return INITIAL_SCORE;
+    }
+    if given_names(a).any(|name| given_names(b).any(|other| name == other)) {
+        return NICKNAME_SCORE;
+    }
+    let ratio = fuzz::ratio(a, b);
+    #[cfg(feature = "phonetic")]
+    if let (Some(a), Some(b)) = (soundex(a), soundex(b)) {
+        if a == b && ratio >= PHONETIC_MIN_RATIO {
+            return ratio.max(PHONETIC_SCORE);
+        }
+    }
+    ratio
+}
+
+/// Compares two person names word by word, see the [module](self) docs.
+///
+/// Each word of the name with fewer words is paired with a different word of
+/// the other, best scoring pairs first, and the pairs' scores are averaged.
+/// Names without any words score 0.
+///
+/// ```
+/// # use fuzzywuzzy::name::name_ratio;
+/// assert_eq!(name_ratio("Dr. Robert Smith Jr.", "smith, bob"), 98);
+/// assert_eq!(name_ratio("John Smith", "John Robert Smith"), 97);
+/// assert_eq!(name_ratio("J. R. Smith", "John Robert Smith"), 93);
+/// assert_eq!(name_ratio("John Smith", "Jane Smith"), 75);
+/// ```
+pub fn name_ratio(a: &str, b: &str) -> u8 {
+    let (a, b) = (words(a), words(b));
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if short.is_empty() {
+        return 0;
+    }
+    let mut pairs: Vec<(u8, usize, usize)> = short
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| {
+            long.iter()
+                .enumerate()
+                .map(move |(j, b)| (word_score(a, b), i, j))
+        })
+        .collect();
+    // Best first, and otherwise in order of the words.
+    pairs.sort_by(|x, y| y.0.cmp(&x.0).then((x.1, x.2).cmp(&(y.1, y.2))));
+    let (mut short_used, mut long_used) = (vec![false; short.len()], vec![false; long.len()]);
+    let mut total = 0.0;
+    for (score, i, j) in pairs {
+        if !short_used[i] && !long_used[j] {
+            short_used[i] = true;
+            long_used[j] = true;
+            total += score as f64;
+        }
+    }
+    let coverage = short.len() as f64 / long.len() as f64;
+    Rounding::Python.round(total / short.len() as f64 * (0.9 + 0.1 * coverage))
+}
+
+/// [Scorer] for [name_ratio].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NameRatio;
+
+impl Scorer for NameRatio {
+    fn score(&self, a: &str, b: &str) -> Score {
+        Score::new(name_ratio(a, b)).expect("name_ratio returns at most 100")
+    }
+}
+
+#[cfg(all(test, feature = "phonetic"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn soundex_matches_reference_codes() {
+        let codes = [
+            ("robert", "R163"),
+            ("rupert", "R163"),
+            ("rubin", "R150"),
+            ("ashcraft", "A261"),
+            ("tymczak", "T522"),
+            ("pfister", "P236"),
+            ("honeyman", "H555"),
+            ("a", "A000"),
+        ];
+        for &(word, code) in codes.iter() {
+            assert_eq!(soundex(word).as_deref(), Some(code), "{}", word);
+        }
+        assert_eq!(soundex("zoë"), None);
+        assert_eq!(soundex(""), None);
+    }
+}
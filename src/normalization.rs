@@ -17,6 +17,9 @@
 //! assert_eq!(multiple_normalizers.normalize(a3), a1);
 //! ```
 
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
 /// Represents a strategy for normalizing string characters into a canonical value of their equivalence class.
 ///
 /// i.e., in a case-insensitive context, 'a' might be the canonical value for the equivalence class of ASCII A's: `['a', 'A']`.
@@ -104,6 +107,75 @@ impl Normalizer for LowerCaseNormalizer {
     }
 }
 
+/// Locales with their own case conversion rules, for use with [LocaleLowerCaseNormalizer].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum CaseLocale {
+    /// The locale-independent Unicode default, as used by [str::to_lowercase].
+    #[default]
+    Default,
+    /// Turkish and Azerbaijani, where dotted `İ` lower-cases to `i` and dotless `I` to `ı`.
+    Turkic,
+}
+
+/// Normalizes strings by lower-casing all letters according to the rules of a locale.
+///
+/// The locale-independent [LowerCaseNormalizer] turns the Turkish `İ` into `i`
+/// followed by U+0307 COMBINING DOT ABOVE, which [full_process](crate::utils::full_process)
+/// then replaces with a space, and turns `I` into `i` rather than `ı`.
+///
+/// ```
+/// # use fuzzywuzzy::normalization::{Normalizer, LowerCaseNormalizer, LocaleLowerCaseNormalizer, CaseLocale};
+/// # use fuzzywuzzy::utils::full_process_normalized;
+/// let turkic = LocaleLowerCaseNormalizer::new(CaseLocale::Turkic);
+/// assert_eq!(turkic.normalize("İSTANBUL"), "istanbul");
+/// assert_eq!(turkic.normalize("ISPARTA"), "ısparta");
+/// assert_eq!(LowerCaseNormalizer.normalize("İSTANBUL"), "i\u{0307}stanbul");
+/// assert_eq!(full_process_normalized("İSTANBUL", false, &turkic), "istanbul");
+/// assert_eq!(full_process_normalized("İSTANBUL", false, &LowerCaseNormalizer), "i stanbul");
+/// assert_eq!(LocaleLowerCaseNormalizer::new(CaseLocale::Default).normalize("İ"), "i\u{0307}");
+/// ```
+pub struct LocaleLowerCaseNormalizer {
+    locale: CaseLocale,
+}
+
+impl LocaleLowerCaseNormalizer {
+    pub fn new(locale: CaseLocale) -> LocaleLowerCaseNormalizer {
+        LocaleLowerCaseNormalizer { locale }
+    }
+}
+
+impl Normalizer for LocaleLowerCaseNormalizer {
+    fn normalize(&self, s: &str) -> String {
+        match self.locale {
+            CaseLocale::Default => s.to_lowercase(),
+            CaseLocale::Turkic => {
+                let mut lowered = String::with_capacity(s.len());
+                let mut rest = s;
+                while let Some(i) = rest.find(['I', 'İ']) {
+                    lowered.push_str(&rest[..i].to_lowercase());
+                    let capital = if rest[i..].starts_with('I') {
+                        'I'
+                    } else {
+                        'İ'
+                    };
+                    rest = &rest[i + capital.len_utf8()..];
+                    if capital == 'İ' {
+                        lowered.push('i');
+                    } else if let Some(after) = rest.strip_prefix('\u{0307}') {
+                        // A decomposed dotted capital I.
+                        lowered.push('i');
+                        rest = after;
+                    } else {
+                        lowered.push('ı');
+                    }
+                }
+                lowered.push_str(&rest.to_lowercase());
+                lowered
+            }
+        }
+    }
+}
+
 /// Removes non-ASCII codepoints.
 ///
 /// Notably, this does not ASCII-ify non-ASCII characters, it just removes them.
@@ -121,10 +193,573 @@ impl Normalizer for AsciiOnlyNormalizer {
     }
 }
 
-#[cfg(feature = "segmentation")]
+/// Removes invisible characters: zero-width spaces and joiners, byte order marks,
+/// bidirectional formatting marks, soft hyphens and control characters other than whitespace.
+///
+/// These are common in text copied from web pages and documents, where they are
+/// invisible to readers but make otherwise identical strings compare unequal.
+/// Whitespace control characters like `\t` and `\n` are kept.
+///
+/// Removing U+200D ZERO WIDTH JOINER splits emoji ZWJ sequences into their parts.
+///
+/// ```
+/// # use fuzzywuzzy::normalization::{Normalizer, StripInvisibleNormalizer};
+/// assert_eq!(StripInvisibleNormalizer.normalize("\u{FEFF}new\u{200B}york"), "newyork");
+/// assert_eq!(StripInvisibleNormalizer.normalize("new\tyork\u{0007}"), "new\tyork");
+/// assert_eq!(StripInvisibleNormalizer.normalize("co\u{00AD}operate"), "cooperate");
+/// ```
+pub struct StripInvisibleNormalizer;
+
+impl Normalizer for StripInvisibleNormalizer {
+    fn normalize(&self, s: &str) -> String {
+        s.chars().filter(|c| !is_invisible(*c)).collect()
+    }
+}
+
+/// Whether `c` is a zero-width or formatting character, or a non-whitespace control character.
+fn is_invisible(c: char) -> bool {
+    (c.is_control() && !c.is_whitespace())
+        || matches!(c,
+            '\u{00AD}'
+            | '\u{180E}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{2069}'
+            | '\u{FEFF}')
+}
+
+/// Common English stopwords, for use with [StopwordNormalizer].
+#[rustfmt::skip]
+pub const ENGLISH_STOPWORDS: &[&str] = &[
+    "a", "about", "above", "after", "again", "against", "all", "am", "an", "and", "any", "are",
+    "as", "at", "be", "because", "been", "before", "being", "below", "between", "both", "but",
+    "by", "can", "did", "do", "does", "doing", "down", "during", "each", "few", "for", "from",
+    "further", "had", "has", "have", "having", "he", "her", "here", "hers", "herself", "him",
+    "himself", "his", "how", "i", "if", "in", "into", "is", "it", "its", "itself", "just", "me",
+    "more", "most", "my", "myself", "no", "nor", "not", "now", "of", "off", "on", "once", "only",
+    "or", "other", "our", "ours", "ourselves", "out", "over", "own", "same", "she", "should", "so",
+    "some", "such", "than", "that", "the", "their", "theirs", "them", "themselves", "then",
+    "there", "these", "they", "this", "those", "through", "to", "too", "under", "until", "up",
+    "very", "was", "we", "were", "what", "when", "where", "which", "while", "who", "whom", "why",
+    "will", "with", "you", "your", "yours", "yourself", "yourselves",
+];
+
+/// Removes stopwords: whitespace separated words carrying little meaning for a comparison.
+///
+/// Words are matched case-insensitively and ignoring leading or trailing
+/// punctuation, so `"Inc."` matches the stopword `"inc"`. The remaining words
+/// are joined by single spaces.
+///
+/// ```
+/// # use fuzzywuzzy::normalization::{Normalizer, StopwordNormalizer, ENGLISH_STOPWORDS};
+/// # use fuzzywuzzy::fuzz::token_set_ratio;
+/// assert_eq!(StopwordNormalizer::english().normalize("The Bank of America"), "Bank America");
+/// let company = StopwordNormalizer::with(ENGLISH_STOPWORDS.iter().chain(&["inc", "corp", "llc"]));
+/// assert_eq!(company.normalize("The Widget Company, Inc."), "Widget Company,");
+/// let (a, b) = ("The Acme Group of Companies Inc.", "Acme Group Inc");
+/// assert_eq!(token_set_ratio(a, b, true, true), 100);
+/// let (a, b) = ("The Acme Corp", "The Apex Corp");
+/// assert_eq!(token_set_ratio(a, b, true, true), 85);
+/// assert_eq!(token_set_ratio(&company.normalize(a), &company.normalize(b), true, true), 60);
+/// ```
+pub struct StopwordNormalizer {
+    stopwords: HashSet<String>,
+}
+
+impl StopwordNormalizer {
+    /// Removes the given stopwords.
+    pub fn with<I, S>(stopwords: I) -> StopwordNormalizer
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        StopwordNormalizer {
+            stopwords: stopwords
+                .into_iter()
+                .map(|w| w.as_ref().to_lowercase())
+                .collect(),
+        }
+    }
+
+    /// Removes the [ENGLISH_STOPWORDS].
+    pub fn english() -> StopwordNormalizer {
+        StopwordNormalizer::with(ENGLISH_STOPWORDS)
+    }
+
+    fn is_stopword(&self, word: &str) -> bool {
+        let bare = word.trim_matches(|c: char| !c.is_alphanumeric());
+        self.stopwords.contains(&bare.to_lowercase())
+    }
+}
+
+impl Normalizer for StopwordNormalizer {
+    fn normalize(&self, s: &str) -> String {
+        s.split_whitespace()
+            .filter(|word| !self.is_stopword(word))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Reduces a word to its stem, e.g. `"running"` and `"runs"` to `"run"`.
+///
+/// This crate doesn't ship a stemmer. Implement this trait for one, e.g. a
+/// Snowball stemmer from another crate, and use it through [StemmingNormalizer].
+/// Functions with a matching type signature also work.
+///
+/// ```
+/// # use fuzzywuzzy::normalization::Stemmer;
+/// fn strip_plural(word: &str) -> String { word.trim_end_matches('s').to_string() }
+/// assert_eq!(strip_plural.stem("cats"), "cat");
+/// ```
+pub trait Stemmer {
+    fn stem(&self, word: &str) -> String;
+}
+
+impl<F: Fn(&str) -> String> Stemmer for F {
+    fn stem(&self, word: &str) -> String {
+        self(word)
+    }
+}
+
+/// Stems every whitespace separated word with a [Stemmer].
+///
+/// Words are stemmed as they are, so this usually runs after lower-casing and
+/// stripping punctuation, e.g. on the output of [full_process](crate::utils::full_process).
+/// The stemmed words are joined by single spaces.
+///
+/// ```
+/// # use fuzzywuzzy::normalization::{Normalizer, StemmingNormalizer};
+/// # use fuzzywuzzy::fuzz::token_sort_ratio;
+/// // A toy stemmer; real applications should use e.g. a Snowball stemmer.
+/// fn stem(word: &str) -> String {
+///     let word = word.strip_suffix("ning").or_else(|| word.strip_suffix('s')).unwrap_or(word);
+///     word.to_string()
+/// }
+/// let stemmer = StemmingNormalizer::with(stem);
+/// assert_eq!(stemmer.normalize("dogs running"), "dog run");
+/// assert_eq!(token_sort_ratio("running dogs", "dog runs", true, true), 70);
+/// assert_eq!(token_sort_ratio(&stemmer.normalize("running dogs"), &stemmer.normalize("dog runs"), true, true), 100);
+/// ```
+pub struct StemmingNormalizer<S: Stemmer> {
+    stemmer: S,
+}
+
+impl<S: Stemmer> StemmingNormalizer<S> {
+    pub fn with(stemmer: S) -> StemmingNormalizer<S> {
+        StemmingNormalizer { stemmer }
+    }
+}
+
+impl<S: Stemmer> Normalizer for StemmingNormalizer<S> {
+    fn normalize(&self, s: &str) -> String {
+        s.split_whitespace()
+            .map(|word| self.stemmer.stem(word))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Replaces synonyms with one word standing for all of them, e.g. `"rd"` and
+/// `"rte"` with `"road"`.
+///
+/// Each group of synonyms is replaced with its first word, in lower case.
+/// Words are matched case-insensitively and ignoring leading or trailing
+/// punctuation, like [StopwordNormalizer] does, and other words are kept as
+/// they are. A word in several groups is replaced with the last one's first
+/// word. The words are joined by single spaces.
+///
+/// ```
+/// # use fuzzywuzzy::normalization::{Normalizer, SynonymNormalizer};
+/// let synonyms = SynonymNormalizer::with(vec![vec!["road", "rd", "rte"], vec!["saint", "st"]]);
+/// assert_eq!(synonyms.normalize("St. Mary's Rd"), "saint Mary's road");
+/// assert_eq!(synonyms.canonical("RTE"), Some("road"));
+/// assert_eq!(synonyms.canonical("avenue"), None);
+/// ```
+pub struct SynonymNormalizer {
+    /// The first word of the group of each word.
+    canonical: HashMap<String, String>,
+}
+
+impl SynonymNormalizer {
+    /// Replaces each group of synonyms with its first word.
+    pub fn with<I, G, S>(groups: I) -> SynonymNormalizer
+    where
+        I: IntoIterator<Item = G>,
+        G: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut canonical = HashMap::new();
+        for group in groups {
+            let mut words = group.into_iter().map(|w| w.as_ref().to_lowercase());
+            if let Some(first) = words.next() {
+                canonical.insert(first.clone(), first.clone());
+                for word in words {
+                    canonical.insert(word, first.clone());
+                }
+            }
+        }
+        SynonymNormalizer { canonical }
+    }
+
+    /// The word standing for `word`'s synonyms, if it has any.
+    pub fn canonical(&self, word: &str) -> Option<&str> {
+        let bare = word.trim_matches(|c: char| !c.is_alphanumeric());
+        self.canonical.get(&bare.to_lowercase()).map(String::as_str)
+    }
+}
+
+impl Normalizer for SynonymNormalizer {
+    fn normalize(&self, s: &str) -> String {
+        s.split_whitespace()
+            .map(|word| self.canonical(word).unwrap_or(word))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Languages whose number words [NumberWordNormalizer] understands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NumberLanguage {
+    /// Cardinals (`"two"`, `"twenty-one"`) and ordinals (`"second"`, `"twenty-first"`).
+    English,
+    /// Cardinals from `"cero"` to `"cien"`.
+    Spanish,
+    /// Cardinals from `"zéro"` to `"cent"`.
+    French,
+    /// Cardinals from `"null"` to `"hundert"`.
+    German,
+}
+
+#[rustfmt::skip]
+const ENGLISH_UNITS: &[&str] = &[
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen",
+    "nineteen",
+];
+#[rustfmt::skip]
+const ENGLISH_ORDINAL_UNITS: &[&str] = &[
+    "zeroth", "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth",
+    "tenth", "eleventh", "twelfth", "thirteenth", "fourteenth", "fifteenth", "sixteenth",
+    "seventeenth", "eighteenth", "nineteenth",
+];
+#[rustfmt::skip]
+const ENGLISH_TENS: &[&str] = &[
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety", "hundred",
+];
+#[rustfmt::skip]
+const ENGLISH_ORDINAL_TENS: &[&str] = &[
+    "", "", "twentieth", "thirtieth", "fortieth", "fiftieth", "sixtieth", "seventieth",
+    "eightieth", "ninetieth", "hundredth",
+];
+#[rustfmt::skip]
+const SPANISH_CARDINALS: &[(&str, u32)] = &[
+    ("cero", 0), ("uno", 1), ("una", 1), ("dos", 2), ("tres", 3), ("cuatro", 4), ("cinco", 5),
+    ("seis", 6), ("siete", 7), ("ocho", 8), ("nueve", 9), ("diez", 10), ("once", 11),
+    ("doce", 12), ("trece", 13), ("catorce", 14), ("quince", 15), ("dieciséis", 16),
+    ("diecisiete", 17), ("dieciocho", 18), ("diecinueve", 19), ("veinte", 20), ("treinta", 30),
+    ("cuarenta", 40), ("cincuenta", 50), ("sesenta", 60), ("setenta", 70), ("ochenta", 80),
+    ("noventa", 90), ("cien", 100),
+];
+#[rustfmt::skip]
+const FRENCH_CARDINALS: &[(&str, u32)] = &[
+    ("zéro", 0), ("un", 1), ("une", 1), ("deux", 2), ("trois", 3), ("quatre", 4), ("cinq", 5),
+    ("six", 6), ("sept", 7), ("huit", 8), ("neuf", 9), ("dix", 10), ("onze", 11), ("douze", 12),
+    ("treize", 13), ("quatorze", 14), ("quinze", 15), ("seize", 16), ("dix-sept", 17),
+    ("dix-huit", 18), ("dix-neuf", 19), ("vingt", 20), ("trente", 30), ("quarante", 40),
+    ("cinquante", 50), ("soixante", 60), ("soixante-dix", 70), ("quatre-vingts", 80),
+    ("quatre-vingt-dix", 90), ("cent", 100),
+];
+#[rustfmt::skip]
+const GERMAN_CARDINALS: &[(&str, u32)] = &[
+    ("null", 0), ("eins", 1), ("zwei", 2), ("drei", 3), ("vier", 4), ("fünf", 5), ("sechs", 6),
+    ("sieben", 7), ("acht", 8), ("neun", 9), ("zehn", 10), ("elf", 11), ("zwölf", 12),
+    ("dreizehn", 13), ("vierzehn", 14), ("fünfzehn", 15), ("sechzehn", 16), ("siebzehn", 17),
+    ("achtzehn", 18), ("neunzehn", 19), ("zwanzig", 20), ("dreißig", 30), ("vierzig", 40),
+    ("fünfzig", 50), ("sechzig", 60), ("siebzig", 70), ("achtzig", 80), ("neunzig", 90),
+    ("hundert", 100),
+];
+
+/// English ordinal suffix for `n`, e.g. `"st"` for 1 and 21 but `"th"` for 11.
+fn english_ordinal_suffix(n: u32) -> &'static str {
+    match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+/// Replaces number words with numerals, e.g. `"two"` with `"2"` and `"second"` with `"2nd"`.
+///
+/// Street addresses, titles and product names are written both ways, and
+/// comparing `"Fifth Avenue"` against `"5th Avenue"` character by character
+/// scores poorly. Converting words to numerals makes both spellings identical.
+///
+/// Words are matched case-insensitively and ignoring surrounding punctuation,
+/// which is kept, as is the whitespace between words. Hyphenated English
+/// compounds like `"twenty-one"` are supported.
+///
+/// To go the other way and spell numerals out as words, see [spelled_out](NumberWordNormalizer::spelled_out).
+///
+/// ```
+/// # use fuzzywuzzy::normalization::{Normalizer, NumberWordNormalizer, NumberLanguage};
+/// let english = NumberWordNormalizer::english();
+/// assert_eq!(english.normalize("Fifth Avenue"), "5th Avenue");
+/// assert_eq!(english.normalize("The Twenty-First Century"), "The 21st Century");
+/// assert_eq!(english.normalize("Ocean's Eleven (2001)"), "Ocean's 11 (2001)");
+/// assert_eq!(english.normalize("  one\ttwo  "), "  1\t2  ");
+/// let many = NumberWordNormalizer::with(&[NumberLanguage::English, NumberLanguage::Spanish]);
+/// assert_eq!(many.normalize("dos or three"), "2 or 3");
+/// ```
+pub struct NumberWordNormalizer {
+    words: HashMap<String, String>,
+    numerals: HashMap<String, String>,
+    spell_out: bool,
+}
+
+impl NumberWordNormalizer {
+    /// Understands number words in each of the `languages`.
+    pub fn with(languages: &[NumberLanguage]) -> NumberWordNormalizer {
+        let mut words = HashMap::new();
+        let mut numerals = HashMap::new();
+        let mut pairs = Vec::new();
+        for language in languages {
+            let cardinals = match language {
+                NumberLanguage::English => {
+                    NumberWordNormalizer::add_english(&mut pairs);
+                    &[][..]
+                }
+                NumberLanguage::Spanish => SPANISH_CARDINALS,
+                NumberLanguage::French => FRENCH_CARDINALS,
+                NumberLanguage::German => GERMAN_CARDINALS,
+            };
+            for (word, n) in cardinals {
+                pairs.push((word.to_string(), n.to_string()));
+            }
+            // Earlier languages take precedence when spelling numerals out.
+            for (word, numeral) in pairs.drain(..) {
+                numerals
+                    .entry(numeral.clone())
+                    .or_insert_with(|| word.clone());
+                words.insert(word, numeral);
+            }
+        }
+        NumberWordNormalizer {
+            words,
+            numerals,
+            spell_out: false,
+        }
+    }
+
+    /// Understands English number words.
+    pub fn english() -> NumberWordNormalizer {
+        NumberWordNormalizer::with(&[NumberLanguage::English])
+    }
+
+    /// Replaces numerals with number words instead, e.g. `"2"` with `"two"` and `"2nd"` with `"second"`.
+    ///
+    /// Numerals are spelled out in the first of the languages which has a word
+    /// for them, in lower case.
+    ///
+    /// ```
+    /// # use fuzzywuzzy::normalization::{Normalizer, NumberWordNormalizer, NumberLanguage};
+    /// let english = NumberWordNormalizer::english().spelled_out();
+    /// assert_eq!(english.normalize("5th Avenue"), "fifth Avenue");
+    /// assert_eq!(english.normalize("Ocean's 11 (2001)"), "Ocean's eleven (2001)");
+    /// let many = NumberWordNormalizer::with(&[NumberLanguage::Spanish, NumberLanguage::English])
+    ///     .spelled_out();
+    /// assert_eq!(many.normalize("2 or 3rd"), "dos or third");
+    /// ```
+    pub fn spelled_out(mut self) -> NumberWordNormalizer {
+        self.spell_out = true;
+        self
+    }
+
+    fn add_english(words: &mut Vec<(String, String)>) {
+        for n in 0..ENGLISH_UNITS.len() as u32 {
+            let (cardinal, ordinal) =
+                (ENGLISH_UNITS[n as usize], ENGLISH_ORDINAL_UNITS[n as usize]);
+            words.push((cardinal.to_string(), n.to_string()));
+            words.push((
+                ordinal.to_string(),
+                format!("{}{}", n, english_ordinal_suffix(n)),
+            ));
+        }
+        for tens in 2..ENGLISH_TENS.len() as u32 {
+            let n = tens * 10;
+            let (cardinal, ordinal) = (
+                ENGLISH_TENS[tens as usize],
+                ENGLISH_ORDINAL_TENS[tens as usize],
+            );
+            words.push((cardinal.to_string(), n.to_string()));
+            words.push((
+                ordinal.to_string(),
+                format!("{}{}", n, english_ordinal_suffix(n)),
+            ));
+            if n == 100 {
+                continue;
+            }
+            for unit in 1..10 {
+                let m = n + unit;
+                let unit = unit as usize;
+                words.push((
+                    format!("{}-{}", cardinal, ENGLISH_UNITS[unit]),
+                    m.to_string(),
+                ));
+                words.push((
+                    format!("{}-{}", cardinal, ENGLISH_ORDINAL_UNITS[unit]),
+                    format!("{}{}", m, english_ordinal_suffix(m)),
+                ));
+            }
+        }
+    }
+
+    fn replace<'a>(&'a self, word: &'a str) -> Cow<'a, str> {
+        let bare = word.trim_matches(|c: char| !c.is_alphanumeric());
+        let table = if self.spell_out {
+            &self.numerals
+        } else {
+            &self.words
+        };
+        match table.get(&bare.to_lowercase()) {
+            Some(numeral) => {
+                let start = word.find(bare).unwrap_or(0);
+                let end = start + bare.len();
+                Cow::Owned(format!("{}{}{}", &word[..start], numeral, &word[end..]))
+            }
+            None => Cow::Borrowed(word),
+        }
+    }
+}
+
+impl Normalizer for NumberWordNormalizer {
+    fn normalize(&self, s: &str) -> String {
+        let mut result = String::with_capacity(s.len());
+        let mut rest = s;
+        while !rest.is_empty() {
+            let word_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let space_end = rest[word_end..]
+                .find(|c: char| !c.is_whitespace())
+                .map_or(rest.len(), |i| word_end + i);
+            result.push_str(&self.replace(&rest[..word_end]));
+            result.push_str(&rest[word_end..space_end]);
+            rest = &rest[space_end..];
+        }
+        result
+    }
+}
+
+/// Common leet-speak substitutions, for use with [LeetSpeakNormalizer].
+#[rustfmt::skip]
+pub const LEET_SPEAK: &[(char, &str)] = &[
+    ('@', "a"), ('4', "a"), ('8', "b"), ('(', "c"), ('3', "e"), ('€', "e"), ('6', "g"),
+    ('#', "h"), ('!', "i"), ('1', "l"), ('|', "l"), ('0', "o"), ('5', "s"), ('$', "s"),
+    ('7', "t"), ('+', "t"), ('2', "z"),
+];
+
+/// Undoes character substitutions used to obfuscate words, e.g. `"p@$$w0rd"` to `"password"`.
+///
+/// Useful for content moderation or brand protection, where strings are
+/// deliberately disguised to evade exact matching. Only words containing at
+/// least one letter are rewritten, so plain numbers like `"2001"` survive.
+///
+/// Run this before [full_process](crate::utils::full_process), which would
+/// otherwise replace symbols like `@` and `$` with spaces.
+///
+/// ```
+/// # use fuzzywuzzy::normalization::{Normalizer, LeetSpeakNormalizer};
+/// # use fuzzywuzzy::processing::{Pipeline, Processor};
+/// let leet = LeetSpeakNormalizer::common();
+/// assert_eq!(leet.normalize("p@$$w0rd"), "password");
+/// assert_eq!(leet.normalize("fr33 r0l3x in 2024"), "free rolex in 2024");
+/// let custom = LeetSpeakNormalizer::with(vec![('0', "o"), ('1', "i")]);
+/// assert_eq!(custom.normalize("m1cr0soft"), "microsoft");
+/// let pipeline = Pipeline::new().normalizer(leet).replace_non_alphanumeric().lowercase().trim();
+/// assert_eq!(pipeline.process("Fr€€ R0L3X ch3@p", false), "free rolex cheap");
+/// ```
+pub struct LeetSpeakNormalizer {
+    substitutions: HashMap<char, String>,
+}
+
+impl LeetSpeakNormalizer {
+    /// Replaces each character with its substitute in `substitutions`.
+    pub fn with<I, S>(substitutions: I) -> LeetSpeakNormalizer
+    where
+        I: IntoIterator<Item = (char, S)>,
+        S: Into<String>,
+    {
+        LeetSpeakNormalizer {
+            substitutions: substitutions
+                .into_iter()
+                .map(|(c, replacement)| (c, replacement.into()))
+                .collect(),
+        }
+    }
+
+    /// Replaces the substitutions in [LEET_SPEAK].
+    pub fn common() -> LeetSpeakNormalizer {
+        LeetSpeakNormalizer::with(LEET_SPEAK.iter().copied())
+    }
+
+    fn deobfuscate<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        if !word.chars().any(char::is_alphabetic) {
+            return Cow::Borrowed(word);
+        }
+        let mut plain = String::with_capacity(word.len());
+        for c in word.chars() {
+            match self.substitutions.get(&c) {
+                Some(replacement) => plain.push_str(replacement),
+                None => plain.push(c),
+            }
+        }
+        Cow::Owned(plain)
+    }
+}
+
+impl Normalizer for LeetSpeakNormalizer {
+    fn normalize(&self, s: &str) -> String {
+        s.split_whitespace()
+            .map(|word| self.deobfuscate(word))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Performs Unicode full default case folding. Requires feature "case-folding".
+///
+/// Unlike [LowerCaseNormalizer], folding maps characters whose lowercase forms
+/// differ but which are considered caseless-equal to the same string, e.g.
+/// `ß` and `ẞ` fold to `ss`, and the Greek final sigma `ς` folds to `σ`.
+///
+/// Folding is locale-independent, so the Turkish capital dotted `İ` becomes `i`
+/// followed by U+0307 COMBINING DOT ABOVE. For Turkish text, use [LocaleLowerCaseNormalizer].
+///
+/// This just delegates to [caseless::default_case_fold_str].
+///
+/// ```
+/// # use fuzzywuzzy::normalization::{Normalizer, LowerCaseNormalizer, CaseFoldNormalizer};
+/// assert_ne!(LowerCaseNormalizer.normalize("STRASSE"), LowerCaseNormalizer.normalize("Straße"));
+/// assert_eq!(CaseFoldNormalizer.normalize("STRASSE"), CaseFoldNormalizer.normalize("Straße"));
+/// assert_eq!(CaseFoldNormalizer.normalize("ΣΊΣΥΦΟΣ"), CaseFoldNormalizer.normalize("σίσυφος"));
+/// assert_eq!(CaseFoldNormalizer.normalize("İ"), "i\u{0307}");
+/// ```
+#[cfg(feature = "case-folding")]
+pub struct CaseFoldNormalizer;
+
+#[cfg(feature = "case-folding")]
+impl Normalizer for CaseFoldNormalizer {
+    fn normalize(&self, s: &str) -> String {
+        caseless::default_case_fold_str(s)
+    }
+}
+
+#[cfg(feature = "normalization")]
 pub use self::unicode_normalizers::*;
 
-#[cfg(feature = "segmentation")]
+#[cfg(feature = "normalization")]
 mod unicode_normalizers {
     use super::Normalizer;
     use unicode_normalization::UnicodeNormalization;
@@ -232,4 +867,77 @@ mod unicode_normalizers {
             s.nfd().filter(char::is_ascii).collect()
         }
     }
+
+    /// Removes diacritics, transliterating accented letters to their base letter. Requires default feature "normalization".
+    ///
+    /// Strings are decomposed, combining diacritical marks are dropped and the rest is recomposed.
+    /// A handful of Latin letters which have no decomposition, like `ø`, `ł` or `æ`,
+    /// are transliterated to their conventional ASCII spelling. Everything else,
+    /// including non-Latin scripts, is left untouched.
+    ///
+    /// Combined with `force_ascii` in [full_process_normalized](crate::utils::full_process_normalized),
+    /// this keeps accented words intact rather than deleting their accented letters.
+    ///
+    /// ```
+    /// # use fuzzywuzzy::normalization::{Normalizer, StripDiacriticsNormalizer};
+    /// # use fuzzywuzzy::utils::{full_process, full_process_normalized};
+    /// assert_eq!(StripDiacriticsNormalizer.normalize("Ça va"), "Ca va");
+    /// assert_eq!(StripDiacriticsNormalizer.normalize("Łódź"), "Lodz");
+    /// assert_eq!(StripDiacriticsNormalizer.normalize("Ærøskøbing"), "AEroskobing");
+    /// assert_eq!(StripDiacriticsNormalizer.normalize("किमपि"), "किमपि");
+    /// assert_eq!(full_process("Ça va?", true), "a va");
+    /// assert_eq!(full_process_normalized("Ça va?", true, &StripDiacriticsNormalizer), "ca va");
+    /// ```
+    pub struct StripDiacriticsNormalizer;
+    impl Normalizer for StripDiacriticsNormalizer {
+        fn normalize(&self, s: &str) -> String {
+            let mut stripped = String::with_capacity(s.len());
+            for c in s.nfd().filter(|c| !is_diacritic(*c)) {
+                match transliterate_latin(c) {
+                    Some(replacement) => stripped.push_str(replacement),
+                    None => stripped.push(c),
+                }
+            }
+            stripped.nfc().collect()
+        }
+    }
+
+    /// Whether `c` is in one of the Combining Diacritical Marks blocks.
+    ///
+    /// Combining marks in general also cover e.g. Devanagari vowel signs, which
+    /// are letters in their own right rather than accents and must be kept.
+    fn is_diacritic(c: char) -> bool {
+        matches!(c,
+            '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}')
+    }
+
+    /// Latin letters without a canonical decomposition into a base letter and marks.
+    fn transliterate_latin(c: char) -> Option<&'static str> {
+        Some(match c {
+            'ø' => "o",
+            'Ø' => "O",
+            'ł' => "l",
+            'Ł' => "L",
+            'đ' => "d",
+            'Đ' => "D",
+            'ħ' => "h",
+            'Ħ' => "H",
+            'ı' => "i",
+            'æ' => "ae",
+            'Æ' => "AE",
+            'œ' => "oe",
+            'Œ' => "OE",
+            'ß' => "ss",
+            'ẞ' => "SS",
+            'þ' => "th",
+            'Þ' => "TH",
+            'ð' => "d",
+            'Ð' => "D",
+            _ => return None,
+        })
+    }
 }
@@ -0,0 +1,214 @@
+//! Evaluating OCR output against ground truth.
+//!
+//! [ocr_report] aligns the output with the ground truth character by
+//! character and lists each error, and computes the character and word
+//! error rates: the [Levenshtein distance](crate::primitives::levenshtein)
+//! between the texts' characters, or words, relative to the length of the
+//! ground truth.
+//!
+//! ```
+//! # use fuzzywuzzy::ocr::{ocr_report, OcrError};
+//! # use fuzzywuzzy::primitives::EditKind;
+//! let report = ocr_report("Tbe quick brwn fox", "The quick brown fox");
+//! assert_eq!(report.errors, vec![
+//!     OcrError { kind: EditKind::Replace, position: 1, output_position: 1, expected: Some('h'), found: Some('b') },
+//!     OcrError { kind: EditKind::Delete, position: 12, output_position: 12, expected: Some('o'), found: None },
+//! ]);
+//! assert_eq!(report.cer(), 2.0 / 19.0);
+//! assert_eq!(report.wer(), 0.5);
+//! ```
+
+use crate::error::Error;
+use crate::primitives::{self, EditKind, EditOp};
+use crate::utils::Rounding;
+
+/// One character the OCR output got wrong.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OcrError {
+    /// [Replace](EditKind::Replace) for a substitution, [Insert](EditKind::Insert)
+    /// for a character only in the output and [Delete](EditKind::Delete) for
+    /// one missing from it.
+    pub kind: EditKind,
+    /// The character index in the ground truth, where an inserted character
+    /// would go for insertions.
+    pub position: usize,
+    /// The character index in the output, where a missing character would go
+    /// for deletions.
+    pub output_position: usize,
+    /// The ground truth's character, except for insertions.
+    pub expected: Option<char>,
+    /// The output's character, except for deletions.
+    pub found: Option<char>,
+}
+
+/// The result of [ocr_report].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OcrReport {
+    /// The character errors in order.
+    pub errors: Vec<OcrError>,
+    /// The number of characters of the ground truth.
+    pub truth_chars: usize,
+    /// The number of word insertions, deletions and substitutions.
+    pub word_errors: usize,
+    /// The number of whitespace separated words of the ground truth.
+    pub truth_words: usize,
+}
+
+/// `errors` relative to `len`, or if `len` is 0, 0 without errors and 1 with some.
+fn rate(errors: usize, len: usize) -> f64 {
+    match len {
+        0 => (errors > 0) as u8 as f64,
+        _ => errors as f64 / len as f64,
+    }
+}
+
+impl OcrReport {
+    /// The character error rate.
+    pub fn cer(&self) -> f64 {
+        rate(self.errors.len(), self.truth_chars)
+    }
+
+    /// The word error rate.
+    pub fn wer(&self) -> f64 {
+        rate(self.word_errors, self.truth_words)
+    }
+}
+
+/// Compares OCR `output` with the `truth`. See the [module](self) docs.
+///
+/// Alignment takes time and memory proportional to the product of the
+/// texts' lengths, so long documents are best compared line by line.
+pub fn ocr_report(output: &str, truth: &str) -> OcrReport {
+    let (output_chars, truth_chars): (Vec<char>, Vec<char>) =
+        (output.chars().collect(), truth.chars().collect());
+    let ops = primitives::levenshtein_editops(&truth_chars, &output_chars);
+    report(output, truth, ops)
+}
+
+/// Like [ocr_report], but aligning with the engine's `confidences` in each
+/// character of `output`, as [confidence_editops](primitives::confidence_editops) does, so substitutions
+/// are attributed to the characters it was least sure of.
+///
+/// # Panics
+///
+/// Panics if there isn't a confidence for each character of `output`, or a
+/// confidence isn't between 0.0 and 1.0, see [try_ocr_report_with_confidences].
+///
+/// ```
+/// # use fuzzywuzzy::ocr::{ocr_report, ocr_report_with_confidences, OcrReport};
+/// let (output, truth) = ("rnodern", "modern");
+/// let errors = |report: OcrReport| -> Vec<(Option<char>, Option<char>)> {
+///     report.errors.iter().map(|error| (error.expected, error.found)).collect()
+/// };
+/// assert_eq!(errors(ocr_report(output, truth)), vec![(None, Some('r')), (Some('m'), Some('n'))]);
+/// // The engine was least sure of the "r", so that's the misread "m".
+/// let confidences = [0.2, 0.3, 0.9, 0.9, 0.9, 0.9, 0.9];
+/// assert_eq!(
+///     errors(ocr_report_with_confidences(output, &confidences, truth)),
+///     vec![(Some('m'), Some('r')), (None, Some('n'))]
+/// );
+/// ```
+pub fn ocr_report_with_confidences(output: &str, confidences: &[f64], truth: &str) -> OcrReport {
+    try_ocr_report_with_confidences(output, confidences, truth)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Like [ocr_report_with_confidences], but returns the [Error] of
+/// [try_confidence_editops](primitives::try_confidence_editops) instead of
+/// panicking if the confidences are invalid.
+pub fn try_ocr_report_with_confidences(
+    output: &str,
+    confidences: &[f64],
+    truth: &str,
+) -> Result<OcrReport, Error> {
+    let (output_chars, truth_chars): (Vec<char>, Vec<char>) =
+        (output.chars().collect(), truth.chars().collect());
+    let (_, ops) = primitives::try_confidence_editops(&output_chars, &truth_chars, confidences)?;
+    // The edits turn the output into the truth; report them the other way.
+    let ops = ops
+        .into_iter()
+        .map(|op| EditOp {
+            kind: match op.kind {
+                EditKind::Insert => EditKind::Delete,
+                EditKind::Delete => EditKind::Insert,
+                EditKind::Replace => EditKind::Replace,
+            },
+            a: op.b,
+            b: op.a,
+        })
+        .collect();
+    Ok(report(output, truth, ops))
+}
+
+/// Scores OCR `output` against a candidate `truth` between 0 and 100, by the
+/// cost of the edits [confidence_editops](primitives::confidence_editops) finds relative to the length of
+/// the longer text, so misreadings of characters the engine wasn't sure of
+/// count less.
+///
+/// # Panics
+///
+/// Panics if there isn't a confidence for each character of `output`, or a
+/// confidence isn't between 0.0 and 1.0, see [try_confidence_ratio].
+///
+/// ```
+/// # use fuzzywuzzy::ocr::confidence_ratio;
+/// assert_eq!(confidence_ratio("he1lo", &[1.0, 1.0, 0.2, 1.0, 1.0], "hello"), 96);
+/// assert_eq!(confidence_ratio("he1lo", &[1.0; 5], "hello"), 80);
+/// ```
+pub fn confidence_ratio(output: &str, confidences: &[f64], truth: &str) -> u8 {
+    try_confidence_ratio(output, confidences, truth).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Like [confidence_ratio], but returns the [Error] of
+/// [try_confidence_editops](primitives::try_confidence_editops) instead of
+/// panicking if the confidences are invalid.
+///
+/// ```
+/// # use fuzzywuzzy::ocr::try_confidence_ratio;
+/// # use fuzzywuzzy::error::Error;
+/// assert_eq!(try_confidence_ratio("he1lo", &[1.0; 5], "hello"), Ok(80));
+/// assert_eq!(try_confidence_ratio("he1lo", &[1.5; 5], "hello"),
+///            Err(Error::InvalidConfig("confidences must be in [0.0, 1.0]")));
+/// ```
+pub fn try_confidence_ratio(output: &str, confidences: &[f64], truth: &str) -> Result<u8, Error> {
+    let (output_chars, truth_chars): (Vec<char>, Vec<char>) =
+        (output.chars().collect(), truth.chars().collect());
+    let longest = output_chars.len().max(truth_chars.len());
+    let (cost, _) = primitives::try_confidence_editops(&output_chars, &truth_chars, confidences)?;
+    if longest == 0 {
+        return Ok(100);
+    }
+    Ok(Rounding::Python.round(100.0 * (1.0 - cost / longest as f64)))
+}
+
+/// An [OcrReport] of `ops` turning the characters of `truth` into `output`.
+fn report(output: &str, truth: &str, ops: Vec<EditOp>) -> OcrReport {
+    let (output_chars, truth_chars): (Vec<char>, Vec<char>) =
+        (output.chars().collect(), truth.chars().collect());
+    let errors = ops
+        .into_iter()
+        .map(|op| OcrError {
+            kind: op.kind,
+            position: op.a,
+            output_position: op.b,
+            expected: match op.kind {
+                EditKind::Insert => None,
+                _ => Some(truth_chars[op.a]),
+            },
+            found: match op.kind {
+                EditKind::Delete => None,
+                _ => Some(output_chars[op.b]),
+            },
+        })
+        .collect();
+    let (output_words, truth_words): (Vec<&str>, Vec<&str>) = (
+        output.split_whitespace().collect(),
+        truth.split_whitespace().collect(),
+    );
+    OcrReport {
+        errors,
+        truth_chars: truth_chars.len(),
+        word_errors: primitives::levenshtein(&truth_words, &output_words),
+        truth_words: truth_words.len(),
+    }
+}
@@ -0,0 +1,283 @@
+//! Finding near-duplicate passages shared by two documents, for plagiarism
+//! and duplication reports.
+//!
+//! Documents are compared word by word, ignoring case and punctuation.
+//! Runs of words, or shingles, which occur in both documents seed the
+//! search, so passages are found wherever they are in either document.
+//! Seeds close to each other in both documents are chained into passages,
+//! bridging small edits, passages are extended over matching words on
+//! either side, and each passage is scored like
+//! [ratio](crate::fuzz::ratio), but over words, with
+//! [matching blocks](crate::primitives::get_matching_blocks).
+//!
+//! ```
+//! # use fuzzywuzzy::passages::near_duplicate_passages;
+//! let original = "It was a bright cold day in April, and the clocks were striking thirteen. \
+//!                 Winston Smith slipped quickly through the glass doors of Victory Mansions.";
+//! let copy = "As the story begins, it was a bright cold day in April and the clocks \
+//!             were all striking thirteen. Nothing else is borrowed.";
+//! let passages = near_duplicate_passages(original, copy);
+//! assert_eq!(passages.len(), 1);
+//! assert_eq!(&original[passages[0].a.clone()], "It was a bright cold day in April, and the clocks were striking thirteen.");
+//! assert_eq!(&copy[passages[0].b.clone()], "it was a bright cold day in April and the clocks \
+//!             were all striking thirteen.");
+//! assert_eq!(passages[0].similarity, 97);
+//! ```
+
+use crate::primitives;
+use crate::utils::{self, Rounding};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// A passage of one document which nearly duplicates one of another.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Passage {
+    /// The byte range of the passage in the first document.
+    pub a: Range<usize>,
+    /// The byte range of the passage in the second document.
+    pub b: Range<usize>,
+    /// How similar the passages' words are, from 0 to 100.
+    pub similarity: u8,
+}
+
+/// A processed word and its byte range in the document.
+struct Word {
+    text: String,
+    span: Range<usize>,
+}
+
+fn words(document: &str) -> Vec<Word> {
+    let start = document.as_ptr() as usize;
+    document
+        .split_whitespace()
+        .filter_map(|word| {
+            let text = utils::full_process(word, false).replace(' ', "");
+            let offset = word.as_ptr() as usize - start;
+            if text.is_empty() {
+                None
+            } else {
+                Some(Word {
+                    text,
+                    span: offset..offset + word.len(),
+                })
+            }
+        })
+        .collect()
+}
+
+/// Extends a passage ending before `a[i]` and `b[j]` while the words match,
+/// skipping single inserted, deleted or substituted words if the two words
+/// after them match, and returns its new end.
+fn extend(a: &[&str], b: &[&str], mut i: usize, mut j: usize) -> (usize, usize) {
+    let matches = |x: usize, y: usize| x < a.len() && y < b.len() && a[x] == b[y];
+    loop {
+        if matches(i, j) {
+            i += 1;
+            j += 1;
+            continue;
+        }
+        let skip = [(i, j + 1), (i + 1, j), (i + 1, j + 1)]
+            .iter()
+            .copied()
+            .find(|&(x, y)| matches(x, y) && matches(x + 1, y + 1));
+        match skip {
+            Some((x, y)) => {
+                i = x;
+                j = y;
+            }
+            None => return (i, j),
+        }
+    }
+}
+
+/// A chain of shared shingles, as word indices.
+struct Chain {
+    a_start: usize,
+    b_start: usize,
+    a_last: usize,
+    b_last: usize,
+}
+
+/// Finds near-duplicate passages. See the [module](self) docs.
+///
+/// By default shingles are 5 words long, seeds up to 10 words apart in both
+/// documents are chained, and passages must be at least 80 similar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PassageFinder {
+    shingle_len: usize,
+    max_gap: usize,
+    threshold: u8,
+}
+
+impl Default for PassageFinder {
+    fn default() -> PassageFinder {
+        PassageFinder::new()
+    }
+}
+
+impl PassageFinder {
+    /// A finder with the default configuration.
+    pub fn new() -> PassageFinder {
+        PassageFinder {
+            shingle_len: 5,
+            max_gap: 10,
+            threshold: 80,
+        }
+    }
+
+    /// The number of consecutive words which must occur in both documents
+    /// to seed a passage, which is also the shortest passage found.
+    ///
+    /// # Panics
+    /// If `shingle_len` is 0.
+    pub fn shingle_len(mut self, shingle_len: usize) -> PassageFinder {
+        assert!(shingle_len > 0, "shingle_len must be greater than 0");
+        self.shingle_len = shingle_len;
+        self
+    }
+
+    /// How many words apart, in either document, seeds may be to belong to
+    /// the same passage.
+    pub fn max_gap(mut self, max_gap: usize) -> PassageFinder {
+        self.max_gap = max_gap;
+        self
+    }
+
+    /// The least similarity of a passage.
+    pub fn threshold(mut self, threshold: u8) -> PassageFinder {
+        self.threshold = threshold;
+        self
+    }
+
+    /// The passages of `a` and `b` which nearly duplicate each other, in
+    /// order of their position in `a`. Passages within another are dropped.
+    pub fn find(&self, a: &str, b: &str) -> Vec<Passage> {
+        let k = self.shingle_len;
+        let (a_words, b_words) = (words(a), words(b));
+        if a_words.len() < k || b_words.len() < k {
+            return vec![];
+        }
+        let a_text: Vec<&str> = a_words.iter().map(|w| w.text.as_str()).collect();
+        let b_text: Vec<&str> = b_words.iter().map(|w| w.text.as_str()).collect();
+        let mut shingles: HashMap<&[&str], Vec<usize>> = HashMap::new();
+        for (j, shingle) in b_text.windows(k).enumerate() {
+            shingles.entry(shingle).or_default().push(j);
+        }
+
+        let mut chains: Vec<Chain> = vec![];
+        // The chains which later seeds may still extend.
+        let mut open: Vec<usize> = vec![];
+        for (i, shingle) in a_text.windows(k).enumerate() {
+            open.retain(|&c| chains[c].a_last + self.max_gap >= i);
+            for &j in shingles.get(shingle).map_or(&[][..], Vec::as_slice) {
+                let extends = open.iter().copied().find(|&c| {
+                    let chain = &chains[c];
+                    chain.a_last < i && chain.b_last < j && j <= chain.b_last + self.max_gap
+                });
+                match extends {
+                    Some(c) => {
+                        chains[c].a_last = i;
+                        chains[c].b_last = j;
+                    }
+                    None => {
+                        open.push(chains.len());
+                        chains.push(Chain {
+                            a_start: i,
+                            b_start: j,
+                            a_last: i,
+                            b_last: j,
+                        });
+                    }
+                }
+            }
+        }
+
+        let (a_reversed, b_reversed): (Vec<&str>, Vec<&str>) = (
+            a_text.iter().rev().copied().collect(),
+            b_text.iter().rev().copied().collect(),
+        );
+        let mut passages: Vec<(Range<usize>, Range<usize>, u8)> = vec![];
+        for chain in chains.iter() {
+            let (a_end, b_end) = extend(&a_text, &b_text, chain.a_last + k, chain.b_last + k);
+            let (a_before, b_before) = extend(
+                &a_reversed,
+                &b_reversed,
+                a_text.len() - chain.a_start,
+                b_text.len() - chain.b_start,
+            );
+            let (a_words_range, b_words_range) = (
+                a_text.len() - a_before..a_end,
+                b_text.len() - b_before..b_end,
+            );
+            let (x, y) = (
+                &a_text[a_words_range.clone()],
+                &b_text[b_words_range.clone()],
+            );
+            let matched: usize = primitives::get_matching_blocks(x, y)
+                .iter()
+                .map(|&(_, _, len)| len)
+                .sum();
+            let similarity =
+                Rounding::Python.round(200.0 * matched as f64 / (x.len() + y.len()) as f64);
+            if similarity >= self.threshold {
+                passages.push((a_words_range, b_words_range, similarity));
+            }
+        }
+        // Longest first, so passages within others can be dropped.
+        passages.sort_by_key(|(a, b, _)| (a.start, std::cmp::Reverse(a.end), b.start));
+        let mut kept: Vec<(Range<usize>, Range<usize>, u8)> = vec![];
+        for passage in passages {
+            let within = kept.iter().any(|(a, b, _)| {
+                a.start <= passage.0.start
+                    && passage.0.end <= a.end
+                    && b.start <= passage.1.start
+                    && passage.1.end <= b.end
+            });
+            if !within {
+                kept.push(passage);
+            }
+        }
+        kept.into_iter()
+            .map(|(a, b, similarity)| Passage {
+                a: a_words[a.start].span.start..a_words[a.end - 1].span.end,
+                b: b_words[b.start].span.start..b_words[b.end - 1].span.end,
+                similarity,
+            })
+            .collect()
+    }
+}
+
+/// The near-duplicate passages of `a` and `b` found by the default
+/// [PassageFinder].
+pub fn near_duplicate_passages(a: &str, b: &str) -> Vec<Passage> {
+    PassageFinder::new().find(a, b)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reordered_passages_are_found() {
+        let first = "one two three four five six";
+        let second = "alpha beta gamma delta epsilon zeta";
+        let a = format!("{} filler words here. {}", first, second);
+        let b = format!("{}! other text entirely, {}", second, first);
+        let passages = near_duplicate_passages(&a, &b);
+        let spans: Vec<(&str, &str, u8)> = passages
+            .iter()
+            .map(|p| (&a[p.a.clone()], &b[p.b.clone()], p.similarity))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (first, first, 100),
+                (second, "alpha beta gamma delta epsilon zeta!", 100)
+            ]
+        );
+        assert!(near_duplicate_passages(first, second).is_empty());
+        assert!(near_duplicate_passages("one two", "one two").is_empty());
+        let short = PassageFinder::new().shingle_len(2);
+        assert_eq!(short.find("one two", "one two").len(), 1);
+    }
+}
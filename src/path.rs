@@ -0,0 +1,176 @@
+//! Scoring [OsStr]s and [Path](std::path::Path)s, e.g. for fuzzy file finders.
+//!
+//! File names aren't necessarily valid UTF-8. Before scoring, each maximal
+//! invalid byte sequence is replaced with a single U+FFFD REPLACEMENT
+//! CHARACTER, as [OsStr::to_string_lossy] does. Valid parts of a name score
+//! exactly as they would as `&str`, and an invalid sequence counts as one
+//! character which matches any other invalid sequence. To compare the raw
+//! bytes instead, see [ratio_bytes](crate::fuzz::ratio_bytes).
+//!
+//! [PathRatio] compares paths component-aware, weighting file names higher
+//! than the directories they're in.
+//!
+//! ```
+//! # use fuzzywuzzy::path::{partial_ratio_os, ratio_os};
+//! use std::path::Path;
+//! assert_eq!(ratio_os(Path::new("src/main.rs"), Path::new("src/main.rs")), 100);
+//! assert_eq!(partial_ratio_os(Path::new("main"), Path::new("src/main.rs")), 100);
+//! ```
+//!
+//! ```
+//! # #[cfg(unix)] {
+//! # use fuzzywuzzy::path::ratio_os;
+//! use std::ffi::OsStr;
+//! use std::os::unix::ffi::OsStrExt;
+//! // "caf\xe9" is Latin-1, not UTF-8.
+//! let latin1 = OsStr::from_bytes(b"caf\xe9.txt");
+//! assert_eq!(ratio_os(latin1, "caf\u{FFFD}.txt"), 100);
+//! assert_eq!(ratio_os(latin1, "café.txt"), 88);
+//! # }
+//! ```
+
+use crate::fuzz::{self, Scorer};
+use crate::score::Score;
+use crate::utils::Rounding;
+use std::ffi::OsStr;
+
+/// Scores `a` against `b` with `scorer`, after lossily converting both to strings.
+///
+/// ```
+/// # use fuzzywuzzy::path::score_os;
+/// # use fuzzywuzzy::fuzz::TokenSortRatio;
+/// use std::path::Path;
+/// assert_eq!(score_os(&TokenSortRatio, Path::new("docs/user guide.md"), Path::new("guide user")), 71);
+/// ```
+pub fn score_os<S: Scorer + ?Sized>(
+    scorer: &S,
+    a: impl AsRef<OsStr>,
+    b: impl AsRef<OsStr>,
+) -> Score {
+    scorer.score(&a.as_ref().to_string_lossy(), &b.as_ref().to_string_lossy())
+}
+
+/// [fuzz::ratio] for [OsStr]s and [Path](std::path::Path)s.
+pub fn ratio_os(a: impl AsRef<OsStr>, b: impl AsRef<OsStr>) -> u8 {
+    fuzz::ratio(a.as_ref().to_string_lossy(), b.as_ref().to_string_lossy())
+}
+
+/// [fuzz::partial_ratio] for [OsStr]s and [Path](std::path::Path)s.
+pub fn partial_ratio_os(a: impl AsRef<OsStr>, b: impl AsRef<OsStr>) -> u8 {
+    fuzz::partial_ratio(a.as_ref().to_string_lossy(), b.as_ref().to_string_lossy())
+}
+
+/// Compares file paths component-aware, e.g. for fuzzy file finders or for
+/// spotting the same file downloaded to different places.
+///
+/// Both `/` and `\` separate components, repeated separators and a leading
+/// `./` are ignored, and the basename, after the last separator, is scored
+/// separately from the directory before it. The basename's
+/// [ratio](fuzz::ratio) makes up [PathRatio::basename_weight] of the score
+/// and the directory's the rest, unless neither path has a directory.
+///
+/// By default case is ignored, extensions are kept and the basename weighs 0.75.
+///
+/// ```
+/// # use fuzzywuzzy::path::PathRatio;
+/// # use fuzzywuzzy::fuzz::Scorer;
+/// let paths = PathRatio::new();
+/// assert_eq!(paths.score(r"src\Parser.rs", "./src//parser.rs").percent(), 100);
+/// assert_eq!(paths.score("docs/readme.md", "src/readme.md").percent(), 82);
+/// assert_eq!(paths.score("docs/readme.md", "docs/index.md").percent(), 69);
+///
+/// let ignoring_extensions = PathRatio::new().ignore_extension(true);
+/// assert_eq!(ignoring_extensions.score("Downloads/Report.pdf", "downloads/report.docx").percent(), 100);
+/// assert_eq!(ignoring_extensions.score("home/.profile", "home/.bashrc").percent(), 45);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PathRatio {
+    ignore_case: bool,
+    ignore_extension: bool,
+    basename_weight: f64,
+}
+
+impl Default for PathRatio {
+    fn default() -> PathRatio {
+        PathRatio::new()
+    }
+}
+
+impl PathRatio {
+    /// A path scorer with the default configuration.
+    pub fn new() -> PathRatio {
+        PathRatio {
+            ignore_case: true,
+            ignore_extension: false,
+            basename_weight: 0.75,
+        }
+    }
+
+    /// Whether to compare paths case-insensitively.
+    pub fn ignore_case(mut self, ignore_case: bool) -> PathRatio {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    /// Whether to drop the basename's extension, from its last `.`, before
+    /// comparing. A leading `.`, as in `.profile`, doesn't start an extension.
+    pub fn ignore_extension(mut self, ignore_extension: bool) -> PathRatio {
+        self.ignore_extension = ignore_extension;
+        self
+    }
+
+    /// The share of the score which comes from the basenames.
+    ///
+    /// # Panics
+    /// If `basename_weight` isn't between 0 and 1.
+    pub fn basename_weight(mut self, basename_weight: f64) -> PathRatio {
+        assert!(
+            (0.0..=1.0).contains(&basename_weight),
+            "basename_weight must be between 0 and 1"
+        );
+        self.basename_weight = basename_weight;
+        self
+    }
+
+    /// Scores two paths which aren't necessarily valid UTF-8.
+    pub fn score_os(&self, a: impl AsRef<OsStr>, b: impl AsRef<OsStr>) -> Score {
+        self.score(&a.as_ref().to_string_lossy(), &b.as_ref().to_string_lossy())
+    }
+
+    /// Splits `path` into its normalized directory and basename.
+    fn split(&self, path: &str) -> (String, String) {
+        let path = if self.ignore_case {
+            path.to_lowercase()
+        } else {
+            path.to_string()
+        };
+        let components: Vec<&str> = path
+            .split(['/', '\\'])
+            .filter(|component| !component.is_empty())
+            .skip_while(|&component| component == ".")
+            .collect();
+        let (basename, directory) = match components.split_last() {
+            Some((basename, directory)) => (*basename, directory.join("/")),
+            None => ("", String::new()),
+        };
+        let basename = match basename.rfind('.') {
+            Some(dot) if self.ignore_extension && dot > 0 => &basename[..dot],
+            _ => basename,
+        };
+        (directory, basename.to_string())
+    }
+}
+
+impl Scorer for PathRatio {
+    fn score(&self, a: &str, b: &str) -> Score {
+        let ((a_directory, a_basename), (b_directory, b_basename)) = (self.split(a), self.split(b));
+        let basename = fuzz::ratio(&a_basename, &b_basename) as f64;
+        let score = if a_directory.is_empty() && b_directory.is_empty() {
+            basename
+        } else {
+            let directory = fuzz::ratio(&a_directory, &b_directory) as f64;
+            self.basename_weight * basename + (1.0 - self.basename_weight) * directory
+        };
+        Score::new(Rounding::Python.round(score)).expect("weighted ratios are at most 100")
+    }
+}
@@ -0,0 +1,135 @@
+//! Patterns processed at compile time. Requires feature "macros".
+//!
+//! Matching many texts against a fixed literal processes the literal and
+//! builds its [search] tables again on every call.
+//! [fuzzy_pattern!] does that work when the program is compiled instead,
+//! and expands to a `&'static` [CompiledPattern].
+//!
+//! ```
+//! # use fuzzywuzzy::pattern::fuzzy_pattern;
+//! let timeout = fuzzy_pattern!("Connection Timed-Out");
+//! assert_eq!(timeout.processed(), "connection timed out");
+//! assert_eq!((timeout.char_len(), timeout.word_count()), (20, 3));
+//! assert_eq!(timeout.ratio("connection timed out!"), 100);
+//! let line = "warn: conection timed out after 30s";
+//! let matches = timeout.find_near_matches(line, 2);
+//! assert_eq!(matches[0].matched, "conection timed out");
+//! ```
+
+use crate::fuzz;
+use crate::search::{self, Masks, NearMatch};
+use crate::utils;
+
+/// Compiles a string literal into a `&'static` [CompiledPattern].
+///
+/// Anything other than a single string literal is a compile error.
+pub use fuzzywuzzy_macros::fuzzy_pattern;
+
+/// A literal processed by [fuzzy_pattern!].
+#[derive(Debug)]
+pub struct CompiledPattern {
+    literal: &'static str,
+    processed: &'static str,
+    chars: &'static [char],
+    word_count: usize,
+    masks: Option<Masks<'static>>,
+}
+
+impl CompiledPattern {
+    /// Used by the expansion of [fuzzy_pattern!], which guarantees that the
+    /// parts agree.
+    #[doc(hidden)]
+    pub const fn __from_parts(
+        literal: &'static str,
+        processed: &'static str,
+        chars: &'static [char],
+        word_count: usize,
+        masks: Option<Masks<'static>>,
+    ) -> CompiledPattern {
+        CompiledPattern {
+            literal,
+            processed,
+            chars,
+            word_count,
+            masks,
+        }
+    }
+
+    /// The literal as written.
+    pub fn literal(&self) -> &'static str {
+        self.literal
+    }
+
+    /// The literal processed by [full_process](utils::full_process).
+    pub fn processed(&self) -> &'static str {
+        self.processed
+    }
+
+    /// The number of characters of the processed literal.
+    pub fn char_len(&self) -> usize {
+        self.chars.len()
+    }
+
+    /// The number of words of the processed literal.
+    pub fn word_count(&self) -> usize {
+        self.word_count
+    }
+
+    /// The [ratio](fuzz::ratio) of the processed literal and `choice`
+    /// processed by [full_process](utils::full_process).
+    pub fn ratio(&self, choice: &str) -> u8 {
+        fuzz::ratio(self.processed, utils::full_process(choice, false))
+    }
+
+    /// The occurrences of the processed literal in `text`, as by
+    /// [search::find_near_matches]. Texts should be lower case to match.
+    pub fn find_near_matches<'a>(&self, text: &'a str, max_distance: usize) -> Vec<NearMatch<'a>> {
+        search::find_near_matches_with(self.chars, self.masks, text, max_distance)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn expansion_agrees_with_runtime_processing() {
+        let patterns = [
+            fuzzy_pattern!("  Hello,\tWörld! "),
+            fuzzy_pattern!(
+                "\u{130}stanbul \x41\
+                            BC"
+            ),
+            fuzzy_pattern!(r#"raw "quoted" \n"#),
+            fuzzy_pattern!(""),
+            fuzzy_pattern!("ΟΔΟΣ ΣΟΦΟΚΛΕΟΥΣ"),
+            fuzzy_pattern!("abcdefghij abcdefghij abcdefghij abcdefghij abcdefghij abcdefghij"),
+        ];
+        let literals = [
+            "  Hello,\tWörld! ",
+            "\u{130}stanbul ABC",
+            r#"raw "quoted" \n"#,
+            "",
+            "ΟΔΟΣ ΣΟΦΟΚΛΕΟΥΣ",
+            "abcdefghij abcdefghij abcdefghij abcdefghij abcdefghij abcdefghij",
+        ];
+        let text = "hello world, istanbul abc, raw quoted n, abcdefghij abcdefghij";
+        for (pattern, literal) in patterns.iter().zip(literals.iter()) {
+            let processed = utils::full_process(literal, false);
+            assert_eq!(pattern.literal(), *literal);
+            assert_eq!(pattern.processed(), processed);
+            assert_eq!(pattern.char_len(), processed.chars().count());
+            assert_eq!(pattern.word_count(), processed.split_whitespace().count());
+            assert_eq!(pattern.masks.is_some(), pattern.char_len() <= 64);
+            for k in 0..4 {
+                assert_eq!(
+                    pattern.find_near_matches(text, k),
+                    search::find_near_matches(&processed, text, k),
+                    "{:?} {}",
+                    literal,
+                    k
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Matching phone numbers.
+//!
+//! [normalize_phone] reduces a phone number to its digits, ignoring
+//! formatting, an extension, and the leading zeros of international ("00")
+//! and national trunk ("0") prefixes. [phone_ratio] aligns numbers at their
+//! end, ignores up to three extra leading digits on one side as a country
+//! code, and weights differences in the last four digits, which tell
+//! numbers of the same exchange apart, double.
+//!
+//! ```
+//! # use fuzzywuzzy::phone::{normalize_phone, phone_ratio};
+//! assert_eq!(normalize_phone("+1 (555) 010-2345 ext. 12"), "15550102345");
+//! assert_eq!(phone_ratio("+44 20 7946 0958", "020 7946 0958"), 100);
+//! assert_eq!(phone_ratio("(555) 010-2345", "555.010.2354"), 71);
+//! assert_eq!(phone_ratio("(555) 010-2345", "(554) 010-2345"), 93);
+//! ```
+
+use crate::fuzz::Scorer;
+use crate::score::Score;
+use crate::utils::Rounding;
+
+/// Leading digits which may be left out of one number as its country code.
+const MAX_COUNTRY_CODE_LEN: usize = 3;
+/// Trailing digits whose differences weigh double.
+const SUBSCRIBER_LEN: usize = 4;
+
+/// The digit a letter is on a phone keypad, for numbers like "1-800-FLOWERS".
+fn keypad_digit(c: char) -> Option<char> {
+    let digit = match c.to_ascii_lowercase() {
+        'a'..='c' => '2',
+        'd'..='f' => '3',
+        'g'..='i' => '4',
+        'j'..='l' => '5',
+        'm'..='o' => '6',
+        'p'..='s' => '7',
+        't'..='v' => '8',
+        'w'..='z' => '9',
+        _ => return None,
+    };
+    Some(digit)
+}
+
+/// Removes an extension like "x12", "ext 12" or "extension 12" from the end of `phone`.
+fn strip_extension(phone: &str) -> &str {
+    let lower = phone.to_ascii_lowercase();
+    let digits = lower.trim_end_matches(|c: char| c.is_ascii_digit());
+    if digits.len() == lower.len() {
+        return phone;
+    }
+    let marker = digits.trim_end_matches(|c: char| c == '.' || c == ':' || c.is_whitespace());
+    for word in ["extension", "ext", "x"].iter() {
+        if let Some(before) = marker.strip_suffix(word) {
+            // Not the end of a longer word.
+            if !before.ends_with(|c: char| c.is_alphabetic()) {
+                return &phone[..before.len()];
+            }
+        }
+    }
+    phone
+}
+
+/// The digits of `phone`, without an extension or leading zeros. Letters
+/// after the first digit become digits as on a keypad, while those before
+/// it, like "Tel:", are dropped.
+///
+/// ```
+/// # use fuzzywuzzy::phone::normalize_phone;
+/// assert_eq!(normalize_phone("0049 (0)30 123456"), "49030123456");
+/// assert_eq!(normalize_phone("1-800-FLOWERS"), "18003569377");
+/// assert_eq!(normalize_phone("Tel: 555-0100 x7"), "5550100");
+/// ```
+pub fn normalize_phone(phone: &str) -> String {
+    let phone = strip_extension(phone);
+    let start = phone
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or(phone.len());
+    let digits: String = phone[start..]
+        .chars()
+        .filter_map(|c| {
+            if c.is_ascii_digit() {
+                Some(c)
+            } else {
+                keypad_digit(c)
+            }
+        })
+        .collect();
+    digits.trim_start_matches('0').to_string()
+}
+
+/// The weight of a difference at `from_end` digits from the end of a number.
+fn weight(from_end: usize) -> usize {
+    if from_end < SUBSCRIBER_LEN {
+        2
+    } else {
+        1
+    }
+}
+
+/// Compares two phone numbers digit by digit after [normalize_phone].
+///
+/// The score is 100 less the percentage of the weighted length of the
+/// longer number taken up by the weighted edit distance, where edits to
+/// the last four digits weigh double. Numbers without digits score 0.
+///
+/// ```
+/// # use fuzzywuzzy::phone::phone_ratio;
+/// assert_eq!(phone_ratio("555-010-2345", "1 555 010 2345"), 100);
+/// assert_eq!(phone_ratio("555-010-2345", "555-010-2346"), 86);
+/// assert_eq!(phone_ratio("555-010-2345", "010-2345"), 71);
+/// ```
+pub fn phone_ratio(a: &str, b: &str) -> u8 {
+    let (a, b) = (normalize_phone(a), normalize_phone(b));
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if short.is_empty() {
+        return 0;
+    }
+    let long = if long.len() - short.len() <= MAX_COUNTRY_CODE_LEN {
+        &long[long.len() - short.len()..]
+    } else {
+        &long[..]
+    };
+    let (short, long) = (short.as_bytes(), long.as_bytes());
+    let (m, n) = (short.len(), long.len());
+    // `row[j]` is the weighted distance between the current prefix of
+    // `short` and the first `j` digits of `long`.
+    let mut row: Vec<usize> = (0..=n).collect();
+    let mut cost = 0;
+    for j in 0..n {
+        cost += weight(n - 1 - j);
+        row[j + 1] = cost;
+    }
+    for (i, &digit) in short.iter().enumerate() {
+        let short_weight = weight(m - 1 - i);
+        let mut diagonal = row[0];
+        row[0] += short_weight;
+        for j in 0..n {
+            let long_weight = weight(n - 1 - j);
+            let substitution = if digit == long[j] {
+                diagonal
+            } else {
+                diagonal + short_weight.max(long_weight)
+            };
+            diagonal = row[j + 1];
+            row[j + 1] = substitution
+                .min(row[j + 1] + short_weight)
+                .min(row[j] + long_weight);
+        }
+    }
+    let total: usize = (0..n).map(weight).sum();
+    let distance = row[n].min(total);
+    Rounding::Python.round(100.0 * (1.0 - distance as f64 / total as f64))
+}
+
+/// [Scorer] for [phone_ratio].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PhoneRatio;
+
+impl Scorer for PhoneRatio {
+    fn score(&self, a: &str, b: &str) -> Score {
+        Score::new(phone_ratio(a, b)).expect("phone_ratio returns at most 100")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extensions_are_stripped() {
+        let cases = [
+            ("555-0100 x7", "555-0100 "),
+            ("555-0100x7", "555-0100"),
+            ("555-0100 Ext. 7", "555-0100 "),
+            ("555-0100 extension: 7", "555-0100 "),
+            ("PO Box 7", "PO Box 7"),
+            ("555-0100", "555-0100"),
+        ];
+        for &(phone, stripped) in cases.iter() {
+            assert_eq!(strip_extension(phone), stripped, "{}", phone);
+        }
+    }
+}
@@ -1,5 +1,11 @@
 //! Contains comparison primitives used to build up the rest of the library.
 
+use crate::error::Error;
+use crate::utils::SmallBuffer;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::Range;
+
 /// Returns list of triples describing matching sequences.
 ///
 /// The first number is the index in the first string of the beginning of the match.
@@ -19,8 +25,29 @@
 /// assert_eq!(get_matching_blocks(&CodePointSegmenter.segment("किमप"), &CodePointSegmenter.segment("किमपि")), vec![(0, 0, 4), (4, 5, 0)]);
 /// assert_eq!(get_matching_blocks(&GraphemeSegmenter.segment("किमप"), &GraphemeSegmenter.segment("किमपि")), vec![(0, 0, 2), (3, 3, 0)]);
 /// ```
-#[allow(clippy::many_single_char_names)]
 pub fn get_matching_blocks<T: Eq>(a: &[T], b: &[T]) -> Vec<(usize, usize, usize)> {
+    get_matching_blocks_reaching(a, b, 0).expect("every list of blocks has at least 0 matches")
+}
+
+/// Like [get_matching_blocks], but gives up as soon as the blocks can't add
+/// up to `min_matches` matched elements, returning `None`.
+///
+/// Each block found rules out matches crossing it, so for dissimilar inputs
+/// this stops after the first few blocks rather than searching every gap.
+///
+/// ```
+/// # use fuzzywuzzy::primitives::{get_matching_blocks, get_matching_blocks_reaching};
+/// let (a, b) = (b"abxcd", b"abcd");
+/// assert_eq!(get_matching_blocks_reaching(a, b, 4), Some(get_matching_blocks(a, b)));
+/// assert_eq!(get_matching_blocks_reaching(a, b, 5), None);
+/// assert_eq!(get_matching_blocks_reaching(b"abcd", b"dcba", 2), None);
+/// ```
+#[allow(clippy::many_single_char_names)]
+pub fn get_matching_blocks_reaching<T: Eq>(
+    a: &[T],
+    b: &[T],
+    min_matches: usize,
+) -> Option<Vec<(usize, usize, usize)>> {
     let flipped;
     let (shorter, len1, longer, len2) = {
         let a_len = a.len();
@@ -34,9 +61,16 @@ pub fn get_matching_blocks<T: Eq>(a: &[T], b: &[T]) -> Vec<(usize, usize, usize)
         }
     };
     // https://github.com/python-git/python/blob/master/Lib/difflib.py#L461
-    let mut queue = vec![(0, len1, 0, len2)];
-    let mut matching_blocks = Vec::new();
+    let mut queue: SmallBuffer<(usize, usize, usize, usize)> = SmallBuffer::new();
+    queue.push((0, len1, 0, len2));
+    let mut matching_blocks: SmallBuffer<(usize, usize, usize)> = SmallBuffer::new();
+    // The matches found so far, plus as many as the queued ranges could hold.
+    let mut reachable = len1.min(len2);
     while let Some((low1, high1, low2, high2)) = queue.pop() {
+        if reachable < min_matches {
+            return None;
+        }
+        reachable -= (high1 - low1).min(high2 - low2);
         // TODO: I'd like to convert this function to use MatchingStreak's internally.
         // It might make it more clear to be comparing low1 < streak.idx1 instead of low1 < i
         let MatchingStreak {
@@ -48,14 +82,20 @@ pub fn get_matching_blocks<T: Eq>(a: &[T], b: &[T]) -> Vec<(usize, usize, usize)
         debug_assert!(j <= longer.len());
         if k != 0 {
             matching_blocks.push((i, j, k));
+            reachable += k;
             if low1 < i && low2 < j {
                 queue.push((low1, i, low2, j));
+                reachable += (i - low1).min(j - low2);
             }
             if i + k < high1 && j + k < high2 {
                 queue.push((i + k, high1, j + k, high2));
+                reachable += (high1 - i - k).min(high2 - j - k);
             }
         }
     }
+    if reachable < min_matches {
+        return None;
+    }
     matching_blocks.sort_unstable();
     let (mut i1, mut j1, mut k1) = (0, 0, 0);
     let mut non_adjacent = Vec::new();
@@ -78,10 +118,12 @@ pub fn get_matching_blocks<T: Eq>(a: &[T], b: &[T]) -> Vec<(usize, usize, usize)
         non_adjacent.push((i1, j1, k1));
     }
     non_adjacent.push((len1, len2, 0));
-    non_adjacent
-        .into_iter()
-        .map(|(i, j, k)| if flipped { (j, i, k) } else { (i, j, k) })
-        .collect()
+    Some(
+        non_adjacent
+            .into_iter()
+            .map(|(i, j, k)| if flipped { (j, i, k) } else { (i, j, k) })
+            .collect(),
+    )
 }
 
 /// Represents a matching streak of characters between two strings.
@@ -104,6 +146,12 @@ pub struct MatchingStreak {
 /// `idx2` is an index into `longer` where the streak begins,
 /// and `size` is the length of the streak.
 ///
+/// Panics if either range is out of bounds, see [try_find_longest_match].
+///
+/// No alignment matrix is materialized: memory use is `O(high2 - low2)`
+/// regardless of input size. Functions which do fill one, like
+/// [levenshtein_editops], stay within a [MemoryLimit].
+///
 /// ```
 /// # use fuzzywuzzy::segmentation::{Segmenter, CodePointSegmenter};
 /// # use fuzzywuzzy::primitives::{ find_longest_match, MatchingStreak};
@@ -124,6 +172,67 @@ pub fn find_longest_match<T: Eq>(
     high1: usize,
     low2: usize,
     high2: usize,
+) -> MatchingStreak {
+    debug_assert!(low1 <= high1);
+    debug_assert!(low2 <= high2);
+    debug_assert!(high1 <= shorter.len());
+    debug_assert!(high2 <= longer.len());
+    // Both strategies return the same streak. Scanning windows is quick when a
+    // long streak exists or the inputs are tiny, but degrades to roughly cubic
+    // time on dissimilar inputs where the dynamic programming approach wins.
+    let (len1, len2) = (high1 - low1, high2 - low2);
+    if len1 <= len2 && len1 * len2 <= SCAN_MAX_CELLS {
+        find_longest_match_scan(shorter, longer, low1, high1, low2, high2)
+    } else {
+        find_longest_match_dp(shorter, longer, low1, high1, low2, high2)
+    }
+}
+
+/// Like [find_longest_match], but returns [Error::InvalidRange] instead of
+/// panicking if `low1..high1` or `low2..high2` is out of bounds.
+///
+/// ```
+/// # use fuzzywuzzy::primitives::{try_find_longest_match, MatchingStreak};
+/// # use fuzzywuzzy::error::Error;
+/// let (a, b) = (b"foo bar", b"bar baz");
+/// assert_eq!(try_find_longest_match(a, b, 0, 7, 0, 7),
+///            Ok(MatchingStreak { idx1: 3, idx2: 3, size: 3 }));
+/// assert_eq!(try_find_longest_match(a, b, 0, 7, 4, 8),
+///            Err(Error::InvalidRange { low: 4, high: 8, len: 7 }));
+/// ```
+pub fn try_find_longest_match<T: Eq>(
+    shorter: &[T],
+    longer: &[T],
+    low1: usize,
+    high1: usize,
+    low2: usize,
+    high2: usize,
+) -> Result<MatchingStreak, Error> {
+    for &(low, high, len) in &[(low1, high1, shorter.len()), (low2, high2, longer.len())] {
+        if low > high || high > len {
+            return Err(Error::InvalidRange { low, high, len });
+        }
+    }
+    Ok(find_longest_match(
+        shorter, longer, low1, high1, low2, high2,
+    ))
+}
+
+/// Inputs whose comparison grid is at most this many cells are matched by
+/// [find_longest_match_scan], larger ones by [find_longest_match_dp].
+const SCAN_MAX_CELLS: usize = 256;
+
+/// Finds the longest streak by trying every window of `longer` for every
+/// substring of `shorter`, longest substrings first.
+///
+/// Requires `high1 - low1 <= high2 - low2`.
+fn find_longest_match_scan<T: Eq>(
+    shorter: &[T],
+    longer: &[T],
+    low1: usize,
+    high1: usize,
+    low2: usize,
+    high2: usize,
 ) -> MatchingStreak {
     // https://github.com/python-git/python/blob/master/Lib/difflib.py#L351
     // algo:
@@ -137,10 +246,6 @@ pub fn find_longest_match<T: Eq>(
     //
     // [1] - because of the calling context, we actually use `high1 - low1`
     // for the length because we might be indexing into the middle of `shorter`
-    debug_assert!(low1 <= high1);
-    debug_assert!(low2 <= high2);
-    debug_assert!(high1 <= shorter.len());
-    debug_assert!(high2 <= longer.len());
     debug_assert!(high1 - low1 <= high2 - low2);
     let longsub = &longer[low2..high2];
     let len = high1 - low1;
@@ -149,6 +254,7 @@ pub fn find_longest_match<T: Eq>(
             let shortsub = &shorter[low1 + start..low1 + start + size];
             for window_start in 0..((high2 - low2) - size + 1) {
                 let window = &longsub[window_start..window_start + size];
+                count!(comparisons, 1);
                 if window == shortsub {
                     return MatchingStreak {
                         idx1: low1 + start,
@@ -165,3 +271,869 @@ pub fn find_longest_match<T: Eq>(
         size: 0,
     }
 }
+
+/// Finds the longest streak with the longest common substring dynamic program
+/// in `O(len1 * len2)` time and `O(len2)` space.
+///
+/// Ties are broken the same way as [find_longest_match_scan]: the streak
+/// starting earliest in `shorter`, then earliest in `longer`.
+fn find_longest_match_dp<T: Eq>(
+    shorter: &[T],
+    longer: &[T],
+    low1: usize,
+    high1: usize,
+    low2: usize,
+    high2: usize,
+) -> MatchingStreak {
+    let len2 = high2 - low2;
+    // `prev[j + 1]` is the length of the streak ending at the previous element
+    // of `shorter` and `longer[low2 + j]`, `cur` is the same for the current one.
+    let mut prev: SmallBuffer<usize> = std::iter::repeat_n(0, len2 + 1).collect();
+    let mut cur = prev.clone();
+    let mut best = MatchingStreak {
+        idx1: low1,
+        idx2: low2,
+        size: 0,
+    };
+    for (i, x) in shorter[low1..high1].iter().enumerate() {
+        for (j, y) in longer[low2..high2].iter().enumerate() {
+            let size = if x == y { prev[j] + 1 } else { 0 };
+            cur[j + 1] = size;
+            // Strictly greater so that the earliest streak wins ties.
+            if size > best.size {
+                best = MatchingStreak {
+                    idx1: low1 + i + 1 - size,
+                    idx2: low2 + j + 1 - size,
+                    size,
+                };
+            }
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    count!(dp_cells, (high1 - low1) * len2);
+    count!(comparisons, (high1 - low1) * len2);
+    best
+}
+
+/// Returns the Levenshtein distance between `a` and `b`: the minimum number of
+/// single element insertions, deletions and substitutions turning one into the other.
+///
+/// Runs in `O(a.len() * b.len())` time and `O(min(a.len(), b.len()))` space.
+///
+/// ```
+/// # use fuzzywuzzy::primitives::levenshtein;
+/// assert_eq!(levenshtein(b"kitten", b"sitting"), 3);
+/// assert_eq!(levenshtein(b"", b"abc"), 3);
+/// let (a, b): (Vec<char>, Vec<char>) = ("café".chars().collect(), "cafe".chars().collect());
+/// assert_eq!(levenshtein(&a, &b), 1);
+/// ```
+pub fn levenshtein<T: Eq>(a: &[T], b: &[T]) -> usize {
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    // `row[j]` is the distance between the prefix of `longer` processed so
+    // far and the first `j` elements of `shorter`.
+    let mut row: SmallBuffer<usize> = (0..=shorter.len()).collect();
+    for (i, x) in longer.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, y) in shorter.iter().enumerate() {
+            let substitution = diagonal + usize::from(x != y);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    count!(dp_cells, a.len() * b.len());
+    row[shorter.len()]
+}
+
+/// The kind of an [EditOp].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EditKind {
+    /// `a[op.a]` is replaced by `b[op.b]`.
+    Replace,
+    /// `b[op.b]` is inserted before `a[op.a]`.
+    Insert,
+    /// `a[op.a]` is deleted, before `b[op.b]`.
+    Delete,
+}
+
+/// One step of turning one sequence into another, see [levenshtein_editops].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EditOp {
+    pub kind: EditKind,
+    /// The position in the first sequence.
+    pub a: usize,
+    /// The position in the second sequence.
+    pub b: usize,
+}
+
+/// How much memory alignment and traceback matrices may take, and what to do
+/// for inputs whose matrix would take more.
+///
+/// Finding the edits between two sequences of lengths `m` and `n` fills an
+/// `(m + 1) × (n + 1)` matrix, which for pathological inputs runs into
+/// gigabytes. Within the limit the full matrix is used. Beyond it, the edits
+/// are either found in linear space by Hirschberg's algorithm, at about twice
+/// the time, or [Error::MemoryLimitExceeded] is returned.
+///
+/// ```
+/// # use fuzzywuzzy::primitives::{levenshtein_editops, levenshtein_editops_limited, MemoryLimit};
+/// # use fuzzywuzzy::error::Error;
+/// let (a, b) = (b"kitten".repeat(100), b"sitting".repeat(100));
+/// // A kilobyte is too little for the full matrix, so Hirschberg's algorithm is used.
+/// let ops = levenshtein_editops_limited(&a, &b, MemoryLimit::bytes(1024)).unwrap();
+/// assert_eq!(ops.len(), levenshtein_editops(&a, &b).len());
+/// assert_eq!(
+///     levenshtein_editops_limited(&a, &b, MemoryLimit::bytes(1024).or_error()),
+///     Err(Error::MemoryLimitExceeded { required: 8 * 601 * 701, limit: 1024 })
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryLimit {
+    bytes: usize,
+    linear_space: bool,
+}
+
+impl MemoryLimit {
+    /// The limit used by functions without a [MemoryLimit] argument: 64 MiB,
+    /// falling back to linear space.
+    pub const DEFAULT: MemoryLimit = MemoryLimit::bytes(64 << 20);
+
+    /// A limit of `bytes`, beyond which linear space algorithms are used.
+    pub const fn bytes(bytes: usize) -> MemoryLimit {
+        MemoryLimit {
+            bytes,
+            linear_space: true,
+        }
+    }
+
+    /// Returns [Error::MemoryLimitExceeded] for inputs beyond the limit
+    /// instead of falling back to linear space.
+    pub const fn or_error(self) -> MemoryLimit {
+        MemoryLimit {
+            linear_space: false,
+            ..self
+        }
+    }
+
+    /// How many matrix cells of `cell_size` bytes fit, or an error if
+    /// `cells` don't fit and there is no fallback.
+    pub(crate) fn cells(self, cells: usize, cell_size: usize) -> Result<usize, Error> {
+        let required = cells.saturating_mul(cell_size);
+        if required > self.bytes && !self.linear_space {
+            return Err(Error::MemoryLimitExceeded {
+                required,
+                limit: self.bytes,
+            });
+        }
+        Ok(self.bytes / cell_size)
+    }
+}
+
+impl Default for MemoryLimit {
+    fn default() -> MemoryLimit {
+        MemoryLimit::DEFAULT
+    }
+}
+
+/// Returns a shortest list of edits turning `a` into `b`, in order, like
+/// python-Levenshtein's `editops`. There are [levenshtein] of them.
+///
+/// Runs in `O(a.len() * b.len())` time and space, switching to linear space
+/// beyond [MemoryLimit::DEFAULT].
+///
+/// ```
+/// # use fuzzywuzzy::primitives::{levenshtein_editops, EditKind, EditOp};
+/// assert_eq!(levenshtein_editops(b"kitten", b"sittn"), vec![
+///     EditOp { kind: EditKind::Replace, a: 0, b: 0 },
+///     EditOp { kind: EditKind::Delete, a: 4, b: 4 },
+/// ]);
+/// assert_eq!(levenshtein_editops(b"ab", b"abc"), vec![EditOp { kind: EditKind::Insert, a: 2, b: 2 }]);
+/// ```
+pub fn levenshtein_editops<T: Eq>(a: &[T], b: &[T]) -> Vec<EditOp> {
+    levenshtein_editops_limited(a, b, MemoryLimit::DEFAULT)
+        .expect("the default memory limit falls back to linear space")
+}
+
+/// Like [levenshtein_editops], but within the given [MemoryLimit].
+pub fn levenshtein_editops_limited<T: Eq>(
+    a: &[T],
+    b: &[T],
+    limit: MemoryLimit,
+) -> Result<Vec<EditOp>, Error> {
+    let substitution = |_: usize| 1.0;
+    Ok(Aligner::align(a, b, &substitution, limit)?.1)
+}
+
+/// Like [levenshtein_editops], but substituting `a[i]` costs `confidences[i]`
+/// rather than 1, e.g. the confidence an OCR engine had in reading it, so
+/// the alignment prefers substituting the elements least likely to be right.
+/// Insertions and deletions cost 1.
+///
+/// Returns the total cost of the edits along with them. Confidences of 1
+/// everywhere give the [levenshtein] distance.
+///
+/// Runs in `O(a.len() * b.len())` time and space, switching to linear space
+/// beyond [MemoryLimit::DEFAULT].
+///
+/// # Panics
+///
+/// Panics if `confidences` and `a` differ in length, or a confidence isn't
+/// between 0.0 and 1.0, see [try_confidence_editops].
+///
+/// ```
+/// # use fuzzywuzzy::primitives::{confidence_editops, EditKind, EditOp};
+/// let (cost, ops) = confidence_editops(b"abc", b"bcd", &[1.0; 3]);
+/// assert_eq!(cost, 2.0);
+/// assert_eq!(ops, vec![
+///     EditOp { kind: EditKind::Delete, a: 0, b: 0 },
+///     EditOp { kind: EditKind::Insert, a: 3, b: 2 },
+/// ]);
+/// // Read with little confidence, the elements were likelier misread than shifted.
+/// let (cost, ops) = confidence_editops(b"abc", b"bcd", &[0.25; 3]);
+/// assert_eq!(cost, 0.75);
+/// assert_eq!(ops.iter().map(|op| (op.kind, op.a, op.b)).collect::<Vec<_>>(), vec![
+///     (EditKind::Replace, 0, 0),
+///     (EditKind::Replace, 1, 1),
+///     (EditKind::Replace, 2, 2),
+/// ]);
+/// ```
+pub fn confidence_editops<T: Eq>(a: &[T], b: &[T], confidences: &[f64]) -> (f64, Vec<EditOp>) {
+    try_confidence_editops(a, b, confidences).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Like [confidence_editops], but returns [Error::LengthMismatch] if
+/// `confidences` and `a` differ in length, and [Error::InvalidConfig] if a
+/// confidence isn't between 0.0 and 1.0, instead of panicking.
+///
+/// ```
+/// # use fuzzywuzzy::primitives::try_confidence_editops;
+/// # use fuzzywuzzy::error::Error;
+/// assert_eq!(try_confidence_editops(b"abc", b"abc", &[1.0; 3]), Ok((0.0, vec![])));
+/// assert_eq!(try_confidence_editops(b"abc", b"abc", &[1.0; 2]),
+///            Err(Error::LengthMismatch { expected: 3, actual: 2 }));
+/// ```
+pub fn try_confidence_editops<T: Eq>(
+    a: &[T],
+    b: &[T],
+    confidences: &[f64],
+) -> Result<(f64, Vec<EditOp>), Error> {
+    // The default limit falls back to linear space, so only the confidences can fail.
+    confidence_editops_limited(a, b, confidences, MemoryLimit::DEFAULT)
+}
+
+/// Like [try_confidence_editops], but within the given [MemoryLimit].
+pub fn confidence_editops_limited<T: Eq>(
+    a: &[T],
+    b: &[T],
+    confidences: &[f64],
+    limit: MemoryLimit,
+) -> Result<(f64, Vec<EditOp>), Error> {
+    if confidences.len() != a.len() {
+        return Err(Error::LengthMismatch {
+            expected: a.len(),
+            actual: confidences.len(),
+        });
+    }
+    if !confidences.iter().all(|c| (0.0..=1.0).contains(c)) {
+        return Err(Error::InvalidConfig("confidences must be in [0.0, 1.0]"));
+    }
+    let substitution = |i: usize| confidences[i];
+    Aligner::align(a, b, &substitution, limit)
+}
+
+/// Finds the cheapest edits turning `a` into `b`, where insertions and
+/// deletions cost 1 and substituting `a[i]` for a different element costs
+/// `substitution(i)`.
+///
+/// Subproblems whose matrix fits in `max_cells` are solved with the full
+/// matrix, larger ones are split by Hirschberg's algorithm: the cheapest
+/// alignment passes through the cheapest split of `b` at the middle of `a`,
+/// which two linear space passes, forwards and backwards, find.
+struct Aligner<'s, T> {
+    a: &'s [T],
+    b: &'s [T],
+    substitution: &'s dyn Fn(usize) -> f64,
+    max_cells: usize,
+    ops: Vec<EditOp>,
+}
+
+impl<'s, T: Eq> Aligner<'s, T> {
+    /// The total cost of the edits, and the edits in order.
+    fn align(
+        a: &'s [T],
+        b: &'s [T],
+        substitution: &'s dyn Fn(usize) -> f64,
+        limit: MemoryLimit,
+    ) -> Result<(f64, Vec<EditOp>), Error> {
+        let cells = (a.len() + 1).saturating_mul(b.len() + 1);
+        let mut aligner = Aligner {
+            a,
+            b,
+            substitution,
+            max_cells: limit.cells(cells, std::mem::size_of::<f64>())?,
+            ops: vec![],
+        };
+        aligner.split(0..a.len(), 0..b.len());
+        count!(dp_cells, a.len() * b.len());
+        let cost = aligner
+            .ops
+            .iter()
+            .map(|op| match op.kind {
+                EditKind::Replace => substitution(op.a),
+                EditKind::Insert | EditKind::Delete => 1.0,
+            })
+            .sum();
+        Ok((cost, aligner.ops))
+    }
+
+    fn cost(&self, i: usize, j: usize) -> f64 {
+        if self.a[i] == self.b[j] {
+            0.0
+        } else {
+            (self.substitution)(i)
+        }
+    }
+
+    /// Appends the edits turning `a[a_range]` into `b[b_range]`.
+    fn split(&mut self, a_range: Range<usize>, b_range: Range<usize>) {
+        let (m, n) = (a_range.len(), b_range.len());
+        if m <= 1 || (m + 1).saturating_mul(n + 1) <= self.max_cells {
+            self.full(a_range, b_range);
+            return;
+        }
+        let mid = a_range.start + m / 2;
+        let forward = self.forward(a_range.start..mid, b_range.clone());
+        let backward = self.backward(mid..a_range.end, b_range.clone());
+        let k = (0..=n)
+            .min_by(|&x, &y| {
+                (forward[x] + backward[x])
+                    .partial_cmp(&(forward[y] + backward[y]))
+                    .expect("costs are not NaN")
+            })
+            .expect("there is at least one split");
+        let b_mid = b_range.start + k;
+        self.split(a_range.start..mid, b_range.start..b_mid);
+        self.split(mid..a_range.end, b_mid..b_range.end);
+    }
+
+    /// The costs of turning `a[a_range]` into each prefix of `b[b_range]`.
+    fn forward(&self, a_range: Range<usize>, b_range: Range<usize>) -> Vec<f64> {
+        let n = b_range.len();
+        let mut row: Vec<f64> = (0..=n).map(|j| j as f64).collect();
+        for (di, i) in a_range.enumerate() {
+            let mut diagonal = row[0];
+            row[0] = (di + 1) as f64;
+            for j in 0..n {
+                let substitution = diagonal + self.cost(i, b_range.start + j);
+                diagonal = row[j + 1];
+                row[j + 1] = substitution.min(row[j] + 1.0).min(diagonal + 1.0);
+            }
+        }
+        row
+    }
+
+    /// The costs of turning `a[a_range]` into each suffix of `b[b_range]`,
+    /// indexed by where the suffix starts.
+    fn backward(&self, a_range: Range<usize>, b_range: Range<usize>) -> Vec<f64> {
+        let n = b_range.len();
+        let mut row: Vec<f64> = (0..=n).map(|j| (n - j) as f64).collect();
+        for (di, i) in a_range.rev().enumerate() {
+            let mut diagonal = row[n];
+            row[n] = (di + 1) as f64;
+            for j in (0..n).rev() {
+                let substitution = diagonal + self.cost(i, b_range.start + j);
+                diagonal = row[j];
+                row[j] = substitution.min(row[j + 1] + 1.0).min(diagonal + 1.0);
+            }
+        }
+        row
+    }
+
+    /// [Aligner::split] with the full matrix.
+    fn full(&mut self, a_range: Range<usize>, b_range: Range<usize>) {
+        let (m, n) = (a_range.len(), b_range.len());
+        let (a0, b0) = (a_range.start, b_range.start);
+        // `d[i * (n + 1) + j]` is the cost of turning the first `i` elements
+        // of `a[a_range]` into the first `j` elements of `b[b_range]`.
+        let width = n + 1;
+        let mut d = vec![0.0; (m + 1) * width];
+        for (j, cell) in d[..width].iter_mut().enumerate() {
+            *cell = j as f64;
+        }
+        for i in 1..=m {
+            d[i * width] = i as f64;
+            for j in 1..=n {
+                d[i * width + j] = (d[(i - 1) * width + j - 1] + self.cost(a0 + i - 1, b0 + j - 1))
+                    .min(d[(i - 1) * width + j] + 1.0)
+                    .min(d[i * width + j - 1] + 1.0);
+            }
+        }
+        let mut ops = vec![];
+        let (mut i, mut j) = (m, n);
+        while i > 0 || j > 0 {
+            let here = d[i * width + j];
+            if i > 0
+                && j > 0
+                && here == d[(i - 1) * width + j - 1] + self.cost(a0 + i - 1, b0 + j - 1)
+            {
+                i -= 1;
+                j -= 1;
+                if self.a[a0 + i] != self.b[b0 + j] {
+                    ops.push(EditOp {
+                        kind: EditKind::Replace,
+                        a: a0 + i,
+                        b: b0 + j,
+                    });
+                }
+            } else if i > 0 && here == d[(i - 1) * width + j] + 1.0 {
+                i -= 1;
+                ops.push(EditOp {
+                    kind: EditKind::Delete,
+                    a: a0 + i,
+                    b: b0 + j,
+                });
+            } else {
+                j -= 1;
+                ops.push(EditOp {
+                    kind: EditKind::Insert,
+                    a: a0 + i,
+                    b: b0 + j,
+                });
+            }
+        }
+        self.ops.extend(ops.into_iter().rev());
+    }
+}
+
+/// Returns the optimal string alignment distance between `a` and `b`: like
+/// [levenshtein], but swapping two adjacent elements also counts as a single
+/// edit. Unlike the unrestricted Damerau-Levenshtein distance, no part of
+/// the sequences is edited more than once.
+///
+/// Runs in `O(a.len() * b.len())` time and `O(min(a.len(), b.len()))` space.
+///
+/// ```
+/// # use fuzzywuzzy::primitives::{levenshtein, osa_distance};
+/// assert_eq!(osa_distance(b"isntall", b"install"), 1);
+/// assert_eq!(levenshtein(b"isntall", b"install"), 2);
+/// assert_eq!(osa_distance(b"ca", b"abc"), 3);
+/// ```
+pub fn osa_distance<T: Eq>(a: &[T], b: &[T]) -> usize {
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    // `row` is as in `levenshtein`, `previous` the row before it and
+    // `before_previous` the one before that, for transpositions.
+    let mut before_previous: SmallBuffer<usize> = (0..=shorter.len()).collect();
+    let mut previous = before_previous.clone();
+    let mut row = previous.clone();
+    for (i, x) in longer.iter().enumerate() {
+        row[0] = i + 1;
+        for (j, y) in shorter.iter().enumerate() {
+            let mut distance = (previous[j] + usize::from(x != y))
+                .min(row[j] + 1)
+                .min(previous[j + 1] + 1);
+            if i > 0 && j > 0 && *x == shorter[j - 1] && longer[i - 1] == *y {
+                distance = distance.min(before_previous[j - 1] + 1);
+            }
+            row[j + 1] = distance;
+        }
+        std::mem::swap(&mut before_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut row);
+    }
+    count!(dp_cells, a.len() * b.len());
+    previous[shorter.len()]
+}
+
+/// Like [get_matching_blocks], but ignoring junk elements of `b` the way Python's
+/// `difflib.SequenceMatcher` does.
+///
+/// Elements of `b` for which `isjunk` returns `true` never start or anchor a
+/// match, though matches are extended over junk that happens to be equal. With
+/// `autojunk`, if `b` has at least 200 elements, elements making up more than
+/// 1% of `b` are treated as junk too. This keeps long text comparisons from
+/// being dominated by spaces and common letters, and makes results agree with
+/// difflib.
+///
+/// Unlike [get_matching_blocks], the arguments are never swapped, because
+/// junk only applies to `b`.
+///
+/// ```
+/// # use fuzzywuzzy::primitives::get_matching_blocks_with_junk;
+/// let a: Vec<char> = "private Thread currentThread;".chars().collect();
+/// let b: Vec<char> = "private volatile Thread currentThread;".chars().collect();
+/// assert_eq!(get_matching_blocks_with_junk(&a, &b, |c| *c == ' ', false),
+///            vec![(0, 0, 8), (8, 17, 21), (29, 38, 0)]);
+/// // With autojunk, every character of `b` is too popular to match on its own.
+/// let a: Vec<char> = "abc ".repeat(60).chars().collect();
+/// let b: Vec<char> = "abd ".repeat(70).chars().collect();
+/// assert_eq!(get_matching_blocks_with_junk(&a, &b, |_| false, true).len(), 2);
+/// assert_eq!(get_matching_blocks_with_junk(&a, &b, |_| false, false).len(), 62);
+/// ```
+pub fn get_matching_blocks_with_junk<T, J>(
+    a: &[T],
+    b: &[T],
+    isjunk: J,
+    autojunk: bool,
+) -> Vec<(usize, usize, usize)>
+where
+    T: Eq + Hash,
+    J: Fn(&T) -> bool,
+{
+    JunkIndex::new(b, isjunk, autojunk).matching_blocks(a)
+}
+
+/// Like [find_longest_match], but ignoring junk elements of `b` as described
+/// in [get_matching_blocks_with_junk].
+///
+/// ```
+/// # use fuzzywuzzy::primitives::{find_longest_match_with_junk, MatchingStreak};
+/// let a: Vec<char> = " abcd".chars().collect();
+/// let b: Vec<char> = "abcd abcd".chars().collect();
+/// assert_eq!(find_longest_match_with_junk(&a, &b, 0, 5, 0, 9, |_| false, false),
+///            MatchingStreak { idx1: 0, idx2: 4, size: 5 });
+/// assert_eq!(find_longest_match_with_junk(&a, &b, 0, 5, 0, 9, |c| *c == ' ', false),
+///            MatchingStreak { idx1: 1, idx2: 0, size: 4 });
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn find_longest_match_with_junk<T, J>(
+    a: &[T],
+    b: &[T],
+    alo: usize,
+    ahi: usize,
+    blo: usize,
+    bhi: usize,
+    isjunk: J,
+    autojunk: bool,
+) -> MatchingStreak
+where
+    T: Eq + Hash,
+    J: Fn(&T) -> bool,
+{
+    JunkIndex::new(b, isjunk, autojunk).find_longest_match(a, alo, ahi, blo, bhi)
+}
+
+/// Sequences at least this long are subject to the autojunk heuristic.
+const AUTOJUNK_MIN_LEN: usize = 200;
+
+/// Positions of the non-junk elements of `b`, difflib's `b2j`, plus its junk.
+pub(crate) struct JunkIndex<'b, T: Eq + Hash> {
+    pub(crate) b: &'b [T],
+    positions: HashMap<&'b T, Vec<usize>>,
+    junk: HashSet<&'b T>,
+}
+
+impl<'b, T: Eq + Hash> JunkIndex<'b, T> {
+    pub(crate) fn new<J: Fn(&T) -> bool>(
+        b: &'b [T],
+        isjunk: J,
+        autojunk: bool,
+    ) -> JunkIndex<'b, T> {
+        let mut positions: HashMap<&T, Vec<usize>> = HashMap::new();
+        for (j, elt) in b.iter().enumerate() {
+            positions.entry(elt).or_default().push(j);
+        }
+        let mut junk = HashSet::new();
+        positions.retain(|elt, _| {
+            let is_junk = isjunk(elt);
+            if is_junk {
+                junk.insert(*elt);
+            }
+            !is_junk
+        });
+        // Popular elements are dropped from `positions` like junk, but unlike
+        // junk, matches are still extended over them.
+        if autojunk && b.len() >= AUTOJUNK_MIN_LEN {
+            let max_count = b.len() / 100 + 1;
+            positions.retain(|_, js| js.len() <= max_count);
+        }
+        JunkIndex { b, positions, junk }
+    }
+
+    /// Matching blocks of `a` against the indexed sequence, see [get_matching_blocks_with_junk].
+    pub(crate) fn matching_blocks(&self, a: &[T]) -> Vec<(usize, usize, usize)> {
+        // https://github.com/python/cpython/blob/3.12/Lib/difflib.py#L421
+        let b = self.b;
+        let mut queue: SmallBuffer<(usize, usize, usize, usize)> = SmallBuffer::new();
+        queue.push((0, a.len(), 0, b.len()));
+        let mut matching_blocks: SmallBuffer<(usize, usize, usize)> = SmallBuffer::new();
+        while let Some((alo, ahi, blo, bhi)) = queue.pop() {
+            let MatchingStreak {
+                idx1: i,
+                idx2: j,
+                size: k,
+            } = self.find_longest_match(a, alo, ahi, blo, bhi);
+            if k != 0 {
+                matching_blocks.push((i, j, k));
+                if alo < i && blo < j {
+                    queue.push((alo, i, blo, j));
+                }
+                if i + k < ahi && j + k < bhi {
+                    queue.push((i + k, ahi, j + k, bhi));
+                }
+            }
+        }
+        matching_blocks.sort_unstable();
+        let (mut i1, mut j1, mut k1) = (0, 0, 0);
+        let mut non_adjacent = Vec::new();
+        for (i2, j2, k2) in matching_blocks {
+            if i1 + k1 == i2 && j1 + k1 == j2 {
+                k1 += k2;
+            } else {
+                if k1 != 0 {
+                    non_adjacent.push((i1, j1, k1));
+                }
+                i1 = i2;
+                j1 = j2;
+                k1 = k2;
+            }
+        }
+        if k1 != 0 {
+            non_adjacent.push((i1, j1, k1));
+        }
+        non_adjacent.push((a.len(), b.len(), 0));
+        non_adjacent
+    }
+
+    pub(crate) fn find_longest_match(
+        &self,
+        a: &[T],
+        alo: usize,
+        ahi: usize,
+        blo: usize,
+        bhi: usize,
+    ) -> MatchingStreak {
+        let b = self.b;
+        let (mut besti, mut bestj, mut bestsize) = (alo, blo, 0);
+        // `j2len[j]` is the length of the longest match ending at the previous
+        // element of `a` and `b[j]`.
+        let mut j2len: HashMap<usize, usize> = HashMap::new();
+        for (i, elt) in a.iter().enumerate().take(ahi).skip(alo) {
+            let mut new_j2len = HashMap::new();
+            for &j in self.positions.get(elt).map_or(&[][..], |js| &js[..]) {
+                if j < blo {
+                    continue;
+                }
+                if j >= bhi {
+                    break;
+                }
+                let k = j
+                    .checked_sub(1)
+                    .and_then(|prev| j2len.get(&prev))
+                    .unwrap_or(&0)
+                    + 1;
+                new_j2len.insert(j, k);
+                count!(comparisons, 1);
+                if k > bestsize {
+                    besti = i + 1 - k;
+                    bestj = j + 1 - k;
+                    bestsize = k;
+                }
+            }
+            j2len = new_j2len;
+        }
+        // Extend the match over equal popular elements, then over equal junk.
+        for extend_over_junk in [false, true] {
+            while besti > alo
+                && bestj > blo
+                && self.junk.contains(&b[bestj - 1]) == extend_over_junk
+                && a[besti - 1] == b[bestj - 1]
+            {
+                besti -= 1;
+                bestj -= 1;
+                bestsize += 1;
+            }
+            while besti + bestsize < ahi
+                && bestj + bestsize < bhi
+                && self.junk.contains(&b[bestj + bestsize]) == extend_over_junk
+                && a[besti + bestsize] == b[bestj + bestsize]
+            {
+                bestsize += 1;
+            }
+        }
+        MatchingStreak {
+            idx1: besti,
+            idx2: bestj,
+            size: bestsize,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn longest_match_strategies_agree() {
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let a: Vec<u8> = (0..rng.gen_range(0..40))
+                .map(|_| rng.gen_range(b'a'..b'e'))
+                .collect();
+            let b: Vec<u8> = (0..rng.gen_range(a.len()..60))
+                .map(|_| rng.gen_range(b'a'..b'e'))
+                .collect();
+            let low1 = rng.gen_range(0..=a.len());
+            let high1 = rng.gen_range(low1..=a.len());
+            let low2 = rng.gen_range(0..=b.len() - (high1 - low1));
+            let high2 = rng.gen_range(low2 + (high1 - low1)..=b.len());
+            assert_eq!(
+                find_longest_match_scan(&a, &b, low1, high1, low2, high2),
+                find_longest_match_dp(&a, &b, low1, high1, low2, high2),
+                "{:?} {:?} {:?}",
+                a,
+                b,
+                (low1, high1, low2, high2)
+            );
+        }
+    }
+
+    #[test]
+    fn junk_free_matching_agrees_with_difflib() {
+        // Expected blocks are from Python's difflib.SequenceMatcher. Without
+        // junk, they're the same as get_matching_blocks finds.
+        let a: Vec<char> = "new york mets vs atlanta braves".chars().collect();
+        let b: Vec<char> = "atlanta braves vs new york mets".chars().collect();
+        assert_eq!(
+            get_matching_blocks_with_junk(&a, &b, |_| false, false),
+            vec![(17, 0, 14), (31, 31, 0)]
+        );
+        assert_eq!(
+            get_matching_blocks(&a, &b),
+            get_matching_blocks_with_junk(&a, &b, |_| false, false)
+        );
+    }
+
+    #[test]
+    fn levenshtein_matches_full_matrix() {
+        fn full_matrix(a: &[u8], b: &[u8]) -> usize {
+            let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+            for (i, row) in d.iter_mut().enumerate() {
+                row[0] = i;
+            }
+            for (j, cell) in d[0].iter_mut().enumerate() {
+                *cell = j;
+            }
+            for i in 1..=a.len() {
+                for j in 1..=b.len() {
+                    let substitution = d[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+                    d[i][j] = substitution.min(d[i - 1][j] + 1).min(d[i][j - 1] + 1);
+                }
+            }
+            d[a.len()][b.len()]
+        }
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let a: Vec<u8> = (0..rng.gen_range(0..20))
+                .map(|_| rng.gen_range(b'a'..b'd'))
+                .collect();
+            let b: Vec<u8> = (0..rng.gen_range(0..20))
+                .map(|_| rng.gen_range(b'a'..b'd'))
+                .collect();
+            assert_eq!(levenshtein(&a, &b), full_matrix(&a, &b), "{:?} {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn levenshtein_editops_turn_a_into_b() {
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let a: Vec<u8> = (0..rng.gen_range(0..20))
+                .map(|_| rng.gen_range(b'a'..b'd'))
+                .collect();
+            let b: Vec<u8> = (0..rng.gen_range(0..20))
+                .map(|_| rng.gen_range(b'a'..b'd'))
+                .collect();
+            // Without room for any matrix, Hirschberg's algorithm splits down
+            // to single elements of `a`.
+            for limit in [MemoryLimit::DEFAULT, MemoryLimit::bytes(0)] {
+                let ops = levenshtein_editops_limited(&a, &b, limit).unwrap();
+                assert_eq!(ops.len(), levenshtein(&a, &b));
+                // Applying the edits from the back keeps the earlier positions valid.
+                let mut edited = a.clone();
+                for op in ops.iter().rev() {
+                    match op.kind {
+                        EditKind::Replace => edited[op.a] = b[op.b],
+                        EditKind::Insert => edited.insert(op.a, b[op.b]),
+                        EditKind::Delete => {
+                            edited.remove(op.a);
+                        }
+                    }
+                }
+                assert_eq!(edited, b, "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn linear_space_confidence_editops_are_as_cheap() {
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let a: Vec<u8> = (0..rng.gen_range(0..20))
+                .map(|_| rng.gen_range(b'a'..b'd'))
+                .collect();
+            let b: Vec<u8> = (0..rng.gen_range(0..20))
+                .map(|_| rng.gen_range(b'a'..b'd'))
+                .collect();
+            // Quarters keep the sums exact.
+            let confidences: Vec<f64> = (0..a.len())
+                .map(|_| f64::from(rng.gen_range(0..=4)) / 4.0)
+                .collect();
+            let (cost, _) = confidence_editops(&a, &b, &confidences);
+            let limit = MemoryLimit::bytes(rng.gen_range(0..200));
+            let (linear_cost, _) = confidence_editops_limited(&a, &b, &confidences, limit).unwrap();
+            assert_eq!(linear_cost, cost, "{:?} {:?} {:?}", a, b, confidences);
+        }
+    }
+
+    #[test]
+    fn osa_distance_matches_full_matrix() {
+        fn full_matrix(a: &[u8], b: &[u8]) -> usize {
+            let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+            for (i, row) in d.iter_mut().enumerate() {
+                row[0] = i;
+            }
+            for (j, cell) in d[0].iter_mut().enumerate() {
+                *cell = j;
+            }
+            for i in 1..=a.len() {
+                for j in 1..=b.len() {
+                    let substitution = d[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+                    d[i][j] = substitution.min(d[i - 1][j] + 1).min(d[i][j - 1] + 1);
+                    if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                        d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+                    }
+                }
+            }
+            d[a.len()][b.len()]
+        }
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let a: Vec<u8> = (0..rng.gen_range(0..20))
+                .map(|_| rng.gen_range(b'a'..b'd'))
+                .collect();
+            let b: Vec<u8> = (0..rng.gen_range(0..20))
+                .map(|_| rng.gen_range(b'a'..b'd'))
+                .collect();
+            assert_eq!(osa_distance(&a, &b), full_matrix(&a, &b), "{:?} {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn full_confidence_matches_levenshtein_editops() {
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let a: Vec<u8> = (0..rng.gen_range(0..12))
+                .map(|_| rng.gen_range(b'a'..b'd'))
+                .collect();
+            let b: Vec<u8> = (0..rng.gen_range(0..12))
+                .map(|_| rng.gen_range(b'a'..b'd'))
+                .collect();
+            let (cost, ops) = confidence_editops(&a, &b, &vec![1.0; a.len()]);
+            assert_eq!(cost, levenshtein(&a, &b) as f64, "{:?} {:?}", a, b);
+            assert_eq!(ops.len(), levenshtein(&a, &b), "{:?} {:?}", a, b);
+            // Lower confidences never make the edits cost more.
+            let confidences: Vec<f64> = (0..a.len()).map(|_| rng.gen_range(0.0..=1.0)).collect();
+            assert!(confidence_editops(&a, &b, &confidences).0 <= cost);
+        }
+    }
+}
@@ -1,14 +1,37 @@
 //! Convenience methods to process fuzzy matching queries for common use cases.
+//!
+//! With feature "tracing", each run of [extract_without_order] and the
+//! functions built on it, [get_close_matches], [match_pairs], the parallel
+//! and streaming extractions and `ChoiceStore::extract` enters a `DEBUG`
+//! span named `extract`, with the name of the extracting `function`. Steps
+//! are only timed if the events are enabled. When done,
+//! it emits a `DEBUG` event in the span with the number of `candidates`, how
+//! many were `scored`, `pruned` by the cutoff and `matched`, the
+//! `prune_rate`, and the microseconds spent processing (`processing_us`) and
+//! scoring (`scoring_us`). Cancelled runs emit an "extraction cancelled"
+//! event instead.
+
+use crate::difflib::SequenceMatcher;
+use crate::error::Error;
+use crate::fuzz::Scorer;
+use crate::processing::Processor;
+use crate::score::Score;
+use crate::trace;
+use crate::utils::full_process;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Score multiple options against a base query string and return all exceeding a cutoff.
 ///
 /// Returns a Vec with the options and their match score if their score is above the cutoff.
 /// Results are configurable using custom text processors and scorers.
-/// Good default choices are `utils::full_process` as the processor, `fuzz:wratio` as the scorer, and zero as the score_cutoff.
+/// Good default choices are `utils::full_process` as the processor, `fuzz::WRatio` as the scorer, and zero as the score_cutoff.
+/// Any [Processor], such as a [Pipeline](crate::processing::Pipeline), can be used as the processor.
 ///
 /// ```
 /// # use fuzzywuzzy::process::extract_without_order;
-/// # use fuzzywuzzy::fuzz::wratio;
+/// # use fuzzywuzzy::fuzz::WRatio;
 /// let choices = vec![
 ///     "new york mets vs chicago cubs",
 ///     "chicago cubs vs chicago white sox",
@@ -25,13 +48,13 @@
 ///     extract_without_order(
 ///         "brave new cubs",
 ///         choices,
-///         |s, b| s.into(), // an alternative to full_process.
-///         &wratio,
+///         |s: &str, _: bool| s.to_owned(), // an alternative to full_process.
+///         WRatio,
 ///         0),
 ///     expected_results);
 /// ```
 pub fn extract_without_order<I, T, P, S>(
-    query: &str,
+    query: impl AsRef<str>,
     choices: I,
     processor: P,
     scorer: S,
@@ -40,10 +63,157 @@ pub fn extract_without_order<I, T, P, S>(
 where
     I: IntoIterator<Item = T>,
     T: AsRef<str>,
-    P: Fn(&str, bool) -> String,
-    S: Fn(&str, &str, bool, bool) -> u8,
+    P: Processor,
+    S: Scorer,
 {
-    let processed_query: String = processor(query, false);
+    let query = query.as_ref();
+    extract(query, choices, processor, scorer, score_cutoff, None)
+        .expect("extraction without a cancellation flag always completes")
+}
+
+/// Like [extract_without_order], but fails with [Error::EmptyInput] if `processor`
+/// reduces `query` to an empty string, in which case every choice would score 0.
+///
+/// ```
+/// # use fuzzywuzzy::process::try_extract_without_order;
+/// # use fuzzywuzzy::fuzz::WRatio;
+/// # use fuzzywuzzy::utils::full_process;
+/// # use fuzzywuzzy::error::Error;
+/// let choices = vec!["new york mets", "atlanta braves"];
+/// assert_eq!(
+///     try_extract_without_order("new york", &choices, &full_process, WRatio, 80),
+///     Ok(vec![("new york mets".to_string(), 90)]));
+/// assert_eq!(
+///     try_extract_without_order("?!", &choices, &full_process, WRatio, 80),
+///     Err(Error::EmptyInput));
+/// ```
+pub fn try_extract_without_order<I, T, P, S>(
+    query: impl AsRef<str>,
+    choices: I,
+    processor: P,
+    scorer: S,
+    score_cutoff: u8,
+) -> Result<Vec<(String, u8)>, Error>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+    P: Processor,
+    S: Scorer,
+{
+    let query = query.as_ref();
+    if processor.process(query, false).is_empty() {
+        return Err(Error::EmptyInput);
+    }
+    Ok(extract_without_order(
+        query,
+        choices,
+        processor,
+        scorer,
+        score_cutoff,
+    ))
+}
+
+/// Like [extract_without_order], but stops early if `cancel` is set.
+///
+/// `cancel` is checked before each choice is scored, so setting it from another
+/// thread (e.g. when a user hits "cancel") stops work on large choice lists promptly.
+/// Returns `None` if the extraction was cancelled before every choice was scored.
+///
+/// ```
+/// # use fuzzywuzzy::process::extract_without_order_cancellable;
+/// # use fuzzywuzzy::fuzz::WRatio;
+/// # use fuzzywuzzy::utils::full_process;
+/// use std::sync::atomic::AtomicBool;
+/// let choices = vec!["new york mets", "atlanta braves"];
+/// let cancel = AtomicBool::new(false);
+/// assert_eq!(
+///     extract_without_order_cancellable("new york", &choices, &full_process, WRatio, 80, &cancel),
+///     Some(vec![("new york mets".to_string(), 90)]));
+/// let cancel = AtomicBool::new(true);
+/// assert_eq!(
+///     extract_without_order_cancellable("new york", &choices, &full_process, WRatio, 80, &cancel),
+///     None);
+/// ```
+pub fn extract_without_order_cancellable<I, T, P, S>(
+    query: impl AsRef<str>,
+    choices: I,
+    processor: P,
+    scorer: S,
+    score_cutoff: u8,
+    cancel: &AtomicBool,
+) -> Option<Vec<(String, u8)>>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+    P: Processor,
+    S: Scorer,
+{
+    let query = query.as_ref();
+    extract(
+        query,
+        choices,
+        processor,
+        scorer,
+        score_cutoff,
+        Some(cancel),
+    )
+}
+
+fn extract<I, T, P, S>(
+    query: &str,
+    choices: I,
+    processor: P,
+    scorer: S,
+    score_cutoff: u8,
+    cancel: Option<&AtomicBool>,
+) -> Option<Vec<(String, u8)>>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+    P: Processor,
+    S: Scorer,
+{
+    let results = extract_items(
+        query,
+        choices,
+        |choice: &T| choice.as_ref(),
+        processor,
+        scorer,
+        score_cutoff,
+        cancel,
+    )?;
+    Some(
+        results
+            .into_iter()
+            .map(|(choice, score)| (choice.as_ref().to_string(), score))
+            .collect(),
+    )
+}
+
+/// Whether the cancellation flag of a cancellable function is set.
+fn is_cancelled(cancel: Option<&AtomicBool>) -> bool {
+    cancel.is_some_and(|c| c.load(Ordering::Relaxed))
+}
+
+/// [extract] for arbitrary items, scoring the text `text` returns for each.
+fn extract_items<I, T, K, P, S>(
+    query: &str,
+    choices: I,
+    text: K,
+    processor: P,
+    scorer: S,
+    score_cutoff: u8,
+    cancel: Option<&AtomicBool>,
+) -> Option<Vec<(T, u8)>>
+where
+    I: IntoIterator<Item = T>,
+    K: Fn(&T) -> &str,
+    P: Processor,
+    S: Scorer,
+{
+    let run = trace::Run::start("extract");
+    let _entered = run.enter();
+    let processed_query: String = run.processing(|| processor.process(query, false));
     if processed_query.is_empty() {
         // TODO: Make warning configurable, instead of being printed by default.
         // println!("Applied processor reduces input query to empty string, all comparisons will have score 0. [Query: '{0}']", processed_query.as_str());
@@ -55,12 +225,439 @@ where
 
     let mut results = vec![];
     for choice in choices {
-        let processed: String = processor(choice.as_ref(), false);
-        let score: u8 = scorer(processed_query.as_str(), processed.as_str(), true, true);
-        if score >= score_cutoff {
-            results.push((choice.as_ref().to_string(), score))
+        if is_cancelled(cancel) {
+            run.cancel();
+            return None;
+        }
+        run.candidate();
+        let processed: String = run.processing(|| processor.process(text(&choice), false));
+        if score_cutoff > 0 && scorer.upper_bound(&processed_query, &processed) < score_cutoff {
+            count!(candidates_pruned, 1);
+            run.pruned();
+            continue;
+        }
+        let score = run.scoring(|| {
+            scorer.score_with_cutoff(processed_query.as_str(), processed.as_str(), score_cutoff)
+        });
+        count!(candidates_scored, 1);
+        if let Some(score) = score {
+            results.push((choice, score.percent()))
+        } else {
+            count!(candidates_pruned, 1);
+            run.pruned();
         }
     }
+    run.finish(results.len());
+    Some(results)
+}
+
+/// Like [extract_without_order], but each choice comes with a payload, such
+/// as a database key, which is returned along with the choice and its score.
+///
+/// ```
+/// # use fuzzywuzzy::process::extract_with_payload;
+/// # use fuzzywuzzy::fuzz::WRatio;
+/// # use fuzzywuzzy::utils::full_process;
+/// let teams = vec![("New York Mets", 17), ("New York Yankees", 42), ("Atlanta Braves", 8)];
+/// assert_eq!(
+///     extract_with_payload("new york", teams, full_process, WRatio, 80),
+///     vec![("New York Mets", 17, 90), ("New York Yankees", 42, 90)]
+/// );
+/// ```
+pub fn extract_with_payload<I, T, D, P, S>(
+    query: impl AsRef<str>,
+    choices: I,
+    processor: P,
+    scorer: S,
+    score_cutoff: u8,
+) -> Vec<(T, D, u8)>
+where
+    I: IntoIterator<Item = (T, D)>,
+    T: AsRef<str>,
+    P: Processor,
+    S: Scorer,
+{
+    extract_items(
+        query.as_ref(),
+        choices,
+        |(choice, _): &(T, D)| choice.as_ref(),
+        processor,
+        scorer,
+        score_cutoff,
+        None,
+    )
+    .expect("extraction without a cancellation flag completes")
+    .into_iter()
+    .map(|((choice, payload), score)| (choice, payload, score))
+    .collect()
+}
+
+/// Like [extract_one], but each choice comes with a payload, see [extract_with_payload].
+///
+/// ```
+/// # use fuzzywuzzy::process::extract_one_with_payload;
+/// # use fuzzywuzzy::fuzz::WRatio;
+/// # use fuzzywuzzy::utils::full_process;
+/// let teams = vec![("New York Mets", 17), ("New York Yankees", 42), ("Atlanta Braves", 8)];
+/// let (_, id, _) = extract_one_with_payload("new york", teams, full_process, WRatio, 0).unwrap();
+/// assert_eq!(id, 17);
+/// ```
+pub fn extract_one_with_payload<I, T, D, P, S>(
+    query: impl AsRef<str>,
+    choices: I,
+    processor: P,
+    scorer: S,
+    score_cutoff: u8,
+) -> Option<(T, D, u8)>
+where
+    I: IntoIterator<Item = (T, D)>,
+    T: AsRef<str>,
+    P: Processor,
+    S: Scorer,
+{
+    extract_with_payload(query, choices, processor, scorer, score_cutoff)
+        .into_iter()
+        // The first of several equal maxima wins, see [extract_one].
+        .rev()
+        .max_by_key(|&(_, _, score)| score)
+}
+
+/// Like [extract_without_order], but identical choices are scored once:
+/// each distinct choice meeting the cutoff is returned once, in order of
+/// first appearance, with the indices of all its occurrences in `choices`.
+///
+/// Saves most of the scoring for choices with many exact duplicates, as in
+/// denormalized exports.
+///
+/// ```
+/// # use fuzzywuzzy::process::extract_deduplicated;
+/// # use fuzzywuzzy::fuzz::WRatio;
+/// # use fuzzywuzzy::utils::full_process;
+/// let cities = ["Boston", "Austin", "Boston", "Houston", "Boston", "Austin"];
+/// assert_eq!(
+///     extract_deduplicated("boston", cities, full_process, WRatio, 60),
+///     vec![("Boston".to_string(), vec![0, 2, 4], 100), ("Houston".to_string(), vec![3], 77)]
+/// );
+/// ```
+pub fn extract_deduplicated<I, T, P, S>(
+    query: impl AsRef<str>,
+    choices: I,
+    processor: P,
+    scorer: S,
+    score_cutoff: u8,
+) -> Vec<(String, Vec<usize>, u8)>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+    P: Processor,
+    S: Scorer,
+{
+    let mut groups: Vec<(T, Vec<usize>)> = vec![];
+    let mut group_of: HashMap<String, usize> = HashMap::new();
+    for (index, choice) in choices.into_iter().enumerate() {
+        match group_of.get(choice.as_ref()) {
+            Some(&group) => groups[group].1.push(index),
+            None => {
+                group_of.insert(choice.as_ref().to_string(), groups.len());
+                groups.push((choice, vec![index]));
+            }
+        }
+    }
+    extract_items(
+        query.as_ref(),
+        groups,
+        |(choice, _): &(T, Vec<usize>)| choice.as_ref(),
+        processor,
+        scorer,
+        score_cutoff,
+        None,
+    )
+    .expect("extraction without a cancellation flag completes")
+    .into_iter()
+    .map(|((choice, indices), score)| (choice.as_ref().to_string(), indices, score))
+    .collect()
+}
+
+/// A cheap test which choices must pass before they're scored, see [extract_filtered].
+///
+/// Closures taking the choice work as prefilters, and so do [Literal] and,
+/// with the "regex" feature, `regex::Regex`.
+pub trait Prefilter {
+    /// Whether to score `choice`.
+    fn accepts(&self, choice: &str) -> bool;
+}
+
+impl<F: Fn(&str) -> bool> Prefilter for F {
+    fn accepts(&self, choice: &str) -> bool {
+        self(choice)
+    }
+}
+
+/// Accepts choices containing a literal string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Literal<'a>(pub &'a str);
+
+impl Prefilter for Literal<'_> {
+    fn accepts(&self, choice: &str) -> bool {
+        choice.contains(self.0)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Prefilter for regex::Regex {
+    fn accepts(&self, choice: &str) -> bool {
+        self.is_match(choice)
+    }
+}
+
+/// Like [extract_without_order], but only scores the choices accepted by
+/// `prefilter`, which sees them before processing.
+///
+/// Use this when a structural requirement, like a product code format or a
+/// mandatory keyword, rules out most choices more cheaply than scoring them.
+///
+/// ```
+/// # use fuzzywuzzy::process::{extract_filtered, Literal};
+/// # use fuzzywuzzy::fuzz::Ratio;
+/// # use fuzzywuzzy::utils::full_process;
+/// let parts = ["M6 bolt, steel", "M8 bolt, steel", "M6 nut, steel", "M6 bolt, brass"];
+/// assert_eq!(
+///     extract_filtered("m6 bolt stel", parts, Literal("M6 bolt"), &full_process, Ratio, 0),
+///     vec![("M6 bolt, steel".to_string(), 92), ("M6 bolt, brass".to_string(), 69)]
+/// );
+/// let in_stock = |part: &str| !part.ends_with("brass");
+/// assert_eq!(extract_filtered("m6 bolt stel", parts, in_stock, &full_process, Ratio, 90).len(), 1);
+/// ```
+pub fn extract_filtered<I, T, F, P, S>(
+    query: impl AsRef<str>,
+    choices: I,
+    prefilter: F,
+    processor: P,
+    scorer: S,
+    score_cutoff: u8,
+) -> Vec<(String, u8)>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+    F: Prefilter,
+    P: Processor,
+    S: Scorer,
+{
+    let choices = choices.into_iter().filter(|choice| {
+        let accepted = prefilter.accepts(choice.as_ref());
+        if !accepted {
+            count!(candidates_pruned, 1);
+        }
+        accepted
+    });
+    extract_without_order(query, choices, processor, scorer, score_cutoff)
+}
+
+/// How [extract_sampled] picks the sample of choices it scores first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sampling {
+    /// Up to this many choices, evenly spaced through the corpus, so every
+    /// part of a sorted or grouped corpus is represented.
+    Stratified(usize),
+    /// Up to `size` choices picked at random, reproducibly for the same `seed`.
+    Random { size: usize, seed: u64 },
+}
+
+impl Sampling {
+    /// The sorted indices of the sample of `len` choices.
+    fn indices(self, len: usize) -> Vec<usize> {
+        match self {
+            Sampling::Stratified(size) => {
+                let size = size.min(len);
+                (0..size).map(|i| i * len / size).collect()
+            }
+            Sampling::Random { size, seed } => {
+                // Floyd's algorithm, drawing from a SplitMix64 generator.
+                let mut state = seed;
+                let mut next = move || {
+                    state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+                    let mut z = state;
+                    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+                    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+                    z ^ (z >> 31)
+                };
+                let mut sample = BTreeSet::new();
+                for j in len - size.min(len)..len {
+                    let i = (next() % (j as u64 + 1)) as usize;
+                    if !sample.insert(i) {
+                        sample.insert(j);
+                    }
+                }
+                sample.into_iter().collect()
+            }
+        }
+    }
+}
+
+/// Forwards to a borrowed scorer or processor, so it can serve several
+/// extractions.
+struct ByRef<'s, S>(&'s S);
+
+impl<P: Processor> Processor for ByRef<'_, P> {
+    fn process(&self, s: &str, force_ascii: bool) -> String {
+        self.0.process(s, force_ascii)
+    }
+}
+
+impl<S: Scorer> Scorer for ByRef<'_, S> {
+    fn score(&self, a: &str, b: &str) -> Score {
+        self.0.score(a, b)
+    }
+
+    fn upper_bound(&self, a: &str, b: &str) -> Score {
+        self.0.upper_bound(a, b)
+    }
+
+    fn score_with_cutoff(&self, a: &str, b: &str, score_cutoff: u8) -> Option<Score> {
+        self.0.score_with_cutoff(a, b, score_cutoff)
+    }
+}
+
+/// The `limit` best scoring choices, best first and otherwise in order, for
+/// exploring very large corpora interactively.
+///
+/// First scores a sample of the choices picked by `sampling` to estimate
+/// the score of the `limit`th best choice of the whole corpus, then scans
+/// the corpus with that as the cutoff, so most choices are pruned by
+/// [Scorer::upper_bound] or rejected early. The results are exact: should
+/// the estimate be too high to find `limit` choices, the corpus is scanned
+/// again with the `limit`th best score of the sample, which can't be.
+///
+/// ```
+/// # use fuzzywuzzy::process::{extract_sampled, Sampling};
+/// # use fuzzywuzzy::fuzz::Ratio;
+/// # use fuzzywuzzy::utils::full_process;
+/// let streets: Vec<String> = (1..=1000).map(|n| format!("{} main street", n)).collect();
+/// assert_eq!(
+///     extract_sampled("42 main st", &streets, full_process, Ratio, 2, Sampling::Stratified(50)),
+///     vec![("42 main street".to_string(), 83), ("142 main street".to_string(), 80)]
+/// );
+/// let random = Sampling::Random { size: 50, seed: 7 };
+/// assert_eq!(extract_sampled("42 main st", &streets, full_process, Ratio, 2, random)[0].1, 83);
+/// ```
+pub fn extract_sampled<T, P, S>(
+    query: impl AsRef<str>,
+    choices: &[T],
+    processor: P,
+    scorer: S,
+    limit: usize,
+    sampling: Sampling,
+) -> Vec<(String, u8)>
+where
+    T: AsRef<str>,
+    P: Processor,
+    S: Scorer,
+{
+    let query = query.as_ref();
+    if limit == 0 || choices.is_empty() {
+        return vec![];
+    }
+    let sample: Vec<&T> = sampling
+        .indices(choices.len())
+        .into_iter()
+        .map(|i| &choices[i])
+        .collect();
+    let mut sample_scores: Vec<u8> =
+        extract_without_order(query, &sample, ByRef(&processor), ByRef(&scorer), 0)
+            .into_iter()
+            .map(|(_, score)| score)
+            .collect();
+    sample_scores.sort_unstable_by(|a, b| b.cmp(a));
+    // Among the choices, the `limit`th best of the sample is at most the
+    // `limit`th best, and it is estimated to rank where `limit` does among
+    // the sample relative to the corpus.
+    let floor = sample_scores.get(limit - 1).copied().unwrap_or(0);
+    let estimate_rank = (limit * sample_scores.len()).div_ceil(choices.len()).max(1);
+    let estimate = sample_scores
+        .get(estimate_rank - 1)
+        .copied()
+        .unwrap_or(0)
+        .max(floor);
+
+    let mut results =
+        extract_without_order(query, choices, ByRef(&processor), ByRef(&scorer), estimate);
+    if results.len() < limit && estimate > floor {
+        results = extract_without_order(query, choices, ByRef(&processor), ByRef(&scorer), floor);
+    }
+    results.sort_by_key(|&(_, score)| Reverse(score));
+    results.truncate(limit);
+    results
+}
+
+/// A two-stage ranking for [extract_reranked]: a cheap scorer shortlists
+/// candidates, which a more precise and expensive scorer then ranks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rerank<F, S> {
+    /// The cheap scorer, e.g. `fuzz::DiceRatio` (feature "ngram").
+    pub first_pass: F,
+    /// How many of the best candidates by `first_pass` are ranked by `scorer`.
+    pub shortlist: usize,
+    /// The scorer ranking the shortlist, e.g. [WRatio](crate::fuzz::WRatio).
+    pub scorer: S,
+}
+
+/// Scores every choice by `rerank.first_pass`, then the `rerank.shortlist`
+/// best of them by `rerank.scorer`, and returns those scoring at least
+/// `score_cutoff` by it, best first and otherwise in order.
+///
+/// Choices the first pass ranks below the shortlist are never returned,
+/// even if `rerank.scorer` would score them higher. Ties at the end of the
+/// shortlist go to the first choices.
+///
+/// ```
+/// # #[cfg(feature = "ngram")] {
+/// # use fuzzywuzzy::process::{extract_reranked, Rerank};
+/// # use fuzzywuzzy::fuzz::{DiceRatio, WRatio};
+/// # use fuzzywuzzy::utils::full_process;
+/// let choices = ["new york mets", "new york yankees", "newark bears", "york minstermen"];
+/// let rerank = Rerank { first_pass: DiceRatio, shortlist: 2, scorer: WRatio };
+/// assert_eq!(
+///     extract_reranked("mets new york", choices, full_process, rerank, 0),
+///     vec![("new york mets".to_string(), 95), ("new york yankees".to_string(), 72)]
+/// );
+/// # }
+/// ```
+pub fn extract_reranked<I, T, P, F, S>(
+    query: impl AsRef<str>,
+    choices: I,
+    processor: P,
+    rerank: Rerank<F, S>,
+    score_cutoff: u8,
+) -> Vec<(String, u8)>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+    P: Processor,
+    F: Scorer,
+    S: Scorer,
+{
+    let query = query.as_ref();
+    let mut shortlist = extract_items(
+        query,
+        choices.into_iter().enumerate(),
+        |(_, choice): &(usize, T)| choice.as_ref(),
+        ByRef(&processor),
+        rerank.first_pass,
+        0,
+        None,
+    )
+    .expect("extraction without a cancellation flag completes");
+    shortlist.sort_by_key(|&(_, score)| Reverse(score));
+    shortlist.truncate(rerank.shortlist);
+    shortlist.sort_unstable_by_key(|((i, _), _)| *i);
+    let mut results = extract_without_order(
+        query,
+        shortlist.iter().map(|((_, choice), _)| choice),
+        ByRef(&processor),
+        rerank.scorer,
+        score_cutoff,
+    );
+    results.sort_by_key(|&(_, score)| Reverse(score));
     results
 }
 
@@ -73,7 +670,7 @@ where
 ///
 /// ```
 /// # use fuzzywuzzy::process::extract_one;
-/// use fuzzywuzzy::fuzz::wratio;
+/// use fuzzywuzzy::fuzz::WRatio;
 /// use fuzzywuzzy::utils::full_process;
 /// let choices = vec![
 ///     "new york mets vs chicago cubs",
@@ -85,7 +682,7 @@ where
 ///    extract_one("brave new cubs",
 ///       choices.iter(),
 ///       &full_process,
-///       &wratio,
+///       WRatio,
 ///       0).unwrap().0,
 ///    choices[0]
 /// );
@@ -94,7 +691,7 @@ where
 ///       "new york mets at atlanta braves",
 ///       choices.iter(),
 ///       &full_process,
-///       &wratio,
+///       WRatio,
 ///       0).unwrap().0,
 ///    choices[3]
 /// );
@@ -103,7 +700,7 @@ where
 ///       "philadelphia phillies at atlanta braves",
 ///       choices.iter(),
 ///       &full_process,
-///       &wratio,
+///       WRatio,
 ///       0).unwrap().0,
 ///    choices[2]
 /// );
@@ -112,7 +709,7 @@ where
 ///       "atlanta braves at philadelphia phillies",
 ///       choices.iter(),
 ///       &full_process,
-///       &wratio,
+///       WRatio,
 ///       0).unwrap().0,
 ///    choices[2]
 /// );
@@ -121,13 +718,13 @@ where
 ///       "chicago cubs vs new york mets",
 ///       choices.iter(),
 ///       &full_process,
-///       &wratio,
+///       WRatio,
 ///       0).unwrap().0,
 ///    choices[0]
 /// );
 /// ```
 pub fn extract_one<I, T, P, S>(
-    query: &str,
+    query: impl AsRef<str>,
     choices: I,
     processor: P,
     scorer: S,
@@ -136,14 +733,70 @@ pub fn extract_one<I, T, P, S>(
 where
     I: IntoIterator<Item = T>,
     T: AsRef<str>,
-    P: Fn(&str, bool) -> String,
-    S: Fn(&str, &str, bool, bool) -> u8,
+    P: Processor,
+    S: Scorer,
 {
-    let best = extract_without_order(query, choices, processor, scorer, score_cutoff);
-    if best.is_empty() {
-        return None;
-    }
-    best.iter()
+    let query = query.as_ref();
+    best_of(extract_without_order(
+        query,
+        choices,
+        processor,
+        scorer,
+        score_cutoff,
+    ))
+}
+
+/// Like [extract_one], but stops early if `cancel` is set, as
+/// [extract_without_order_cancellable] does.
+///
+/// Returns `None` if the extraction was cancelled, and `Some(None)` if it
+/// completed without a choice meeting the cutoff.
+///
+/// ```
+/// # use fuzzywuzzy::process::extract_one_cancellable;
+/// # use fuzzywuzzy::fuzz::WRatio;
+/// # use fuzzywuzzy::utils::full_process;
+/// use std::sync::atomic::AtomicBool;
+/// let choices = vec!["new york mets", "atlanta braves"];
+/// let cancel = AtomicBool::new(false);
+/// assert_eq!(
+///     extract_one_cancellable("new york", &choices, &full_process, WRatio, 80, &cancel),
+///     Some(Some(("new york mets".to_string(), 90))));
+/// let cancel = AtomicBool::new(true);
+/// assert_eq!(
+///     extract_one_cancellable("new york", &choices, &full_process, WRatio, 80, &cancel),
+///     None);
+/// ```
+pub fn extract_one_cancellable<I, T, P, S>(
+    query: impl AsRef<str>,
+    choices: I,
+    processor: P,
+    scorer: S,
+    score_cutoff: u8,
+    cancel: &AtomicBool,
+) -> Option<Option<(String, u8)>>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+    P: Processor,
+    S: Scorer,
+{
+    let query = query.as_ref();
+    extract(
+        query,
+        choices,
+        processor,
+        scorer,
+        score_cutoff,
+        Some(cancel),
+    )
+    .map(best_of)
+}
+
+/// The first of the highest scoring `results`.
+fn best_of(results: Vec<(String, u8)>) -> Option<(String, u8)> {
+    results
+        .into_iter()
         // Python and Rust have different semantics for which maximum value from an iterator is
         // returned when there are multiple equal values! In Python (when using max built-in), if
         // multiple items are maximal, the function returns the first one encountered. In Rust (when
@@ -152,6 +805,1032 @@ where
         // The solution here is to reverse the iterator to ensure the actual first item in the
         // original ordering of `choices` is returned (as this is the behavior of fuzzywuzzy).
         .rev()
-        .cloned()
         .max_by(|(_, acc_score), (_, score)| acc_score.cmp(score))
 }
+
+/// Like [extract_one], but fails with [Error::EmptyInput] if `processor`
+/// reduces `query` to an empty string, in which case every choice would score 0.
+pub fn try_extract_one<I, T, P, S>(
+    query: impl AsRef<str>,
+    choices: I,
+    processor: P,
+    scorer: S,
+    score_cutoff: u8,
+) -> Result<Option<(String, u8)>, Error>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+    P: Processor,
+    S: Scorer,
+{
+    let query = query.as_ref();
+    if processor.process(query, false).is_empty() {
+        return Err(Error::EmptyInput);
+    }
+    Ok(extract_one(query, choices, processor, scorer, score_cutoff))
+}
+
+/// Removes near-duplicates from `contains_dupes`, like fuzzywuzzy-py's `process.dedupe`.
+///
+/// Each item is compared with every item, after [full_process]. Of the items
+/// scoring more than `threshold` against it, the longest (then
+/// alphabetically first) represents it. The distinct representatives are
+/// returned in order of first appearance. fuzzywuzzy-py defaults to a
+/// threshold of 70 and [TokenSetRatio](crate::fuzz::TokenSetRatio).
+///
+/// This is quadratic in the number of items.
+///
+/// ```
+/// # use fuzzywuzzy::process::dedupe;
+/// # use fuzzywuzzy::fuzz::TokenSetRatio;
+/// let contains_dupes = ["Frodo Baggins", "Tom Sawyer", "Bilbo Baggin", "Samuel L. Jackson",
+///                       "F. Baggins", "Frody Baggins", "Bilbo Baggins"];
+/// assert_eq!(dedupe(contains_dupes, 70, TokenSetRatio),
+///            vec!["Frodo Baggins", "Tom Sawyer", "Bilbo Baggins", "Samuel L. Jackson"]);
+/// ```
+pub fn dedupe<I, T, S>(contains_dupes: I, threshold: u8, scorer: S) -> Vec<String>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+    S: Scorer,
+{
+    dedupe_items(contains_dupes, threshold, scorer, None)
+        .expect("deduplication without a cancellation flag completes")
+}
+
+/// Like [dedupe], but stops early if `cancel` is set.
+///
+/// `cancel` is checked before each item is compared with the others.
+/// Returns `None` if the deduplication was cancelled.
+///
+/// ```
+/// # use fuzzywuzzy::process::dedupe_cancellable;
+/// # use fuzzywuzzy::fuzz::TokenSetRatio;
+/// use std::sync::atomic::AtomicBool;
+/// let contains_dupes = ["Frodo Baggins", "F. Baggins", "Tom Sawyer"];
+/// let cancel = AtomicBool::new(false);
+/// assert_eq!(dedupe_cancellable(contains_dupes, 70, TokenSetRatio, &cancel),
+///            Some(vec!["Frodo Baggins".to_string(), "Tom Sawyer".to_string()]));
+/// let cancel = AtomicBool::new(true);
+/// assert_eq!(dedupe_cancellable(contains_dupes, 70, TokenSetRatio, &cancel), None);
+/// ```
+pub fn dedupe_cancellable<I, T, S>(
+    contains_dupes: I,
+    threshold: u8,
+    scorer: S,
+    cancel: &AtomicBool,
+) -> Option<Vec<String>>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+    S: Scorer,
+{
+    dedupe_items(contains_dupes, threshold, scorer, Some(cancel))
+}
+
+fn dedupe_items<I, T, S>(
+    contains_dupes: I,
+    threshold: u8,
+    scorer: S,
+    cancel: Option<&AtomicBool>,
+) -> Option<Vec<String>>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+    S: Scorer,
+{
+    let items: Vec<String> = contains_dupes
+        .into_iter()
+        .map(|item| item.as_ref().to_string())
+        .collect();
+    let processed: Vec<String> = items.iter().map(|item| full_process(item, false)).collect();
+    let mut representatives: Vec<String> = vec![];
+    for query in processed.iter() {
+        if is_cancelled(cancel) {
+            return None;
+        }
+        let representative = items
+            .iter()
+            .zip(processed.iter())
+            .filter(|(_, choice)| scorer.score(query, choice) > threshold)
+            .map(|(item, _)| item)
+            .min_by(|a, b| {
+                b.chars()
+                    .count()
+                    .cmp(&a.chars().count())
+                    .then_with(|| a.cmp(b))
+            });
+        if let Some(representative) = representative {
+            if !representatives.contains(representative) {
+                representatives.push(representative.clone());
+            }
+        }
+    }
+    if representatives.len() == items.len() {
+        Some(items)
+    } else {
+        Some(representatives)
+    }
+}
+
+/// Returns up to `n` of `possibilities` which are close matches for `word`,
+/// best first, like Python's `difflib.get_close_matches`.
+///
+/// Possibilities are compared by their [SequenceMatcher] ratio with `word`,
+/// character by character and without any processing, and those with a
+/// ratio below `cutoff` are dropped. Ties are broken the way difflib does, by
+/// ordering the possibilities themselves from greatest to least.
+///
+/// difflib's defaults are `n = 3` and `cutoff = 0.6`.
+///
+/// # Panics
+/// If `n` is 0 or `cutoff` isn't between 0.0 and 1.0, which difflib rejects
+/// too, see [try_get_close_matches].
+///
+/// ```
+/// # use fuzzywuzzy::process::get_close_matches;
+/// assert_eq!(get_close_matches("appel", ["ape", "apple", "peach", "puppy"], 3, 0.6), vec!["apple", "ape"]);
+/// let keywords = ["break", "else", "if", "while", "with"];
+/// assert_eq!(get_close_matches("wheel", keywords, 3, 0.6), vec!["while"]);
+/// assert!(get_close_matches("pineapple", keywords, 3, 0.6).is_empty());
+/// ```
+pub fn get_close_matches<I, T>(
+    word: impl AsRef<str>,
+    possibilities: I,
+    n: usize,
+    cutoff: f64,
+) -> Vec<String>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+{
+    try_get_close_matches(word, possibilities, n, cutoff)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Like [get_close_matches], but returns [Error::InvalidConfig] instead of
+/// panicking if `n` is 0 or `cutoff` isn't between 0.0 and 1.0.
+///
+/// ```
+/// # use fuzzywuzzy::process::try_get_close_matches;
+/// # use fuzzywuzzy::error::Error;
+/// assert_eq!(try_get_close_matches("appel", ["ape", "apple"], 1, 0.6), Ok(vec!["apple".to_string()]));
+/// assert!(matches!(try_get_close_matches("appel", ["apple"], 0, 0.6), Err(Error::InvalidConfig(_))));
+/// assert!(matches!(try_get_close_matches("appel", ["apple"], 3, 1.5), Err(Error::InvalidConfig(_))));
+/// ```
+pub fn try_get_close_matches<I, T>(
+    word: impl AsRef<str>,
+    possibilities: I,
+    n: usize,
+    cutoff: f64,
+) -> Result<Vec<String>, Error>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+{
+    if n == 0 {
+        return Err(Error::InvalidConfig("n must be greater than 0"));
+    }
+    if !(0.0..=1.0).contains(&cutoff) {
+        return Err(Error::InvalidConfig("cutoff must be in [0.0, 1.0]"));
+    }
+    let run = trace::Run::start("get_close_matches");
+    let _entered = run.enter();
+    // https://github.com/python/cpython/blob/3.12/Lib/difflib.py#L688
+    let word: Vec<char> = run.processing(|| word.as_ref().chars().collect());
+    let possibilities: Vec<(T, Vec<char>)> = possibilities
+        .into_iter()
+        .map(|x| {
+            let chars = run.processing(|| x.as_ref().chars().collect());
+            (x, chars)
+        })
+        .collect();
+    // `word` is indexed once and reused for every possibility.
+    let mut matcher = SequenceMatcher::new(&[], &word);
+    let mut matches: Vec<(f64, &str)> = vec![];
+    for (x, chars) in possibilities.iter() {
+        run.candidate();
+        matcher.set_seq1(chars);
+        count!(candidates_scored, 1);
+        let ratio = run.scoring(|| {
+            (matcher.real_quick_ratio() >= cutoff && matcher.quick_ratio() >= cutoff)
+                .then(|| matcher.ratio())
+                .filter(|&ratio| ratio >= cutoff)
+        });
+        if let Some(ratio) = ratio {
+            matches.push((ratio, x.as_ref()));
+        } else {
+            count!(candidates_pruned, 1);
+            run.pruned();
+        }
+    }
+    matches.sort_by(|(a_ratio, a), (b_ratio, b)| {
+        b_ratio.partial_cmp(a_ratio).unwrap().then_with(|| b.cmp(a))
+    });
+    matches.truncate(n);
+    run.finish(matches.len());
+    Ok(matches.into_iter().map(|(_, x)| x.to_string()).collect())
+}
+
+/// How [match_pairs_with] pairs items.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Assignment {
+    /// The pairs with the highest total score, found by the Hungarian
+    /// algorithm in `O(n² m)` time for `n` items on the smaller side and `m`
+    /// on the larger.
+    Optimal,
+    /// Repeatedly pairs the highest scoring items not yet paired, in
+    /// `O(n m log(n m))` time. The total score may be lower than optimal.
+    Greedy,
+}
+
+/// Pairs each item of the smaller of `left` and `right` with a different
+/// item of the other, maximizing the total score by `scorer`.
+///
+/// Returns the indices of the paired items and their scores, by left index.
+/// Unlike extraction, which may match several items to the same choice,
+/// each item is paired at most once, as reconciling two lists requires.
+/// Items are scored as given, so the scorer should process them if needed.
+///
+/// ```
+/// # use fuzzywuzzy::process::match_pairs;
+/// # use fuzzywuzzy::fuzz::Ratio;
+/// let invoices = ["acme corp", "acme corporation", "globex"];
+/// let payments = ["acme corporation inc", "acme co", "globex llc", "hooli"];
+/// assert_eq!(
+///     match_pairs(invoices, payments, Ratio),
+///     vec![(0, 1, 88), (1, 0, 89), (2, 2, 75)]
+/// );
+/// ```
+pub fn match_pairs<L, R, T, U, S>(left: L, right: R, scorer: S) -> Vec<(usize, usize, u8)>
+where
+    L: IntoIterator<Item = T>,
+    R: IntoIterator<Item = U>,
+    T: AsRef<str>,
+    U: AsRef<str>,
+    S: Scorer,
+{
+    match_pairs_with(left, right, scorer, Assignment::Optimal)
+}
+
+/// [match_pairs] with the given [Assignment].
+///
+/// ```
+/// # use fuzzywuzzy::process::{match_pairs_with, Assignment};
+/// # use fuzzywuzzy::fuzz::Ratio;
+/// let customers = ["jon smith", "john smithe"];
+/// let patients = ["john smith", "jon smyth"];
+/// assert_eq!(
+///     match_pairs_with(customers, patients, Ratio, Assignment::Optimal),
+///     vec![(0, 1, 89), (1, 0, 95)]
+/// );
+/// // The best single pair leaves a worse one.
+/// assert_eq!(
+///     match_pairs_with(customers, patients, Ratio, Assignment::Greedy),
+///     vec![(0, 0, 95), (1, 1, 80)]
+/// );
+/// ```
+pub fn match_pairs_with<L, R, T, U, S>(
+    left: L,
+    right: R,
+    scorer: S,
+    assignment: Assignment,
+) -> Vec<(usize, usize, u8)>
+where
+    L: IntoIterator<Item = T>,
+    R: IntoIterator<Item = U>,
+    T: AsRef<str>,
+    U: AsRef<str>,
+    S: Scorer,
+{
+    pair_items(left, right, scorer, assignment, None)
+        .expect("pairing without a cancellation flag completes")
+}
+
+/// Like [match_pairs_with], but stops early if `cancel` is set.
+///
+/// `cancel` is checked before each left item is scored against the right
+/// items, and before each is paired. Returns `None` if the pairing was
+/// cancelled.
+///
+/// ```
+/// # use fuzzywuzzy::process::{match_pairs_with_cancellable, Assignment};
+/// # use fuzzywuzzy::fuzz::Ratio;
+/// use std::sync::atomic::AtomicBool;
+/// let customers = ["jon smith", "john smithe"];
+/// let patients = ["john smith", "jon smyth"];
+/// let cancel = AtomicBool::new(false);
+/// assert_eq!(
+///     match_pairs_with_cancellable(customers, patients, Ratio, Assignment::Optimal, &cancel),
+///     Some(vec![(0, 1, 89), (1, 0, 95)])
+/// );
+/// let cancel = AtomicBool::new(true);
+/// assert_eq!(
+///     match_pairs_with_cancellable(customers, patients, Ratio, Assignment::Optimal, &cancel),
+///     None
+/// );
+/// ```
+pub fn match_pairs_with_cancellable<L, R, T, U, S>(
+    left: L,
+    right: R,
+    scorer: S,
+    assignment: Assignment,
+    cancel: &AtomicBool,
+) -> Option<Vec<(usize, usize, u8)>>
+where
+    L: IntoIterator<Item = T>,
+    R: IntoIterator<Item = U>,
+    T: AsRef<str>,
+    U: AsRef<str>,
+    S: Scorer,
+{
+    pair_items(left, right, scorer, assignment, Some(cancel))
+}
+
+fn pair_items<L, R, T, U, S>(
+    left: L,
+    right: R,
+    scorer: S,
+    assignment: Assignment,
+    cancel: Option<&AtomicBool>,
+) -> Option<Vec<(usize, usize, u8)>>
+where
+    L: IntoIterator<Item = T>,
+    R: IntoIterator<Item = U>,
+    T: AsRef<str>,
+    U: AsRef<str>,
+    S: Scorer,
+{
+    let run = trace::Run::start("match_pairs");
+    let _entered = run.enter();
+    let right: Vec<U> = right.into_iter().collect();
+    let mut scores: Vec<Vec<u8>> = vec![];
+    for a in left {
+        if is_cancelled(cancel) {
+            run.cancel();
+            return None;
+        }
+        count!(candidates_scored, right.len());
+        scores.push(
+            right
+                .iter()
+                .map(|b| {
+                    run.candidate();
+                    run.scoring(|| scorer.score(a.as_ref(), b.as_ref()).percent())
+                })
+                .collect(),
+        );
+    }
+    let pairs = match assignment {
+        Assignment::Optimal => optimal_pairs(&scores, cancel),
+        Assignment::Greedy => Some(greedy_pairs(&scores)),
+    };
+    let Some(mut pairs) = pairs else {
+        run.cancel();
+        return None;
+    };
+    pairs.sort_unstable();
+    run.finish(pairs.len());
+    Some(
+        pairs
+            .into_iter()
+            .map(|(i, j)| (i, j, scores[i][j]))
+            .collect(),
+    )
+}
+
+/// The pairs of rows and columns of `scores` with the highest total score,
+/// by the Hungarian algorithm with potentials, or `None` if cancelled.
+fn optimal_pairs(scores: &[Vec<u8>], cancel: Option<&AtomicBool>) -> Option<Vec<(usize, usize)>> {
+    let rows = scores.len();
+    let columns = scores.first().map_or(0, Vec::len);
+    if rows > columns {
+        let transposed: Vec<Vec<u8>> = (0..columns)
+            .map(|j| scores.iter().map(|row| row[j]).collect())
+            .collect();
+        let pairs = optimal_pairs(&transposed, cancel)?;
+        return Some(pairs.into_iter().map(|(j, i)| (i, j)).collect());
+    }
+    // Minimizes the cost `100 - score`, with rows and columns numbered from
+    // 1 and column 0 standing for the row being added.
+    let cost = |i: usize, j: usize| 100 - i64::from(scores[i - 1][j - 1]);
+    let (mut u, mut v) = (vec![0i64; rows + 1], vec![0i64; columns + 1]);
+    // `row_of[j]` is the row paired with column `j`, or 0.
+    let mut row_of = vec![0; columns + 1];
+    let mut way = vec![0; columns + 1];
+    for i in 1..=rows {
+        if is_cancelled(cancel) {
+            return None;
+        }
+        row_of[0] = i;
+        let mut j0 = 0;
+        let mut min_slack = vec![i64::MAX; columns + 1];
+        let mut used = vec![false; columns + 1];
+        loop {
+            used[j0] = true;
+            let i0 = row_of[j0];
+            let (mut delta, mut j1) = (i64::MAX, 0);
+            for j in 1..=columns {
+                if used[j] {
+                    continue;
+                }
+                let slack = cost(i0, j) - u[i0] - v[j];
+                if slack < min_slack[j] {
+                    min_slack[j] = slack;
+                    way[j] = j0;
+                }
+                if min_slack[j] < delta {
+                    delta = min_slack[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=columns {
+                if used[j] {
+                    u[row_of[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_slack[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if row_of[j0] == 0 {
+                break;
+            }
+        }
+        // Shifts the pairs along the augmenting path.
+        while j0 != 0 {
+            let j1 = way[j0];
+            row_of[j0] = row_of[j1];
+            j0 = j1;
+        }
+    }
+    Some(
+        (1..=columns)
+            .filter(|&j| row_of[j] != 0)
+            .map(|j| (row_of[j] - 1, j - 1))
+            .collect(),
+    )
+}
+
+/// The pairs of rows and columns of `scores` chosen highest score first,
+/// and otherwise in order.
+fn greedy_pairs(scores: &[Vec<u8>]) -> Vec<(usize, usize)> {
+    let mut candidates: Vec<(u8, usize, usize)> = scores
+        .iter()
+        .enumerate()
+        .flat_map(|(i, row)| row.iter().enumerate().map(move |(j, &score)| (score, i, j)))
+        .collect();
+    candidates.sort_by(|x, y| y.0.cmp(&x.0).then((x.1, x.2).cmp(&(y.1, y.2))));
+    let columns = scores.first().map_or(0, Vec::len);
+    let (mut row_used, mut column_used) = (vec![false; scores.len()], vec![false; columns]);
+    let mut pairs = vec![];
+    for (_, i, j) in candidates {
+        if !row_used[i] && !column_used[j] {
+            row_used[i] = true;
+            column_used[j] = true;
+            pairs.push((i, j));
+        }
+    }
+    pairs
+}
+
+#[cfg(feature = "async")]
+pub use self::async_extract::*;
+
+#[cfg(feature = "async")]
+mod async_extract {
+    use crate::fuzz::Scorer;
+    use crate::processing::Processor;
+    use crate::trace;
+    use futures_core::Stream;
+    use std::collections::VecDeque;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    /// Score choices lazily, yielding those exceeding a cutoff as a [Stream]. Requires feature "async".
+    ///
+    /// This is the asynchronous counterpart of [super::extract_without_order].
+    /// Choices are scored `chunk_size` at a time. After each chunk the stream
+    /// returns [Poll::Pending] (waking itself immediately), giving the executor
+    /// a chance to run other tasks so large extractions don't starve it.
+    ///
+    /// Results are yielded in the same order as [super::extract_without_order] returns them.
+    ///
+    /// ```
+    /// # use fuzzywuzzy::process::extract_stream;
+    /// # use fuzzywuzzy::fuzz::WRatio;
+    /// # use fuzzywuzzy::utils::full_process;
+    /// use futures::executor::block_on;
+    /// use futures::stream::StreamExt;
+    /// let choices = vec!["new york mets", "atlanta braves", "new york yankees"];
+    /// let stream = extract_stream("new york", choices, full_process, WRatio, 80, 2);
+    /// assert_eq!(
+    ///     block_on(stream.collect::<Vec<_>>()),
+    ///     vec![("new york mets".to_string(), 90), ("new york yankees".to_string(), 90)]);
+    /// ```
+    pub fn extract_stream<I, T, P, S>(
+        query: impl AsRef<str>,
+        choices: I,
+        processor: P,
+        scorer: S,
+        score_cutoff: u8,
+        chunk_size: usize,
+    ) -> ExtractStream<I::IntoIter, P, S>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+        P: Processor,
+        S: Scorer,
+    {
+        let query = query.as_ref();
+        let run = trace::Run::start("extract_stream");
+        let processed_query = {
+            let _entered = run.enter();
+            run.processing(|| processor.process(query, false))
+        };
+        ExtractStream {
+            run,
+            matched: 0,
+            processed_query,
+            choices: choices.into_iter(),
+            processor,
+            scorer,
+            score_cutoff,
+            chunk_size: chunk_size.max(1),
+            ready: VecDeque::new(),
+            should_yield: false,
+            exhausted: false,
+        }
+    }
+
+    /// The [Stream] returned by [extract_stream].
+    pub struct ExtractStream<I, P, S> {
+        run: trace::Run,
+        matched: usize,
+        processed_query: String,
+        choices: I,
+        processor: P,
+        scorer: S,
+        score_cutoff: u8,
+        chunk_size: usize,
+        ready: VecDeque<(String, u8)>,
+        should_yield: bool,
+        exhausted: bool,
+    }
+
+    impl<I, T, P, S> ExtractStream<I, P, S>
+    where
+        I: Iterator<Item = T>,
+        T: AsRef<str>,
+        P: Processor,
+        S: Scorer,
+    {
+        fn score_chunk(&mut self) {
+            let run = &self.run;
+            let _entered = run.enter();
+            for _ in 0..self.chunk_size {
+                let choice = match self.choices.next() {
+                    Some(choice) => choice,
+                    None => {
+                        self.exhausted = true;
+                        run.finish(self.matched);
+                        return;
+                    }
+                };
+                run.candidate();
+                let processed: String =
+                    run.processing(|| self.processor.process(choice.as_ref(), false));
+                if self.score_cutoff > 0
+                    && self.scorer.upper_bound(&self.processed_query, &processed)
+                        < self.score_cutoff
+                {
+                    count!(candidates_pruned, 1);
+                    run.pruned();
+                    continue;
+                }
+                let score = run.scoring(|| {
+                    self.scorer.score_with_cutoff(
+                        &self.processed_query,
+                        &processed,
+                        self.score_cutoff,
+                    )
+                });
+                count!(candidates_scored, 1);
+                if let Some(score) = score {
+                    self.matched += 1;
+                    self.ready
+                        .push_back((choice.as_ref().to_string(), score.percent()));
+                } else {
+                    count!(candidates_pruned, 1);
+                    run.pruned();
+                }
+            }
+        }
+    }
+
+    impl<I, T, P, S> Stream for ExtractStream<I, P, S>
+    where
+        I: Iterator<Item = T> + Unpin,
+        T: AsRef<str>,
+        P: Processor + Unpin,
+        S: Scorer + Unpin,
+    {
+        type Item = (String, u8);
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let this = self.get_mut();
+            loop {
+                if let Some(result) = this.ready.pop_front() {
+                    return Poll::Ready(Some(result));
+                }
+                if this.exhausted {
+                    return Poll::Ready(None);
+                }
+                if this.should_yield {
+                    this.should_yield = false;
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+                this.score_chunk();
+                this.should_yield = true;
+            }
+        }
+    }
+
+    #[cfg(all(test, feature = "token"))]
+    mod test {
+        use super::*;
+        use crate::fuzz::WRatio;
+        use crate::process::extract_without_order;
+        use crate::utils::full_process;
+        use futures::executor::block_on;
+        use futures::stream::StreamExt;
+
+        #[test]
+        fn stream_matches_serial() {
+            let choices = [
+                "new york mets",
+                "new york yankees",
+                "newark",
+                "atlanta braves",
+                "",
+            ];
+            for cutoff in [0, 50, 80, 95].iter() {
+                for chunk_size in 1..4 {
+                    let stream = extract_stream(
+                        "new york",
+                        choices,
+                        full_process,
+                        WRatio,
+                        *cutoff,
+                        chunk_size,
+                    );
+                    assert_eq!(
+                        block_on(stream.collect::<Vec<_>>()),
+                        extract_without_order("new york", choices, full_process, WRatio, *cutoff)
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+pub use self::parallel_extract::*;
+
+#[cfg(feature = "parallel")]
+mod parallel_extract {
+    use super::{is_cancelled, ByRef};
+    use crate::fuzz::Scorer;
+    use crate::processing::Processor;
+    use crate::trace;
+    use rayon::prelude::*;
+    use rayon::ThreadPool;
+    use std::sync::atomic::AtomicBool;
+
+    /// Parallel version of [super::extract_without_order] using rayon's global thread pool. Requires feature "parallel".
+    ///
+    /// The output is identical to [super::extract_without_order], including its order,
+    /// regardless of how the work is split between threads.
+    ///
+    /// ```
+    /// # use fuzzywuzzy::process::{extract_without_order, par_extract_without_order};
+    /// # use fuzzywuzzy::fuzz::WRatio;
+    /// # use fuzzywuzzy::utils::full_process;
+    /// let choices = vec!["new york mets", "atlanta braves", "new york yankees"];
+    /// assert_eq!(
+    ///     par_extract_without_order("new york", &choices, &full_process, WRatio, 80),
+    ///     extract_without_order("new york", &choices, &full_process, WRatio, 80));
+    /// ```
+    pub fn par_extract_without_order<I, T, P, S>(
+        query: impl AsRef<str>,
+        choices: I,
+        processor: P,
+        scorer: S,
+        score_cutoff: u8,
+    ) -> Vec<(String, u8)>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str> + Send + Sync,
+        P: Processor + Sync,
+        S: Scorer + Sync,
+    {
+        let query = query.as_ref();
+        par_extract(query, choices, processor, scorer, score_cutoff, None)
+            .expect("extraction without a cancellation flag always completes")
+    }
+
+    /// Like [par_extract_without_order], but stops early if `cancel` is set,
+    /// as [super::extract_without_order_cancellable] does.
+    ///
+    /// Each thread checks `cancel` before scoring a choice. Returns `None` if
+    /// the extraction was cancelled before every choice was scored. Run it
+    /// inside [ThreadPool::install] to use another pool.
+    ///
+    /// ```
+    /// # use fuzzywuzzy::process::par_extract_without_order_cancellable;
+    /// # use fuzzywuzzy::fuzz::WRatio;
+    /// # use fuzzywuzzy::utils::full_process;
+    /// use std::sync::atomic::AtomicBool;
+    /// let choices = vec!["new york mets", "atlanta braves"];
+    /// let cancel = AtomicBool::new(false);
+    /// assert_eq!(
+    ///     par_extract_without_order_cancellable("new york", &choices, &full_process, WRatio, 80, &cancel),
+    ///     Some(vec![("new york mets".to_string(), 90)]));
+    /// let cancel = AtomicBool::new(true);
+    /// assert_eq!(
+    ///     par_extract_without_order_cancellable("new york", &choices, &full_process, WRatio, 80, &cancel),
+    ///     None);
+    /// ```
+    pub fn par_extract_without_order_cancellable<I, T, P, S>(
+        query: impl AsRef<str>,
+        choices: I,
+        processor: P,
+        scorer: S,
+        score_cutoff: u8,
+        cancel: &AtomicBool,
+    ) -> Option<Vec<(String, u8)>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str> + Send + Sync,
+        P: Processor + Sync,
+        S: Scorer + Sync,
+    {
+        let query = query.as_ref();
+        par_extract(
+            query,
+            choices,
+            processor,
+            scorer,
+            score_cutoff,
+            Some(cancel),
+        )
+    }
+
+    fn par_extract<I, T, P, S>(
+        query: &str,
+        choices: I,
+        processor: P,
+        scorer: S,
+        score_cutoff: u8,
+        cancel: Option<&AtomicBool>,
+    ) -> Option<Vec<(String, u8)>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str> + Send + Sync,
+        P: Processor + Sync,
+        S: Scorer + Sync,
+    {
+        let run = trace::Run::start("par_extract_without_order");
+        let _entered = run.enter();
+        // The worker threads report to the run without entering its span.
+        let stats = &*run;
+        let processed_query: String = run.processing(|| processor.process(query, false));
+        let choices: Vec<T> = choices.into_iter().collect();
+        // The outer `Option` is `None` once cancelled, which stops the
+        // collection, and the inner one whether the choice matched.
+        let results: Option<Vec<Option<(String, u8)>>> = choices
+            .par_iter()
+            .map(|choice| {
+                if is_cancelled(cancel) {
+                    return None;
+                }
+                stats.candidate();
+                let processed: String =
+                    stats.processing(|| processor.process(choice.as_ref(), false));
+                if score_cutoff > 0
+                    && scorer.upper_bound(&processed_query, &processed) < score_cutoff
+                {
+                    count!(candidates_pruned, 1);
+                    stats.pruned();
+                    return Some(None);
+                }
+                let score = stats.scoring(|| {
+                    scorer.score_with_cutoff(
+                        processed_query.as_str(),
+                        processed.as_str(),
+                        score_cutoff,
+                    )
+                });
+                count!(candidates_scored, 1);
+                if let Some(score) = score {
+                    Some(Some((choice.as_ref().to_string(), score.percent())))
+                } else {
+                    count!(candidates_pruned, 1);
+                    stats.pruned();
+                    Some(None)
+                }
+            })
+            .collect();
+        let Some(results) = results else {
+            run.cancel();
+            return None;
+        };
+        let results: Vec<(String, u8)> = results.into_iter().flatten().collect();
+        run.finish(results.len());
+        Some(results)
+    }
+
+    /// Like [par_extract_without_order], but runs on the supplied `pool` instead of rayon's global pool.
+    ///
+    /// ```
+    /// # use fuzzywuzzy::process::{extract_without_order, par_extract_without_order_in};
+    /// # use fuzzywuzzy::fuzz::WRatio;
+    /// # use fuzzywuzzy::utils::full_process;
+    /// let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+    /// let choices = vec!["new york mets", "atlanta braves", "new york yankees"];
+    /// assert_eq!(
+    ///     par_extract_without_order_in(&pool, "new york", &choices, &full_process, WRatio, 80),
+    ///     extract_without_order("new york", &choices, &full_process, WRatio, 80));
+    /// ```
+    pub fn par_extract_without_order_in<I, T, P, S>(
+        pool: &ThreadPool,
+        query: impl AsRef<str>,
+        choices: I,
+        processor: P,
+        scorer: S,
+        score_cutoff: u8,
+    ) -> Vec<(String, u8)>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str> + Send + Sync,
+        P: Processor + Sync,
+        S: Scorer + Sync,
+    {
+        let query = query.as_ref();
+        let choices: Vec<T> = choices.into_iter().collect();
+        pool.install(|| {
+            par_extract_without_order(
+                query,
+                choices,
+                ByRef(&processor),
+                ByRef(&scorer),
+                score_cutoff,
+            )
+        })
+    }
+
+    #[cfg(all(test, feature = "token"))]
+    mod test {
+        use super::*;
+        use crate::fuzz::WRatio;
+        use crate::process::extract_without_order;
+        use crate::utils::full_process;
+        use rand::distributions::Alphanumeric;
+        use rand::{thread_rng, Rng};
+
+        #[test]
+        fn parallel_matches_serial() {
+            let mut rng = thread_rng();
+            let choices: Vec<String> = (0..2000)
+                .map(|_| {
+                    let len = rng.gen_range(1..24);
+                    (&mut rng)
+                        .sample_iter(&Alphanumeric)
+                        .take(len)
+                        .map(char::from)
+                        .collect()
+                })
+                .collect();
+            let serial = extract_without_order("abc xyz", &choices, full_process, WRatio, 20);
+            assert_eq!(
+                par_extract_without_order("abc xyz", &choices, full_process, WRatio, 20),
+                serial
+            );
+            for threads in 1..5 {
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .build()
+                    .unwrap();
+                assert_eq!(
+                    par_extract_without_order_in(
+                        &pool,
+                        "abc xyz",
+                        &choices,
+                        full_process,
+                        WRatio,
+                        20
+                    ),
+                    serial
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fuzz::Ratio;
+    use rand::{thread_rng, Rng};
+
+    /// The highest total score of pairing every row with a different column.
+    fn brute_force(scores: &[Vec<u8>], i: usize, used: &mut Vec<bool>) -> u32 {
+        if i == scores.len() {
+            return 0;
+        }
+        let mut best = 0;
+        for j in 0..used.len() {
+            if !used[j] {
+                used[j] = true;
+                best = best.max(u32::from(scores[i][j]) + brute_force(scores, i + 1, used));
+                used[j] = false;
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn optimal_pairs_maximize_total_score() {
+        let mut rng = thread_rng();
+        for _ in 0..300 {
+            let (rows, columns) = (rng.gen_range(0..6), rng.gen_range(0..6));
+            let scores: Vec<Vec<u8>> = (0..rows)
+                .map(|_| (0..columns).map(|_| rng.gen_range(0..=100)).collect())
+                .collect();
+            let total = |pairs: &[(usize, usize)]| -> u32 {
+                pairs.iter().map(|&(i, j)| u32::from(scores[i][j])).sum()
+            };
+            let optimal = optimal_pairs(&scores, None).unwrap();
+            let greedy = greedy_pairs(&scores);
+            for pairs in [&optimal, &greedy] {
+                assert_eq!(pairs.len(), rows.min(columns));
+                let mut rows_seen: Vec<usize> = pairs.iter().map(|&(i, _)| i).collect();
+                let mut columns_seen: Vec<usize> = pairs.iter().map(|&(_, j)| j).collect();
+                rows_seen.sort_unstable();
+                rows_seen.dedup();
+                columns_seen.sort_unstable();
+                columns_seen.dedup();
+                assert_eq!(
+                    (rows_seen.len(), columns_seen.len()),
+                    (pairs.len(), pairs.len())
+                );
+            }
+            let best = if rows <= columns {
+                brute_force(&scores, 0, &mut vec![false; columns])
+            } else {
+                let transposed: Vec<Vec<u8>> = (0..columns)
+                    .map(|j| scores.iter().map(|row| row[j]).collect())
+                    .collect();
+                brute_force(&transposed, 0, &mut vec![false; rows])
+            };
+            assert_eq!(total(&optimal), best, "{:?}", scores);
+            assert!(total(&greedy) <= best);
+        }
+    }
+
+    #[test]
+    fn sampled_extraction_matches_a_full_sort() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let choices: Vec<String> = (0..rng.gen_range(0..60))
+                .map(|_| {
+                    (0..rng.gen_range(1..6))
+                        .map(|_| rng.gen_range('a'..='d'))
+                        .collect()
+                })
+                .collect();
+            let limit = rng.gen_range(0..8);
+            let mut expected = extract_without_order("abcd", &choices, full_process, Ratio, 0);
+            expected.sort_by_key(|&(_, score)| Reverse(score));
+            expected.truncate(limit);
+            for sampling in [
+                Sampling::Stratified(rng.gen_range(0..20)),
+                Sampling::Random {
+                    size: rng.gen_range(0..20),
+                    seed: rng.gen(),
+                },
+            ] {
+                let sample = sampling.indices(choices.len());
+                assert!(sample.windows(2).all(|pair| pair[0] < pair[1]));
+                assert!(sample.iter().all(|&i| i < choices.len()));
+                assert_eq!(
+                    extract_sampled("abcd", &choices, full_process, Ratio, limit, sampling),
+                    expected,
+                    "{:?}",
+                    sampling
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Processor trait and a builder for composing preprocessing pipelines.
+//!
+//! A processor turns raw input into the 'canonical' form which is actually
+//! scored. [full_process](crate::utils::full_process) is the default and is
+//! what [Pipeline::default] reproduces, but any set of steps can be assembled.
+//!
+//! ```
+//! # use fuzzywuzzy::processing::{Pipeline, Processor};
+//! # use fuzzywuzzy::utils::full_process;
+//! let pipeline = Pipeline::new().lowercase().collapse_whitespace();
+//! assert_eq!(pipeline.process("  New   York  Mets ", false), "new york mets");
+//! assert_eq!(
+//!     Pipeline::default().process("C'est la vie!", false),
+//!     full_process("C'est la vie!", false));
+//! ```
+
+use crate::normalization::{
+    AsciiOnlyNormalizer, CaseLocale, LocaleLowerCaseNormalizer, LowerCaseNormalizer, Normalizer,
+    StripInvisibleNormalizer,
+};
+
+/// Represents a strategy for preprocessing strings before they are scored.
+///
+/// `force_ascii` asks the processor to discard non-ASCII characters, as
+/// [full_process](crate::utils::full_process) does.
+///
+/// Functions with the signature of [full_process](crate::utils::full_process)
+/// are processors too, so existing processors keep working.
+/// ```
+/// # use fuzzywuzzy::processing::Processor;
+/// # use fuzzywuzzy::utils::full_process;
+/// assert_eq!(full_process.process("Lorem Ipsum", false), "lorem ipsum");
+/// ```
+pub trait Processor {
+    fn process(&self, s: &str, force_ascii: bool) -> String;
+}
+
+impl<F: Fn(&str, bool) -> String> Processor for F {
+    fn process(&self, s: &str, force_ascii: bool) -> String {
+        self(s, force_ascii)
+    }
+}
+
+/// A [Processor] built from a sequence of steps, executed in the order they were added.
+///
+/// If `force_ascii` is set, non-ASCII characters are removed before the first step.
+///
+/// A pipeline, or a reference to one, can be passed as the processor to the
+/// functions in [process](crate::process).
+///
+/// ```
+/// # use fuzzywuzzy::processing::{Pipeline, Processor};
+/// # use fuzzywuzzy::normalization::StopwordNormalizer;
+/// # use fuzzywuzzy::fuzz::Ratio;
+/// # use fuzzywuzzy::process::extract_one;
+/// let pipeline = Pipeline::new()
+///     .replace_non_alphanumeric()
+///     .lowercase()
+///     .normalizer(StopwordNormalizer::english())
+///     .collapse_whitespace();
+/// assert_eq!(pipeline.process("The Lord of the Rings", false), "lord rings");
+/// assert_eq!(pipeline.process("Ça va?", true), "va");
+/// let choices = ["The Two Towers", "Lord of the Rings"];
+/// let best = extract_one("the lord rings", &choices, &pipeline, Ratio, 0);
+/// assert_eq!(best, Some(("Lord of the Rings".to_string(), 100)));
+/// ```
+pub struct Pipeline {
+    steps: Vec<Box<dyn Normalizer + Send + Sync>>,
+}
+
+impl Pipeline {
+    /// An empty pipeline, which leaves strings untouched.
+    pub fn new() -> Pipeline {
+        Pipeline { steps: Vec::new() }
+    }
+
+    /// Appends an arbitrary [Normalizer] step.
+    pub fn normalizer<N: Normalizer + Send + Sync + 'static>(mut self, normalizer: N) -> Pipeline {
+        self.steps.push(Box::new(normalizer));
+        self
+    }
+
+    /// Lower-cases all letters like [full_process](crate::utils::full_process),
+    /// see [LowerCaseNormalizer].
+    pub fn lowercase(self) -> Pipeline {
+        self.normalizer(LowerCaseNormalizer)
+    }
+
+    /// Lower-cases all letters according to the rules of `locale`, see [LocaleLowerCaseNormalizer].
+    pub fn lowercase_in(self, locale: CaseLocale) -> Pipeline {
+        self.normalizer(LocaleLowerCaseNormalizer::new(locale))
+    }
+
+    /// Removes non-ASCII characters, regardless of `force_ascii`.
+    pub fn ascii_only(self) -> Pipeline {
+        self.normalizer(AsciiOnlyNormalizer)
+    }
+
+    /// Removes diacritics, see `normalization::StripDiacriticsNormalizer`. Requires default feature "normalization".
+    #[cfg(feature = "normalization")]
+    pub fn strip_accents(self) -> Pipeline {
+        self.normalizer(crate::normalization::StripDiacriticsNormalizer)
+    }
+
+    /// Removes zero-width, formatting and control characters, see
+    /// [StripInvisibleNormalizer].
+    ///
+    /// Add this before [Pipeline::replace_non_alphanumeric], which would
+    /// otherwise turn them into spaces in the middle of words.
+    pub fn strip_invisible(self) -> Pipeline {
+        self.normalizer(StripInvisibleNormalizer)
+    }
+
+    /// Replaces every character which isn't alphanumeric with a space.
+    pub fn replace_non_alphanumeric(self) -> Pipeline {
+        self.normalizer(|s: &str| {
+            s.chars()
+                .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+                .collect::<String>()
+        })
+    }
+
+    /// Removes leading and trailing whitespace.
+    pub fn trim(self) -> Pipeline {
+        self.normalizer(|s: &str| s.trim().to_string())
+    }
+
+    /// Trims and replaces each run of whitespace with a single space.
+    pub fn collapse_whitespace(self) -> Pipeline {
+        self.normalizer(|s: &str| s.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+}
+
+impl Default for Pipeline {
+    /// The steps of [full_process](crate::utils::full_process).
+    fn default() -> Pipeline {
+        Pipeline::new()
+            .replace_non_alphanumeric()
+            .lowercase()
+            .trim()
+    }
+}
+
+impl Processor for Pipeline {
+    fn process(&self, s: &str, force_ascii: bool) -> String {
+        let mut current = if force_ascii {
+            AsciiOnlyNormalizer.normalize(s)
+        } else {
+            s.to_owned()
+        };
+        for step in self.steps.iter() {
+            current = step.normalize(&current);
+        }
+        current
+    }
+}
+
+impl Processor for &Pipeline {
+    fn process(&self, s: &str, force_ascii: bool) -> String {
+        (**self).process(s, force_ascii)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::full_process;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn default_pipeline_matches_full_process() {
+        let pipeline = Pipeline::default();
+        let alphabet: Vec<char> = "aZ9 _-'¬€ሴ耀ÄäİßΣ\t".chars().collect();
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let s: String = (0..rng.gen_range(0..20))
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                .collect();
+            for force_ascii in [false, true] {
+                assert_eq!(
+                    pipeline.process(&s, force_ascii),
+                    full_process(&s, force_ascii),
+                    "{:?}",
+                    s
+                );
+            }
+        }
+    }
+}
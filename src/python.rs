@@ -0,0 +1,616 @@
+//! Python bindings. Requires feature "python".
+//!
+//! Building with `maturin build --features python` (or `pip install .`, which
+//! reads `pyproject.toml`) produces an extension module named `fuzzywuzzy`
+//! with the same `fuzz`, `process` and `utils` modules as fuzzywuzzy-py, so
+//! code importing them keeps working unchanged:
+//!
+//! ```python
+//! from fuzzywuzzy import fuzz, process
+//!
+//! fuzz.token_sort_ratio("new york mets", "mets new york")  # 100
+//! process.extractOne("cowboys", ["Atlanta Falcons", "Dallas Cowboys"])  # ('Dallas Cowboys', 90)
+//! ```
+//!
+//! Scores are this crate's, which follow fuzzywuzzy-py without
+//! python-Levenshtein. When the built-in scorers and `utils.full_process` are
+//! passed to `process` functions, extraction runs natively without holding
+//! the GIL. Any other callable is called like fuzzywuzzy-py would call it,
+//! and custom scorers must return integers. `process.extractWithoutOrder`
+//! returns a list rather than a generator.
+
+use crate::{fuzz, process, utils};
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyString, PyTuple};
+use pyo3::Borrowed;
+use std::cmp::Reverse;
+
+/// The functions of fuzzywuzzy-py's `fuzz` module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Ratio,
+    PartialRatio,
+    TokenSortRatio,
+    PartialTokenSortRatio,
+    TokenSetRatio,
+    PartialTokenSetRatio,
+    QRatio,
+    UQRatio,
+    WRatio,
+    UWRatio,
+}
+
+impl Kind {
+    const ALL: [Kind; 10] = [
+        Kind::Ratio,
+        Kind::PartialRatio,
+        Kind::TokenSortRatio,
+        Kind::PartialTokenSortRatio,
+        Kind::TokenSetRatio,
+        Kind::PartialTokenSetRatio,
+        Kind::QRatio,
+        Kind::UQRatio,
+        Kind::WRatio,
+        Kind::UWRatio,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Ratio => "ratio",
+            Kind::PartialRatio => "partial_ratio",
+            Kind::TokenSortRatio => "token_sort_ratio",
+            Kind::PartialTokenSortRatio => "partial_token_sort_ratio",
+            Kind::TokenSetRatio => "token_set_ratio",
+            Kind::PartialTokenSetRatio => "partial_token_set_ratio",
+            Kind::QRatio => "QRatio",
+            Kind::UQRatio => "UQRatio",
+            Kind::WRatio => "WRatio",
+            Kind::UWRatio => "UWRatio",
+        }
+    }
+
+    /// The parameters after the two strings, in order.
+    fn options(self) -> &'static [&'static str] {
+        match self {
+            Kind::Ratio | Kind::PartialRatio => &[],
+            Kind::UQRatio | Kind::UWRatio => &["full_process"],
+            _ => &["force_ascii", "full_process"],
+        }
+    }
+
+    /// Whether extraction runs `full_process` itself before calling this
+    /// scorer, and if so whether it forces ASCII, as fuzzywuzzy-py does.
+    fn preprocess(self) -> Option<bool> {
+        match self {
+            Kind::Ratio | Kind::PartialRatio => None,
+            Kind::UQRatio | Kind::UWRatio => Some(false),
+            _ => Some(true),
+        }
+    }
+
+    fn score(self, s1: &str, s2: &str, force_ascii: bool, full_process: bool) -> u8 {
+        match self {
+            Kind::Ratio => fuzz::ratio(s1, s2),
+            Kind::PartialRatio => fuzz::partial_ratio(s1, s2),
+            Kind::TokenSortRatio => fuzz::token_sort_ratio(s1, s2, force_ascii, full_process),
+            Kind::PartialTokenSortRatio => {
+                fuzz::partial_token_sort_ratio(s1, s2, force_ascii, full_process)
+            }
+            Kind::TokenSetRatio => fuzz::token_set_ratio(s1, s2, force_ascii, full_process),
+            Kind::PartialTokenSetRatio => {
+                fuzz::partial_token_set_ratio(s1, s2, force_ascii, full_process)
+            }
+            Kind::QRatio => qratio(s1, s2, force_ascii, full_process),
+            Kind::UQRatio => qratio(s1, s2, false, full_process),
+            Kind::WRatio => fuzz::wratio(s1, s2, force_ascii, full_process),
+            Kind::UWRatio => fuzz::uwratio(s1, s2, full_process),
+        }
+    }
+}
+
+/// fuzzywuzzy-py's `QRatio`, which unlike [fuzz::qratio] can skip processing.
+fn qratio(s1: &str, s2: &str, force_ascii: bool, full_process: bool) -> u8 {
+    if full_process {
+        fuzz::qratio(s1, s2, force_ascii)
+    } else if !utils::validate_string(s1) || !utils::validate_string(s2) {
+        0
+    } else {
+        fuzz::ratio(s1, s2)
+    }
+}
+
+/// A function of the `fuzz` module.
+#[pyclass(frozen, module = "fuzzywuzzy.fuzz")]
+struct BuiltinScorer {
+    kind: Kind,
+}
+
+#[pymethods]
+impl BuiltinScorer {
+    /// Scores like the fuzzywuzzy-py function of the same name. Returns 0 if
+    /// either string is None.
+    #[pyo3(signature = (s1, s2, *args, **kwargs))]
+    fn __call__(
+        &self,
+        s1: Option<&str>,
+        s2: Option<&str>,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<u8> {
+        let name = self.kind.name();
+        let options = self.kind.options();
+        if args.len() > options.len() {
+            return Err(PyTypeError::new_err(format!(
+                "{}() takes at most {} arguments ({} given)",
+                name,
+                options.len() + 2,
+                args.len() + 2
+            )));
+        }
+        let (mut force_ascii, mut full_process) = (true, true);
+        let mut set = |option: &str, value: &Bound<'_, PyAny>| -> PyResult<()> {
+            match option {
+                "force_ascii" if options.contains(&option) => force_ascii = value.is_truthy()?,
+                "full_process" if options.contains(&option) => full_process = value.is_truthy()?,
+                _ => {
+                    return Err(PyTypeError::new_err(format!(
+                        "{}() got an unexpected keyword argument '{}'",
+                        name, option
+                    )))
+                }
+            }
+            Ok(())
+        };
+        for (option, value) in options.iter().zip(args.iter()) {
+            set(option, &value)?;
+        }
+        if let Some(kwargs) = kwargs {
+            for (option, value) in kwargs.iter() {
+                set(&option.extract::<String>()?, &value)?;
+            }
+        }
+        Ok(match (s1, s2) {
+            (Some(s1), Some(s2)) => self.kind.score(s1, s2, force_ascii, full_process),
+            _ => 0,
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<fuzzywuzzy.fuzz.{}>", self.kind.name())
+    }
+}
+
+/// `utils.full_process`.
+#[pyclass(frozen, module = "fuzzywuzzy.utils")]
+struct FullProcess;
+
+#[pymethods]
+impl FullProcess {
+    /// Like fuzzywuzzy-py's `utils.full_process`.
+    #[pyo3(signature = (s, force_ascii = false))]
+    fn __call__(&self, s: &str, force_ascii: bool) -> String {
+        utils::full_process(s, force_ascii)
+    }
+
+    fn __repr__(&self) -> &'static str {
+        "<fuzzywuzzy.utils.full_process>"
+    }
+}
+
+/// A `processor` argument.
+enum Processor<'py> {
+    Identity,
+    FullProcess,
+    Callable(Bound<'py, PyAny>),
+}
+
+impl<'a, 'py> FromPyObject<'a, 'py> for Processor<'py> {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        Ok(if obj.is_none() {
+            Processor::Identity
+        } else if obj.cast::<FullProcess>().is_ok() {
+            Processor::FullProcess
+        } else {
+            Processor::Callable(obj.to_owned())
+        })
+    }
+}
+
+impl<'py> Processor<'py> {
+    fn process(&self, s: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        match self {
+            Processor::Identity => Ok(s.clone()),
+            Processor::FullProcess => {
+                let processed = utils::full_process(&s.extract::<String>()?, false);
+                Ok(PyString::new(s.py(), &processed).into_any())
+            }
+            Processor::Callable(processor) => processor.call1((s,)),
+        }
+    }
+
+    fn process_to_string(&self, s: &Bound<'py, PyAny>) -> PyResult<String> {
+        match self {
+            Processor::Identity => s.extract(),
+            Processor::FullProcess => Ok(utils::full_process(&s.extract::<String>()?, false)),
+            Processor::Callable(processor) => processor.call1((s,))?.extract(),
+        }
+    }
+}
+
+/// A `scorer` argument.
+enum Scorer<'py> {
+    Builtin(Kind),
+    Callable(Bound<'py, PyAny>),
+}
+
+impl<'a, 'py> FromPyObject<'a, 'py> for Scorer<'py> {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        Ok(match obj.cast::<BuiltinScorer>() {
+            Ok(builtin) => Scorer::Builtin(builtin.get().kind),
+            Err(_) => Scorer::Callable(obj.to_owned()),
+        })
+    }
+}
+
+/// A choice with its score, and its key if choices were given as a mapping.
+struct Extracted<'py> {
+    choice: Bound<'py, PyAny>,
+    score: i64,
+    key: Option<Bound<'py, PyAny>>,
+}
+
+impl<'py> Extracted<'py> {
+    fn into_tuple(self) -> PyResult<Bound<'py, PyTuple>> {
+        let py = self.choice.py();
+        let score = self.score.into_pyobject(py)?.into_any();
+        match self.key {
+            Some(key) => PyTuple::new(py, [self.choice, score, key]),
+            None => PyTuple::new(py, [self.choice, score]),
+        }
+    }
+}
+
+/// fuzzywuzzy-py's `extractWithoutOrder`, collected in order.
+fn extract_without_order<'py>(
+    query: &Bound<'py, PyAny>,
+    choices: &Bound<'py, PyAny>,
+    processor: Processor<'py>,
+    scorer: Scorer<'py>,
+    score_cutoff: i64,
+) -> PyResult<Vec<Extracted<'py>>> {
+    let py = query.py();
+    let choices: Vec<(Option<Bound<'py, PyAny>>, Bound<'py, PyAny>)> = if choices.is_none() {
+        vec![]
+    } else if choices.hasattr("items")? {
+        choices
+            .call_method0("items")?
+            .try_iter()?
+            .map(|item| {
+                let (key, choice) = item?.extract()?;
+                Ok((Some(key), choice))
+            })
+            .collect::<PyResult<_>>()?
+    } else {
+        choices
+            .try_iter()?
+            .map(|choice| Ok((None, choice?)))
+            .collect::<PyResult<_>>()?
+    };
+    let scores: Vec<i64> = match scorer {
+        Scorer::Builtin(kind) => {
+            let processor = match (kind.preprocess(), processor) {
+                // The scorer's own processing makes full_process redundant.
+                (Some(_), Processor::FullProcess) => Processor::Identity,
+                (_, processor) => processor,
+            };
+            let process = |s: &Bound<'py, PyAny>| -> PyResult<String> {
+                let processed = processor.process_to_string(s)?;
+                Ok(match kind.preprocess() {
+                    Some(force_ascii) => utils::full_process(&processed, force_ascii),
+                    None => processed,
+                })
+            };
+            let query = process(query)?;
+            let processed: Vec<String> = choices
+                .iter()
+                .map(|(_, choice)| process(choice))
+                .collect::<PyResult<_>>()?;
+            py.detach(|| {
+                processed
+                    .iter()
+                    .map(|choice| kind.score(&query, choice, true, false) as i64)
+                    .collect()
+            })
+        }
+        Scorer::Callable(scorer) => {
+            let query = processor.process(query)?;
+            choices
+                .iter()
+                .map(|(_, choice)| {
+                    scorer
+                        .call1((&query, processor.process(choice)?))?
+                        .extract()
+                })
+                .collect::<PyResult<_>>()?
+        }
+    };
+    Ok(choices
+        .into_iter()
+        .zip(scores)
+        .filter(|&(_, score)| score >= score_cutoff)
+        .map(|((key, choice), score)| Extracted { choice, score, key })
+        .collect())
+}
+
+/// Sorts `extracted` by score, best first and otherwise in order, keeping at most `limit`.
+fn best<'py>(
+    mut extracted: Vec<Extracted<'py>>,
+    limit: Option<usize>,
+) -> PyResult<Vec<Bound<'py, PyTuple>>> {
+    extracted.sort_by_key(|extracted| Reverse(extracted.score));
+    extracted.truncate(limit.unwrap_or(usize::MAX));
+    extracted.into_iter().map(Extracted::into_tuple).collect()
+}
+
+/// Fast fuzzy string matching, compatible with fuzzywuzzy-py.
+#[pymodule(name = "fuzzywuzzy")]
+mod fuzzywuzzy_module {
+    use pyo3::prelude::*;
+
+    #[pymodule_export]
+    use super::fuzz_module;
+    #[pymodule_export]
+    use super::process_module;
+    #[pymodule_export]
+    use super::utils_module;
+
+    /// Registers the submodules, so they can also be imported as `fuzzywuzzy.fuzz` etc.
+    #[pymodule_init]
+    fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        let modules = m.py().import("sys")?.getattr("modules")?;
+        for name in ["fuzz", "process", "utils"].iter() {
+            modules.set_item(format!("fuzzywuzzy.{}", name), m.getattr(*name)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Scorers, like fuzzywuzzy-py's `fuzz` module.
+#[pymodule(name = "fuzz", submodule)]
+mod fuzz_module {
+    use super::{BuiltinScorer, Kind};
+    use pyo3::prelude::*;
+
+    #[pymodule_init]
+    fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        for &kind in Kind::ALL.iter() {
+            m.add(kind.name(), BuiltinScorer { kind })?;
+        }
+        Ok(())
+    }
+}
+
+/// Processing, like fuzzywuzzy-py's `utils` module.
+#[pymodule(name = "utils", submodule)]
+mod utils_module {
+    use super::FullProcess;
+    use pyo3::prelude::*;
+
+    #[pymodule_init]
+    fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add("full_process", FullProcess)
+    }
+}
+
+/// Extraction, like fuzzywuzzy-py's `process` module.
+#[pymodule(name = "process", submodule)]
+mod process_module {
+    use super::{best, extract_without_order, process, Extracted, Kind, Processor, Scorer};
+    use crate::score::Score;
+    use pyo3::prelude::*;
+    use pyo3::types::PyTuple;
+    use std::cell::RefCell;
+
+    #[pyfunction(name = "extractWithoutOrder")]
+    #[pyo3(signature = (
+        query,
+        choices,
+        processor = Processor::FullProcess,
+        scorer = Scorer::Builtin(Kind::WRatio),
+        score_cutoff = 0
+    ))]
+    fn extract_without_order_py<'py>(
+        query: &Bound<'py, PyAny>,
+        choices: &Bound<'py, PyAny>,
+        processor: Processor<'py>,
+        scorer: Scorer<'py>,
+        score_cutoff: i64,
+    ) -> PyResult<Vec<Bound<'py, PyTuple>>> {
+        extract_without_order(query, choices, processor, scorer, score_cutoff)?
+            .into_iter()
+            .map(|extracted| extracted.into_tuple())
+            .collect()
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (
+        query,
+        choices,
+        processor = Processor::FullProcess,
+        scorer = Scorer::Builtin(Kind::WRatio),
+        limit = Some(5)
+    ))]
+    fn extract<'py>(
+        query: &Bound<'py, PyAny>,
+        choices: &Bound<'py, PyAny>,
+        processor: Processor<'py>,
+        scorer: Scorer<'py>,
+        limit: Option<usize>,
+    ) -> PyResult<Vec<Bound<'py, PyTuple>>> {
+        best(
+            extract_without_order(query, choices, processor, scorer, 0)?,
+            limit,
+        )
+    }
+
+    #[pyfunction(name = "extractBests")]
+    #[pyo3(signature = (
+        query,
+        choices,
+        processor = Processor::FullProcess,
+        scorer = Scorer::Builtin(Kind::WRatio),
+        score_cutoff = 0,
+        limit = Some(5)
+    ))]
+    fn extract_bests<'py>(
+        query: &Bound<'py, PyAny>,
+        choices: &Bound<'py, PyAny>,
+        processor: Processor<'py>,
+        scorer: Scorer<'py>,
+        score_cutoff: i64,
+        limit: Option<usize>,
+    ) -> PyResult<Vec<Bound<'py, PyTuple>>> {
+        best(
+            extract_without_order(query, choices, processor, scorer, score_cutoff)?,
+            limit,
+        )
+    }
+
+    #[pyfunction(name = "extractOne")]
+    #[pyo3(signature = (
+        query,
+        choices,
+        processor = Processor::FullProcess,
+        scorer = Scorer::Builtin(Kind::WRatio),
+        score_cutoff = 0
+    ))]
+    fn extract_one<'py>(
+        query: &Bound<'py, PyAny>,
+        choices: &Bound<'py, PyAny>,
+        processor: Processor<'py>,
+        scorer: Scorer<'py>,
+        score_cutoff: i64,
+    ) -> PyResult<Option<Bound<'py, PyTuple>>> {
+        let mut top: Option<Extracted> = None;
+        for extracted in extract_without_order(query, choices, processor, scorer, score_cutoff)? {
+            match &top {
+                Some(top) if extracted.score <= top.score => {}
+                _ => top = Some(extracted),
+            }
+        }
+        top.map(Extracted::into_tuple).transpose()
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (contains_dupes, threshold = 70, scorer = Scorer::Builtin(Kind::TokenSetRatio)))]
+    fn dedupe(
+        py: Python<'_>,
+        contains_dupes: Vec<String>,
+        threshold: u8,
+        scorer: Scorer<'_>,
+    ) -> PyResult<Vec<String>> {
+        match scorer {
+            Scorer::Builtin(kind) => Ok(py.detach(|| {
+                process::dedupe(&contains_dupes, threshold, |a: &str, b: &str| {
+                    Score::new(kind.score(a, b, true, true))
+                        .expect("built-in scorers return at most 100")
+                })
+            })),
+            Scorer::Callable(scorer) => {
+                // Scorers can't fail, so the first error is kept until dedupe returns.
+                let error = RefCell::new(None);
+                let deduped =
+                    process::dedupe(&contains_dupes, threshold, |a: &str, b: &str| match scorer
+                        .call1((a, b))
+                        .and_then(|score| score.extract::<u8>())
+                    {
+                        Ok(score) => Score::new(score.min(100)).unwrap_or(Score::MAX),
+                        Err(e) => {
+                            error.borrow_mut().get_or_insert(e);
+                            Score::MIN
+                        }
+                    });
+                match error.into_inner() {
+                    Some(error) => Err(error),
+                    None => Ok(deduped),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::ffi::CString;
+
+    /// Runs Python `code` with the module importable as `fuzzywuzzy`.
+    fn run(code: &str) {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = pyo3::wrap_pymodule!(fuzzywuzzy_module)(py);
+            let modules = py.import("sys").unwrap().getattr("modules").unwrap();
+            modules.set_item("fuzzywuzzy", module).unwrap();
+            let code = CString::new(code).unwrap();
+            py.run(&code, None, None)
+                .unwrap_or_else(|e| panic!("{}", e));
+        })
+    }
+
+    #[test]
+    fn scorers_take_fuzzywuzzy_py_arguments() {
+        run(r#"
+from fuzzywuzzy import fuzz
+assert fuzz.WRatio("new york mets", "new YORK mets") == 100
+assert fuzz.ratio("new york mets", "new YORK mets") == 69
+assert fuzz.token_sort_ratio("new york mets", "mets YORK new", True, False) == 62
+assert fuzz.token_sort_ratio("new york mets", "mets YORK new", full_process=True) == 100
+assert fuzz.UQRatio("new york mets", "new YORK mets", False) == 69
+assert fuzz.ratio(None, "new york mets") == 0
+for call in [lambda: fuzz.ratio("a", "b", True), lambda: fuzz.UWRatio("a", "b", force_ascii=True)]:
+    try:
+        call()
+        assert False
+    except TypeError:
+        pass
+"#);
+    }
+
+    #[test]
+    fn builtin_scorers_agree_with_callables() {
+        run(r#"
+from fuzzywuzzy import fuzz, process, utils
+choices = ["new york mets vs chicago cubs", "chicago cubs vs chicago white sox",
+           "philladelphia phillies vs atlanta braves", "braves vs mets"]
+for scorer in [fuzz.ratio, fuzz.token_set_ratio, fuzz.QRatio, fuzz.WRatio, fuzz.UWRatio]:
+    wrapped = lambda a, b: scorer(a, b)
+    for query in ["brave new cubs", "new york mets at atlanta braves"]:
+        for processor in [utils.full_process, None, lambda s: s.upper()]:
+            assert (process.extract(query, choices, processor, scorer, limit=None)
+                    == process.extract(query, choices, processor, wrapped, limit=None))
+    assert process.dedupe(choices, 50, scorer) == process.dedupe(choices, 50, wrapped)
+"#);
+    }
+
+    #[test]
+    fn extraction_matches_fuzzywuzzy_py() {
+        run(r#"
+import fuzzywuzzy.process as process
+teams = ["Atlanta Falcons", "New York Jets", "New York Giants", "Dallas Cowboys"]
+assert process.extract("new york jets", teams, limit=2) == [("New York Jets", 100), ("New York Giants", 79)]
+assert process.extractOne("cowboys", teams) == ("Dallas Cowboys", 90)
+assert process.extractOne("cowboys", dict(enumerate(teams))) == ("Dallas Cowboys", 90, 3)
+assert process.extractOne("cowboys", teams, score_cutoff=95) is None
+assert process.extractBests("new york", teams, score_cutoff=80) == [("New York Jets", 90), ("New York Giants", 90)]
+assert process.extractWithoutOrder("cowboys", [], score_cutoff=0) == []
+dupes = ["Frodo Baggins", "Tom Sawyer", "Bilbo Baggin", "Samuel L. Jackson",
+         "F. Baggins", "Frody Baggins", "Bilbo Baggins"]
+assert process.dedupe(dupes) == ["Frodo Baggins", "Tom Sawyer", "Bilbo Baggins", "Samuel L. Jackson"]
+"#);
+    }
+}
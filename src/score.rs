@@ -0,0 +1,122 @@
+//! The [Score] type returned by [Scorer](crate::fuzz::Scorer)s.
+//!
+//! Scores are percentages from 0 to 100. Keeping them in their own type rather
+//! than a bare `u8` or `f64` means an out of range score can't be constructed,
+//! and a percentage can't be mistaken for a fraction between 0.0 and 1.0.
+//!
+//! ```
+//! # use fuzzywuzzy::score::Score;
+//! use std::convert::TryFrom;
+//! let score = Score::try_from(86).unwrap();
+//! assert_eq!(score.percent(), 86);
+//! assert_eq!(score.fraction(), 0.86);
+//! assert!(Score::try_from(101).is_err());
+//! assert_eq!(Score::from_fraction(0.86), Some(score));
+//! assert!(Score::MIN < score && score < Score::MAX);
+//! ```
+
+use crate::error::Error;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A similarity score, as a whole percentage between 0 and 100 inclusive.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Score(u8);
+
+impl Score {
+    /// The score of completely dissimilar strings.
+    pub const MIN: Score = Score(0);
+    /// The score of equivalent strings.
+    pub const MAX: Score = Score(100);
+
+    /// A score of `percent`, or `None` if it is greater than 100.
+    pub fn new(percent: u8) -> Option<Score> {
+        if percent <= 100 {
+            Some(Score(percent))
+        } else {
+            None
+        }
+    }
+
+    /// A score from a fraction between 0.0 and 1.0, rounded to the nearest
+    /// percent, or `None` if `fraction` is outside that range or NaN.
+    ///
+    /// ```
+    /// # use fuzzywuzzy::score::Score;
+    /// assert_eq!(Score::from_fraction(0.5).map(Score::percent), Some(50));
+    /// assert_eq!(Score::from_fraction(0.666).map(Score::percent), Some(67));
+    /// assert_eq!(Score::from_fraction(50.0), None);
+    /// ```
+    pub fn from_fraction(fraction: f64) -> Option<Score> {
+        if (0.0..=1.0).contains(&fraction) {
+            Some(Score((fraction * 100.0).round_ties_even() as u8))
+        } else {
+            None
+        }
+    }
+
+    /// This score as a percentage between 0 and 100.
+    pub fn percent(self) -> u8 {
+        self.0
+    }
+
+    /// This score as a fraction between 0.0 and 1.0.
+    pub fn fraction(self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+
+    /// Multiplies this score by `factor`, rounding to the nearest percent and
+    /// saturating at [Score::MIN] and [Score::MAX].
+    ///
+    /// ```
+    /// # use fuzzywuzzy::score::Score;
+    /// assert_eq!(Score::MAX.scale(0.95).percent(), 95);
+    /// assert_eq!(Score::MAX.scale(2.0), Score::MAX);
+    /// ```
+    pub fn scale(self, factor: f64) -> Score {
+        Score((self.0 as f64 * factor).round().clamp(0.0, 100.0) as u8)
+    }
+
+    /// Adds `points`, saturating at [Score::MAX].
+    pub fn saturating_add(self, points: u8) -> Score {
+        Score(self.0.saturating_add(points).min(100))
+    }
+
+    /// Subtracts `points`, saturating at [Score::MIN].
+    pub fn saturating_sub(self, points: u8) -> Score {
+        Score(self.0.saturating_sub(points))
+    }
+}
+
+impl TryFrom<u8> for Score {
+    type Error = Error;
+
+    /// Fails with [Error::ScoreOutOfRange] if `percent` is greater than 100.
+    fn try_from(percent: u8) -> Result<Score, Error> {
+        Score::new(percent).ok_or(Error::ScoreOutOfRange(percent))
+    }
+}
+
+impl From<Score> for u8 {
+    fn from(score: Score) -> u8 {
+        score.0
+    }
+}
+
+impl PartialEq<u8> for Score {
+    fn eq(&self, percent: &u8) -> bool {
+        self.0 == *percent
+    }
+}
+
+impl PartialOrd<u8> for Score {
+    fn partial_cmp(&self, percent: &u8) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(percent)
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -0,0 +1,705 @@
+//! Approximate search for a pattern within a longer text.
+//!
+//! Occurrences of the pattern may differ from it by up to a number of
+//! character insertions, deletions and substitutions. They're found from
+//! the [Levenshtein distance](crate::primitives::levenshtein) between the
+//! pattern and the best substring ending at each position of the text,
+//! computed with the bit-parallel bitap algorithm for patterns of up to 64
+//! characters and with Sellers' dynamic programming for longer ones. Where
+//! occurrences overlap, the closest one is kept.
+//!
+//! Unlike [partial_ratio](crate::fuzz::partial_ratio), which scores the
+//! single best matching window, this finds every occurrence with its exact
+//! position and distance. [find_near_matches_in_reader] searches a stream,
+//! such as a large log file, a chunk at a time.
+//!
+//! ```
+//! # use fuzzywuzzy::search::{find_near_matches, NearMatch};
+//! let text = "PATTERN with typos: PATERN, PATTREN, PTTERN";
+//! let matches: Vec<(&str, usize)> = find_near_matches("PATTERN", text, 1)
+//!     .iter()
+//!     .map(|m| (m.matched, m.distance))
+//!     .collect();
+//! assert_eq!(matches, vec![("PATTERN", 0), ("PATERN", 1), ("PTTERN", 1)]);
+//! ```
+//!
+//! ```
+//! # use fuzzywuzzy::search::fuzzy_replace;
+//! let text = "The colour of the sky, the color of the sea.";
+//! let (replaced, spans) = fuzzy_replace(text, "colour", "hue", 1);
+//! assert_eq!(replaced, "The hue of the sky, the hue of the sea.");
+//! assert_eq!(spans, vec![4..10, 27..32]);
+//! ```
+
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::ops::Range;
+
+/// How many bytes [find_near_matches_in_reader] reads at a time.
+const CHUNK_LEN: usize = 64 * 1024;
+
+/// The longest pattern [Bitap] handles.
+const BITAP_MAX_LEN: usize = 64;
+
+/// The [Bitap] masks of a pattern, computed ahead of time: those of ASCII
+/// characters by their code, and those of the other characters.
+pub(crate) type Masks<'m> = (&'m [u64; 128], &'m [(char, u64)]);
+
+/// The Baeza-Yates–Gonnet bitap algorithm with Wu and Manber's extension to
+/// k errors, for patterns of up to 64 characters.
+///
+/// Bit `i` of `states[d]` is set if the first `i + 1` characters of the
+/// pattern match a substring ending at the current position with at most
+/// `d` edits, so each character of the text takes `O(k)` word operations
+/// instead of the `O(m)` of a dynamic programming column.
+#[derive(Clone, Debug)]
+struct Bitap {
+    /// The positions of each ASCII character in the pattern, as bits.
+    ascii: [u64; 128],
+    other: HashMap<char, u64>,
+    states: Vec<u64>,
+    last: u64,
+}
+
+impl Bitap {
+    fn new(pattern: &[char], max_distance: usize) -> Bitap {
+        let mut ascii = [0; 128];
+        let mut other: HashMap<char, u64> = HashMap::new();
+        for (i, &c) in pattern.iter().enumerate() {
+            match ascii.get_mut(c as usize) {
+                Some(mask) => *mask |= 1 << i,
+                None => *other.entry(c).or_default() |= 1 << i,
+            }
+        }
+        Bitap::with_masks(pattern, max_distance, ascii, other)
+    }
+
+    fn with_masks(
+        pattern: &[char],
+        max_distance: usize,
+        ascii: [u64; 128],
+        other: HashMap<char, u64>,
+    ) -> Bitap {
+        Bitap {
+            ascii,
+            other,
+            // Before any text, the first `d` characters can be deleted.
+            states: (0..=max_distance).map(|d| (1u64 << d) - 1).collect(),
+            last: 1 << (pattern.len() - 1),
+        }
+    }
+
+    /// Processes the next character and returns the least distance of a
+    /// match ending at it, if within the maximum.
+    fn step(&mut self, c: char) -> Option<usize> {
+        let mask = match self.ascii.get(c as usize) {
+            Some(&mask) => mask,
+            None => self.other.get(&c).copied().unwrap_or(0),
+        };
+        let mut previous = self.states[0];
+        self.states[0] = ((previous << 1) | 1) & mask;
+        for d in 1..self.states.len() {
+            let old = self.states[d];
+            let matched = ((old << 1) | 1) & mask;
+            let substituted = (previous << 1) | 1;
+            let inserted = previous;
+            let deleted = (self.states[d - 1] << 1) | 1;
+            self.states[d] = matched | substituted | inserted | deleted;
+            previous = old;
+        }
+        self.states.iter().position(|&state| state & self.last != 0)
+    }
+}
+
+/// How a [Scanner] computes distances.
+#[derive(Clone, Debug)]
+enum Engine {
+    Bitap(Box<Bitap>),
+    /// `column[i]` is the least distance between the first `i` characters of
+    /// the pattern and a substring ending at the current position.
+    Column(Vec<usize>),
+}
+
+/// Scans a text for one pattern, a character at a time.
+struct Scanner<'p> {
+    pattern: &'p [char],
+    max_distance: usize,
+    engine: Engine,
+    /// The index in the whole text of the first character of the text the
+    /// scanner is given, which drops characters a stream no longer needs.
+    base: usize,
+    /// The best match so far ending in the current run of consecutive ends
+    /// within the distance: the closest, and of those the one with the
+    /// length closest to the pattern's.
+    candidate: Option<(Range<usize>, usize)>,
+    matches: Vec<(Range<usize>, usize)>,
+}
+
+impl<'p> Scanner<'p> {
+    /// A scanner for `pattern`, or `None` if it's empty.
+    fn new(pattern: &'p [char], max_distance: usize) -> Option<Scanner<'p>> {
+        Scanner::with_masks(pattern, max_distance, None)
+    }
+
+    /// A scanner for `pattern` using its bitap `masks` if given.
+    fn with_masks(
+        pattern: &'p [char],
+        max_distance: usize,
+        masks: Option<Masks>,
+    ) -> Option<Scanner<'p>> {
+        let m = pattern.len();
+        // Otherwise even empty substrings would match.
+        let max_distance = max_distance.min(m.checked_sub(1)?);
+        let engine = if let Some((ascii, other)) = masks {
+            let other = other.iter().copied().collect();
+            Engine::Bitap(Box::new(Bitap::with_masks(
+                pattern,
+                max_distance,
+                *ascii,
+                other,
+            )))
+        } else if m <= BITAP_MAX_LEN {
+            Engine::Bitap(Box::new(Bitap::new(pattern, max_distance)))
+        } else {
+            Engine::Column((0..=m).collect())
+        };
+        Some(Scanner {
+            pattern,
+            max_distance,
+            engine,
+            base: 0,
+            candidate: None,
+            matches: vec![],
+        })
+    }
+
+    /// Processes `text[j]`, where `text` holds at least the pattern's length
+    /// plus the maximum distance of characters before it. Returns whether
+    /// the candidate match changed.
+    fn step(&mut self, text: &[char], j: usize) -> bool {
+        let distance = match &mut self.engine {
+            Engine::Bitap(bitap) => bitap.step(text[j]),
+            Engine::Column(column) => {
+                let pattern = self.pattern;
+                let m = pattern.len();
+                let mut diagonal = column[0];
+                for i in 1..=m {
+                    let substitution = diagonal + usize::from(pattern[i - 1] != text[j]);
+                    diagonal = column[i];
+                    column[i] = substitution.min(column[i] + 1).min(column[i - 1] + 1);
+                }
+                count!(dp_cells, m);
+                Some(column[m]).filter(|&distance| distance <= self.max_distance)
+            }
+        };
+        match distance {
+            Some(distance) => self.extend_run(text, j + 1, distance),
+            None => {
+                self.matches.extend(self.candidate.take());
+                false
+            }
+        }
+    }
+
+    /// Considers the match ending at `text[..end]` as the candidate.
+    fn extend_run(&mut self, text: &[char], end: usize, distance: usize) -> bool {
+        let m = self.pattern.len();
+        let off_by = |range: &Range<usize>| range.len().abs_diff(m);
+        if let Some((range, best)) = &self.candidate {
+            if distance > *best || (distance == *best && off_by(range) == 0) {
+                return false;
+            }
+        }
+        let start = best_start(self.pattern, &text[..end], distance);
+        let range = self.base + start..self.base + end;
+        let better = match &self.candidate {
+            Some((candidate, best)) => distance < *best || off_by(&range) < off_by(candidate),
+            None => true,
+        };
+        if better {
+            self.candidate = Some((range, distance));
+        }
+        better
+    }
+
+    /// Records that the first `n` characters of the text are dropped.
+    fn discard(&mut self, n: usize) {
+        self.base += n;
+    }
+
+    /// The non-overlapping matches in order, once all of the text is processed.
+    fn finish(mut self) -> Vec<(Range<usize>, usize)> {
+        self.matches.extend(self.candidate.take());
+        let m = self.pattern.len();
+        let mut matches = self.matches;
+        // Keep the closest of overlapping matches, then the one with the
+        // length closest to the pattern's, then the earliest.
+        matches.sort_by_key(|(range, distance)| (*distance, range.len().abs_diff(m), range.start));
+        let mut kept: Vec<(Range<usize>, usize)> = vec![];
+        for (range, distance) in matches {
+            if kept
+                .iter()
+                .all(|(other, _)| range.end <= other.start || other.end <= range.start)
+            {
+                kept.push((range, distance));
+            }
+        }
+        kept.sort_by_key(|(range, _)| range.start);
+        kept
+    }
+}
+
+/// The non-overlapping occurrences of `pattern` in `text` with at most
+/// `max_distance` edits, but fewer than the pattern's length, as character
+/// ranges with their distances, in order. Uses the bitap `masks` of
+/// `pattern` if given.
+fn approximate_matches(
+    pattern: &[char],
+    masks: Option<Masks>,
+    text: &[char],
+    max_distance: usize,
+) -> Vec<(Range<usize>, usize)> {
+    let mut scanner = match Scanner::with_masks(pattern, max_distance, masks) {
+        Some(scanner) => scanner,
+        None => return vec![],
+    };
+    for j in 0..text.len() {
+        scanner.step(text, j);
+    }
+    scanner.finish()
+}
+
+/// The start of the substring at the end of `text` which is `distance`
+/// edits from `pattern`, preferring the length closest to the pattern's.
+fn best_start(pattern: &[char], text: &[char], distance: usize) -> usize {
+    let m = pattern.len();
+    let window = &text[text.len().saturating_sub(m + distance)..];
+    // `column[i]` is the distance between the last `i` characters of the
+    // pattern and the suffix of the window processed so far.
+    let mut column: Vec<usize> = (0..=m).collect();
+    let mut best = (usize::MAX, text.len());
+    for (len, &c) in window.iter().rev().enumerate() {
+        let mut diagonal = column[0];
+        column[0] = len + 1;
+        for i in 1..=m {
+            let substitution = diagonal + usize::from(pattern[m - i] != c);
+            diagonal = column[i];
+            column[i] = substitution.min(column[i] + 1).min(column[i - 1] + 1);
+        }
+        if column[m] == distance {
+            let off_by = (len + 1).abs_diff(m);
+            if off_by < best.0 {
+                best = (off_by, text.len() - len - 1);
+            }
+        }
+    }
+    best.1
+}
+
+/// An occurrence of a pattern found by [find_near_matches].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NearMatch<'a> {
+    /// The byte offset of the occurrence in the text.
+    pub start: usize,
+    /// The byte offset of the end of the occurrence in the text.
+    pub end: usize,
+    /// The Levenshtein distance between the occurrence and the pattern.
+    pub distance: usize,
+    /// The occurrence itself.
+    pub matched: &'a str,
+}
+
+impl<'a> NearMatch<'a> {
+    /// The match of the characters `range` of `text`, whose characters start
+    /// at the byte `offsets`.
+    fn new(
+        text: &'a str,
+        offsets: &[usize],
+        range: Range<usize>,
+        distance: usize,
+    ) -> NearMatch<'a> {
+        let byte_offset = |i: usize| offsets.get(i).copied().unwrap_or(text.len());
+        let (start, end) = (byte_offset(range.start), byte_offset(range.end));
+        NearMatch {
+            start,
+            end,
+            distance,
+            matched: &text[start..end],
+        }
+    }
+}
+
+/// Finds the occurrences of `pattern` in `text` with at most `max_distance`
+/// edits, like Python's `fuzzysearch.find_near_matches`, in order. See the
+/// [module](self) docs.
+///
+/// `max_distance` is lowered to one less than the number of characters of
+/// `pattern`, which any substring of that length would match, and an empty
+/// pattern matches nowhere.
+///
+/// ```
+/// # use fuzzywuzzy::search::{find_near_matches, NearMatch};
+/// assert_eq!(find_near_matches("naïve", "a naive résumé", 1), vec![
+///     NearMatch { start: 2, end: 7, distance: 1, matched: "naive" },
+/// ]);
+/// assert!(find_near_matches("xyz", "abc", 2).is_empty());
+/// ```
+pub fn find_near_matches<'a>(
+    pattern: &str,
+    text: &'a str,
+    max_distance: usize,
+) -> Vec<NearMatch<'a>> {
+    let pattern: Vec<char> = pattern.chars().collect();
+    find_near_matches_with(&pattern, None, text, max_distance)
+}
+
+/// [find_near_matches] for the characters of a pattern, using its bitap
+/// `masks` if given.
+pub(crate) fn find_near_matches_with<'a>(
+    pattern: &[char],
+    masks: Option<Masks>,
+    text: &'a str,
+    max_distance: usize,
+) -> Vec<NearMatch<'a>> {
+    let (offsets, chars): (Vec<usize>, Vec<char>) = text.char_indices().unzip();
+    approximate_matches(pattern, masks, &chars, max_distance)
+        .into_iter()
+        .map(|(range, distance)| NearMatch::new(text, &offsets, range, distance))
+        .collect()
+}
+
+/// Searches texts for many patterns at once, such as a watchlist of terms.
+///
+/// Each text is scanned a single time, advancing the search for every
+/// pattern with each character, and each pattern's occurrences are found
+/// as by [find_near_matches]. Occurrences of different patterns may overlap.
+///
+/// ```
+/// # use fuzzywuzzy::search::MultiPatternSearcher;
+/// let watchlist = MultiPatternSearcher::new(["acme corp", "globex", "initech"], 1);
+/// let text = "Payment from ACME Corp. to Glbex, cc Initek.";
+/// let found: Vec<(usize, &str, usize)> = watchlist
+///     .find(&text.to_lowercase())
+///     .into_iter()
+///     .map(|(pattern, m)| (pattern, &text[m.start..m.end], m.distance))
+///     .collect();
+/// assert_eq!(found, vec![(0, "ACME Corp", 0), (1, "Glbex", 1)]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiPatternSearcher {
+    patterns: Vec<Vec<char>>,
+    max_distance: usize,
+}
+
+impl MultiPatternSearcher {
+    /// Searches for `patterns` with up to `max_distance` edits each, but
+    /// fewer than their number of characters. Empty patterns match nowhere.
+    pub fn new<I, T>(patterns: I, max_distance: usize) -> MultiPatternSearcher
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        MultiPatternSearcher {
+            patterns: patterns
+                .into_iter()
+                .map(|p| p.as_ref().chars().collect())
+                .collect(),
+            max_distance,
+        }
+    }
+
+    /// The occurrences of the patterns in `text`, as the index of the
+    /// pattern and the match, ordered by position and then pattern.
+    pub fn find<'a>(&self, text: &'a str) -> Vec<(usize, NearMatch<'a>)> {
+        let (offsets, chars): (Vec<usize>, Vec<char>) = text.char_indices().unzip();
+        let mut scanners: Vec<(usize, Scanner)> = self
+            .patterns
+            .iter()
+            .enumerate()
+            .filter_map(|(i, pattern)| Some((i, Scanner::new(pattern, self.max_distance)?)))
+            .collect();
+        for j in 0..chars.len() {
+            for (_, scanner) in scanners.iter_mut() {
+                scanner.step(&chars, j);
+            }
+        }
+        let mut matches: Vec<(usize, NearMatch<'a>)> = scanners
+            .into_iter()
+            .flat_map(|(i, scanner)| {
+                let offsets = &offsets;
+                scanner.finish().into_iter().map(move |(range, distance)| {
+                    (i, NearMatch::new(text, offsets, range, distance))
+                })
+            })
+            .collect();
+        matches.sort_by_key(|&(i, m)| (m.start, i));
+        matches
+    }
+}
+
+/// Replaces each occurrence of `pattern` in `text` found by
+/// [find_near_matches] with up to `max_errors` edits by `replacement`. Returns the edited text and the byte ranges of
+/// the replaced occurrences in `text`.
+///
+/// ```
+/// # use fuzzywuzzy::search::fuzzy_replace;
+/// assert_eq!(fuzzy_replace("recieve and recive", "receive", "get", 2).0, "get and get");
+/// assert_eq!(fuzzy_replace("unchanged", "", "x", 1), ("unchanged".to_string(), vec![]));
+/// ```
+pub fn fuzzy_replace(
+    text: &str,
+    pattern: &str,
+    replacement: &str,
+    max_errors: usize,
+) -> (String, Vec<Range<usize>>) {
+    let spans: Vec<Range<usize>> = find_near_matches(pattern, text, max_errors)
+        .into_iter()
+        .map(|m| m.start..m.end)
+        .collect();
+    let mut replaced = String::with_capacity(text.len());
+    let mut last = 0;
+    for span in spans.iter() {
+        replaced.push_str(&text[last..span.start]);
+        replaced.push_str(replacement);
+        last = span.end;
+    }
+    replaced.push_str(&text[last..]);
+    (replaced, spans)
+}
+
+/// An occurrence of a pattern found by [find_near_matches_in_reader].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StreamMatch {
+    /// The byte offset of the occurrence in the stream.
+    pub start: u64,
+    /// The byte offset of the end of the occurrence in the stream.
+    pub end: u64,
+    /// The Levenshtein distance between the occurrence and the pattern.
+    pub distance: usize,
+    /// The occurrence itself.
+    pub matched: String,
+}
+
+/// Finds the occurrences of `pattern` in the UTF-8 text read from `reader`,
+/// as [find_near_matches] would in the whole text, without holding more than
+/// a chunk of it in memory. Occurrences spanning the chunks are found too.
+///
+/// # Errors
+///
+/// Returns errors from `reader` other than [io::ErrorKind::Interrupted],
+/// and an error of kind [io::ErrorKind::InvalidData] if the text isn't UTF-8.
+///
+/// ```
+/// # use fuzzywuzzy::search::{find_near_matches_in_reader, StreamMatch};
+/// let log = "12:00 conection refused\n12:01 connected\n12:02 connection refused\n";
+/// let matches = find_near_matches_in_reader("connection refused", log.as_bytes(), 2).unwrap();
+/// let found: Vec<(u64, &str)> = matches.iter().map(|m| (m.start, m.matched.as_str())).collect();
+/// assert_eq!(found, vec![(6, "conection refused"), (46, "connection refused")]);
+/// ```
+pub fn find_near_matches_in_reader<R: Read>(
+    pattern: &str,
+    mut reader: R,
+    max_distance: usize,
+) -> io::Result<Vec<StreamMatch>> {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let mut scanner = match Scanner::new(&pattern, max_distance) {
+        Some(scanner) => scanner,
+        None => return Ok(vec![]),
+    };
+    // Matches start at most this many characters before their end.
+    let keep = pattern.len() + scanner.max_distance;
+    let (mut chars, mut offsets): (Vec<char>, Vec<u64>) = (vec![], vec![]);
+    let mut position = 0;
+    // The start of a character split between chunks.
+    let mut pending: Vec<u8> = vec![];
+    let mut buffer = vec![0; CHUNK_LEN];
+    // The closed matches by their end, and the candidate, with their text.
+    let mut closed: Vec<(usize, StreamMatch)> = vec![];
+    let mut candidate = None;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if read == 0 {
+            break;
+        }
+        pending.extend_from_slice(&buffer[..read]);
+        let valid = match std::str::from_utf8(&pending) {
+            Ok(text) => text,
+            Err(e) if e.error_len().is_none() => {
+                std::str::from_utf8(&pending[..e.valid_up_to()]).expect("valid up to here")
+            }
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        };
+        let first = chars.len();
+        for c in valid.chars() {
+            chars.push(c);
+            offsets.push(position);
+            position += c.len_utf8() as u64;
+        }
+        let consumed = valid.len();
+        pending.drain(..consumed);
+        for j in first..chars.len() {
+            let closed_before = scanner.matches.len();
+            if scanner.step(&chars, j) {
+                let (range, distance) = scanner.candidate.clone().expect("candidate changed");
+                let (start, end) = (range.start - scanner.base, range.end - scanner.base);
+                candidate = Some((
+                    range.end,
+                    StreamMatch {
+                        start: offsets[start],
+                        end: offsets[end - 1] + chars[end - 1].len_utf8() as u64,
+                        distance,
+                        matched: chars[start..end].iter().collect(),
+                    },
+                ));
+            } else if scanner.matches.len() > closed_before {
+                closed.extend(candidate.take());
+            }
+        }
+        let dropped = chars.len().saturating_sub(keep);
+        chars.drain(..dropped);
+        offsets.drain(..dropped);
+        scanner.discard(dropped);
+    }
+    if !pending.is_empty() {
+        let e = std::str::from_utf8(&pending).expect_err("an incomplete character");
+        return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+    }
+    closed.extend(candidate);
+    Ok(scanner
+        .finish()
+        .into_iter()
+        .map(|(range, _)| {
+            let i = closed
+                .binary_search_by_key(&range.end, |(end, _)| *end)
+                .expect("kept matches were closed");
+            closed[i].1.clone()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::primitives::levenshtein;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn matches_are_within_distance_and_disjoint() {
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let text: Vec<char> = (0..rng.gen_range(0..30))
+                .map(|_| rng.gen_range(b'a'..b'd') as char)
+                .collect();
+            let pattern: Vec<char> = (0..rng.gen_range(1..6))
+                .map(|_| rng.gen_range(b'a'..b'd') as char)
+                .collect();
+            let k = rng.gen_range(0..3);
+            let matches = approximate_matches(&pattern, None, &text, k);
+            let k = k.min(pattern.len() - 1);
+            for (range, distance) in matches.iter() {
+                assert!(*distance <= k);
+                assert_eq!(levenshtein(&pattern, &text[range.clone()]), *distance);
+            }
+            assert!(matches.windows(2).all(|w| w[0].0.end <= w[1].0.start));
+            let any = (0..=text.len())
+                .any(|i| (i + 1..=text.len()).any(|j| levenshtein(&pattern, &text[i..j]) <= k));
+            assert_eq!(any, !matches.is_empty(), "{:?} {:?} {}", pattern, text, k);
+        }
+    }
+
+    #[test]
+    fn multi_pattern_search_agrees_with_single_patterns() {
+        let text = "abracadabra, abacus, cadaver, candelabra";
+        let patterns = ["abra", "cada", "", "zzz", "labra"];
+        let searcher = MultiPatternSearcher::new(patterns.iter(), 1);
+        let mut expected: Vec<(usize, NearMatch)> = patterns
+            .iter()
+            .enumerate()
+            .flat_map(|(i, p)| {
+                find_near_matches(p, text, 1)
+                    .into_iter()
+                    .map(move |m| (i, m))
+            })
+            .collect();
+        expected.sort_by_key(|&(i, m)| (m.start, i));
+        assert_eq!(searcher.find(text), expected);
+        assert!(!expected.is_empty());
+    }
+
+    #[test]
+    fn bitap_agrees_with_dynamic_programming() {
+        let mut rng = thread_rng();
+        let alphabet = ['a', 'b', 'c', 'é'];
+        for _ in 0..500 {
+            let text: Vec<char> = (0..rng.gen_range(0..40))
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                .collect();
+            let pattern: Vec<char> = (0..rng.gen_range(1..BITAP_MAX_LEN + 1))
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                .collect();
+            let k = rng.gen_range(0..4).min(pattern.len() - 1);
+            let mut bitap = Bitap::new(&pattern, k);
+            let mut column: Vec<usize> = (0..=pattern.len()).collect();
+            for &c in text.iter() {
+                let mut diagonal = column[0];
+                for i in 1..=pattern.len() {
+                    let substitution = diagonal + usize::from(pattern[i - 1] != c);
+                    diagonal = column[i];
+                    column[i] = substitution.min(column[i] + 1).min(column[i - 1] + 1);
+                }
+                let expected = Some(column[pattern.len()]).filter(|&d| d <= k);
+                assert_eq!(bitap.step(c), expected, "{:?} {:?} {}", pattern, text, k);
+            }
+        }
+        let long: String = "abcdefghij".repeat(7);
+        let text = format!("xx{}yy", long.replacen('e', "", 1));
+        let matches = find_near_matches(&long, &text, 2);
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].start, matches[0].distance), (2, 1));
+    }
+
+    /// Reads a few bytes at a time, splitting characters and matches.
+    struct Trickle<'a>(&'a [u8], usize);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.1.min(self.0.len()).min(buf.len());
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn streaming_search_agrees_with_whole_text() {
+        let mut rng = thread_rng();
+        let alphabet = ['a', 'b', 'c', 'é', '日'];
+        for _ in 0..300 {
+            let text: String = (0..rng.gen_range(0..60))
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                .collect();
+            let pattern: String = (0..rng.gen_range(0..8))
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                .collect();
+            let k = rng.gen_range(0..3);
+            let expected: Vec<StreamMatch> = find_near_matches(&pattern, &text, k)
+                .into_iter()
+                .map(|m| StreamMatch {
+                    start: m.start as u64,
+                    end: m.end as u64,
+                    distance: m.distance,
+                    matched: m.matched.to_string(),
+                })
+                .collect();
+            let reader = Trickle(text.as_bytes(), rng.gen_range(1..5));
+            let found = find_near_matches_in_reader(&pattern, reader, k).unwrap();
+            assert_eq!(found, expected, "{:?} {:?} {}", pattern, text, k);
+        }
+        let invalid = find_near_matches_in_reader("a", Trickle(b"ab\xc3", 1), 0);
+        assert_eq!(invalid.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -79,7 +79,7 @@ impl<'a> Segmenter<'a> for ByteSegmenter {
     // Returns an owned `Vec<u8>` because allocating additional `u8`s is cheaper than pointers into the original string.
     type Output = u8;
     fn segment(&self, s: &'a str) -> Vec<Self::Output> {
-        s.as_bytes().iter().copied().collect()
+        s.as_bytes().to_vec()
     }
 }
 
@@ -110,6 +110,159 @@ impl<'a> Segmenter<'a> for CodePointSegmenter {
     }
 }
 
+/// A [Segmenter] splitting strings into borrowed substrings, i.e. tokens.
+///
+/// This is what the token based scorers like [token_sort_ratio_with](crate::fuzz::token_sort_ratio_with)
+/// use to find the words of a string. It is implemented for every suitable [Segmenter].
+pub trait Tokenizer: for<'a> Segmenter<'a, Output = &'a str> {}
+
+impl<T: for<'a> Segmenter<'a, Output = &'a str>> Tokenizer for T {}
+
+/// A strategy for segmenting strings into the tokens separated by whitespace.
+///
+/// This is the [Tokenizer] used by default by the token based scorers.
+///
+/// ```
+/// # use fuzzywuzzy::segmentation::{Segmenter, WhitespaceSegmenter};
+/// assert_eq!(WhitespaceSegmenter.segment(" new  york\tmets "), vec!["new", "york", "mets"]);
+/// ```
+pub struct WhitespaceSegmenter;
+
+impl<'a> Segmenter<'a> for WhitespaceSegmenter {
+    type Output = &'a str;
+    fn segment(&self, s: &'a str) -> Vec<Self::Output> {
+        s.split_whitespace().collect()
+    }
+}
+
+/// A [Tokenizer] splitting programming identifiers into words at case,
+/// underscore and digit boundaries.
+///
+/// Runs of non-alphanumeric characters separate words. Within a run of
+/// letters and digits, a new word starts at an upper case letter following a
+/// lower case one, at the last letter of a run of upper case letters followed
+/// by a lower case one, and wherever letters and digits meet. Tokens keep
+/// their case, so use [identifier_process](crate::utils::identifier_process)
+/// to compare identifiers with the token scorers.
+///
+/// ```
+/// # use fuzzywuzzy::segmentation::{Segmenter, IdentifierSegmenter};
+/// assert_eq!(IdentifierSegmenter.segment("getUserIDFromDB"), vec!["get", "User", "ID", "From", "DB"]);
+/// assert_eq!(IdentifierSegmenter.segment("MAX_RETRY_count"), vec!["MAX", "RETRY", "count"]);
+/// assert_eq!(IdentifierSegmenter.segment("base64Encode v2"), vec!["base", "64", "Encode", "v", "2"]);
+/// ```
+pub struct IdentifierSegmenter;
+
+impl<'a> Segmenter<'a> for IdentifierSegmenter {
+    type Output = &'a str;
+    fn segment(&self, s: &'a str) -> Vec<Self::Output> {
+        let mut tokens = Vec::new();
+        for word in s.split(|c: char| !c.is_alphanumeric()) {
+            let chars: Vec<(usize, char)> = word.char_indices().collect();
+            let mut start = 0;
+            for k in 1..chars.len() {
+                let (prev, c) = (chars[k - 1].1, chars[k].1);
+                let next = chars.get(k + 1).map(|&(_, next)| next);
+                let boundary = prev.is_lowercase() && c.is_uppercase()
+                    || prev.is_uppercase()
+                        && c.is_uppercase()
+                        && next.is_some_and(char::is_lowercase)
+                    || prev.is_numeric() != c.is_numeric();
+                if boundary {
+                    tokens.push(&word[start..chars[k].0]);
+                    start = chars[k].0;
+                }
+            }
+            if start < word.len() {
+                tokens.push(&word[start..]);
+            }
+        }
+        tokens
+    }
+}
+
+/// A [Tokenizer] which splits on whitespace, then breaks runs of CJK characters into overlapping bigrams.
+///
+/// Chinese, Japanese and Korean text is often written without spaces, so
+/// [WhitespaceSegmenter] would treat an entire sentence as a single token.
+/// Character bigrams are the conventional fallback when no dictionary based
+/// word segmentation is available. A run of a single CJK character is kept as is,
+/// and non-CJK parts of a token are emitted as their own tokens.
+///
+/// ```
+/// # use fuzzywuzzy::segmentation::{Segmenter, CjkBigramSegmenter};
+/// assert_eq!(CjkBigramSegmenter.segment("我喜欢猫"), vec!["我喜", "喜欢", "欢猫"]);
+/// assert_eq!(CjkBigramSegmenter.segment("new york 纽约"), vec!["new", "york", "纽约"]);
+/// assert_eq!(CjkBigramSegmenter.segment("iPhone手机壳"), vec!["iPhone", "手机", "机壳"]);
+/// assert_eq!(CjkBigramSegmenter.segment("猫 cat"), vec!["猫", "cat"]);
+/// ```
+///
+/// ```
+/// # use fuzzywuzzy::fuzz::{token_set_ratio, token_set_ratio_with};
+/// # use fuzzywuzzy::segmentation::CjkBigramSegmenter;
+/// // "Peking University" and "Peking University Health Science Center"
+/// assert_eq!(token_set_ratio("北京大学", "北京大学医学部", false, true), 77);
+/// assert_eq!(token_set_ratio_with("北京大学", "北京大学医学部", false, true, &CjkBigramSegmenter), 100);
+/// ```
+pub struct CjkBigramSegmenter;
+
+impl<'a> Segmenter<'a> for CjkBigramSegmenter {
+    type Output = &'a str;
+    fn segment(&self, s: &'a str) -> Vec<Self::Output> {
+        let mut tokens = Vec::new();
+        for word in s.split_whitespace() {
+            let mut rest = word;
+            while let Some(first) = rest.chars().next() {
+                let cjk = is_cjk(first);
+                let run_len = rest
+                    .char_indices()
+                    .find(|&(_, c)| is_cjk(c) != cjk)
+                    .map_or(rest.len(), |(idx, _)| idx);
+                let (run, remainder) = rest.split_at(run_len);
+                if cjk {
+                    push_bigrams(run, &mut tokens);
+                } else {
+                    tokens.push(run);
+                }
+                rest = remainder;
+            }
+        }
+        tokens
+    }
+}
+
+/// Pushes the overlapping character bigrams of `run`, or `run` itself if it is a single character.
+fn push_bigrams<'a>(run: &'a str, tokens: &mut Vec<&'a str>) {
+    let offsets: Vec<usize> = run
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .chain(std::iter::once(run.len()))
+        .collect();
+    if offsets.len() <= 2 {
+        tokens.push(run);
+        return;
+    }
+    for window in offsets.windows(3) {
+        tokens.push(&run[window[0]..window[2]]);
+    }
+}
+
+/// Whether `c` is a Han ideograph, Japanese kana or Hangul character.
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}' // Hangul Jamo
+        | '\u{3040}'..='\u{30FF}' // Hiragana, Katakana
+        | '\u{3130}'..='\u{318F}' // Hangul Compatibility Jamo
+        | '\u{31F0}'..='\u{31FF}' // Katakana Phonetic Extensions
+        | '\u{3400}'..='\u{4DBF}' // CJK Unified Ideographs Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}' // Hangul Syllables
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+        | '\u{FF66}'..='\u{FF9F}' // Halfwidth Katakana
+        | '\u{20000}'..='\u{2EBEF}' // CJK Unified Ideographs Extensions B-F
+    )
+}
+
 #[cfg(feature = "segmentation")]
 pub use self::unicode_segmenters::*;
 
@@ -140,4 +293,29 @@ mod unicode_segmenters {
             s.graphemes(true).collect()
         }
     }
+
+    /// A strategy for segmenting strings into words according to the
+    /// [Unicode word boundary rules (UAX#29)](https://www.unicode.org/reports/tr29/#Word_Boundaries).
+    /// Requires default feature "segmentation".
+    ///
+    /// Punctuation and whitespace between words is dropped. Unlike [WhitespaceSegmenter](super::WhitespaceSegmenter),
+    /// words are found even when not separated by spaces, e.g. each ideograph of
+    /// Chinese or Japanese text is its own word.
+    ///
+    /// This just delegates to [unicode_segmentation::UnicodeSegmentation::unicode_words].
+    ///
+    /// ```
+    /// # use fuzzywuzzy::segmentation::{Segmenter, WordSegmenter};
+    /// assert_eq!(WordSegmenter.segment("The quick (\"brown\") fox can't jump 32.3 feet, right?"),
+    ///            vec!["The", "quick", "brown", "fox", "can't", "jump", "32.3", "feet", "right"]);
+    /// assert_eq!(WordSegmenter.segment("我喜欢猫"), vec!["我", "喜", "欢", "猫"]);
+    /// ```
+    pub struct WordSegmenter;
+
+    impl<'a> Segmenter<'a> for WordSegmenter {
+        type Output = &'a str;
+        fn segment(&self, s: &'a str) -> Vec<Self::Output> {
+            s.unicode_words().collect()
+        }
+    }
 }
@@ -0,0 +1,159 @@
+//! Correcting misspelled words and search queries against a vocabulary.
+//!
+//! A [Vocabulary] knows words and how often they occur. Unknown words are
+//! corrected to the known word with the fewest edits, counting swapped
+//! letters as one edit, preferring more frequent words on ties. As with
+//! Elasticsearch's `AUTO` fuzziness, words of up to 2 characters aren't
+//! corrected, words of 3 to 5 characters may be off by one edit and longer
+//! words by two.
+//!
+//! ```
+//! # use fuzzywuzzy::spell::Vocabulary;
+//! let vocabulary: Vocabulary = "the quick brown fox jumps over the lazy dog".split(' ').collect();
+//! assert_eq!(vocabulary.correct("qiuck"), Some("quick"));
+//! assert_eq!(vocabulary.correct("teh"), Some("the"));
+//! assert_eq!(vocabulary.correct("elephant"), None);
+//! let correction = vocabulary.correct_query("Quikc brwon fox");
+//! assert_eq!(correction.query, "quick brown fox");
+//! assert_eq!(correction.confidence, 87);
+//! ```
+
+use crate::fuzz;
+use crate::primitives;
+use crate::utils;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+/// The most edits a word of `len` characters may be corrected by.
+fn max_edits(len: usize) -> usize {
+    match len {
+        0..=2 => 0,
+        3..=5 => 1,
+        _ => 2,
+    }
+}
+
+/// Known words and their frequencies.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: HashMap<String, u64>,
+}
+
+/// The result of [Vocabulary::correct_query].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Correction {
+    /// The processed query with each misspelled word corrected.
+    pub query: String,
+    /// The [ratio](fuzz::ratio) of the corrected query and the processed
+    /// original, which is 100 if nothing was corrected.
+    pub confidence: u8,
+}
+
+impl Vocabulary {
+    /// An empty vocabulary.
+    pub fn new() -> Vocabulary {
+        Vocabulary::default()
+    }
+
+    /// Records `count` more occurrences of `word`.
+    pub fn add(&mut self, word: &str, count: u64) {
+        *self.words.entry(word.to_lowercase()).or_default() += count;
+    }
+
+    /// How often `word` occurred, ignoring case, or 0 if it's unknown.
+    pub fn frequency(&self, word: &str) -> u64 {
+        self.words.get(&word.to_lowercase()).copied().unwrap_or(0)
+    }
+
+    /// The known word closest to `word`, which is `word` itself if it's
+    /// known, or `None` if no known word is close enough. Case is ignored,
+    /// and the correction is lower case.
+    pub fn correct(&self, word: &str) -> Option<&str> {
+        let word = word.to_lowercase();
+        if let Some((known, _)) = self.words.get_key_value(&word) {
+            return Some(known);
+        }
+        let chars: Vec<char> = word.chars().collect();
+        let max_edits = max_edits(chars.len());
+        if max_edits == 0 {
+            return None;
+        }
+        self.words
+            .iter()
+            .filter_map(|(known, &count)| {
+                let known_chars: Vec<char> = known.chars().collect();
+                // The distance is at least the difference in length.
+                if known_chars.len().abs_diff(chars.len()) > max_edits {
+                    return None;
+                }
+                let distance = primitives::osa_distance(&chars, &known_chars);
+                if distance <= max_edits {
+                    Some(((distance, Reverse(count), known.as_str()), known.as_str()))
+                } else {
+                    None
+                }
+            })
+            .min()
+            .map(|(_, known)| known)
+    }
+
+    /// Processes `query` with [full_process](utils::full_process) and
+    /// [corrects](Vocabulary::correct) each of its words, keeping those
+    /// which can't be corrected.
+    pub fn correct_query(&self, query: &str) -> Correction {
+        let processed = utils::full_process(query, false);
+        let corrected: Vec<&str> = processed
+            .split_whitespace()
+            .map(|word| self.correct(word).unwrap_or(word))
+            .collect();
+        let corrected = corrected.join(" ");
+        let confidence = if corrected == processed {
+            100
+        } else {
+            fuzz::ratio(&processed, &corrected)
+        };
+        Correction {
+            query: corrected,
+            confidence,
+        }
+    }
+}
+
+impl<T: AsRef<str>> FromIterator<T> for Vocabulary {
+    /// A vocabulary of words counted from a corpus.
+    fn from_iter<I: IntoIterator<Item = T>>(words: I) -> Vocabulary {
+        let mut vocabulary = Vocabulary::new();
+        for word in words {
+            vocabulary.add(word.as_ref(), 1);
+        }
+        vocabulary
+    }
+}
+
+impl<T: AsRef<str>> Extend<T> for Vocabulary {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, words: I) {
+        for word in words {
+            self.add(word.as_ref(), 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn frequent_words_win_ties() {
+        let mut vocabulary = Vocabulary::new();
+        vocabulary.add("cart", 1);
+        vocabulary.add("cast", 5);
+        vocabulary.add("Card", 1);
+        assert_eq!(vocabulary.correct("carst"), Some("cast"));
+        assert_eq!(vocabulary.correct("CAXT"), Some("cast"));
+        vocabulary.add("cart", 10);
+        assert_eq!(vocabulary.correct("CAXT"), Some("cart"));
+        assert_eq!(vocabulary.frequency("card"), 1);
+        assert_eq!(vocabulary.correct("ca"), None);
+    }
+}
@@ -0,0 +1,338 @@
+//! Statistics of score distributions, for picking cutoffs empirically.
+//!
+//! Collect the scores of a representative extraction, or of every pair of
+//! two lists, into [ScoreStats] and look at their histogram and percentiles.
+//! Scores of true matches and of unrelated strings often form two clusters,
+//! and [ScoreStats::cutoff_candidates] suggests cutoffs in the widest gaps
+//! between the scores that occur. With pairs labeled as matches or not,
+//! [calibrate] measures how well each cutoff tells them apart.
+//!
+//! ```
+//! # use fuzzywuzzy::stats::ScoreStats;
+//! # use fuzzywuzzy::process::extract_without_order;
+//! # use fuzzywuzzy::fuzz::Ratio;
+//! # use fuzzywuzzy::utils::full_process;
+//! let choices = ["apple pie", "apple pies", "aple pie", "banana split", "cherry tart"];
+//! let results = extract_without_order("apple pie", choices, full_process, Ratio, 0);
+//! let stats: ScoreStats = results.iter().map(|&(_, score)| score).collect();
+//! assert_eq!(stats.count(), 5);
+//! assert_eq!(stats.percentile(50.0), Some(94));
+//! assert_eq!(stats.cutoff_candidates(1), vec![94]);
+//! ```
+//!
+//! A matrix of scores is flattened first:
+//!
+//! ```
+//! # use fuzzywuzzy::stats::ScoreStats;
+//! let matrix = vec![vec![100, 12], vec![8, 91]];
+//! let stats: ScoreStats = matrix.iter().flatten().copied().collect();
+//! assert_eq!(stats.histogram(50), vec![2, 1, 1]);
+//! ```
+
+use crate::error::Error;
+use crate::fuzz::Scorer;
+use std::iter::FromIterator;
+
+/// The distribution of a collection of scores.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScoreStats {
+    /// `counts[score]` is the number of times `score` occurred.
+    counts: [usize; 101],
+    count: usize,
+}
+
+impl Default for ScoreStats {
+    fn default() -> ScoreStats {
+        ScoreStats::new()
+    }
+}
+
+impl ScoreStats {
+    /// Statistics of no scores.
+    pub fn new() -> ScoreStats {
+        ScoreStats {
+            counts: [0; 101],
+            count: 0,
+        }
+    }
+
+    /// Adds a score.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `score` is greater than 100.
+    pub fn add(&mut self, score: u8) {
+        assert!(score <= 100, "scores must be at most 100");
+        self.counts[score as usize] += 1;
+        self.count += 1;
+    }
+
+    /// The number of scores.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The number of times `score` occurred.
+    pub fn frequency(&self, score: u8) -> usize {
+        self.counts.get(score as usize).copied().unwrap_or(0)
+    }
+
+    /// The mean score, or `None` without scores.
+    pub fn mean(&self) -> Option<f64> {
+        let total: usize = self.counts.iter().enumerate().map(|(s, &n)| s * n).sum();
+        (self.count > 0).then(|| total as f64 / self.count as f64)
+    }
+
+    /// The number of scores in buckets of `width` scores: the first counts
+    /// scores from 0 to `width - 1`, and so on, with 100 in the last.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is 0.
+    pub fn histogram(&self, width: u8) -> Vec<usize> {
+        assert!(width > 0, "width must be greater than 0");
+        let width = width as usize;
+        let mut buckets = vec![0; 100 / width + 1];
+        for (score, &n) in self.counts.iter().enumerate() {
+            buckets[score / width] += n;
+        }
+        buckets
+    }
+
+    /// The least score which at least `percentile` percent of the scores are
+    /// at most, by the nearest-rank method, or `None` without scores.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `percentile` isn't between 0 and 100.
+    pub fn percentile(&self, percentile: f64) -> Option<u8> {
+        assert!(
+            (0.0..=100.0).contains(&percentile),
+            "percentile must be in [0.0, 100.0]"
+        );
+        let rank = ((percentile / 100.0 * self.count as f64).ceil() as usize).max(1);
+        let mut seen = 0;
+        for (score, &n) in self.counts.iter().enumerate() {
+            seen += n;
+            if seen >= rank {
+                return Some(score as u8);
+            }
+        }
+        None
+    }
+
+    /// Up to `n` suggested cutoffs, best first: the scores right above the
+    /// widest gaps between the scores that occur, wider gaps first and then
+    /// higher scores. A cutoff keeps the cluster of scores above its gap.
+    ///
+    /// ```
+    /// # use fuzzywuzzy::stats::ScoreStats;
+    /// let stats: ScoreStats = vec![20, 25, 31, 88, 90, 97, 100].into_iter().collect();
+    /// assert_eq!(stats.cutoff_candidates(3), vec![88, 97, 31]);
+    /// ```
+    pub fn cutoff_candidates(&self, n: usize) -> Vec<u8> {
+        let occurring: Vec<usize> = (0..=100).filter(|&s| self.counts[s] > 0).collect();
+        let mut gaps: Vec<(usize, usize)> = occurring
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0], pair[1]))
+            .collect();
+        gaps.sort_unstable_by(|x, y| y.cmp(x));
+        gaps.into_iter()
+            .take(n)
+            .map(|(_, score)| score as u8)
+            .collect()
+    }
+}
+
+impl Extend<u8> for ScoreStats {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, scores: I) {
+        for score in scores {
+            self.add(score);
+        }
+    }
+}
+
+impl FromIterator<u8> for ScoreStats {
+    fn from_iter<I: IntoIterator<Item = u8>>(scores: I) -> ScoreStats {
+        let mut stats = ScoreStats::new();
+        stats.extend(scores);
+        stats
+    }
+}
+
+/// How well a cutoff separates pairs labeled as matches from non-matches,
+/// see [calibrate].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ThresholdMetrics {
+    /// The cutoff, accepting pairs scoring at least this.
+    pub cutoff: u8,
+    /// The number of accepted matches.
+    pub true_positives: usize,
+    /// The number of accepted non-matches.
+    pub false_positives: usize,
+    /// The number of rejected matches.
+    pub false_negatives: usize,
+    /// The number of rejected non-matches.
+    pub true_negatives: usize,
+}
+
+impl ThresholdMetrics {
+    /// The fraction of accepted pairs which are matches, or 1.0 if none are
+    /// accepted.
+    pub fn precision(&self) -> f64 {
+        let accepted = self.true_positives + self.false_positives;
+        if accepted == 0 {
+            1.0
+        } else {
+            self.true_positives as f64 / accepted as f64
+        }
+    }
+
+    /// The fraction of matches which are accepted, or 1.0 if there are none.
+    pub fn recall(&self) -> f64 {
+        let matches = self.true_positives + self.false_negatives;
+        if matches == 0 {
+            1.0
+        } else {
+            self.true_positives as f64 / matches as f64
+        }
+    }
+
+    /// The harmonic mean of [precision](Self::precision) and
+    /// [recall](Self::recall), or 0.0 if no match is accepted.
+    pub fn f1(&self) -> f64 {
+        if self.true_positives == 0 {
+            return 0.0;
+        }
+        let (precision, recall) = (self.precision(), self.recall());
+        2.0 * precision * recall / (precision + recall)
+    }
+}
+
+/// The metrics of every cutoff for a set of labeled pairs, see [calibrate].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Calibration {
+    thresholds: Vec<ThresholdMetrics>,
+}
+
+impl Calibration {
+    /// The metrics of each cutoff from 0 to 100, in that order.
+    pub fn thresholds(&self) -> &[ThresholdMetrics] {
+        &self.thresholds
+    }
+
+    /// The metrics of `cutoff`, or `None` if it is greater than 100.
+    pub fn threshold(&self, cutoff: u8) -> Option<&ThresholdMetrics> {
+        self.thresholds.get(cutoff as usize)
+    }
+
+    /// The cutoff with the highest F1 score, the highest of several, or
+    /// `None` if no cutoff accepts a match.
+    pub fn recommended(&self) -> Option<u8> {
+        let mut best: Option<&ThresholdMetrics> = None;
+        for metrics in &self.thresholds {
+            if metrics.true_positives > 0 && best.is_none_or(|best| metrics.f1() >= best.f1()) {
+                best = Some(metrics);
+            }
+        }
+        best.map(|metrics| metrics.cutoff)
+    }
+}
+
+/// Scores `pairs` labeled as matches (`true`) or not by `labels` with
+/// `scorer`, and computes the precision, recall and F1 score of every cutoff.
+///
+/// Pairs are scored as given, so the scorer should process them if needed.
+///
+/// # Panics
+///
+/// Panics if `pairs` and `labels` differ in length, see [try_calibrate].
+///
+/// ```
+/// # use fuzzywuzzy::stats::calibrate;
+/// # use fuzzywuzzy::fuzz::Ratio;
+/// let pairs = [
+///     ("jon smith", "john smith"),
+///     ("jane doe", "jane dow"),
+///     ("bob jones", "rob jones"),
+///     ("alice wong", "alice wang"),
+///     ("mary lee", "larry lee"),
+///     ("tom hanks", "tim banks"),
+/// ];
+/// let labels = [true, true, false, true, false, false];
+/// let calibration = calibrate(&pairs, &labels, Ratio);
+/// let cutoff = calibration.recommended().unwrap();
+/// assert_eq!(cutoff, 88);
+/// let metrics = calibration.threshold(cutoff).unwrap();
+/// assert_eq!((metrics.precision(), metrics.recall()), (0.75, 1.0));
+/// // "bob jones" scores 89, between the matches.
+/// assert_eq!(calibration.threshold(90).unwrap().false_negatives, 1);
+/// ```
+pub fn calibrate<A, B, S>(pairs: &[(A, B)], labels: &[bool], scorer: S) -> Calibration
+where
+    A: AsRef<str>,
+    B: AsRef<str>,
+    S: Scorer,
+{
+    try_calibrate(pairs, labels, scorer).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Like [calibrate], but returns [Error::LengthMismatch] instead of panicking
+/// if `pairs` and `labels` differ in length.
+///
+/// ```
+/// # use fuzzywuzzy::stats::try_calibrate;
+/// # use fuzzywuzzy::fuzz::Ratio;
+/// # use fuzzywuzzy::error::Error;
+/// let pairs = [("jon smith", "john smith"), ("jane doe", "jane dow")];
+/// assert_eq!(
+///     try_calibrate(&pairs, &[true], Ratio).err(),
+///     Some(Error::LengthMismatch { expected: 2, actual: 1 })
+/// );
+/// ```
+pub fn try_calibrate<A, B, S>(
+    pairs: &[(A, B)],
+    labels: &[bool],
+    scorer: S,
+) -> Result<Calibration, Error>
+where
+    A: AsRef<str>,
+    B: AsRef<str>,
+    S: Scorer,
+{
+    if labels.len() != pairs.len() {
+        return Err(Error::LengthMismatch {
+            expected: pairs.len(),
+            actual: labels.len(),
+        });
+    }
+    let mut matches = ScoreStats::new();
+    let mut non_matches = ScoreStats::new();
+    for ((a, b), &label) in pairs.iter().zip(labels) {
+        let score = scorer.score(a.as_ref(), b.as_ref()).percent();
+        if label {
+            matches.add(score);
+        } else {
+            non_matches.add(score);
+        }
+    }
+    count!(candidates_scored, pairs.len());
+
+    let (mut accepted_matches, mut accepted_non_matches) = (0, 0);
+    let mut thresholds: Vec<ThresholdMetrics> = (0..=100u8)
+        .rev()
+        .map(|cutoff| {
+            accepted_matches += matches.frequency(cutoff);
+            accepted_non_matches += non_matches.frequency(cutoff);
+            ThresholdMetrics {
+                cutoff,
+                true_positives: accepted_matches,
+                false_positives: accepted_non_matches,
+                false_negatives: matches.count() - accepted_matches,
+                true_negatives: non_matches.count() - accepted_non_matches,
+            }
+        })
+        .collect();
+    thresholds.reverse();
+    Ok(Calibration { thresholds })
+}
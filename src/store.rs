@@ -0,0 +1,314 @@
+//! [ChoiceStore], a compact store of choices for extracting from many times.
+//!
+//! Large choice lists tend to repeat themselves: exports of customer or
+//! product records share a handful of cities, suffixes and words, and often
+//! whole values. A [ChoiceStore] processes each choice once when it is added
+//! and interns the original, the processed string and each of its tokens,
+//! so every distinct string is stored once, back to back in a single buffer,
+//! and the choices themselves are just a few integers each.
+//!
+//! ```
+//! # use fuzzywuzzy::store::ChoiceStore;
+//! # use fuzzywuzzy::fuzz::Ratio;
+//! let store: ChoiceStore = ["New York", "new york", "York", "New Haven"].iter().collect();
+//! assert_eq!(store.len(), 4);
+//! // "New York" and "new york" process alike, and share their tokens with the others.
+//! assert_eq!(store.distinct_strings(), 8);
+//! assert_eq!(store.processed(1), Some("new york"));
+//! assert_eq!(store.tokens(3).collect::<Vec<_>>(), vec!["new", "haven"]);
+//! assert_eq!(store.extract("NEW YORK!", Ratio, 80), vec![(0, 100), (1, 100)]);
+//! ```
+
+use crate::fuzz::Scorer;
+use crate::processing::Processor;
+use crate::trace;
+use crate::utils::full_process;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::hash::BuildHasher;
+use std::iter::FromIterator;
+
+/// Marks an empty slot of an [Interner]'s table.
+const EMPTY: u32 = u32::MAX;
+
+/// Distinct strings stored back to back in one buffer, identified by the
+/// order they were first interned in.
+#[derive(Clone, Debug, Default)]
+struct Interner {
+    arena: String,
+    /// The end of each string in `arena`, where the next one starts.
+    ends: Vec<usize>,
+    /// An open addressing hash table of the strings' ids, with linear
+    /// probing, kept at most half full.
+    table: Vec<u32>,
+    hasher: RandomState,
+}
+
+impl Interner {
+    fn get(&self, id: u32) -> &str {
+        let id = id as usize;
+        let start = if id == 0 { 0 } else { self.ends[id - 1] };
+        &self.arena[start..self.ends[id]]
+    }
+
+    /// The slot in `table` holding `s`, or the empty one it would go in.
+    fn slot(&self, s: &str) -> usize {
+        let mask = self.table.len() - 1;
+        let mut slot = self.hasher.hash_one(s) as usize & mask;
+        while self.table[slot] != EMPTY && self.get(self.table[slot]) != s {
+            slot = (slot + 1) & mask;
+        }
+        slot
+    }
+
+    fn intern(&mut self, s: &str) -> u32 {
+        if 2 * (self.ends.len() + 1) > self.table.len() {
+            self.grow();
+        }
+        let slot = self.slot(s);
+        if self.table[slot] != EMPTY {
+            return self.table[slot];
+        }
+        let id = u32::try_from(self.ends.len())
+            .ok()
+            .filter(|&id| id != EMPTY)
+            .expect("a store holds fewer than u32::MAX distinct strings");
+        self.arena.push_str(s);
+        self.ends.push(self.arena.len());
+        self.table[slot] = id;
+        id
+    }
+
+    fn grow(&mut self) {
+        self.table = vec![EMPTY; (2 * self.table.len()).max(16)];
+        for id in 0..self.ends.len() as u32 {
+            let slot = self.slot(self.get(id));
+            self.table[slot] = id;
+        }
+    }
+}
+
+/// A choice of a [ChoiceStore], as ids of interned strings.
+#[derive(Clone, Copy, Debug)]
+struct Choice {
+    original: u32,
+    processed: u32,
+    /// The range of the processed string's tokens in `ChoiceStore::tokens`.
+    tokens: (u32, u32),
+}
+
+/// Choices processed once and interned, see the [module](self) docs.
+pub struct ChoiceStore {
+    processor: Option<Box<dyn Processor + Send + Sync>>,
+    strings: Interner,
+    choices: Vec<Choice>,
+    /// The token ids of each distinct processed string, back to back.
+    tokens: Vec<u32>,
+    /// The range in `tokens` of the processed string with each id, or
+    /// `(EMPTY, EMPTY)` if no choice processes to it.
+    token_ranges: Vec<(u32, u32)>,
+}
+
+impl Default for ChoiceStore {
+    fn default() -> ChoiceStore {
+        ChoiceStore::new()
+    }
+}
+
+impl ChoiceStore {
+    /// An empty store processing choices with [full_process].
+    pub fn new() -> ChoiceStore {
+        ChoiceStore {
+            processor: None,
+            strings: Interner::default(),
+            choices: vec![],
+            tokens: vec![],
+            token_ranges: vec![],
+        }
+    }
+
+    /// Processes choices, and queries, with `processor` instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the store isn't empty, as its choices were processed
+    /// differently.
+    pub fn processor<P: Processor + Send + Sync + 'static>(mut self, processor: P) -> Self {
+        assert!(
+            self.choices.is_empty(),
+            "the processor must be set before adding choices"
+        );
+        self.processor = Some(Box::new(processor));
+        self
+    }
+
+    /// Processes `s` like the choices are.
+    pub fn process(&self, s: &str) -> String {
+        match &self.processor {
+            Some(processor) => processor.process(s, false),
+            None => full_process(s, false),
+        }
+    }
+
+    /// Adds a choice, returning its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the store would hold `u32::MAX` or more distinct strings or
+    /// tokens.
+    pub fn push(&mut self, choice: &str) -> usize {
+        let processed = self.process(choice);
+        let original = self.strings.intern(choice);
+        let processed_id = self.strings.intern(&processed);
+        let index = processed_id as usize;
+        if self.token_ranges.len() <= index {
+            self.token_ranges.resize(index + 1, (EMPTY, EMPTY));
+        }
+        if self.token_ranges[index].0 == EMPTY {
+            let offset =
+                |n: usize| u32::try_from(n).expect("a store holds fewer than u32::MAX tokens");
+            let start = offset(self.tokens.len());
+            for token in processed.split_whitespace() {
+                let id = self.strings.intern(token);
+                self.tokens.push(id);
+            }
+            self.token_ranges[index] = (start, offset(self.tokens.len()));
+        }
+        self.choices.push(Choice {
+            original,
+            processed: processed_id,
+            tokens: self.token_ranges[index],
+        });
+        self.choices.len() - 1
+    }
+
+    /// The number of choices.
+    pub fn len(&self) -> usize {
+        self.choices.len()
+    }
+
+    /// Whether there are no choices.
+    pub fn is_empty(&self) -> bool {
+        self.choices.is_empty()
+    }
+
+    /// The number of distinct strings stored: originals, processed strings
+    /// and tokens.
+    pub fn distinct_strings(&self) -> usize {
+        self.strings.ends.len()
+    }
+
+    /// The choice at `index`, as it was added.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        let choice = self.choices.get(index)?;
+        Some(self.strings.get(choice.original))
+    }
+
+    /// The processed choice at `index`.
+    pub fn processed(&self, index: usize) -> Option<&str> {
+        let choice = self.choices.get(index)?;
+        Some(self.strings.get(choice.processed))
+    }
+
+    /// The whitespace separated tokens of the processed choice at `index`,
+    /// in order, or none if there is no such choice.
+    pub fn tokens(&self, index: usize) -> impl Iterator<Item = &str> + '_ {
+        let (start, end) = self
+            .choices
+            .get(index)
+            .map_or((0, 0), |choice| choice.tokens);
+        self.tokens[start as usize..end as usize]
+            .iter()
+            .map(move |&id| self.strings.get(id))
+    }
+
+    /// The choices in order, as they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.choices
+            .iter()
+            .map(move |choice| self.strings.get(choice.original))
+    }
+
+    /// The indices of the choices scoring at least `score_cutoff` against
+    /// `query` by `scorer`, with their scores, in order.
+    ///
+    /// `query` is processed like the choices, and `scorer` is given the
+    /// processed strings. Choices processing alike are scored once.
+    pub fn extract<S: Scorer>(
+        &self,
+        query: impl AsRef<str>,
+        scorer: S,
+        score_cutoff: u8,
+    ) -> Vec<(usize, u8)> {
+        let run = trace::Run::start("ChoiceStore::extract");
+        let _entered = run.enter();
+        let query = run.processing(|| self.process(query.as_ref()));
+        // The score of each processed string by id, once scored, or `None`
+        // if it falls below the cutoff.
+        let mut scores: HashMap<u32, Option<u8>> = HashMap::new();
+        let mut results = vec![];
+        for (index, choice) in self.choices.iter().enumerate() {
+            run.candidate();
+            let score = *scores.entry(choice.processed).or_insert_with(|| {
+                let processed = self.strings.get(choice.processed);
+                if score_cutoff > 0 && scorer.upper_bound(&query, processed) < score_cutoff {
+                    return None;
+                }
+                count!(candidates_scored, 1);
+                Some(run.scoring(|| scorer.score(&query, processed).percent()))
+                    .filter(|&s| s >= score_cutoff)
+            });
+            if let Some(score) = score {
+                results.push((index, score));
+            } else {
+                count!(candidates_pruned, 1);
+                run.pruned();
+            }
+        }
+        run.finish(results.len());
+        results
+    }
+}
+
+impl<T: AsRef<str>> Extend<T> for ChoiceStore {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, choices: I) {
+        for choice in choices {
+            self.push(choice.as_ref());
+        }
+    }
+}
+
+impl<T: AsRef<str>> FromIterator<T> for ChoiceStore {
+    fn from_iter<I: IntoIterator<Item = T>>(choices: I) -> ChoiceStore {
+        let mut store = ChoiceStore::new();
+        store.extend(choices);
+        store
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn interned_strings_survive_growing() {
+        let mut store = ChoiceStore::new().processor(|s: &str, _: bool| s.to_string());
+        for i in 0..1000 {
+            assert_eq!(store.push(&format!("{} {}", i % 100, i % 7)), i);
+        }
+        for i in 0..1000 {
+            let choice = format!("{} {}", i % 100, i % 7);
+            assert_eq!(store.get(i), Some(choice.as_str()));
+            assert_eq!(store.processed(i), Some(choice.as_str()));
+            assert_eq!(
+                store.tokens(i).collect::<Vec<_>>(),
+                choice.split(' ').collect::<Vec<_>>()
+            );
+        }
+        // 700 distinct choices, and the tokens 0 to 99.
+        assert_eq!(store.distinct_strings(), 700 + 100);
+        assert_eq!(store.get(1000), None);
+        assert_eq!(store.tokens(1000).count(), 0);
+    }
+}
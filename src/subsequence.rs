@@ -0,0 +1,819 @@
+//! Scorers for interactive filtering, where the query must be a subsequence of the candidate.
+//! Requires feature "subsequence".
+//!
+//! Unlike the scorers in [fuzz](crate::fuzz), these are asymmetric: every
+//! character of the query has to appear in the candidate, in order, but may be
+//! spread out. Matches at word boundaries and runs of consecutive characters
+//! score higher, which suits typing a few letters of a file path or command
+//! name. Matching ignores case.
+//!
+//! ```
+//! # use fuzzywuzzy::subsequence::fzf_score;
+//! assert!(fzf_score("fb", "foo_bar") > fzf_score("fb", "afoobar"));
+//! assert_eq!(fzf_score("fbz", "foo_bar"), None);
+//! ```
+//!
+//! Two algorithms are available: fzf's, which finds the optimal alignment
+//! with dynamic programming, and the bonus heuristics popularized by Sublime
+//! Text, which search alternatives recursively. [FzfScorer] and
+//! [SublimeScorer] make them usable with [process](crate::process).
+
+use crate::fuzz::Scorer;
+use crate::score::Score;
+use std::cmp::Reverse;
+
+const SCORE_MATCH: i32 = 16;
+const SCORE_GAP_START: i32 = -3;
+const SCORE_GAP_EXTENSION: i32 = -1;
+const BONUS_BOUNDARY: i32 = SCORE_MATCH / 2;
+const BONUS_NON_WORD: i32 = SCORE_MATCH / 2;
+const BONUS_CAMEL_123: i32 = BONUS_BOUNDARY + SCORE_GAP_EXTENSION;
+const BONUS_CONSECUTIVE: i32 = -(SCORE_GAP_START + SCORE_GAP_EXTENSION);
+const BONUS_FIRST_CHAR_MULTIPLIER: i32 = 2;
+const BONUS_BOUNDARY_WHITE: i32 = BONUS_BOUNDARY + 2;
+const BONUS_BOUNDARY_DELIMITER: i32 = BONUS_BOUNDARY + 1;
+
+/// Kinds of characters, in fzf's order, which matters for [bonus_for].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum CharClass {
+    White,
+    NonWord,
+    Delimiter,
+    Lower,
+    Upper,
+    Letter,
+    Number,
+}
+
+fn char_class(c: char) -> CharClass {
+    if c.is_lowercase() {
+        CharClass::Lower
+    } else if c.is_uppercase() {
+        CharClass::Upper
+    } else if c.is_numeric() {
+        CharClass::Number
+    } else if c.is_alphabetic() {
+        CharClass::Letter
+    } else if c.is_whitespace() {
+        CharClass::White
+    } else if "/,:;|".contains(c) {
+        CharClass::Delimiter
+    } else {
+        CharClass::NonWord
+    }
+}
+
+/// The bonus for matching a character of class `class` following one of class `prev`.
+fn bonus_for(prev: CharClass, class: CharClass) -> i32 {
+    if class > CharClass::NonWord {
+        match prev {
+            CharClass::White => return BONUS_BOUNDARY_WHITE,
+            CharClass::Delimiter => return BONUS_BOUNDARY_DELIMITER,
+            CharClass::NonWord => return BONUS_BOUNDARY,
+            _ => {}
+        }
+    }
+    if prev == CharClass::Lower && class == CharClass::Upper
+        || prev != CharClass::Number && class == CharClass::Number
+    {
+        return BONUS_CAMEL_123;
+    }
+    match class {
+        CharClass::NonWord | CharClass::Delimiter => BONUS_NON_WORD,
+        CharClass::White => BONUS_BOUNDARY_WHITE,
+        _ => 0,
+    }
+}
+
+/// The result of a successful [fzf_match].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FzfMatch {
+    /// The raw score, which grows with the length of the query.
+    pub score: u32,
+    /// The indices of the characters of the candidate matched by the query, in increasing order.
+    pub positions: Vec<usize>,
+}
+
+/// Scores `candidate` for `query` like fzf's default algorithm, or returns
+/// `None` if `query` isn't a subsequence of `candidate`.
+///
+/// See [fzf_match].
+pub fn fzf_score(query: &str, candidate: &str) -> Option<u32> {
+    fzf_match(query, candidate).map(|m| m.score)
+}
+
+/// Finds the best scoring occurrence of `query` as a subsequence of
+/// `candidate`, like fzf's default algorithm, or `None` if there is none.
+///
+/// Each matched character scores 16 points. Gaps between matched
+/// characters cost 3 points to open and 1 point per further character.
+/// Characters matched at word boundaries, after separators, at camelCase
+/// humps or at the start of numbers earn bonuses, which also extend to the
+/// characters following them in a consecutive run. A bonus on the first
+/// character of `query` counts double.
+///
+/// ```
+/// # use fuzzywuzzy::subsequence::fzf_match;
+/// let m = fzf_match("sm", "src/main.rs").unwrap();
+/// assert_eq!(m.positions, vec![0, 4]);
+/// // A match after the start of the string and one after a slash, three characters apart.
+/// assert_eq!(m.score, 2 * 16 + 2 * 10 + 9 - 3 - 1 - 1);
+/// assert_eq!(fzf_match("fooBar", "foobar").unwrap().positions, vec![0, 1, 2, 3, 4, 5]);
+/// assert!(fzf_match("abc", "acb").is_none());
+/// ```
+pub fn fzf_match(query: &str, candidate: &str) -> Option<FzfMatch> {
+    // https://github.com/junegunn/fzf/blob/master/src/algo/algo.go, FuzzyMatchV2
+    let pattern: Vec<char> = query.chars().map(fold).collect();
+    let text: Vec<char> = candidate.chars().collect();
+    let folded: Vec<char> = text.iter().copied().map(fold).collect();
+    let (m, n) = (pattern.len(), text.len());
+    if m == 0 {
+        return Some(FzfMatch {
+            score: 0,
+            positions: vec![],
+        });
+    }
+
+    // The first position each prefix of the pattern can end at.
+    let mut first = Vec::with_capacity(m);
+    let mut next = 0;
+    for &p in pattern.iter() {
+        let offset = folded[next..].iter().position(|&c| c == p)?;
+        first.push(next + offset);
+        next += offset + 1;
+    }
+
+    let bonus = bonuses(&text);
+
+    // `h[i * n + j]` is the best score of the first `i + 1` characters of the
+    // pattern ending at or before `j`, and `c[i * n + j]` is the length of the
+    // consecutive run ending at `j` if the pattern's `i`th character is matched there.
+    let mut h = vec![0i32; m * n];
+    let mut c = vec![0u32; m * n];
+    for i in 0..m {
+        let (done_h, h_rest) = h.split_at_mut(i * n);
+        let (done_c, c_rest) = c.split_at_mut(i * n);
+        // The previous row, or nothing for the first.
+        let from = i.saturating_sub(1) * n;
+        fzf_row(
+            pattern[i],
+            first[i],
+            &folded,
+            &bonus,
+            (&done_h[from..], &done_c[from..]),
+            (&mut h_rest[..n], &mut c_rest[..n]),
+        );
+    }
+
+    let last = (m - 1) * n;
+    let (mut j, score) =
+        (first[m - 1]..n)
+            .map(|j| (j, h[last + j]))
+            .fold((first[m - 1], -1), |best, cell| {
+                if cell.1 > best.1 {
+                    cell
+                } else {
+                    best
+                }
+            });
+
+    let mut positions = Vec::with_capacity(m);
+    let mut i = m - 1;
+    let mut prefer_match = true;
+    loop {
+        let row = i;
+        let s = h[i * n + j];
+        let diagonal = if i > 0 && j >= first[i] {
+            h[(i - 1) * n + j - 1]
+        } else {
+            0
+        };
+        let left = if j > first[i] { h[i * n + j - 1] } else { 0 };
+        if s > diagonal && (s > left || s == left && prefer_match) {
+            positions.push(j);
+            if i == 0 {
+                break;
+            }
+            i -= 1;
+        }
+        prefer_match =
+            c[row * n + j] > 1 || row + 1 < m && j + 1 < n && c[(row + 1) * n + j + 1] > 0;
+        j -= 1;
+    }
+    positions.reverse();
+    Some(FzfMatch {
+        score: score as u32,
+        positions,
+    })
+}
+
+/// Folds the case of `c` for matching.
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// The bonus for matching each character of `text`.
+fn bonuses(text: &[char]) -> Vec<i32> {
+    let mut prev_class = CharClass::White;
+    text.iter()
+        .map(|&c| {
+            let class = char_class(c);
+            let bonus = bonus_for(prev_class, class);
+            prev_class = class;
+            bonus
+        })
+        .collect()
+}
+
+/// Fills in the row of fzf's score matrices for pattern character `p`,
+/// which can first be matched at `start`, from the previous row, which is
+/// empty for the first character of the pattern. See [fzf_match].
+fn fzf_row(
+    p: char,
+    start: usize,
+    folded: &[char],
+    bonus: &[i32],
+    (prev_h, prev_c): (&[i32], &[u32]),
+    (h, c): (&mut [i32], &mut [u32]),
+) {
+    let first_row = prev_h.is_empty();
+    let mut in_gap = false;
+    for j in start..folded.len() {
+        let left = if j > start { h[j - 1] } else { 0 };
+        let gap = left
+            + if in_gap {
+                SCORE_GAP_EXTENSION
+            } else {
+                SCORE_GAP_START
+            };
+        let mut matched = 0;
+        let mut consecutive = 0;
+        if folded[j] == p {
+            if first_row {
+                matched = SCORE_MATCH + bonus[j] * BONUS_FIRST_CHAR_MULTIPLIER;
+                consecutive = 1;
+            } else {
+                let mut b = bonus[j];
+                consecutive = prev_c[j - 1] + 1;
+                if consecutive > 1 {
+                    let run_bonus = bonus[j + 1 - consecutive as usize];
+                    if b >= BONUS_BOUNDARY && b > run_bonus {
+                        consecutive = 1;
+                    } else {
+                        b = b.max(BONUS_CONSECUTIVE).max(run_bonus);
+                    }
+                }
+                matched = prev_h[j - 1] + SCORE_MATCH;
+                if matched + b < gap {
+                    matched += bonus[j];
+                    consecutive = 0;
+                } else {
+                    matched += b;
+                }
+            }
+        }
+        // The first character's row doesn't carry scores to the right
+        // across matches, only across gaps.
+        let score = if first_row && consecutive == 1 {
+            matched
+        } else {
+            matched.max(gap).max(0)
+        };
+        in_gap = if first_row {
+            consecutive == 0
+        } else {
+            matched < gap
+        };
+        h[j] = score;
+        c[j] = consecutive;
+    }
+}
+
+/// The highest [fzf_score] a query of `len` characters can get.
+fn max_fzf_score(len: usize) -> u32 {
+    let first = SCORE_MATCH + BONUS_BOUNDARY_WHITE * BONUS_FIRST_CHAR_MULTIPLIER;
+    let rest = (SCORE_MATCH + BONUS_BOUNDARY_WHITE) * (len as i32 - 1);
+    (first + rest) as u32
+}
+
+/// [Scorer] for [fzf_score], scaled to a percentage of the best possible
+/// score for the query. The first string is the query and the second the
+/// candidate, which is what [process](crate::process) functions pass.
+///
+/// Candidates which don't contain the query as a subsequence score 0.
+///
+/// ```
+/// # use fuzzywuzzy::subsequence::FzfScorer;
+/// # use fuzzywuzzy::fuzz::Scorer;
+/// # use fuzzywuzzy::process::extract_one;
+/// let files = ["src/fuzz.rs", "src/utils.rs", "tests/fuzz_test.rs"];
+/// let (best, _) = extract_one("su", files.iter(), |s: &str, _| s.to_string(), FzfScorer, 0).unwrap();
+/// assert_eq!(best, "src/utils.rs");
+/// assert_eq!(FzfScorer.score("zz", "src/fuzz.rs"), 58);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FzfScorer;
+
+impl Scorer for FzfScorer {
+    fn score(&self, query: &str, candidate: &str) -> Score {
+        let best = max_fzf_score(query.chars().count()) as i32;
+        scale(
+            query,
+            candidate,
+            fzf_score(query, candidate).map(|s| s as i32),
+            best,
+        )
+    }
+}
+
+/// Scales a raw score for `query` to a percentage of `best`, scoring
+/// candidates which don't match 0.
+fn scale(query: &str, candidate: &str, raw: Option<i32>, best: i32) -> Score {
+    if query.is_empty() {
+        return if candidate.is_empty() {
+            Score::MAX
+        } else {
+            Score::MIN
+        };
+    }
+    match raw {
+        Some(raw) => Score::from_fraction((raw as f64 / best as f64).clamp(0.0, 1.0))
+            .expect("fraction is between 0 and 1"),
+        None => Score::MIN,
+    }
+}
+
+/// The state an [IncrementalMatcher] keeps for one candidate.
+#[derive(Clone, Debug)]
+struct Candidate {
+    folded: Vec<char>,
+    bonus: Vec<i32>,
+    /// The first position each prefix of the query can end at, for the
+    /// prefixes which are subsequences of the candidate.
+    first: Vec<usize>,
+    /// A row of fzf's score matrices per entry of `first`, concatenated.
+    h: Vec<i32>,
+    c: Vec<u32>,
+}
+
+impl Candidate {
+    fn push(&mut self, p: char, query_len: usize) {
+        // The query already stopped matching.
+        if self.first.len() + 1 < query_len {
+            return;
+        }
+        let next = self.first.last().map_or(0, |&j| j + 1);
+        let start = match self.folded[next..].iter().position(|&c| c == p) {
+            Some(offset) => next + offset,
+            None => return,
+        };
+        let n = self.folded.len();
+        let i = self.first.len();
+        self.first.push(start);
+        self.h.resize((i + 1) * n, 0);
+        self.c.resize((i + 1) * n, 0);
+        let (done_h, h) = self.h.split_at_mut(i * n);
+        let (done_c, c) = self.c.split_at_mut(i * n);
+        let from = i.saturating_sub(1) * n;
+        fzf_row(
+            p,
+            start,
+            &self.folded,
+            &self.bonus,
+            (&done_h[from..], &done_c[from..]),
+            (h, c),
+        );
+    }
+
+    fn truncate(&mut self, query_len: usize) {
+        let n = self.folded.len();
+        self.first.truncate(query_len);
+        self.h.truncate(query_len * n);
+        self.c.truncate(query_len * n);
+    }
+
+    fn score(&self, query_len: usize) -> Option<u32> {
+        if query_len == 0 {
+            return Some(0);
+        }
+        if self.first.len() < query_len {
+            return None;
+        }
+        let n = self.folded.len();
+        let row = &self.h[(query_len - 1) * n..];
+        row[self.first[query_len - 1]..]
+            .iter()
+            .max()
+            .map(|&s| s as u32)
+    }
+}
+
+/// Scores a fixed list of candidates for a query typed one character at a
+/// time, giving the same scores as [fzf_score].
+///
+/// fzf's dynamic programming computes one row per character of the query,
+/// each from the one before, so the matcher keeps the rows of every
+/// candidate and appending a character computes a single row, while
+/// removing one just drops it. Candidates stop being updated once the query
+/// isn't a subsequence of them. This makes each keystroke linear in the
+/// total length of the candidates still matching, at the cost of keeping a
+/// row per character of the query for each of them.
+///
+/// ```
+/// # use fuzzywuzzy::subsequence::{fzf_score, IncrementalMatcher};
+/// let files = ["src/main.rs", "src/lib.rs", "Cargo.toml"];
+/// let mut matcher = IncrementalMatcher::new(files.iter());
+/// matcher.push('s');
+/// matcher.push('l');
+/// assert_eq!(matcher.query(), "sl");
+/// assert_eq!(matcher.matches(), vec![(1, fzf_score("sl", "src/lib.rs").unwrap())]);
+/// matcher.pop();
+/// matcher.push('m');
+/// assert_eq!(matcher.score(0), fzf_score("sm", "src/main.rs"));
+/// matcher.set_query("cargo");
+/// assert_eq!(matcher.matches().len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IncrementalMatcher {
+    query: Vec<char>,
+    candidates: Vec<Candidate>,
+}
+
+impl IncrementalMatcher {
+    /// A matcher for `candidates` with an empty query.
+    pub fn new<I, T>(candidates: I) -> IncrementalMatcher
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let candidates = candidates
+            .into_iter()
+            .map(|candidate| {
+                let text: Vec<char> = candidate.as_ref().chars().collect();
+                Candidate {
+                    folded: text.iter().copied().map(fold).collect(),
+                    bonus: bonuses(&text),
+                    first: vec![],
+                    h: vec![],
+                    c: vec![],
+                }
+            })
+            .collect();
+        IncrementalMatcher {
+            query: vec![],
+            candidates,
+        }
+    }
+
+    /// The current query.
+    pub fn query(&self) -> String {
+        self.query.iter().collect()
+    }
+
+    /// Appends `c` to the query.
+    pub fn push(&mut self, c: char) {
+        self.query.push(c);
+        let len = self.query.len();
+        for candidate in self.candidates.iter_mut() {
+            candidate.push(fold(c), len);
+        }
+    }
+
+    /// Removes the last character of the query and returns it, or `None` if
+    /// the query is empty.
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.query.pop()?;
+        let len = self.query.len();
+        for candidate in self.candidates.iter_mut() {
+            candidate.truncate(len);
+        }
+        Some(c)
+    }
+
+    /// Changes the query to `query`, keeping the work done for the prefix
+    /// it shares with the current one.
+    pub fn set_query(&mut self, query: &str) {
+        let query: Vec<char> = query.chars().collect();
+        let shared = self
+            .query
+            .iter()
+            .zip(query.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        while self.query.len() > shared {
+            self.pop();
+        }
+        for &c in query[shared..].iter() {
+            self.push(c);
+        }
+    }
+
+    /// The [fzf_score] of the candidate at `index` for the current query.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn score(&self, index: usize) -> Option<u32> {
+        self.candidates[index].score(self.query.len())
+    }
+
+    /// The indices and scores of the candidates matching the current query,
+    /// best first and otherwise in order.
+    pub fn matches(&self) -> Vec<(usize, u32)> {
+        let len = self.query.len();
+        let mut matches: Vec<(usize, u32)> = self
+            .candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(i, candidate)| candidate.score(len).map(|score| (i, score)))
+            .collect();
+        matches.sort_by_key(|&(i, score)| (Reverse(score), i));
+        matches
+    }
+}
+
+const SEQUENTIAL_BONUS: i32 = 15;
+const SEPARATOR_BONUS: i32 = 30;
+const CAMEL_BONUS: i32 = 30;
+const FIRST_LETTER_BONUS: i32 = 15;
+const LEADING_LETTER_PENALTY: i32 = -5;
+const MAX_LEADING_LETTER_PENALTY: i32 = -15;
+const UNMATCHED_LETTER_PENALTY: i32 = -1;
+const RECURSION_LIMIT: usize = 10;
+const MAX_MATCHES: usize = 256;
+
+/// The result of a successful [sublime_match].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SublimeMatch {
+    /// The raw score, starting from 100 and possibly negative.
+    pub score: i32,
+    /// The indices of the characters of the candidate matched by the query, in increasing order.
+    pub positions: Vec<usize>,
+}
+
+/// Scores `candidate` for `query` like Sublime Text's "Goto Anything", or
+/// returns `None` if `query` isn't a subsequence of `candidate`.
+///
+/// See [sublime_match].
+pub fn sublime_score(query: &str, candidate: &str) -> Option<i32> {
+    sublime_match(query, candidate).map(|m| m.score)
+}
+
+/// Finds the best scoring occurrence of `query` as a subsequence of
+/// `candidate` with the heuristics popularized by Sublime Text, or `None` if
+/// there is none.
+///
+/// A match starts at 100 points, and:
+/// - earns 15 points for each character directly following the previous match,
+/// - earns 30 points for each character after a `_` or a space, or starting
+///   a camelCase hump,
+/// - earns 15 points if it starts with the first character of `candidate`,
+/// - loses 5 points for each character before the first match, up to 15,
+/// - loses 1 point for each character of `candidate` that isn't matched.
+///
+/// Alternative matches are searched recursively, to a limited depth.
+///
+/// ```
+/// # use fuzzywuzzy::subsequence::sublime_match;
+/// let m = sublime_match("gp", "getPath").unwrap();
+/// assert_eq!(m.positions, vec![0, 3]);
+/// assert_eq!(m.score, 100 + 15 + 30 - 5);
+/// assert!(sublime_match("pg", "getPath").is_none());
+/// ```
+pub fn sublime_match(query: &str, candidate: &str) -> Option<SublimeMatch> {
+    // https://github.com/forrestthewoods/lib_fts/blob/master/code/fts_fuzzy_match.h
+    let pattern: Vec<char> = query.chars().map(fold).collect();
+    let text: Vec<char> = candidate.chars().collect();
+    let folded: Vec<char> = text.iter().copied().map(fold).collect();
+    let mut recursion_count = 0;
+    let mut positions = Vec::new();
+    let score = sublime_recursive(
+        &pattern,
+        &text,
+        &folded,
+        0,
+        &[],
+        &mut positions,
+        &mut recursion_count,
+    )?;
+    Some(SublimeMatch { score, positions })
+}
+
+/// Matches `pattern` against `text[start..]`, continuing from the matches in
+/// `previous` and writing the best complete set of matches to `matches`.
+fn sublime_recursive(
+    pattern: &[char],
+    text: &[char],
+    folded: &[char],
+    start: usize,
+    previous: &[usize],
+    matches: &mut Vec<usize>,
+    recursion_count: &mut usize,
+) -> Option<i32> {
+    *recursion_count += 1;
+    if *recursion_count >= RECURSION_LIMIT || pattern.is_empty() || start >= text.len() {
+        return None;
+    }
+    matches.clear();
+    matches.extend_from_slice(previous);
+    let mut best_recursive: Option<(i32, Vec<usize>)> = None;
+    let mut p = 0;
+    for j in start..text.len() {
+        if p == pattern.len() {
+            break;
+        }
+        if folded[j] != pattern[p] {
+            continue;
+        }
+        if matches.len() >= MAX_MATCHES {
+            return None;
+        }
+        // Try skipping this occurrence, in case a later one scores higher.
+        let mut recursive_matches = Vec::new();
+        if let Some(score) = sublime_recursive(
+            &pattern[p..],
+            text,
+            folded,
+            j + 1,
+            matches,
+            &mut recursive_matches,
+            recursion_count,
+        ) {
+            if best_recursive
+                .as_ref()
+                .is_none_or(|(best, _)| score > *best)
+            {
+                best_recursive = Some((score, recursive_matches));
+            }
+        }
+        matches.push(j);
+        p += 1;
+    }
+    let score = (p == pattern.len()).then(|| sublime_points(text, matches));
+    match (score, best_recursive) {
+        (score, Some((best, best_matches))) if score.is_none_or(|score| best > score) => {
+            *matches = best_matches;
+            Some(best)
+        }
+        (score, _) => score,
+    }
+}
+
+/// The score of matching the characters of `text` at `matches`.
+fn sublime_points(text: &[char], matches: &[usize]) -> i32 {
+    let mut score = 100;
+    score += (LEADING_LETTER_PENALTY * matches[0] as i32).max(MAX_LEADING_LETTER_PENALTY);
+    score += UNMATCHED_LETTER_PENALTY * (text.len() - matches.len()) as i32;
+    for (k, &j) in matches.iter().enumerate() {
+        if k > 0 && j == matches[k - 1] + 1 {
+            score += SEQUENTIAL_BONUS;
+        }
+        if j == 0 {
+            score += FIRST_LETTER_BONUS;
+            continue;
+        }
+        let (neighbor, current) = (text[j - 1], text[j]);
+        if neighbor.is_lowercase() && current.is_uppercase() {
+            score += CAMEL_BONUS;
+        }
+        if neighbor == '_' || neighbor == ' ' {
+            score += SEPARATOR_BONUS;
+        }
+    }
+    score
+}
+
+/// The highest [sublime_score] a query of `len` characters can get.
+fn max_sublime_score(len: usize) -> i32 {
+    100 + FIRST_LETTER_BONUS
+        + (SEQUENTIAL_BONUS + CAMEL_BONUS.max(SEPARATOR_BONUS)) * (len as i32 - 1)
+}
+
+/// [Scorer] for [sublime_score], scaled to a percentage of the best possible
+/// score for the query. Like [FzfScorer], the first string is the query and
+/// the second the candidate.
+///
+/// ```
+/// # use fuzzywuzzy::subsequence::SublimeScorer;
+/// # use fuzzywuzzy::process::extract_one;
+/// let symbols = ["gather_unused", "get_user_name", "GetUserName"];
+/// let (best, _) = extract_one("gun", symbols.iter(), |s: &str, _| s.to_string(), SublimeScorer, 0).unwrap();
+/// assert_eq!(best, "GetUserName");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SublimeScorer;
+
+impl Scorer for SublimeScorer {
+    fn score(&self, query: &str, candidate: &str) -> Score {
+        let best = max_sublime_score(query.chars().count());
+        scale(query, candidate, sublime_score(query, candidate), best)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn fzf_match_positions_spell_the_query() {
+        let alphabet: Vec<char> = "abAB_/ 1".chars().collect();
+        let mut rng = thread_rng();
+        for _ in 0..2000 {
+            let text: String = (0..rng.gen_range(0..12))
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                .collect();
+            let pattern: String = (0..rng.gen_range(1..4))
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                .collect();
+            let folded: Vec<char> = text.to_lowercase().chars().collect();
+            let is_subsequence = {
+                let mut chars = folded.iter();
+                pattern
+                    .to_lowercase()
+                    .chars()
+                    .all(|p| chars.any(|&c| c == p))
+            };
+            match fzf_match(&pattern, &text) {
+                Some(found) => {
+                    assert!(is_subsequence);
+                    let spelled: String = found.positions.iter().map(|&j| folded[j]).collect();
+                    assert_eq!(spelled, pattern.to_lowercase(), "{:?}", text);
+                    assert!(found.positions.windows(2).all(|w| w[0] < w[1]));
+                    assert!(found.score <= max_fzf_score(pattern.len()));
+                }
+                None => assert!(!is_subsequence, "{:?} {:?}", pattern, text),
+            }
+        }
+    }
+
+    #[test]
+    fn incremental_matcher_agrees_with_fzf_score() {
+        let alphabet: Vec<char> = "abAB_/ 1".chars().collect();
+        let mut rng = thread_rng();
+        let candidates: Vec<String> = (0..50)
+            .map(|_| {
+                (0..rng.gen_range(0..12))
+                    .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                    .collect()
+            })
+            .collect();
+        let mut matcher = IncrementalMatcher::new(candidates.iter());
+        for _ in 0..200 {
+            if rng.gen_bool(0.5) {
+                matcher.pop();
+            } else {
+                matcher.push(alphabet[rng.gen_range(0..alphabet.len())]);
+            }
+            let query = matcher.query();
+            for (i, candidate) in candidates.iter().enumerate() {
+                assert_eq!(
+                    matcher.score(i),
+                    fzf_score(&query, candidate),
+                    "{:?} {:?}",
+                    query,
+                    candidate
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fzf_prefers_boundaries_and_runs() {
+        let cases = [
+            ("fb", "foo_bar", "fooxbar"),
+            ("ab", "x ab", "xa b"),
+            ("mr", "main.rs", "mirror"),
+            ("cb", "CamelBack", "cambered"),
+        ];
+        for (query, better, worse) in cases.iter() {
+            assert!(
+                fzf_score(query, better) > fzf_score(query, worse),
+                "{} {} {}",
+                query,
+                better,
+                worse
+            );
+        }
+    }
+
+    #[test]
+    fn sublime_prefers_boundaries_and_runs() {
+        let cases = [
+            ("fb", "foo_bar", "fooxbar"),
+            ("gun", "GetUserName", "gunslinger_of_the_west"),
+            ("ab", "ab", "axxb"),
+            ("mr", "main rs", "mirror"),
+        ];
+        for (query, better, worse) in cases.iter() {
+            assert!(
+                sublime_score(query, better) > sublime_score(query, worse),
+                "{} {} {}",
+                query,
+                better,
+                worse
+            );
+        }
+        assert_eq!(sublime_score("", "abc"), None);
+        assert_eq!(sublime_score("abc", ""), None);
+    }
+}
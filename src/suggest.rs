@@ -0,0 +1,130 @@
+//! Suggesting the valid command or flag closest to a mistyped one, as in
+//! "unknown command 'isntall', did you mean 'install'?".
+//!
+//! Candidates are compared with the input case-insensitively by
+//! [optimal string alignment distance](crate::primitives::osa_distance), so
+//! swapped letters count as a single typo, and only close candidates are
+//! suggested. Candidates the input is an abbreviation of come first. Ties keep
+//! the order the candidates were given in, so suggestions are deterministic.
+//!
+//! ```
+//! # use fuzzywuzzy::suggest::did_you_mean;
+//! let commands = ["build", "check", "clean", "install", "uninstall"];
+//! assert_eq!(did_you_mean("isntall", commands.iter().copied()), Some("install"));
+//! assert_eq!(did_you_mean("Chek", commands.iter().copied()), Some("check"));
+//! assert_eq!(did_you_mean("deploy", commands.iter().copied()), None);
+//! ```
+
+use crate::primitives;
+
+/// Inputs shorter than this aren't considered abbreviations.
+const MIN_PREFIX_LEN: usize = 2;
+
+/// Suggests candidates for a mistyped input.
+///
+/// By default one typo is allowed per three characters of the input, and at
+/// least one; abbreviations are suggested; and all suggestions are returned.
+///
+/// ```
+/// # use fuzzywuzzy::suggest::Suggester;
+/// let flags = ["--verbose", "--version", "--verify", "--quiet"];
+/// let suggester = Suggester::new();
+/// assert_eq!(
+///     suggester.suggest("--ver", flags.iter().copied()),
+///     vec!["--verify", "--verbose", "--version"]
+/// );
+/// assert_eq!(
+///     suggester.suggest("--verison", flags.iter().copied()),
+///     vec!["--version", "--verbose", "--verify"]
+/// );
+/// let strict = Suggester::new().max_distance(1).abbreviations(false).limit(1);
+/// assert_eq!(strict.suggest("--verison", flags.iter().copied()), vec!["--version"]);
+/// assert!(strict.suggest("--ver", flags.iter().copied()).is_empty());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Suggester {
+    max_distance: Option<usize>,
+    abbreviations: bool,
+    limit: Option<usize>,
+}
+
+impl Default for Suggester {
+    fn default() -> Suggester {
+        Suggester::new()
+    }
+}
+
+impl Suggester {
+    /// A suggester with the default configuration.
+    pub fn new() -> Suggester {
+        Suggester {
+            max_distance: None,
+            abbreviations: true,
+            limit: None,
+        }
+    }
+
+    /// Only suggests candidates within `max_distance` edits of the input.
+    pub fn max_distance(mut self, max_distance: usize) -> Suggester {
+        self.max_distance = Some(max_distance);
+        self
+    }
+
+    /// Whether to suggest candidates starting with the input regardless of
+    /// their distance, ahead of the others.
+    pub fn abbreviations(mut self, abbreviations: bool) -> Suggester {
+        self.abbreviations = abbreviations;
+        self
+    }
+
+    /// Returns at most `limit` suggestions.
+    pub fn limit(mut self, limit: usize) -> Suggester {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// The candidates close to `input`, best first: abbreviations, then by
+    /// distance, then in their original order.
+    pub fn suggest<'a, I>(&self, input: &str, candidates: I) -> Vec<&'a str>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let input: Vec<char> = input.to_lowercase().chars().collect();
+        let max_distance = self
+            .max_distance
+            .unwrap_or_else(|| (input.len() / 3).max(1));
+        let mut suggestions: Vec<(bool, usize, usize, &str)> = vec![];
+        for (index, candidate) in candidates.into_iter().enumerate() {
+            let lowered: Vec<char> = candidate.to_lowercase().chars().collect();
+            let abbreviation =
+                self.abbreviations && input.len() >= MIN_PREFIX_LEN && lowered.starts_with(&input);
+            // The distance is at least the difference in length.
+            if !abbreviation && lowered.len().abs_diff(input.len()) > max_distance {
+                continue;
+            }
+            let distance = primitives::osa_distance(&input, &lowered);
+            if abbreviation || distance <= max_distance {
+                suggestions.push((!abbreviation, distance, index, candidate));
+            }
+        }
+        suggestions.sort_unstable();
+        suggestions
+            .into_iter()
+            .take(self.limit.unwrap_or(usize::MAX))
+            .map(|(_, _, _, candidate)| candidate)
+            .collect()
+    }
+}
+
+/// The best suggestion for `input` among `candidates` with the default
+/// [Suggester], if any is close enough.
+pub fn did_you_mean<'a, I>(input: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    Suggester::new()
+        .limit(1)
+        .suggest(input, candidates)
+        .into_iter()
+        .next()
+}
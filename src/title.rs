@@ -0,0 +1,172 @@
+//! Matching titles of books, films, games and products.
+//!
+//! Titles of the same work are written in ways which throw off character
+//! based scorers: "Part II" and "Part 2", "Vol. 3" and "Volume Three",
+//! "The Matrix" and "Matrix, The", with or without qualifiers like
+//! "(Director's Cut)" or "\[Remastered\]". [normalize_title] removes those
+//! differences before scoring.
+//!
+//! ```
+//! # use fuzzywuzzy::title::title_ratio;
+//! # use fuzzywuzzy::fuzz::ratio;
+//! let (a, b) = ("Harry Potter: Part II (Deluxe Edition)", "harry potter part 2");
+//! assert_eq!(ratio(a, b), 53);
+//! assert_eq!(title_ratio(a, b), 100);
+//! ```
+
+use crate::fuzz::{self, Scorer};
+use crate::normalization::{Normalizer, NumberWordNormalizer};
+use crate::score::Score;
+use crate::utils;
+use std::sync::OnceLock;
+
+/// Words introducing a volume, part or edition number, and their abbreviations.
+const MARKERS: &[(&str, &str)] = &[
+    ("volume", "vol"),
+    ("vol", "vol"),
+    ("part", "pt"),
+    ("pt", "pt"),
+    ("number", "no"),
+    ("no", "no"),
+    ("edition", "ed"),
+    ("ed", "ed"),
+    ("episode", "ep"),
+    ("ep", "ep"),
+    ("chapter", "ch"),
+    ("ch", "ch"),
+    ("book", "book"),
+    ("season", "season"),
+    ("series", "series"),
+];
+
+const ARTICLES: &[&str] = &["the", "a", "an"];
+
+/// The value of `word` as a Roman numeral from 1 to 39, written canonically.
+fn roman_value(word: &str) -> Option<u32> {
+    const TENS: &[&str] = &["", "x", "xx", "xxx"];
+    const UNITS: &[&str] = &["", "i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix"];
+    (1..40)
+        .find(|&n| {
+            let (tens, units) = (TENS[n / 10], UNITS[n % 10]);
+            word.len() == tens.len() + units.len()
+                && word.starts_with(tens)
+                && word.ends_with(units)
+        })
+        .map(|n| n as u32)
+}
+
+/// Removes text in parentheses, square brackets and braces, including nested brackets.
+fn strip_brackets(title: &str) -> String {
+    let mut depth = 0usize;
+    let mut stripped = String::with_capacity(title.len());
+    for c in title.chars() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth = depth.saturating_sub(1);
+                // Keep the words on either side apart.
+                stripped.push(' ');
+            }
+            _ if depth == 0 => stripped.push(c),
+            _ => {}
+        }
+    }
+    stripped
+}
+
+/// `title` in a canonical form for comparisons.
+///
+/// 1. Text in brackets is removed.
+/// 2. Number words become numerals, see [NumberWordNormalizer].
+/// 3. The title is [processed](utils::full_process).
+/// 4. Volume, part and edition markers are abbreviated, e.g. "volume" to "vol".
+/// 5. Roman numerals up to 39 become numerals after a marker, and at the end
+///    of the title unless they're a single letter, like the "I" of "Rocky I"
+///    rather than of "I, Robot".
+/// 6. A leading or trailing article is removed, so "Matrix, The" equals
+///    "The Matrix".
+///
+/// ```
+/// # use fuzzywuzzy::title::normalize_title;
+/// assert_eq!(normalize_title("Rocky II"), "rocky 2");
+/// assert_eq!(normalize_title("Final Fantasy VII [Remake]"), "final fantasy 7");
+/// assert_eq!(normalize_title("The Art of Computer Programming, Volume One"), "art of computer programming vol 1");
+/// assert_eq!(normalize_title("Matrix, The"), "matrix");
+/// assert_eq!(normalize_title("I, Robot"), "i robot");
+/// assert_eq!(normalize_title("Second Edition"), "2nd ed");
+/// ```
+pub fn normalize_title(title: &str) -> String {
+    static NUMBER_WORDS: OnceLock<NumberWordNormalizer> = OnceLock::new();
+    let number_words = NUMBER_WORDS.get_or_init(NumberWordNormalizer::english);
+    let processed = utils::full_process(&number_words.normalize(&strip_brackets(title)), false);
+    let mut words: Vec<String> = vec![];
+    let mut tokens = processed.split_whitespace().peekable();
+    while let Some(token) = tokens.next() {
+        let after_marker = words
+            .last()
+            .is_some_and(|last| MARKERS.iter().any(|&(_, short)| short == last));
+        let at_end = tokens.peek().is_none() && token.len() > 1 && !words.is_empty();
+        let word = match MARKERS.iter().find(|&&(full, _)| full == token) {
+            Some(&(_, short)) => short.to_string(),
+            None => match roman_value(token) {
+                Some(n) if after_marker || at_end => n.to_string(),
+                _ => token.to_string(),
+            },
+        };
+        words.push(word);
+    }
+    if words.len() > 1 && ARTICLES.contains(&words[0].as_str()) {
+        words.remove(0);
+    }
+    if words.len() > 1 && ARTICLES.contains(&words[words.len() - 1].as_str()) {
+        words.pop();
+    }
+    words.join(" ")
+}
+
+/// The [ratio](fuzz::ratio) of two titles after [normalize_title].
+///
+/// ```
+/// # use fuzzywuzzy::title::title_ratio;
+/// assert_eq!(title_ratio("Star Wars: Episode IV", "Star Wars Episode 4 (1977)"), 100);
+/// assert_eq!(title_ratio("Star Wars: Episode IV", "Star Wars: Episode V"), 93);
+/// ```
+pub fn title_ratio(a: &str, b: &str) -> u8 {
+    fuzz::ratio(normalize_title(a), normalize_title(b))
+}
+
+/// [Scorer] for [title_ratio].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TitleRatio;
+
+impl Scorer for TitleRatio {
+    fn score(&self, a: &str, b: &str) -> Score {
+        Score::new(title_ratio(a, b)).expect("title_ratio returns at most 100")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn roman_numerals_are_canonical() {
+        let numerals = ["i", "ii", "iv", "ix", "xiv", "xix", "xxxix"];
+        let values: Vec<_> = numerals.iter().map(|n| roman_value(n)).collect();
+        assert_eq!(
+            values,
+            vec![
+                Some(1),
+                Some(2),
+                Some(4),
+                Some(9),
+                Some(14),
+                Some(19),
+                Some(39)
+            ]
+        );
+        for word in ["iiii", "vv", "ic", "xl", "mix", "", "iix"].iter() {
+            assert_eq!(roman_value(word), None, "{}", word);
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! Reporting extraction runs to [tracing](https://docs.rs/tracing) with
+//! feature "tracing", as described in the [process](crate::process) docs.
+//! Without the feature, this compiles to nothing.
+
+#[cfg(feature = "tracing")]
+pub(crate) use self::enabled::Run;
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) use self::disabled::Run;
+
+#[cfg(feature = "tracing")]
+mod enabled {
+    use std::ops::Deref;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{Duration, Instant};
+    use tracing::span::{Entered, Span};
+
+    /// An extraction run being traced.
+    ///
+    /// The span isn't entered by starting the run, so that a run can be kept
+    /// across the polls of a stream. Its [Stats] can be shared between threads.
+    pub(crate) struct Run {
+        span: Span,
+        stats: Stats,
+    }
+
+    /// The counts of an extraction run.
+    pub(crate) struct Stats {
+        /// Whether the run will be reported, so its steps are worth timing.
+        timing: bool,
+        candidates: AtomicU64,
+        pruned: AtomicU64,
+        scored: AtomicU64,
+        processing_ns: AtomicU64,
+        scoring_ns: AtomicU64,
+    }
+
+    impl Run {
+        /// Starts a run of the extraction function named `function`.
+        pub(crate) fn start(function: &'static str) -> Run {
+            Run {
+                span: tracing::debug_span!("extract", function),
+                stats: Stats {
+                    timing: tracing::enabled!(tracing::Level::DEBUG),
+                    candidates: AtomicU64::new(0),
+                    pruned: AtomicU64::new(0),
+                    scored: AtomicU64::new(0),
+                    processing_ns: AtomicU64::new(0),
+                    scoring_ns: AtomicU64::new(0),
+                },
+            }
+        }
+
+        /// Enters the run's span until the guard is dropped.
+        pub(crate) fn enter(&self) -> Entered<'_> {
+            self.span.enter()
+        }
+
+        pub(crate) fn finish(&self, matched: usize) {
+            let stats = &self.stats;
+            let candidates = stats.candidates.load(Ordering::Relaxed);
+            let pruned = stats.pruned.load(Ordering::Relaxed);
+            let prune_rate = if candidates == 0 {
+                0.0
+            } else {
+                pruned as f64 / candidates as f64
+            };
+            let micros = |ns: &AtomicU64| {
+                Duration::from_nanos(ns.load(Ordering::Relaxed)).as_micros() as u64
+            };
+            self.span.in_scope(|| {
+                tracing::debug!(
+                    candidates,
+                    scored = stats.scored.load(Ordering::Relaxed),
+                    pruned,
+                    matched = matched as u64,
+                    prune_rate,
+                    processing_us = micros(&stats.processing_ns),
+                    scoring_us = micros(&stats.scoring_ns),
+                    "extraction finished"
+                )
+            });
+        }
+
+        pub(crate) fn cancel(&self) {
+            let stats = &self.stats;
+            self.span.in_scope(|| {
+                tracing::debug!(
+                    candidates = stats.candidates.load(Ordering::Relaxed),
+                    scored = stats.scored.load(Ordering::Relaxed),
+                    "extraction cancelled"
+                )
+            });
+        }
+    }
+
+    impl Deref for Run {
+        type Target = Stats;
+
+        fn deref(&self) -> &Stats {
+            &self.stats
+        }
+    }
+
+    impl Stats {
+        fn timed<T>(&self, elapsed: &AtomicU64, f: impl FnOnce() -> T) -> T {
+            if !self.timing {
+                return f();
+            }
+            let start = Instant::now();
+            let result = f();
+            elapsed.fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+            result
+        }
+
+        /// Runs the processor.
+        pub(crate) fn processing<T>(&self, f: impl FnOnce() -> T) -> T {
+            self.timed(&self.processing_ns, f)
+        }
+
+        /// Runs the scorer on a candidate.
+        pub(crate) fn scoring<T>(&self, f: impl FnOnce() -> T) -> T {
+            self.scored.fetch_add(1, Ordering::Relaxed);
+            self.timed(&self.scoring_ns, f)
+        }
+
+        pub(crate) fn candidate(&self) {
+            self.candidates.fetch_add(1, Ordering::Relaxed);
+        }
+
+        /// Records a candidate pruned for its bound or its score.
+        pub(crate) fn pruned(&self) {
+            self.pruned.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+mod disabled {
+    use std::ops::Deref;
+
+    pub(crate) struct Run;
+
+    pub(crate) struct Stats;
+
+    pub(crate) struct Entered;
+
+    impl Run {
+        pub(crate) fn start(_function: &'static str) -> Run {
+            Run
+        }
+
+        pub(crate) fn enter(&self) -> Entered {
+            Entered
+        }
+
+        pub(crate) fn finish(&self, _matched: usize) {}
+
+        pub(crate) fn cancel(&self) {}
+    }
+
+    impl Deref for Run {
+        type Target = Stats;
+
+        fn deref(&self) -> &Stats {
+            &Stats
+        }
+    }
+
+    impl Stats {
+        pub(crate) fn processing<T>(&self, f: impl FnOnce() -> T) -> T {
+            f()
+        }
+
+        pub(crate) fn scoring<T>(&self, f: impl FnOnce() -> T) -> T {
+            f()
+        }
+
+        pub(crate) fn candidate(&self) {}
+
+        pub(crate) fn pruned(&self) {}
+    }
+}
+
+#[cfg(all(test, feature = "tracing", feature = "token", feature = "store"))]
+mod test {
+    use crate::fuzz::WRatio;
+    use crate::process::{extract_without_order, get_close_matches, match_pairs};
+    use crate::store::ChoiceStore;
+    use crate::utils::full_process;
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Collects the integer fields of events.
+    #[derive(Clone, Default)]
+    struct Collector(Arc<Mutex<Vec<(&'static str, u64)>>>);
+
+    impl Visit for Collector {
+        fn record_u64(&mut self, field: &Field, value: u64) {
+            self.0.lock().unwrap().push((field.name(), value));
+        }
+
+        fn record_debug(&mut self, _: &Field, _: &dyn fmt::Debug) {}
+    }
+
+    impl Subscriber for Collector {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut self.clone());
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    /// The integer fields of the events `f` emits.
+    fn fields<T>(f: impl FnOnce() -> T) -> Vec<(&'static str, u64)> {
+        let collector = Collector::default();
+        tracing::subscriber::with_default(collector.clone(), f);
+        let fields = collector.0.lock().unwrap().clone();
+        fields
+    }
+
+    #[test]
+    fn other_extractions_report_counts() {
+        let choices = ["new york mets", "atlanta braves", "chicago cubs"];
+        let field =
+            |fields: &[(&str, u64)], name| fields.iter().find(|(n, _)| *n == name).map(|&(_, v)| v);
+        let close = fields(|| get_close_matches("new york", choices, 3, 0.6));
+        assert_eq!(field(&close, "candidates"), Some(3));
+        assert_eq!(field(&close, "matched"), Some(1));
+        let pairs = fields(|| match_pairs(["new york"], choices, WRatio));
+        assert_eq!(field(&pairs, "candidates"), Some(3));
+        assert_eq!(field(&pairs, "matched"), Some(1));
+        let store: ChoiceStore = choices.iter().collect();
+        let stored = fields(|| store.extract("new york", WRatio, 80));
+        assert_eq!(field(&stored, "candidates"), Some(3));
+        assert_eq!(field(&stored, "matched"), Some(1));
+    }
+
+    #[test]
+    fn extraction_reports_counts() {
+        let collector = Collector::default();
+        let choices = ["new york mets", "atlanta braves", "chicago cubs"];
+        let results = tracing::subscriber::with_default(collector.clone(), || {
+            extract_without_order("new york", choices, full_process, WRatio, 80)
+        });
+        let fields = collector.0.lock().unwrap().clone();
+        let field = |name| fields.iter().find(|(n, _)| *n == name).map(|&(_, v)| v);
+        assert_eq!(field("candidates"), Some(3));
+        assert_eq!(field("matched"), Some(results.len() as u64));
+        assert_eq!(field("pruned"), Some(3 - results.len() as u64));
+        assert!(field("scored").is_some() && field("scoring_us").is_some());
+    }
+}
@@ -0,0 +1,213 @@
+//! Aligning speech recognition transcripts with reference scripts.
+//!
+//! [align_transcript] maps each word of a reference, like a subtitle script,
+//! to the words of a hypothesis, like an ASR transcript, which were
+//! recognized for it. Words are compared ignoring case and punctuation, and
+//! misrecognized words are aligned by their [ratio](crate::fuzz::ratio),
+//! so small errors don't throw off the alignment. Fillers like "um" in the
+//! hypothesis are skipped cheaply, and a reference word may map to two
+//! hypothesis words ("can not" for "cannot") or two reference words to one.
+//!
+//! ```
+//! # use fuzzywuzzy::transcript::align_transcript;
+//! let script = "We cannot go back to the old ways.";
+//! let asr = "we uh can not go bak to the the old ways";
+//! let spans: Vec<Option<&str>> = align_transcript(script, asr)
+//!     .iter()
+//!     .map(|word| word.hypothesis.clone().map(|span| &asr[span]))
+//!     .collect();
+//! assert_eq!(spans, vec![
+//!     Some("we"), Some("can not"), Some("go"), Some("bak"), Some("to"),
+//!     Some("the"), Some("old"), Some("ways"),
+//! ]);
+//! ```
+
+use crate::fuzz;
+use crate::utils;
+use std::ops::Range;
+
+/// Hesitation words which are cheap to skip in the hypothesis.
+pub const FILLERS: &[&str] = &[
+    "uh", "um", "uhm", "er", "erm", "ah", "eh", "hm", "hmm", "mm", "mhm",
+];
+
+/// The cost of skipping a filler, relative to 1 for any other word.
+const FILLER_COST: f64 = 0.1;
+
+/// The extra cost of aligning two words with one, so that words are only
+/// merged when they nearly equal the word they're aligned with together.
+const MERGE_PENALTY: f64 = 0.9;
+
+/// How a word of the reference was aligned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AlignedWord {
+    /// The byte range of the word in the reference.
+    pub reference: Range<usize>,
+    /// The byte range of the hypothesis words recognized for it, if any.
+    pub hypothesis: Option<Range<usize>>,
+    /// The [ratio](fuzz::ratio) of the words, or 0 if there are none.
+    pub score: u8,
+}
+
+/// A word, processed for comparison, and its byte range.
+struct Word {
+    text: String,
+    span: Range<usize>,
+}
+
+fn words(text: &str) -> Vec<Word> {
+    let start = text.as_ptr() as usize;
+    text.split_whitespace()
+        .map(|word| {
+            let offset = word.as_ptr() as usize - start;
+            Word {
+                text: utils::full_process(word, false).replace(' ', ""),
+                span: offset..offset + word.len(),
+            }
+        })
+        .collect()
+}
+
+/// The steps of an alignment, as the numbers of reference and hypothesis words consumed.
+const STEPS: &[(usize, usize)] = &[(1, 1), (1, 2), (2, 1), (1, 0), (0, 1)];
+
+/// Aligns transcripts. See the [module](self) docs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TranscriptAligner {
+    fillers: Vec<String>,
+}
+
+impl Default for TranscriptAligner {
+    fn default() -> TranscriptAligner {
+        TranscriptAligner::new()
+    }
+}
+
+impl TranscriptAligner {
+    /// An aligner skipping the English [FILLERS].
+    pub fn new() -> TranscriptAligner {
+        TranscriptAligner {
+            fillers: FILLERS.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    /// Skips `fillers` instead, which are compared after processing.
+    pub fn fillers<I, T>(mut self, fillers: I) -> TranscriptAligner
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        self.fillers = fillers
+            .into_iter()
+            .map(|f| utils::full_process(f.as_ref(), false))
+            .collect();
+        self
+    }
+
+    /// The cost of skipping a hypothesis word.
+    fn insertion_cost(&self, word: &Word) -> f64 {
+        if word.text.is_empty() || self.fillers.contains(&word.text) {
+            FILLER_COST
+        } else {
+            1.0
+        }
+    }
+
+    /// Aligns the words of `reference` with those of `hypothesis`, returning
+    /// one [AlignedWord] per reference word, in order.
+    ///
+    /// The alignment minimizes a cost: 1 for each reference word left out,
+    /// 1 for each hypothesis word skipped, or 0.1 for a filler, and
+    /// `1 - ratio / 100` for each aligned group of words, plus 0.9 if a
+    /// group has three words.
+    pub fn align(&self, reference: &str, hypothesis: &str) -> Vec<AlignedWord> {
+        let (r, h) = (words(reference), words(hypothesis));
+        let (n, m) = (r.len(), h.len());
+        let width = m + 1;
+        // `cost[i * width + j]` is the cost of aligning the first `i`
+        // reference words with the first `j` hypothesis words, and `step`
+        // the index into STEPS of the last step taken.
+        let mut cost = vec![f64::INFINITY; (n + 1) * width];
+        let mut step = vec![0usize; (n + 1) * width];
+        let joined = |words: &[Word]| words.iter().map(|w| w.text.as_str()).collect::<String>();
+        cost[0] = 0.0;
+        for i in 0..=n {
+            for j in 0..=m {
+                for (s, &(di, dj)) in STEPS.iter().enumerate() {
+                    if di > i || dj > j {
+                        continue;
+                    }
+                    let step_cost = match (di, dj) {
+                        (1, 0) => 1.0,
+                        (0, 1) => self.insertion_cost(&h[j - 1]),
+                        _ => {
+                            let ratio = fuzz::ratio(joined(&r[i - di..i]), joined(&h[j - dj..j]));
+                            let merge_penalty = (di + dj - 2) as f64 * MERGE_PENALTY;
+                            1.0 - ratio as f64 / 100.0 + merge_penalty
+                        }
+                    };
+                    let total = cost[(i - di) * width + j - dj] + step_cost;
+                    if total < cost[i * width + j] {
+                        cost[i * width + j] = total;
+                        step[i * width + j] = s;
+                    }
+                }
+            }
+        }
+
+        let mut aligned = Vec::with_capacity(n);
+        let (mut i, mut j) = (n, m);
+        while i > 0 || j > 0 {
+            let (di, dj) = STEPS[step[i * width + j]];
+            if di > 0 {
+                let (hypothesis, score) = if dj > 0 {
+                    let span = h[j - dj].span.start..h[j - 1].span.end;
+                    let ratio = fuzz::ratio(joined(&r[i - di..i]), joined(&h[j - dj..j]));
+                    (Some(span), ratio)
+                } else {
+                    (None, 0)
+                };
+                for word in r[i - di..i].iter().rev() {
+                    aligned.push(AlignedWord {
+                        reference: word.span.clone(),
+                        hypothesis: hypothesis.clone(),
+                        score,
+                    });
+                }
+            }
+            i -= di;
+            j -= dj;
+        }
+        aligned.reverse();
+        aligned
+    }
+}
+
+/// Aligns `reference` and `hypothesis` with the default [TranscriptAligner].
+pub fn align_transcript(reference: &str, hypothesis: &str) -> Vec<AlignedWord> {
+    TranscriptAligner::new().align(reference, hypothesis)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unrecognized_words_map_to_nothing() {
+        let aligned = align_transcript("one two three four", "one three four");
+        let hypothesis: Vec<_> = aligned.iter().map(|w| w.hypothesis.clone()).collect();
+        assert_eq!(hypothesis, vec![Some(0..3), None, Some(4..9), Some(10..14)]);
+        assert_eq!(aligned[1].reference, 4..7);
+        assert_eq!(aligned[1].score, 0);
+
+        let joined = align_transcript("can not", "cannot");
+        assert_eq!(joined[0].hypothesis, Some(0..6));
+        assert_eq!(joined[1].hypothesis, Some(0..6));
+        assert_eq!(joined[0].score, 100);
+
+        let aligner = TranscriptAligner::new().fillers(["euh"]);
+        let aligned = aligner.align("oui", "euh euh oui");
+        assert_eq!(aligned[0].hypothesis, Some(8..11));
+        assert!(align_transcript("", "uh").is_empty());
+    }
+}
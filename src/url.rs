@@ -0,0 +1,199 @@
+//! Matching URLs and domains.
+//!
+//! [parse_url] splits a URL into the parts which matter for comparisons,
+//! ignoring its scheme, credentials, port, a leading "www.", a trailing
+//! slash, the fragment and the order of query parameters. [url_ratio] then
+//! weights the registrable domain, like "example.co.uk", above the rest, so
+//! look-alike domains used for typo-squatting score high while the same
+//! path on unrelated sites doesn't.
+//!
+//! The registrable domain is found without the Public Suffix List: it's the
+//! last two labels of the host, or the last three when the top-level domain
+//! is a two-letter country code preceded by a common second-level label like
+//! "co" or "com". IP addresses are their own registrable domain.
+//!
+//! ```
+//! # use fuzzywuzzy::url::{normalize_url, url_ratio};
+//! assert_eq!(normalize_url("HTTPS://www.Example.com/a/b/?y=2&x=1#top"), "example.com/a/b?x=1&y=2");
+//! assert_eq!(url_ratio("http://example.com/docs/", "https://www.example.com/docs"), 100);
+//! assert_eq!(url_ratio("https://paypal.com/signin", "https://paypa1.com/signin"), 94);
+//! assert_eq!(url_ratio("https://github.com/rust-lang", "https://gitlab.com/rust-lang"), 88);
+//! ```
+
+use crate::fuzz::{self, Scorer};
+use crate::score::Score;
+use crate::utils::Rounding;
+use std::fmt;
+
+/// Second-level labels under which country code domains are registered.
+const SECOND_LEVEL_LABELS: &[&str] = &["ac", "co", "com", "edu", "gov", "net", "org", "ne", "or"];
+
+/// The parts of a URL.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Url {
+    /// The labels of the host in front of the registrable domain, without "www".
+    pub subdomain: String,
+    /// The registrable domain, lower case, e.g. "example.co.uk".
+    pub domain: String,
+    /// The path without its trailing slash, e.g. "/a/b", or empty.
+    pub path: String,
+    /// The query parameters, sorted.
+    pub query: Vec<String>,
+}
+
+impl fmt::Display for Url {
+    /// Writes the host, path and sorted query.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.subdomain.is_empty() {
+            write!(f, "{}.", self.subdomain)?;
+        }
+        write!(f, "{}{}", self.domain, self.path)?;
+        if !self.query.is_empty() {
+            write!(f, "?{}", self.query.join("&"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits a lower case host into its subdomain and registrable domain.
+fn split_host(host: &str) -> (String, String) {
+    let labels: Vec<&str> = host.split('.').filter(|label| !label.is_empty()).collect();
+    let is_ip = labels.len() == 4 && labels.iter().all(|l| l.parse::<u8>().is_ok());
+    let len = labels.len();
+    let registrable = if is_ip || host.starts_with('[') {
+        len
+    } else if len >= 3
+        && labels[len - 1].len() == 2
+        && SECOND_LEVEL_LABELS.contains(&labels[len - 2])
+    {
+        3
+    } else {
+        len.min(2)
+    };
+    let subdomain: Vec<&str> = labels[..len - registrable]
+        .iter()
+        .copied()
+        .skip_while(|&label| label == "www")
+        .collect();
+    (subdomain.join("."), labels[len - registrable..].join("."))
+}
+
+/// Splits `url` into its parts. URLs without a scheme, like
+/// "example.com/path", are understood too.
+///
+/// ```
+/// # use fuzzywuzzy::url::{parse_url, Url};
+/// assert_eq!(parse_url("https://user@www.docs.example.co.uk:8080/guide/?b=2&a=1"), Url {
+///     subdomain: "docs".to_string(),
+///     domain: "example.co.uk".to_string(),
+///     path: "/guide".to_string(),
+///     query: vec!["a=1".to_string(), "b=2".to_string()],
+/// });
+/// ```
+pub fn parse_url(url: &str) -> Url {
+    let url = url.trim();
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let url = url.split_once('#').map_or(url, |(before, _)| before);
+    let (rest, query) = url.split_once('?').unwrap_or((url, ""));
+    let (authority, path) = match rest.find('/') {
+        Some(slash) => rest.split_at(slash),
+        None => (rest, ""),
+    };
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    // A port follows the last colon, unless it's inside an IPv6 address.
+    let host = match host.rfind(':') {
+        Some(colon) if !host[colon..].contains(']') => &host[..colon],
+        _ => host,
+    };
+    let (subdomain, domain) = split_host(&host.to_lowercase());
+    let mut query: Vec<String> = query
+        .split('&')
+        .filter(|param| !param.is_empty())
+        .map(str::to_string)
+        .collect();
+    query.sort();
+    Url {
+        subdomain,
+        domain,
+        path: path.trim_end_matches('/').to_string(),
+        query,
+    }
+}
+
+/// `url` in a canonical form. See [parse_url].
+pub fn normalize_url(url: &str) -> String {
+    parse_url(url).to_string()
+}
+
+/// The [ratio](fuzz::ratio) of two parts, which agree fully if both are missing.
+fn part_ratio(a: &str, b: &str) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        100.0
+    } else {
+        fuzz::ratio(a, b) as f64
+    }
+}
+
+/// Compares two URLs part by part.
+///
+/// The [ratio](fuzz::ratio) of the registrable domains makes up 60% of the
+/// score, that of the paths 20% and those of the subdomains and the queries
+/// 10% each. Parts missing from both URLs agree fully.
+///
+/// ```
+/// # use fuzzywuzzy::url::url_ratio;
+/// assert_eq!(url_ratio("example.com/a?x=1&y=2", "example.com/a?y=2&x=1"), 100);
+/// assert_eq!(url_ratio("example.com/blog", "blog.example.com"), 70);
+/// assert_eq!(url_ratio("example.com/about", "example.org/about"), 89);
+/// assert_eq!(url_ratio("example.com/about", "evil.net/about"), 59);
+/// ```
+pub fn url_ratio(a: &str, b: &str) -> u8 {
+    let (a, b) = (parse_url(a), parse_url(b));
+    let score = 60.0 * part_ratio(&a.domain, &b.domain)
+        + 20.0 * part_ratio(&a.path, &b.path)
+        + 10.0 * part_ratio(&a.subdomain, &b.subdomain)
+        + 10.0 * part_ratio(&a.query.join("&"), &b.query.join("&"));
+    Rounding::Python.round(score / 100.0)
+}
+
+/// [Scorer] for [url_ratio].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UrlRatio;
+
+impl Scorer for UrlRatio {
+    fn score(&self, a: &str, b: &str) -> Score {
+        Score::new(url_ratio(a, b)).expect("url_ratio returns at most 100")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn registrable_domains() {
+        let hosts = [
+            ("example.com", ("", "example.com")),
+            ("www.example.com", ("", "example.com")),
+            ("a.b.example.com", ("a.b", "example.com")),
+            ("shop.example.co.uk", ("shop", "example.co.uk")),
+            ("example.co.uk", ("", "example.co.uk")),
+            ("co.uk", ("", "co.uk")),
+            ("mail.google.de", ("mail", "google.de")),
+            ("192.168.0.1", ("", "192.168.0.1")),
+            ("localhost", ("", "localhost")),
+            ("", ("", "")),
+        ];
+        for &(host, (subdomain, domain)) in hosts.iter() {
+            assert_eq!(
+                split_host(host),
+                (subdomain.to_string(), domain.to_string()),
+                "{}",
+                host
+            );
+        }
+        assert_eq!(parse_url("http://[::1]:8080/x").domain, "[::1]");
+    }
+}
@@ -1,5 +1,37 @@
 //! Standalone functions used by the rest of the crate. You might also find them useful.
 
+use crate::normalization::{Normalizer, StripInvisibleNormalizer};
+use fuzzywuzzy_processing::process_chars;
+use std::ops::Range;
+
+/// Inputs up to this many elements keep their per-call scratch buffers on the
+/// stack when the "small-buffers" feature is enabled.
+#[cfg(feature = "small-buffers")]
+pub(crate) const SMALL_BUFFER_LEN: usize = 64;
+
+/// Scratch storage for buffers which never outlive a single call.
+///
+/// With the "small-buffers" feature, short inputs (the common case for
+/// autocomplete style queries) don't touch the heap at all.
+#[cfg(feature = "small-buffers")]
+pub(crate) type SmallBuffer<T> = smallvec::SmallVec<[T; SMALL_BUFFER_LEN]>;
+#[cfg(not(feature = "small-buffers"))]
+pub(crate) type SmallBuffer<T> = Vec<T>;
+
+/// Adds to one of the [crate::instrumentation] counters.
+/// Compiles to nothing unless the "instrumentation" feature is enabled.
+#[cfg(feature = "instrumentation")]
+macro_rules! count {
+    ($counter:ident, $n:expr) => {
+        $crate::instrumentation::record(|c| c.$counter += $n as u64)
+    };
+}
+
+#[cfg(not(feature = "instrumentation"))]
+macro_rules! count {
+    ($counter:ident, $n:expr) => {};
+}
+
 /// Used to preprocess strings into 'canonical' forms.
 ///
 /// Process string by
@@ -17,6 +49,7 @@
 /// assert_eq!(full_process("¬Camarões assados", false), "camarões assados");
 /// assert_eq!(full_process("a¬4ሴ2€耀", false), "a 4ሴ2 耀");
 /// assert_eq!(full_process("Á", false), "á");
+/// assert_eq!(full_process("ΟΔΟΣ ΣΟΦΟΚΛΕΟΥΣ", false), "οδος σοφοκλεους");
 ///
 /// assert_eq!(full_process("Lorem Ipsum", true), "lorem ipsum");
 /// assert_eq!(full_process("C'est la vie", true), "c est la vie");
@@ -29,15 +62,305 @@
 /// assert_eq!(full_process("Á", true), "");
 /// ```
 pub fn full_process(s: &str, force_ascii: bool) -> String {
-    let mut result = s.to_string();
-    if force_ascii {
-        result = result.chars().filter(char::is_ascii).collect();
+    full_process_preserving(s, force_ascii, char::is_alphanumeric)
+}
+
+/// [full_process], after splitting programming identifiers into words with
+/// [IdentifierSegmenter](crate::segmentation::IdentifierSegmenter).
+///
+/// Lower-casing would otherwise merge the words of a camelCase identifier, so
+/// this makes the token scorers work for symbol search.
+///
+/// ```
+/// # use fuzzywuzzy::utils::identifier_process;
+/// # use fuzzywuzzy::fuzz::token_set_ratio;
+/// assert_eq!(identifier_process("getUserIDFromDB", false), "get user id from db");
+/// assert_eq!(token_set_ratio("getUserIDFromDB", "fetch_user_id", true, true), 47);
+/// assert_eq!(token_set_ratio(
+///     identifier_process("getUserIDFromDB", true),
+///     identifier_process("fetch_user_id", true), true, true), 70);
+/// ```
+pub fn identifier_process(s: &str, force_ascii: bool) -> String {
+    use crate::segmentation::{IdentifierSegmenter, Segmenter};
+    full_process(&IdentifierSegmenter.segment(s).join(" "), force_ascii)
+}
+
+/// [full_process], but with a custom rule for which characters are preserved.
+///
+/// Characters for which `preserve` returns `false` are replaced with a space,
+/// [full_process] uses [char::is_alphanumeric]. Preserving a few extra
+/// characters keeps identifiers, email addresses or version strings intact.
+///
+/// ```
+/// # use fuzzywuzzy::utils::{full_process, full_process_preserving};
+/// let keep_identifiers = |c: char| c.is_alphanumeric() || c == '_';
+/// assert_eq!(full_process("MAX_VALUE", false), "max value");
+/// assert_eq!(full_process_preserving("MAX_VALUE", false, keep_identifiers), "max_value");
+/// let keep_versions = |c: char| c.is_alphanumeric() || "+-.".contains(c);
+/// assert_eq!(full_process_preserving("GCC 4.9-r2 (C++)", false, keep_versions), "gcc 4.9-r2  c++");
+/// assert_eq!(full_process_preserving("  _a_  ", false, keep_identifiers), "_a_");
+/// ```
+pub fn full_process_preserving<F: Fn(char) -> bool>(
+    s: &str,
+    force_ascii: bool,
+    preserve: F,
+) -> String {
+    process_chars(s, force_ascii, preserve, true)
+}
+
+/// [full_process], preceded by running `normalizer` over the input.
+///
+/// Unicode offers several encodings of the same user-perceived text. For
+/// example, `"é"` may be the single precomposed code point U+00E9 or `"e"`
+/// followed by the combining acute accent U+0301. [full_process] treats the
+/// combining accent as a non-alphanumeric character, so the two encodings
+/// process to different strings. Normalizing first, e.g. with
+/// [FormCNormalizer](crate::normalization::FormCNormalizer) or
+/// [FormKCNormalizer](crate::normalization::FormKCNormalizer), makes them compare equal.
+///
+/// ```
+/// # use fuzzywuzzy::utils::{full_process, full_process_normalized};
+/// # use fuzzywuzzy::normalization::FormCNormalizer;
+/// let precomposed = "Caf\u{00e9}";
+/// let decomposed = "Cafe\u{0301}";
+/// assert_ne!(full_process(precomposed, false), full_process(decomposed, false));
+/// assert_eq!(
+///     full_process_normalized(precomposed, false, &FormCNormalizer),
+///     full_process_normalized(decomposed, false, &FormCNormalizer));
+/// assert_eq!(full_process_normalized(decomposed, false, &FormCNormalizer), "café");
+/// ```
+///
+/// Case folding, rather than just lower-casing, can be had with
+/// `normalization::CaseFoldNormalizer` (feature "case-folding").
+pub fn full_process_normalized<N: Normalizer + ?Sized>(
+    s: &str,
+    force_ascii: bool,
+    normalizer: &N,
+) -> String {
+    full_process(&normalizer.normalize(s), force_ascii)
+}
+
+/// [full_process], but keeping emoji as standalone tokens. Requires default feature "segmentation".
+///
+/// [full_process] replaces emoji with spaces, discarding what is often the most
+/// meaningful part of social media text. Here every emoji, including multi code
+/// point sequences like flags, skin tone variants and ZWJ sequences, is kept
+/// intact and separated from its neighbours by spaces, so token based scorers
+/// see it as a word of its own. Emoji are kept even if `force_ascii` is set.
+///
+/// To have each emoji count as a single unit when scoring, compare the results
+/// with [segmented_ratio](crate::fuzz::segmented_ratio) and
+/// [GraphemeSegmenter](crate::segmentation::GraphemeSegmenter).
+///
+/// ```
+/// # use fuzzywuzzy::utils::{full_process, full_process_keep_emoji};
+/// assert_eq!(full_process("Great game!🔥🔥", false), "great game");
+/// assert_eq!(full_process_keep_emoji("Great game!🔥🔥", false), "great game 🔥 🔥");
+/// assert_eq!(full_process_keep_emoji("thumbs👍🏽up", true), "thumbs 👍🏽 up");
+/// assert_eq!(full_process_keep_emoji("👨‍👩‍👧 family", false), "👨‍👩‍👧 family");
+/// ```
+#[cfg(feature = "segmentation")]
+pub fn full_process_keep_emoji(s: &str, force_ascii: bool) -> String {
+    use unicode_segmentation::UnicodeSegmentation;
+    let mut result = String::with_capacity(s.len());
+    // Whether a space must be written before the next character to separate it from an emoji.
+    let mut after_emoji = false;
+    let mut sigma = false;
+    for grapheme in s.graphemes(true) {
+        if grapheme.chars().next().is_some_and(is_emoji) {
+            if !result.is_empty() && !result.ends_with(' ') {
+                result.push(' ');
+            }
+            result.push_str(grapheme);
+            after_emoji = true;
+            continue;
+        }
+        for c in grapheme.chars() {
+            if force_ascii && !c.is_ascii() {
+                continue;
+            }
+            if c.is_alphanumeric() {
+                if after_emoji {
+                    result.push(' ');
+                }
+                if c == 'Σ' {
+                    // Lower-cased with its neighbours below, as by full_process.
+                    sigma = true;
+                    result.push(c);
+                } else {
+                    result.extend(c.to_lowercase());
+                }
+            } else if !result.is_empty() {
+                result.push(' ');
+            }
+            after_emoji = false;
+        }
+    }
+    let trimmed_len = result.trim_end().len();
+    result.truncate(trimmed_len);
+    if sigma {
+        result = result.to_lowercase();
+    }
+    result
+}
+
+/// Whether `c` is (approximately) an `Extended_Pictographic` emoji code point.
+#[cfg(feature = "segmentation")]
+fn is_emoji(c: char) -> bool {
+    matches!(c,
+        '\u{231A}'..='\u{231B}'
+        | '\u{23E9}'..='\u{23FA}'
+        | '\u{2600}'..='\u{27BF}'
+        | '\u{2934}'..='\u{2935}'
+        | '\u{2B05}'..='\u{2B07}'
+        | '\u{2B1B}'..='\u{2B1C}'
+        | '\u{2B50}'
+        | '\u{2B55}'
+        | '\u{3030}'
+        | '\u{303D}'
+        | '\u{3297}'
+        | '\u{3299}'
+        | '\u{1F000}'..='\u{1FAFF}')
+}
+
+/// Chooses between reproducing fuzzywuzzy-py exactly and fixing its known oddities.
+///
+/// [Mode::PythonCompat] is the default and is what every function without a
+/// `mode` parameter uses.
+///
+/// ```
+/// # use fuzzywuzzy::utils::{full_process, Mode};
+/// assert_eq!(Mode::PythonCompat.process("a¬4ሴ2€耀", true), full_process("a¬4ሴ2€耀", true));
+/// assert_eq!(Mode::PythonCompat.process("a¬4ሴ2€耀", true), "a42");
+/// assert_eq!(Mode::Improved.process("a¬4ሴ2€耀", true), "a 4 2");
+/// assert_eq!(Mode::PythonCompat.process("new\u{200B}york", false), "new york");
+/// assert_eq!(Mode::Improved.process("new\u{200B}york", false), "newyork");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Mode {
+    /// Reproduces the results of fuzzywuzzy-py, quirks included.
+    #[default]
+    PythonCompat,
+    /// Fixes known oddities of fuzzywuzzy-py:
+    /// * with `force_ascii`, non-ASCII characters separate words rather than
+    ///   vanishing, so `"4ሴ2"` processes to `"4 2"` rather than `"42"`.
+    /// * scores are rounded exactly and half up, see [Rounding::HalfUp].
+    /// * invisible characters, like zero-width spaces and byte order marks, are
+    ///   removed rather than splitting words, see
+    ///   [StripInvisibleNormalizer].
+    Improved,
+}
+
+impl Mode {
+    /// Preprocesses `s` like [full_process], with this mode's handling of `force_ascii`.
+    pub fn process(self, s: &str, force_ascii: bool) -> String {
+        self.process_chars(s, force_ascii, true)
+    }
+
+    /// [Mode::process], but without lower-casing, for case sensitive comparisons.
+    ///
+    /// ```
+    /// # use fuzzywuzzy::utils::Mode;
+    /// assert_eq!(Mode::PythonCompat.process_preserving_case("C'est la Vie!", false), "C est la Vie");
+    /// ```
+    pub fn process_preserving_case(self, s: &str, force_ascii: bool) -> String {
+        self.process_chars(s, force_ascii, false)
+    }
+
+    fn process_chars(self, s: &str, force_ascii: bool, lowercase: bool) -> String {
+        match self {
+            Mode::PythonCompat => process_chars(s, force_ascii, char::is_alphanumeric, lowercase),
+            Mode::Improved => process_chars(
+                &StripInvisibleNormalizer.normalize(s),
+                false,
+                |c| c.is_alphanumeric() && (!force_ascii || c.is_ascii()),
+                lowercase,
+            ),
+        }
+    }
+
+    /// How similarities are rounded to integer scores in this mode.
+    pub fn rounding(self) -> Rounding {
+        match self {
+            Mode::PythonCompat => Rounding::Python,
+            Mode::Improved => Rounding::HalfUp,
+        }
+    }
+
+    /// Converts `matches` out of `total` compared elements into a score between 0 and 100.
+    pub(crate) fn score(self, matches: usize, total: usize) -> u8 {
+        self.rounding().score(matches, total)
+    }
+}
+
+impl crate::processing::Processor for Mode {
+    fn process(&self, s: &str, force_ascii: bool) -> String {
+        Mode::process(*self, s, force_ascii)
+    }
+}
+
+/// How a similarity is rounded to an integer score between 0 and 100.
+///
+/// fuzzywuzzy-py computes `int(round(100 * ratio))` on a double, and Python
+/// rounds ties to even. Because the ratio is first rounded to a double, a
+/// similarity lying exactly halfway between two scores may land on either side.
+/// [Rounding::HalfEven] and [Rounding::HalfUp] instead round the exact
+/// similarity, so only true ties are treated as ties.
+///
+/// ```
+/// # use fuzzywuzzy::utils::Rounding;
+/// // 5 matches out of 16 elements is a similarity of exactly 62.5.
+/// assert_eq!(Rounding::Python.score(5, 16), 62);
+/// assert_eq!(Rounding::HalfEven.score(5, 16), 62);
+/// assert_eq!(Rounding::HalfUp.score(5, 16), 63);
+/// assert_eq!(Rounding::Python.round(94.5), 94);
+/// assert_eq!(Rounding::HalfUp.round(94.5), 95);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Rounding {
+    /// Exactly what fuzzywuzzy-py computes: half to even, on a double.
+    #[default]
+    Python,
+    /// Half to even, on the exact similarity.
+    HalfEven,
+    /// Half away from zero, on the exact similarity.
+    HalfUp,
+}
+
+impl Rounding {
+    /// Converts `matches` out of `total` compared elements into a score between 0 and 100.
+    ///
+    /// Both sides of a match count, so the similarity is `2 * matches / total`.
+    /// A `total` of zero, i.e. two empty inputs, scores 100.
+    pub fn score(self, matches: usize, total: usize) -> u8 {
+        if total == 0 {
+            return 100;
+        }
+        let (quotient, remainder) = (200 * matches / total, 200 * matches % total);
+        let rounded = match self {
+            Rounding::Python => return self.round(100.0 * (2.0 * matches as f64 / total as f64)),
+            Rounding::HalfEven if 2 * remainder == total => quotient + quotient % 2,
+            Rounding::HalfEven | Rounding::HalfUp if 2 * remainder >= total => quotient + 1,
+            Rounding::HalfEven | Rounding::HalfUp => quotient,
+        };
+        rounded as u8
+    }
+
+    /// Rounds an already computed score, e.g. a weighted one, to an integer.
+    ///
+    /// Without an exact similarity to go by, [Rounding::Python] and
+    /// [Rounding::HalfEven] agree here.
+    pub fn round(self, score: f64) -> u8 {
+        match self {
+            Rounding::Python | Rounding::HalfEven => score.round_ties_even() as u8,
+            Rounding::HalfUp => score.round() as u8,
+        }
     }
-    result = result
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
-        .collect();
-    result.to_lowercase().trim().into()
 }
 
 /// A vestigial function from the port from Python's fuzzywuzzy.
@@ -59,105 +382,75 @@ pub fn validate_string(s: &str) -> bool {
     !s.is_empty()
 }
 
-/// A function to handle slicing into strings which may contain unicode.
+/// Slices a string by character indices rather than byte offsets.
 ///
 /// Unlike Python, Rust cares deeply about unicode strings and ensuring every
-/// slice into them remains valid. As slicing assumes that each Unicode
-/// character is either in or outside of a slice, a simple slice operation can
-/// panic when run on a Rust string. We manually slice a Rust string by
-/// iterating over it's character points. This avoids panic on unicode strings.
-///
-/// typical usage would be:
-///     `slice_utf8(simple_string, 3, 7)` instead of `&simple_string[3..7]` (see caveats)
-///
-/// Panics if `low` > `high` or `high` > `string.len`
-///
-/// Caveat: `slice_utf8` arguments should use indices based on Unicode Scalar
-/// Values, not byte offsets like the example.
-///
-/// `slice_utf8` works with Rust primitives that operate on Unicode
-/// Scalar Values which are distinct from bytes and grapheme clusters. For
-/// example, `y̆` is three bytes (b'y\xcc\x86'), two Unicode Scalar Values
-/// ('y\u{0306}'), but just one grapheme cluster (`y̆`).
-fn slice_utf8(string: &str, low: usize, high: usize) -> &str {
-    // I'm unsure if this is O(1) or O(n) due to the implementation.
-    let char_count = string.chars().count();
-    debug_assert!(!(low > high));
-    debug_assert!(!(high > char_count));
-    if low == high {
-        return "";
+/// slice into them remains valid. Indexing a `str` takes byte offsets and
+/// panics if they don't fall on character boundaries, so slicing with
+/// character indices (like those returned by [get_matching_blocks]) is a
+/// common source of panics on non-ASCII input.
+///
+/// Typical usage would be `slice_chars(s, 3..7)` instead of `&s[3..7]`.
+///
+/// Returns `None` if `range.start > range.end` or `range.end` is greater than
+/// the number of characters in `s`.
+///
+/// Caveat: indices are based on Unicode Scalar Values (`char`s), which are
+/// distinct from bytes and grapheme clusters. For example, `y̆` is three bytes
+/// (b'y\xcc\x86'), two Unicode Scalar Values ('y\u{0306}'), but just one
+/// grapheme cluster (`y̆`).
+///
+/// ```
+/// # use fuzzywuzzy::utils::{get_matching_blocks, slice_chars};
+/// let (a, b) = ("スマホでchance", "chance");
+/// let (i, _, k) = get_matching_blocks(a, b)[0];
+/// assert_eq!(slice_chars(a, i..i + k), Some("chance"));
+/// assert_eq!(slice_chars("y\u{0306}es", 1..3), Some("\u{0306}e"));
+/// assert_eq!(slice_chars("abc", 2..4), None);
+/// assert_eq!(slice_chars("abc", 2..1), None);
+/// ```
+pub fn slice_chars(s: &str, range: Range<usize>) -> Option<&str> {
+    if range.start > range.end {
+        return None;
     }
-    let mut indices = string
-        .char_indices()
-        .enumerate()
-        .map(|(char_offset, (byte_offset, _))| (byte_offset, char_offset));
-    // We need to find the byte-indices of the start and end of our substring
-    // that will result in a valid unicode slice.
-    // Find start of utf 8 slice by grabbing the byte offset of the char with
-    // the correct character offset.
-    let low_index = indices
-        .find(|(_, co)| *co == low)
-        .expect("Beginning of slice not found.")
-        .0;
-    // skip while we have not found our ending character of the utf 8 slice
-    let mut indices = indices.skip_while(|(_, co)| *co != high);
-    // grab the byte-offset of the element *after* the ending character in case
-    // the ending character is multi-byte
-    // if we selected for the entire slice, there will not be a next element
-    #[allow(clippy::or_fun_call)]
-    let high_index = indices.next().map(|(bo, _)| bo).unwrap_or(string.len());
-    &string[low_index..high_index]
+    let start = char_to_byte_index(s, range.start)?;
+    let len = char_to_byte_index(&s[start..], range.end - range.start)?;
+    Some(&s[start..start + len])
 }
 
-fn find_longest_match<'a>(
-    shorter: &'a str,
-    longer: &'a str,
-    low1: usize,
-    high1: usize,
-    low2: usize,
-    high2: usize,
-) -> (usize, usize, usize) {
-    // https://github.com/python-git/python/blob/master/Lib/difflib.py#L351
-    // algo:
-    //  In other words, of all maximal matching blocks, return one that
-    //  starts earliest in a, and of all those maximal matching blocks that
-    //  start earliest in a, return the one that starts earliest in b.
-    //
-    // In MY words: So, try to find a block of size shorter.len(), else
-    // decrement size. for each block size, start from the front of a and return
-    // if only one match If multiple matches for a given block size and index,
-    // return the one that starts earliest in b.
-    debug_assert!(low1 <= high1);
-    debug_assert!(low2 <= high2);
-    debug_assert!(high1 <= shorter.chars().count());
-    debug_assert!(high2 <= longer.chars().count());
-    let longsub = slice_utf8(longer, low2, high2);
-    // a map from byte offset to character offset, but we skip the hashing and use an array.
-    // for most strings, the byte and character lengths are almost the same.
-    // we only index into the map at byte offsets where characters begin,
-    // which all correct implementations below should do.
-    let mut byte_to_char_map = vec![0; longsub.len()];
-    longsub
-        .char_indices()
-        .enumerate()
-        .for_each(|(char_offset, (byte_offset, _))| {
-            byte_to_char_map[byte_offset] = char_offset;
-        });
-    let slen = high1 - low1;
-    for size in (1..slen + 1).rev() {
-        for start in 0..slen - size + 1 {
-            let substr = slice_utf8(&shorter, low1 + start, low1 + start + size);
-            // Note: str::match_indices returns byte offsets, not char indices.
-            if let Some((startb, matchstr)) = longsub.match_indices(substr).next() {
-                return (
-                    low1 + start,
-                    low2 + byte_to_char_map[startb],
-                    matchstr.chars().count(),
-                );
-            }
-        }
+/// Converts a character index into `s` into a byte offset.
+///
+/// The index one past the last character maps to `s.len()`. Returns `None` for
+/// indices beyond that.
+///
+/// ```
+/// # use fuzzywuzzy::utils::char_to_byte_index;
+/// assert_eq!(char_to_byte_index("añb", 2), Some(3));
+/// assert_eq!(char_to_byte_index("añb", 3), Some(4));
+/// assert_eq!(char_to_byte_index("añb", 4), None);
+/// ```
+pub fn char_to_byte_index(s: &str, char_idx: usize) -> Option<usize> {
+    s.char_indices()
+        .map(|(byte_idx, _)| byte_idx)
+        .chain(std::iter::once(s.len()))
+        .nth(char_idx)
+}
+
+/// Converts a byte offset into `s` into a character index.
+///
+/// Returns `None` if `byte_idx` is not on a character boundary or is beyond `s.len()`.
+///
+/// ```
+/// # use fuzzywuzzy::utils::byte_to_char_index;
+/// assert_eq!(byte_to_char_index("añb", 3), Some(2));
+/// assert_eq!(byte_to_char_index("añb", 4), Some(3));
+/// assert_eq!(byte_to_char_index("añb", 2), None);
+/// ```
+pub fn byte_to_char_index(s: &str, byte_idx: usize) -> Option<usize> {
+    if !s.is_char_boundary(byte_idx) {
+        return None;
     }
-    (low1, low2, 0)
+    Some(s[..byte_idx].chars().count())
 }
 
 /// Returns list of triples describing matching sequences.
@@ -169,66 +462,20 @@ fn find_longest_match<'a>(
 /// The final matching sequence will be a trivial matching sequence of (a.len(),
 /// b.len(), 0) and will be the only match of length 0.
 ///
+/// The strings are compared character by character with
+/// [primitives::get_matching_blocks](crate::primitives::get_matching_blocks),
+/// which picks its longest match algorithm by the lengths of the strings.
+///
 /// ```
 /// # use fuzzywuzzy::utils::get_matching_blocks;
 /// assert_eq!(get_matching_blocks("abxcd", "abcd"), vec![(0, 0, 2), (3, 2, 2), (5, 4, 0)]);
 /// assert_eq!(get_matching_blocks("abcd", "abxcd"), vec![(0, 0, 2), (2, 3, 2), (4, 5, 0)]);
 /// assert_eq!(get_matching_blocks("chance", "スマホでchance"), vec![(0, 4, 6), (6, 10, 0)]);
 /// ```
-#[allow(clippy::many_single_char_names)]
-pub fn get_matching_blocks<'a>(a: &'a str, b: &'a str) -> Vec<(usize, usize, usize)> {
-    let flipped;
-    let (shorter, len1, longer, len2) = {
-        let a_len = a.chars().count();
-        let b_len = b.chars().count();
-        if a_len <= b_len {
-            flipped = false;
-            (a, a_len, b, b_len)
-        } else {
-            flipped = true;
-            (b, b_len, a, a_len)
-        }
-    };
-    // https://github.com/python-git/python/blob/master/Lib/difflib.py#L461
-    let mut queue: Vec<(usize, usize, usize, usize)> = vec![(0, len1, 0, len2)];
-    let mut matching_blocks = Vec::new();
-    while let Some((low1, high1, low2, high2)) = queue.pop() {
-        let (i, j, k) = find_longest_match(shorter, longer, low1, high1, low2, high2);
-        debug_assert!(i <= shorter.chars().count());
-        debug_assert!(j <= longer.chars().count());
-        if k != 0 {
-            matching_blocks.push((i, j, k));
-            if low1 < i && low2 < j {
-                queue.push((low1, i, low2, j));
-            }
-            if i + k < high1 && j + k < high2 {
-                queue.push((i + k, high1, j + k, high2));
-            }
-        }
-    }
-    matching_blocks.sort_unstable(); // Is this necessary?
-    let (mut i1, mut j1, mut k1) = (0, 0, 0);
-    let mut non_adjacent = Vec::new();
-    for (i2, j2, k2) in matching_blocks {
-        if i1 + k1 == i2 && j1 + k1 == j2 {
-            k1 += k2;
-        } else {
-            if k1 != 0 {
-                non_adjacent.push((i1, j1, k1));
-            }
-            i1 = i2;
-            j1 = j2;
-            k1 = k2;
-        }
-    }
-    if k1 != 0 {
-        non_adjacent.push((i1, j1, k1));
-    }
-    non_adjacent.push((len1, len2, 0));
-    non_adjacent
-        .into_iter()
-        .map(|(i, j, k)| if flipped { (j, i, k) } else { (i, j, k) })
-        .collect()
+pub fn get_matching_blocks(a: &str, b: &str) -> Vec<(usize, usize, usize)> {
+    let a: SmallBuffer<char> = a.chars().collect();
+    let b: SmallBuffer<char> = b.chars().collect();
+    crate::primitives::get_matching_blocks(&a, &b)
 }
 
 /// some common short circuiting for ratio finding functions.
@@ -253,52 +500,112 @@ mod test {
     #[test]
     fn slice_at_the_end() {
         let s = "this is a test"; // No Unicode
-        assert_eq!(slice_utf8(s, 3, s.len()), &s[3..(s.len())]);
+        assert_eq!(slice_chars(s, 3..s.len()), Some(&s[3..(s.len())]));
     }
 
     #[test]
     fn slice_in_the_utf8() {
         let s = "ϵthiϕś αβ a test"; // Unicode
-        assert_eq!(slice_utf8(s, 2, 6), "hiϕś");
+        assert_eq!(slice_chars(s, 2..6), Some("hiϕś"));
     }
 
     #[test]
     fn short() {
-        assert_eq!(slice_utf8("ö", 0, 1), "ö");
+        assert_eq!(slice_chars("ö", 0..1), Some("ö"));
     }
 
     #[test]
     fn arabic() {
         let s = "من"; // entire string is unicode
-        assert_eq!(slice_utf8(s, 0, 2), s);
+        assert_eq!(slice_chars(s, 0..2), Some(s));
     }
 
     #[test]
     fn empty() {
         let s = "abcd";
-        assert_eq!(slice_utf8(s, 2, 2), &s[2..2]);
+        assert_eq!(slice_chars(s, 2..2), Some(&s[2..2]));
     }
 
     #[test]
     fn split_cluster() {
         let s = "y̆es";
-        assert_eq!(slice_utf8(s, 0, 1), "y");
-        assert_eq!(slice_utf8(s, 1, 4), "\u{0306}es");
+        assert_eq!(slice_chars(s, 0..1), Some("y"));
+        assert_eq!(slice_chars(s, 1..4), Some("\u{0306}es"));
+    }
+
+    #[test]
+    fn full_process_matches_multi_pass() {
+        fn multi_pass(s: &str, force_ascii: bool) -> String {
+            let mut result = s.to_string();
+            if force_ascii {
+                result = result.chars().filter(char::is_ascii).collect();
+            }
+            result = result
+                .chars()
+                .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+                .collect();
+            result.to_lowercase().trim().into()
+        }
+        let inputs = [
+            "",
+            "   ",
+            "  Lorem  Ipsum!! ",
+            "¬Camarões assados",
+            "a¬4ሴ2€耀",
+            "İstanbul",
+            "--x--",
+            "ΟΔΟΣ",
+            "ΟΔΟΣ ΣΟΦΟΚΛΕΟΥΣ",
+            "Σ-ΑΣ-ΣΑ",
+        ];
+        for s in inputs.iter() {
+            assert_eq!(full_process(s, false), multi_pass(s, false));
+            assert_eq!(full_process(s, true), multi_pass(s, true));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "segmentation")]
+    fn keep_emoji_matches_full_process_without_emoji() {
+        let inputs = [
+            "",
+            "  Lorem  Ipsum!! ",
+            "¬Camarões assados",
+            "a¬4ሴ2€耀",
+            "y\u{0306}es",
+            "ΟΔΟΣ ΣΟΦΟΚΛΕΟΥΣ",
+            "ΣΑΣ, Σ!",
+        ];
+        for s in inputs.iter() {
+            assert_eq!(full_process_keep_emoji(s, false), full_process(s, false));
+            assert_eq!(full_process_keep_emoji(s, true), full_process(s, true));
+        }
+        assert_eq!(full_process_keep_emoji("ΟΔΟΣ🔥", false), "οδος 🔥");
     }
 
     #[test]
-    #[should_panic]
-    #[cfg(debug)]
     fn overlarge() {
         let s = "abcde";
-        slice_utf8(s, 0, 10);
+        assert_eq!(slice_chars(s, 0..10), None);
     }
 
     #[test]
-    #[should_panic]
-    #[cfg(debug)]
     fn low_greater_then_high() {
         let s = "abcde";
-        slice_utf8(s, 4, 2);
+        #[allow(clippy::reversed_empty_ranges)]
+        let range = 4..2;
+        assert_eq!(slice_chars(s, range), None);
+    }
+
+    #[test]
+    fn half_up_score_rounds_exactly() {
+        for total in 1..300usize {
+            for matches in 0..=total / 2 {
+                // Round half up: bump the quotient when the remainder is at least half of `total`.
+                let (quotient, remainder) = (200 * matches / total, 200 * matches % total);
+                let expected = quotient + usize::from(2 * remainder >= total);
+                assert_eq!(Rounding::HalfUp.score(matches, total) as usize, expected);
+            }
+        }
     }
 }
@@ -0,0 +1,78 @@
+//! JavaScript bindings for use from WebAssembly. Requires feature "wasm-bindgen".
+//!
+//! Build with
+//! `cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm-bindgen --crate-type cdylib`
+//! and run `wasm-bindgen` on the resulting `.wasm` to produce a JavaScript
+//! package exposing the same scorers used natively, so browser-side
+//! autocomplete and the backend agree on every score.
+
+use crate::{fuzz, process, utils};
+use wasm_bindgen::prelude::*;
+
+/// See [fuzz::ratio].
+#[wasm_bindgen]
+pub fn ratio(a: &str, b: &str) -> u8 {
+    fuzz::ratio(a, b)
+}
+
+/// See [fuzz::partial_ratio].
+#[wasm_bindgen(js_name = partialRatio)]
+pub fn partial_ratio(a: &str, b: &str) -> u8 {
+    fuzz::partial_ratio(a, b)
+}
+
+/// See [fuzz::token_sort_ratio].
+#[wasm_bindgen(js_name = tokenSortRatio)]
+pub fn token_sort_ratio(a: &str, b: &str) -> u8 {
+    fuzz::token_sort_ratio(a, b, true, true)
+}
+
+/// See [fuzz::token_set_ratio].
+#[wasm_bindgen(js_name = tokenSetRatio)]
+pub fn token_set_ratio(a: &str, b: &str) -> u8 {
+    fuzz::token_set_ratio(a, b, true, true)
+}
+
+/// See [fuzz::wratio].
+#[wasm_bindgen]
+pub fn wratio(a: &str, b: &str) -> u8 {
+    fuzz::wratio(a, b, true, true)
+}
+
+/// A choice and its score, as returned by [extract].
+#[wasm_bindgen]
+pub struct ExtractedChoice {
+    choice: String,
+    score: u8,
+}
+
+#[wasm_bindgen]
+impl ExtractedChoice {
+    #[wasm_bindgen(getter)]
+    pub fn choice(&self) -> String {
+        self.choice.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn score(&self) -> u8 {
+        self.score
+    }
+}
+
+/// Scores `choices` against `query` with [fuzz::wratio] after [utils::full_process],
+/// returning those scoring at least `score_cutoff` in their original order.
+///
+/// See [process::extract_without_order].
+#[wasm_bindgen]
+pub fn extract(query: &str, choices: Vec<String>, score_cutoff: u8) -> Vec<ExtractedChoice> {
+    process::extract_without_order(
+        query,
+        choices,
+        utils::full_process,
+        fuzz::WRatio,
+        score_cutoff,
+    )
+    .into_iter()
+    .map(|(choice, score)| ExtractedChoice { choice, score })
+    .collect()
+}